@@ -0,0 +1,108 @@
+//! ``benches/directory_scan.rs``
+//! ============================================================================
+//! # Directory Scan Benchmark
+//!
+//! Lists and stats a generated directory (one entry in ten a subdirectory
+//! with a few files of its own) through the scanner's two phases, with a
+//! fresh cache per run.
+//!
+//! `batched` reads with blocking `readdir` chunks and stats on the rayon pool.
+//! `legacy` is the scanner from before batching, vendored in
+//! `legacy/dir_scanner.rs`: tokio `read_dir`, one stat per entry to learn its
+//! type, then one more per entry plus a full `read_dir` of every subdirectory
+//! to count its items.
+
+#[path = "legacy/dir_scanner.rs"]
+mod legacy;
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+
+use fsm_core::cache::cache_manager::ObjectInfoCache;
+use fsm_core::fs::dir_scanner::{SCAN_CHUNK_SIZE, read_dir_chunks};
+use fsm_core::fs::object_info::{LightObjectInfo, ObjectInfo};
+use fsm_core::tasks::metadata_task::load_metadata_parallel;
+use tempfile::TempDir;
+
+/// `count` entries directly under a temporary directory.
+fn synthetic_directory(count: usize) -> TempDir {
+    let root: TempDir = tempfile::tempdir().expect("create temp dir");
+
+    for n in 0..count {
+        if n.is_multiple_of(10) {
+            let dir: PathBuf = root.path().join(format!("dir{n}"));
+            std::fs::create_dir(&dir).expect("create subdir");
+
+            for child in 0..8 {
+                std::fs::write(dir.join(format!("child{child}")), b"x").expect("create child");
+            }
+        } else {
+            std::fs::write(root.path().join(format!("file{n}.txt")), b"data").expect("create file");
+        }
+    }
+
+    root
+}
+
+/// The current scanner's phases: chunked reads with a cache lookup per
+/// entry, then parallel stats of the misses, cached as they load.
+async fn scan_batched(path: &Path, cache: &ObjectInfoCache) -> Vec<ObjectInfo> {
+    let mut chunks: Vec<Vec<LightObjectInfo>> = Vec::new();
+
+    read_dir_chunks(path, false, SCAN_CHUNK_SIZE, |chunk: Vec<LightObjectInfo>| -> bool {
+        chunks.push(chunk);
+        true
+    })
+    .expect("read dir");
+
+    let mut light_entries: Vec<LightObjectInfo> = Vec::new();
+
+    for light_info in chunks.into_iter().flatten() {
+        if cache.get_by_path(&light_info.path).await.is_none() {
+            light_entries.push(light_info);
+        }
+    }
+
+    let loaded: Vec<ObjectInfo> = load_metadata_parallel(light_entries);
+
+    for info in &loaded {
+        cache.insert_path(&info.path, info.clone()).await;
+    }
+
+    loaded
+}
+
+fn bench_directory_scan(c: &mut Criterion) {
+    let runtime: tokio::runtime::Runtime = tokio::runtime::Runtime::new().expect("tokio runtime");
+    let mut group = c.benchmark_group("directory_scan");
+    group.sample_size(10);
+
+    for count in [1_000, 20_000] {
+        let dir: TempDir = synthetic_directory(count);
+        let path: PathBuf = dir.path().to_path_buf();
+
+        group.bench_with_input(BenchmarkId::new("batched", count), &path, |b, path| {
+            b.to_async(&runtime).iter_batched(
+                ObjectInfoCache::new,
+                |cache: ObjectInfoCache| async move { black_box(scan_batched(path, &cache).await) },
+                BatchSize::SmallInput,
+            );
+        });
+
+        group.bench_with_input(BenchmarkId::new("legacy", count), &path, |b, path| {
+            b.to_async(&runtime).iter_batched(
+                ObjectInfoCache::new,
+                |cache: ObjectInfoCache| async move {
+                    black_box(legacy::scan(path, false, SCAN_CHUNK_SIZE, &cache).await)
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_directory_scan);
+criterion_main!(benches);
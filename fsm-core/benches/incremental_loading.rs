@@ -0,0 +1,115 @@
+//! ``benches/incremental_loading.rs``
+//! ============================================================================
+//! # Streaming Insertion Benchmark
+//!
+//! Feeds a synthetic directory listing into `PaneState` one entry at a time,
+//! in scan (unsorted) order, the way `ScanUpdate::Entries` delivers it.
+//!
+//! `legacy` replays the old algorithm (push, clone the whole listing, re-sort).
+//! Both run on the same listings in `incremental_loading`; the old algorithm
+//! is quadratic and does not finish at 500k, so that size is measured alone
+//! in `incremental_loading_large`.
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::ffi::OsString;
+use std::hint::black_box;
+use std::path::PathBuf;
+
+use compact_str::CompactString;
+use fsm_core::config::SortConfig;
+use fsm_core::fs::object_info::ObjectInfo;
+use fsm_core::model::fs_state::{EntrySort, PaneState, sort_objects};
+
+/// `count` entries (one in ten a directory) in a scrambled, deterministic
+/// order, like a real `readdir`.
+fn synthetic_listing(count: usize) -> Vec<ObjectInfo> {
+    // Stepping by a prime coprime to `count` visits every index once
+    let step: usize = 7_919;
+
+    (0..count)
+        .map(|i: usize| -> ObjectInfo {
+            let n: usize = (i * step) % count;
+            let is_dir: bool = n.is_multiple_of(10);
+            let name: String = if is_dir { format!("dir{n}") } else { format!("file{n}.txt") };
+
+            ObjectInfo {
+                path: PathBuf::from("/bench").join(&name),
+                name: OsString::from(&name),
+                extension: (!is_dir).then(|| CompactString::const_new("txt")),
+                is_dir,
+                size: n as u64,
+                ..ObjectInfo::default()
+            }
+        })
+        .collect()
+}
+
+fn stream_into_pane(entries: Vec<ObjectInfo>) -> PaneState {
+    let mut pane: PaneState = PaneState::new(PathBuf::from("/bench"));
+    pane.start_incremental_loading(1);
+
+    for entry in entries {
+        pane.add_incremental_entry(entry);
+    }
+
+    pane.complete_incremental_loading();
+    pane
+}
+
+fn stream_legacy(entries: Vec<ObjectInfo>) -> Vec<ObjectInfo> {
+    let sort_config: SortConfig = SortConfig::default();
+    let mut incremental: Vec<ObjectInfo> = Vec::new();
+    let mut shown: Vec<ObjectInfo> = Vec::new();
+
+    for entry in entries {
+        incremental.push(entry);
+        shown.clone_from(&incremental);
+        sort_objects(&mut shown, &EntrySort::NameAsc, &sort_config);
+    }
+
+    shown
+}
+
+fn bench_incremental_loading(c: &mut Criterion) {
+    let mut group = c.benchmark_group("incremental_loading");
+    group.sample_size(10);
+
+    for count in [500, 2_000] {
+        let listing: Vec<ObjectInfo> = synthetic_listing(count);
+
+        group.bench_with_input(BenchmarkId::new("batched", count), &listing, |b, listing| {
+            b.iter_batched(
+                || -> Vec<ObjectInfo> { listing.clone() },
+                |entries: Vec<ObjectInfo>| black_box(stream_into_pane(entries)),
+                BatchSize::LargeInput,
+            );
+        });
+
+        group.bench_with_input(BenchmarkId::new("legacy", count), &listing, |b, listing| {
+            b.iter_batched(
+                || -> Vec<ObjectInfo> { listing.clone() },
+                |entries: Vec<ObjectInfo>| black_box(stream_legacy(entries)),
+                BatchSize::LargeInput,
+            );
+        });
+    }
+
+    group.finish();
+
+    let mut group = c.benchmark_group("incremental_loading_large");
+    group.sample_size(10);
+
+    let listing: Vec<ObjectInfo> = synthetic_listing(500_000);
+    group.bench_with_input(BenchmarkId::new("batched", 500_000), &listing, |b, listing| {
+        b.iter_batched(
+            || -> Vec<ObjectInfo> { listing.clone() },
+            |entries: Vec<ObjectInfo>| black_box(stream_into_pane(entries)),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_incremental_loading);
+criterion_main!(benches);
@@ -0,0 +1,132 @@
+//! ``benches/legacy/dir_scanner.rs``
+//! ============================================================================
+//! # Pre-batching Directory Scanner (vendored baseline)
+//!
+//! The streaming scanner and metadata task as they were before directory
+//! reads were batched, kept as the baseline of `directory_scan`. Tracing and
+//! profiling are stripped; every filesystem call and cache access is kept.
+//! Phase 2 ran as a background task; here it runs inline so the benchmark
+//! times the whole listing.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use fsm_core::cache::cache_manager::ObjectInfoCache;
+use fsm_core::config::SortConfig;
+use fsm_core::error::AppError;
+use fsm_core::fs::object_info::{LightObjectInfo, ObjectInfo};
+use fsm_core::model::fs_state::{EntrySort, sort_objects};
+use tokio::fs::{self as TokioFs, DirEntry, ReadDir};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+/// Entries per metadata batch of the old `start_background_metadata_loading`.
+const METADATA_BATCH_SIZE: usize = 5;
+
+/// Scan `path` in two phases like the old `DirectoryScanner::scan` followed
+/// by `batch_load_metadata_task`. Returns the entries with full metadata.
+pub async fn scan(path: &Path, show_hidden: bool, batch_size: usize, cache: &ObjectInfoCache) -> Vec<ObjectInfo> {
+    // Stands in for the scan update and action channels of the event loop
+    let (tx, mut rx): (UnboundedSender<ObjectInfo>, UnboundedReceiver<ObjectInfo>) = mpsc::unbounded_channel();
+
+    // Phase 1: quick scan, one stat per entry, placeholders streamed
+    let mut entries: Vec<ObjectInfo> = Vec::new();
+    let mut light_entries: Vec<LightObjectInfo> = Vec::new();
+    let mut read_dir: ReadDir = TokioFs::read_dir(path).await.expect("read dir");
+    let mut processed: usize = 0;
+
+    while let Some(entry) = read_dir.next_entry().await.transpose() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+
+        if should_skip_entry(&entry, show_hidden) {
+            continue;
+        }
+
+        let Ok(light_info) = LightObjectInfo::from_path(&entry.path()).await else {
+            continue;
+        };
+
+        let placeholder: ObjectInfo = match cache.get_by_path(&light_info.path).await {
+            Some(cached_info) => cached_info,
+            None => light_info.placeholder(),
+        };
+
+        let _ = tx.send(placeholder.clone());
+        entries.push(placeholder);
+        light_entries.push(light_info);
+
+        processed += 1;
+        if processed.is_multiple_of(batch_size) {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    sort_objects(&mut entries, &EntrySort::NameAsc, &SortConfig::default());
+
+    // Phase 2: metadata, one entry at a time through the cache
+    let mut loaded: Vec<ObjectInfo> = Vec::with_capacity(light_entries.len());
+
+    for (count, light_info) in light_entries.into_iter().enumerate() {
+        let entry_path: PathBuf = light_info.path.clone();
+
+        if let Ok(full_info) = cache.get_or_load_path(entry_path, || into_full_info(light_info)).await {
+            let _ = tx.send(full_info.clone());
+            loaded.push(full_info);
+        }
+
+        if (count + 1).is_multiple_of(METADATA_BATCH_SIZE) {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    drop(tx);
+    while rx.recv().await.is_some() {}
+
+    loaded
+}
+
+fn should_skip_entry(entry: &DirEntry, show_hidden: bool) -> bool {
+    if show_hidden {
+        return false;
+    }
+
+    let entry_path: PathBuf = entry.path();
+    let file_name: &str = entry_path
+        .file_name()
+        .and_then(|s: &std::ffi::OsStr| -> Option<&str> { s.to_str() })
+        .unwrap_or("");
+
+    file_name.starts_with('.')
+}
+
+/// The old `LightObjectInfo::into_full_info`: a second stat, and a full
+/// `read_dir` of every subdirectory to count its items.
+async fn into_full_info(light_info: LightObjectInfo) -> Result<ObjectInfo, AppError> {
+    let metadata: std::fs::Metadata = TokioFs::symlink_metadata(&light_info.path).await?;
+
+    let size: u64 = if light_info.is_dir { 0 } else { metadata.len() };
+    let items_count: u64 = if light_info.is_dir {
+        match TokioFs::read_dir(&light_info.path).await {
+            Ok(mut children) => {
+                let mut count: u64 = 0;
+                while children.next_entry().await?.is_some() {
+                    count += 1;
+                }
+                count
+            }
+            Err(_) => 0,
+        }
+    } else {
+        0
+    };
+
+    Ok(ObjectInfo {
+        size,
+        items_count,
+        modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+        created: metadata.created().unwrap_or(SystemTime::UNIX_EPOCH),
+        metadata_loaded: true,
+        ..light_info.placeholder()
+    })
+}
@@ -3,28 +3,41 @@
 //! # High-Performance `ObjectInfo` Cache
 //!
 //! A thread-safe, async cache for filesystem metadata with the following features:
-//! - LRU eviction with TTL support
+//! - Size-aware LRU eviction (by estimated bytes) with TTL support
 //! - Batch operations for improved performance
 //! - Cache statistics and monitoring
 //! - Configurable cache policies
 //! - Memory-efficient key management
 //! - Proper error handling without poisoning cache
+//! - Entries restored from the on-disk snapshot, validated lazily
 
 use std::{
-    collections::VecDeque, path::{Path, PathBuf}, sync::{
-        atomic::{AtomicU64, Ordering}, Arc
-    }, time::{Duration, Instant}
+    collections::{HashSet, VecDeque}, ffi::OsStr, path::{Path, PathBuf}, sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering}, Arc, PoisonError, RwLock
+    }, time::{Duration, Instant, SystemTime}
 };
 
-use moka::future::Cache;
+use dashmap::DashMap;
+use moka::{future::Cache, notification::RemovalCause};
 // Serde traits now imported via config module
 use thiserror::Error;
 use tracing::{debug, error, info, warn, instrument};
 
 use crate::{config::CacheConfig, error::AppError, fs::object_info::ObjectInfo};
 
-/// Compact string key for cache entries (uses Arc for cheap cloning)
-pub type ObjectKey = Arc<str>;
+/// Path key for cache entries (uses Arc for cheap cloning). Kept as an OS
+/// string so paths that are not valid UTF-8 get distinct keys.
+pub type ObjectKey = Arc<OsStr>;
+
+/// Maximum number of directory listings remembered by warming
+const LISTING_CAPACITY: u64 = 1024;
+
+/// Approximate per-entry bookkeeping of the cache itself (hash table slot,
+/// access order nodes, timestamps), added to every entry's weight
+const ENTRY_OVERHEAD: usize = 64;
+
+/// Fraction of `max_memory_mb` at which `health_check` starts warning
+const NEAR_BUDGET_RATIO: f64 = 0.9;
 
 /// Cache-specific errors
 #[derive(Debug, Error)]
@@ -57,6 +70,10 @@ pub struct CacheStats {
     evictions: AtomicU64,
     load_exceptions: AtomicU64,
     total_load_time_ns: AtomicU64,
+    invalidations: AtomicU64,
+    invalidated_keys: AtomicU64,
+    restored_hits: AtomicU64,
+    restored_stale: AtomicU64,
 }
 
 impl CacheStats {
@@ -83,6 +100,21 @@ impl CacheStats {
         self.evictions.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// A path changed on disk and `keys` cached entries were dropped for it.
+    pub fn record_invalidation(&self, keys: u64) {
+        self.invalidations.fetch_add(1, Ordering::Relaxed);
+        self.invalidated_keys.fetch_add(keys, Ordering::Relaxed);
+    }
+
+    /// A snapshot entry was looked up; `fresh` if it still matched the disk.
+    pub fn record_restored(&self, fresh: bool) {
+        if fresh {
+            self.restored_hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.restored_stale.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
     pub fn snapshot(&self) -> CacheStatsSnapshot {
         let hits: u64 = self.hits.load(Ordering::Relaxed);
         let misses: u64 = self.misses.load(Ordering::Relaxed);
@@ -95,12 +127,14 @@ impl CacheStats {
             loads,
             evictions: self.evictions.load(Ordering::Relaxed),
             load_exceptions: self.load_exceptions.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+            invalidated_keys: self.invalidated_keys.load(Ordering::Relaxed),
+            restored_hits: self.restored_hits.load(Ordering::Relaxed),
+            restored_stale: self.restored_stale.load(Ordering::Relaxed),
             total_load_time: Duration::from_nanos(total_load_time_ns),
-            average_load_penalty: if loads > 0 {
-                Duration::from_nanos(total_load_time_ns / loads)
-            } else {
-                Duration::ZERO
-            },
+            average_load_penalty: total_load_time_ns
+                .checked_div(loads)
+                .map_or(Duration::ZERO, Duration::from_nanos),
         }
     }
 }
@@ -112,6 +146,14 @@ pub struct CacheStatsSnapshot {
     pub loads: u64,
     pub evictions: u64,
     pub load_exceptions: u64,
+    /// Changed paths reported by the file watcher
+    pub invalidations: u64,
+    /// Cached keys dropped for those paths (ancestors included)
+    pub invalidated_keys: u64,
+    /// Misses served from the disk snapshot after validation
+    pub restored_hits: u64,
+    /// Snapshot entries dropped because the disk changed
+    pub restored_stale: u64,
     pub total_load_time: Duration,
     pub average_load_penalty: Duration,
 }
@@ -153,12 +195,34 @@ enum CacheEntry {
     Failed,
 }
 
+/// Estimated bytes held by one cache entry, used as its eviction weight.
+fn entry_weight(key: &ObjectKey, entry: &CacheEntry) -> u32 {
+    let value: usize = match entry {
+        CacheEntry::Success(info) => info.heap_size(),
+
+        CacheEntry::Failed => std::mem::size_of::<CacheEntry>(),
+    };
+
+    u32::try_from(ENTRY_OVERHEAD + key.len() + value).unwrap_or(u32::MAX)
+}
+
 /// High-performance, async cache for `ObjectInfo` with comprehensive features
 #[derive(Clone)]
 pub struct ObjectInfoCache {
-    inner: Cache<ObjectKey, CacheEntry>,
+    /// Replaced by a smaller cache under memory pressure (`shrink_to`), as
+    /// moka's capacity is fixed once built
+    inner: Arc<RwLock<Cache<ObjectKey, CacheEntry>>>,
+    /// Capacity of `inner` in bytes: `max_memory_mb`, or lower after
+    /// `shrink_to` until `restore_budget`
+    budget: Arc<AtomicU64>,
+    /// Child paths of directories read while warming (for side columns)
+    listings: Cache<ObjectKey, Arc<[PathBuf]>>,
+    /// Entries loaded from the disk snapshot, not yet checked against disk
+    restored: Arc<DashMap<ObjectKey, ObjectInfo>>,
     config: CacheConfig,
     stats: Arc<CacheStats>,
+    /// Set while usage is above `NEAR_BUDGET_RATIO`, so it is logged once
+    budget_warned: Arc<AtomicBool>,
     startup_time: Instant,
 }
 
@@ -166,38 +230,68 @@ impl ObjectInfoCache {
     /// Create a new cache with custom configuration
     #[must_use] 
     pub fn with_config(config: CacheConfig) -> Self {
-        let mut cache_builder = Cache::builder()
-            .max_capacity(config.max_capacity)
+        if let Some(entries) = config.max_capacity {
+            warn!(
+                "cache.max_capacity ({entries}) is deprecated and ignored; the cache is bounded by cache.max_memory_mb ({} MB)",
+                config.max_memory_mb
+            );
+        }
+
+        let stats = Arc::new(CacheStats::default());
+        let budget: u64 = config.max_memory_mb.saturating_mul(1024 * 1024);
+        let inner: Cache<ObjectKey, CacheEntry> = Self::build_inner(&config, &stats, budget);
+        
+        let listings = Cache::builder()
+            .max_capacity(LISTING_CAPACITY)
             .time_to_live(config.ttl)
-            .time_to_idle(config.tti);
+            .time_to_idle(config.tti)
+            .build();
 
-        // Configure sharding for better concurrent performance
-        if config.num_shards > 0 {
-            cache_builder = cache_builder.initial_capacity((config.max_capacity / 4) as usize);
+        Self {
+            inner: Arc::new(RwLock::new(inner)),
+            budget: Arc::new(AtomicU64::new(budget)),
+            listings,
+            restored: Arc::new(DashMap::new()),
+            config,
+            stats,
+            budget_warned: Arc::new(AtomicBool::new(false)),
+            startup_time: Instant::now(),
         }
+    }
 
-        let stats = Arc::new(CacheStats::default());
+    /// Entry cache holding at most `capacity` bytes.
+    fn build_inner(config: &CacheConfig, stats: &Arc<CacheStats>, capacity: u64) -> Cache<ObjectKey, CacheEntry> {
+        // Capacity is in bytes: every entry weighs its estimated footprint
+        let cache_builder = Cache::builder()
+            .max_capacity(capacity)
+            .weigher(entry_weight)
+            .time_to_live(config.ttl)
+            .time_to_idle(config.tti);
 
-        // Add eviction listener for statistics
-        let inner = if config.enable_stats {
+        // Add eviction listener for statistics (explicit removals are
+        // counted as invalidations instead)
+        if config.enable_stats {
             let stats_clone = stats.clone();
             cache_builder
-                .eviction_listener(move |_key, _value, _cause| {
-                    stats_clone.record_eviction();
+                .eviction_listener(move |_key, _value, cause: RemovalCause| {
+                    if cause.was_evicted() {
+                        stats_clone.record_eviction();
+                    }
                 })
                 .build()
         } else {
             cache_builder.build()
-        };
-        
-        Self {
-            inner,
-            config,
-            stats,
-            startup_time: Instant::now(),
         }
     }
 
+    /// The entry cache currently in use.
+    fn inner(&self) -> Cache<ObjectKey, CacheEntry> {
+        self.inner
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
     /// Create cache with default configuration
     #[must_use] 
     pub fn new() -> Self {
@@ -215,22 +309,19 @@ impl ObjectInfoCache {
         // Normalize path for consistent keys
         let path: &Path = path.as_ref();
 
-        // Use to_string_lossy for better Unicode handling
-        let key_str: String = if cfg!(windows) {
+        if cfg!(windows) {
             // Windows: normalize separators
-            path.to_string_lossy().replace('\\', "/")
+            Arc::from(OsStr::new(&path.to_string_lossy().replace('\\', "/")))
         } else {
-            path.to_string_lossy().to_string()
-        };
-
-        Arc::from(key_str)
+            Arc::from(path.as_os_str())
+        }
     }
 
     /// Get entry if present in cache (non-blocking) with operational tracing
-    #[instrument(skip(self), fields(cache_key = %key))]
+    #[instrument(skip(self), fields(cache_key = ?key))]
     pub async fn get(&self, key: &ObjectKey) -> Option<ObjectInfo> {
         let lookup_start = Instant::now();
-        let result: Option<CacheEntry> = self.inner.get(key).await;
+        let result: Option<CacheEntry> = self.inner().get(key).await;
         let lookup_duration = lookup_start.elapsed();
 
         if self.config.enable_stats {
@@ -240,7 +331,7 @@ impl ObjectInfoCache {
                     info!(
                         marker = "CACHE_OPERATION",
                         operation_type = "cache_hit",
-                        cache_key = %key,
+                        cache_key = ?key,
                         lookup_time_us = lookup_duration.as_micros(),
                         "Cache hit - fast retrieval"
                     );
@@ -250,7 +341,7 @@ impl ObjectInfoCache {
                     warn!(
                         marker = "CACHE_OPERATION", 
                         operation_type = "cache_failed_entry",
-                        cache_key = %key,
+                        cache_key = ?key,
                         "Cache contained failed entry"
                     );
                 }
@@ -259,7 +350,7 @@ impl ObjectInfoCache {
                     debug!(
                         marker = "CACHE_OPERATION",
                         operation_type = "cache_miss", 
-                        cache_key = %key,
+                        cache_key = ?key,
                         lookup_time_us = lookup_duration.as_micros(),
                         "Cache miss - key not found"
                     );
@@ -269,10 +360,89 @@ impl ObjectInfoCache {
 
         match result {
             Some(CacheEntry::Success(info)) => Some(info),
-            _ => None,
+
+            Some(CacheEntry::Failed) => None,
+
+            None => self.revalidate_restored(key).await,
         }
     }
 
+    /// Serve a snapshot entry if its mtime (and size, for files) still
+    /// matches the disk, moving it into the cache proper. Stale entries are
+    /// dropped either way.
+    async fn revalidate_restored(&self, key: &ObjectKey) -> Option<ObjectInfo> {
+        let (_, info) = self.restored.remove(key)?;
+
+        let fresh: bool = tokio::fs::symlink_metadata(&info.path)
+            .await
+            .is_ok_and(|metadata: std::fs::Metadata| -> bool {
+                let is_symlink: bool = metadata.file_type().is_symlink();
+
+                is_symlink == info.is_symlink
+                    && (is_symlink || metadata.is_dir() == info.is_dir)
+                    && metadata.modified().is_ok_and(|t: SystemTime| -> bool { t == info.modified })
+                    && (info.is_dir || is_symlink || metadata.len() == info.size)
+            });
+
+        if self.config.enable_stats {
+            self.stats.record_restored(fresh);
+        }
+
+        if !fresh {
+            debug!(
+                marker = "CACHE_OPERATION",
+                operation_type = "restored_stale",
+                cache_key = ?key,
+                "Dropped stale snapshot entry"
+            );
+            return None;
+        }
+
+        self.inner().insert(key.clone(), CacheEntry::Success(info.clone())).await;
+        Some(info)
+    }
+
+    /// Queue entries from the disk snapshot for lazy validation. Keys
+    /// already cached are skipped. Returns the number queued.
+    pub fn restore(&self, entries: impl IntoIterator<Item = ObjectInfo>) -> usize {
+        let mut queued: usize = 0;
+
+        for info in entries {
+            let key: ObjectKey = Self::path_to_key(&info.path);
+
+            if !self.inner().contains_key(&key) {
+                self.restored.insert(key, info);
+                queued += 1;
+            }
+        }
+
+        queued
+    }
+
+    /// Entries worth writing to the disk snapshot: everything with loaded
+    /// metadata, plus snapshot entries not validated this session.
+    /// Directories with computed sizes come first, as they are the most
+    /// expensive to rebuild.
+    #[must_use]
+    pub fn persistable_entries(&self) -> Vec<ObjectInfo> {
+        let mut entries: Vec<ObjectInfo> = self
+            .inner()
+            .iter()
+            .filter_map(|(_, entry)| match entry {
+                CacheEntry::Success(info) if info.metadata_loaded => Some(info),
+                _ => None,
+            })
+            .collect();
+
+        entries.extend(self.restored.iter().map(|entry| entry.value().clone()));
+
+        entries.sort_by_key(|info: &ObjectInfo| -> (bool, bool) {
+            (!info.is_dir, info.size == 0 && info.items_count == 0)
+        });
+
+        entries
+    }
+
     /// Get entry by path (convenience method) with path normalization tracing
     #[instrument(skip(self), fields(path = %path.as_ref().display()))]
     pub async fn get_by_path<P: AsRef<Path>>(&self, path: P) -> Option<ObjectInfo> {
@@ -281,7 +451,7 @@ impl ObjectInfoCache {
             marker = "CACHE_OPERATION",
             operation_type = "path_to_key_conversion",
             original_path = %path.as_ref().display(),
-            cache_key = %key,
+            cache_key = ?key,
             "Converted path to cache key"
         );
         self.get(&key).await
@@ -289,7 +459,7 @@ impl ObjectInfoCache {
 
     /// Get entry or load if missing with comprehensive tracing
     #[expect(clippy::too_many_lines, reason = "Expected due to logging")]
-    #[instrument(skip(self, loader), fields(cache_key = %key, operation_type = "cache_get_or_load"))]
+    #[instrument(skip(self, loader), fields(cache_key = ?key, operation_type = "cache_get_or_load"))]
     pub async fn get_or_load<F, Fut>(
         &self,
         key: ObjectKey,
@@ -301,14 +471,14 @@ impl ObjectInfoCache {
     {
         // Fast path: check if already cached
         let fast_check_start = Instant::now();
-        if let Some(entry) = self.inner.get(&key).await {
+        if let Some(entry) = self.inner().get(&key).await {
             let fast_check_duration = fast_check_start.elapsed();
             match entry {
                 CacheEntry::Success(info) => {
                     info!(
                         marker = "CACHE_OPERATION",
                         operation_type = "fast_path_hit",
-                        cache_key = %key,
+                        cache_key = ?key,
                         fast_check_time_us = fast_check_duration.as_micros(),
                         "Fast path cache hit in get_or_load"
                     );
@@ -318,11 +488,11 @@ impl ObjectInfoCache {
                     warn!(
                         marker = "CACHE_OPERATION",
                         operation_type = "fast_path_failed_entry",
-                        cache_key = %key,
+                        cache_key = ?key,
                         "Fast path found failed entry"
                     );
                     return Err(CacheError::LoaderFailed(format!(
-                        "Previous load failed for key: {key}"
+                        "Previous load failed for key: {}", key.display()
                     ))
                     .into());
                 }
@@ -332,24 +502,24 @@ impl ObjectInfoCache {
         info!(
             marker = "CACHE_OPERATION",
             operation_type = "slow_path_loading",
-            cache_key = %key,
+            cache_key = ?key,
             fast_check_time_us = fast_check_start.elapsed().as_micros(),
             "Taking slow path - loading with try_get_with"
         );
 
         // Slow path: load with proper stats tracking
         let load_start: Instant = Instant::now();
-        let key_clone: Arc<str> = key.clone();
+        let key_clone: ObjectKey = key.clone();
         let stats: Arc<CacheStats> = self.stats.clone();
         let enable_stats: bool = self.config.enable_stats;
 
         let result: Result<CacheEntry, Arc<AppError>> = self
-            .inner
+            .inner()
             .try_get_with(key.clone(), async move {
                 info!(
                     marker = "CACHE_OPERATION",
                     operation_type = "loader_function_start",
-                    cache_key = %key_clone,
+                    cache_key = ?key_clone,
                     "Starting loader function execution"
                 );
                 
@@ -365,7 +535,7 @@ impl ObjectInfoCache {
                         info!(
                             marker = "CACHE_OPERATION", 
                             operation_type = "loader_success",
-                            cache_key = %key_clone,
+                            cache_key = ?key_clone,
                             load_duration_ms = load_duration.as_millis(),
                             "Loader function succeeded, caching result"
                         );
@@ -375,7 +545,7 @@ impl ObjectInfoCache {
                         error!(
                             marker = "CACHE_OPERATION",
                             operation_type = "loader_failure", 
-                            cache_key = %key_clone,
+                            cache_key = ?key_clone,
                             load_duration_ms = load_duration.as_millis(),
                             error = %e,
                             "Loader function failed, not caching failure"
@@ -392,7 +562,7 @@ impl ObjectInfoCache {
                 info!(
                     marker = "CACHE_OPERATION",
                     operation_type = "get_or_load_success",
-                    cache_key = %key,
+                    cache_key = ?key,
                     total_duration_ms = load_start.elapsed().as_millis(),
                     "get_or_load completed successfully"
                 );
@@ -402,16 +572,16 @@ impl ObjectInfoCache {
                 error!(
                     marker = "CACHE_OPERATION", 
                     operation_type = "get_or_load_failed_entry",
-                    cache_key = %key,
+                    cache_key = ?key,
                     "get_or_load returned failed entry"
                 );
-                Err(CacheError::LoaderFailed(format!("Load failed for key: {key}")).into())
+                Err(CacheError::LoaderFailed(format!("Load failed for key: {}", key.display())).into())
             }
             Err(e) => {
                 error!(
                     marker = "CACHE_OPERATION",
                     operation_type = "get_or_load_error", 
-                    cache_key = %key,
+                    cache_key = ?key,
                     error = %e,
                     "get_or_load encountered error"
                 );
@@ -431,21 +601,21 @@ impl ObjectInfoCache {
         F: FnOnce() -> Fut + Send,
         Fut: std::future::Future<Output = Result<ObjectInfo, AppError>> + Send,
     {
-        let key: Arc<str> = Self::path_to_key(path);
+        let key: ObjectKey = Self::path_to_key(path);
         self.get_or_load(key, loader).await
     }
 
     /// Insert entry into cache with operational tracing
-    #[instrument(skip(self, info), fields(cache_key = %key))]
+    #[instrument(skip(self, info), fields(cache_key = ?key))]
     pub async fn insert(&self, key: ObjectKey, info: ObjectInfo) {
         let insert_start: Instant = Instant::now();
-        self.inner.insert(key.clone(), CacheEntry::Success(info.clone())).await;
+        self.inner().insert(key.clone(), CacheEntry::Success(info.clone())).await;
         let insert_duration: Duration = insert_start.elapsed();
         
         info!(
             marker = "CACHE_OPERATION",
             operation_type = "cache_insert",
-            cache_key = %key,
+            cache_key = ?key,
             insert_duration_us = insert_duration.as_micros(),
             path = %info.path.display(),
             "Inserted entry into cache"
@@ -460,7 +630,7 @@ impl ObjectInfoCache {
             marker = "CACHE_OPERATION",
             operation_type = "insert_path_conversion",
             original_path = %path.as_ref().display(),
-            cache_key = %key,
+            cache_key = ?key,
             "Converting path to key for cache insertion"
         );
         self.insert(key, info).await;
@@ -476,13 +646,15 @@ impl ObjectInfoCache {
 
         // Insert entries
         for (key, entry) in cache_entries {
-            self.inner.insert(key, entry).await;
+            self.inner().insert(key, entry).await;
         }
     }
 
     /// Remove entry from cache
     pub async fn remove(&self, key: &ObjectKey) {
-        self.inner.invalidate(key).await;
+        self.inner().invalidate(key).await;
+        self.listings.invalidate(key).await;
+        self.restored.remove(key);
     }
 
     /// Remove entry by path
@@ -497,8 +669,102 @@ impl ObjectInfoCache {
         let keys: Vec<_> = keys.into_iter().collect();
 
         for key in keys {
-            self.inner.invalidate(&key).await;
+            self.inner().invalidate(&key).await;
+            self.restored.remove(&key);
+        }
+    }
+
+    /// Drop a path that changed on disk together with its cached ancestors,
+    /// whose sizes and listings include it. A directory that is gone or was
+    /// replaced also takes everything cached below it along, so a directory
+    /// recreated under the same name starts out clean. Returns the number of
+    /// keys dropped.
+    pub async fn invalidate_path<P: AsRef<Path>>(&self, path: P) -> u64 {
+        self.invalidate_paths([path.as_ref()]).await
+    }
+
+    /// [`Self::invalidate_path`] for a batch of changes, e.g. one watcher
+    /// flush: the cache is scanned for descendants once for the whole batch
+    /// rather than once per changed directory.
+    pub async fn invalidate_paths<'a>(&self, paths: impl IntoIterator<Item = &'a Path>) -> u64 {
+        let mut gone_dirs: HashSet<PathBuf> = HashSet::new();
+        let mut keys: HashSet<ObjectKey> = HashSet::new();
+
+        for path in paths {
+            let key: ObjectKey = Self::path_to_key(path);
+
+            let was_dir: bool = self.listings.contains_key(&key)
+                || matches!(self.inner().get(&key).await, Some(CacheEntry::Success(info)) if info.is_dir)
+                || self.restored.get(&key).is_some_and(|info| info.is_dir);
+
+            if was_dir || tokio::fs::symlink_metadata(path).await.is_err() {
+                gone_dirs.insert(path.to_path_buf());
+            }
+
+            keys.extend(path.ancestors().map(Self::path_to_key));
+        }
+
+        if !gone_dirs.is_empty() {
+            keys.extend(self.keys_below(&gone_dirs));
+        }
+
+        let mut dropped: u64 = 0;
+
+        for key in &keys {
+            if self.inner().contains_key(key) || self.listings.contains_key(key) || self.restored.contains_key(key) {
+                dropped += 1;
+            }
+
+            self.remove(key).await;
+        }
+
+        if self.config.enable_stats {
+            self.stats.record_invalidation(dropped);
+        }
+
+        dropped
+    }
+
+    /// Every cached key strictly below one of `dirs`, in one pass over the
+    /// cache.
+    fn keys_below(&self, dirs: &HashSet<PathBuf>) -> HashSet<ObjectKey> {
+        let below = |key: &ObjectKey| -> bool {
+            Path::new(&**key)
+                .ancestors()
+                .skip(1)
+                .any(|ancestor: &Path| dirs.contains(ancestor))
+        };
+
+        let mut keys: HashSet<ObjectKey> = HashSet::new();
+
+        keys.extend(self.inner().iter().map(|(key, _)| ObjectKey::clone(&key)).filter(|key| below(key)));
+        keys.extend(self.listings.iter().map(|(key, _)| ObjectKey::clone(&key)).filter(|key| below(key)));
+        keys.extend(self.restored.iter().map(|entry| entry.key().clone()).filter(|key| below(key)));
+
+        keys
+    }
+
+    /// Parent directories of cached entries (at most `limit`), i.e. the
+    /// directories to watch for invalidation.
+    #[must_use]
+    pub fn cached_dirs(&self, limit: usize) -> Vec<PathBuf> {
+        let mut dirs: HashSet<PathBuf> = HashSet::new();
+
+        let inner: Cache<ObjectKey, CacheEntry> = self.inner();
+
+        for (key, _) in &inner {
+            if dirs.len() >= limit {
+                break;
+            }
+
+            if let Some(parent) = Path::new(&**key).parent()
+                && !parent.as_os_str().is_empty()
+            {
+                dirs.insert(parent.to_path_buf());
+            }
         }
+
+        dirs.into_iter().collect()
     }
 
     /// Invalidate entries matching a predicate
@@ -507,7 +773,7 @@ impl ObjectInfoCache {
         F: Fn(&ObjectKey, &ObjectInfo) -> bool + Send + Sync + 'static,
     {
         // This is more efficient than iterating and removing individually
-        let _ = self.inner.invalidate_entries_if(move |k, v| match v {
+        let _ = self.inner().invalidate_entries_if(move |k, v| match v {
             CacheEntry::Success(info) => predicate(k, info),
             CacheEntry::Failed => false,
         });
@@ -515,7 +781,9 @@ impl ObjectInfoCache {
 
     /// Clear all entries
     pub fn clear(&self) {
-        self.inner.invalidate_all();
+        self.inner().invalidate_all();
+        self.listings.invalidate_all();
+        self.restored.clear();
 
         if self.config.enable_stats {
             // Reset stats (create new Arc since we can't modify the existing one)
@@ -527,13 +795,26 @@ impl ObjectInfoCache {
     /// Get current cache entry count
     #[must_use] 
     pub fn entry_count(&self) -> u64 {
-        self.inner.entry_count()
+        self.inner().entry_count()
+    }
+
+    /// Memory budget in bytes: `max_memory_mb`, or less while shrunk by
+    /// `shrink_to`
+    #[must_use]
+    pub fn memory_budget(&self) -> u64 {
+        self.budget.load(Ordering::Relaxed)
     }
 
-    /// Get weighted size (approximate memory usage)
+    /// Entries from the disk snapshot still waiting for validation
+    #[must_use]
+    pub fn restored_len(&self) -> usize {
+        self.restored.len()
+    }
+
+    /// Get weighted size (estimated memory usage in bytes)
     #[must_use] 
     pub fn weighted_size(&self) -> u64 {
-        self.inner.weighted_size()
+        self.inner().weighted_size()
     }
 
     /// Get cache statistics snapshot
@@ -548,6 +829,10 @@ impl ObjectInfoCache {
                 loads: 0,
                 evictions: 0,
                 load_exceptions: 0,
+                invalidations: 0,
+                invalidated_keys: 0,
+                restored_hits: 0,
+                restored_stale: 0,
                 total_load_time: Duration::ZERO,
                 average_load_penalty: Duration::ZERO,
             }
@@ -556,7 +841,7 @@ impl ObjectInfoCache {
 
     /// Run cache maintenance (force cleanup)
     pub async fn run_pending_tasks(&self) {
-        self.inner.run_pending_tasks().await;
+        self.inner().run_pending_tasks().await;
     }
 
     /// Print cache statistics for debugging
@@ -581,6 +866,8 @@ impl ObjectInfoCache {
             Load Count: {}\n\
             Exception Rate: {:.2}%\n\
             Evictions: {}\n\
+            Invalidations: {} ({} keys)\n\
+            Restored: {} valid, {} stale, {} pending\n\
             Average Load Time: {:?}\n\
             Uptime: {:?}",
             entry_count,
@@ -591,6 +878,11 @@ impl ObjectInfoCache {
             stats.loads,
             stats.exception_rate() * 100.0,
             stats.evictions,
+            stats.invalidations,
+            stats.invalidated_keys,
+            stats.restored_hits,
+            stats.restored_stale,
+            self.restored.len(),
             stats.average_load_penalty,
             uptime
         );
@@ -610,12 +902,17 @@ impl ObjectInfoCache {
             );
         }
 
-        // Warn if memory usage is high
-        if memory_usage_mb > self.config.max_memory_mb {
-            info!(
-                "Cache memory usage ({} MB) exceeds configured limit ({} MB)",
-                memory_usage_mb, self.config.max_memory_mb
-            );
+        // Warn once when usage gets close to the budget (eviction keeps it
+        // from going over)
+        if self.is_near_capacity() {
+            if !self.budget_warned.swap(true, Ordering::Relaxed) {
+                warn!(
+                    "Cache memory usage ({} MB) is close to the configured limit ({} MB)",
+                    memory_usage_mb, self.config.max_memory_mb
+                );
+            }
+        } else {
+            self.budget_warned.store(false, Ordering::Relaxed);
         }
 
         // Warn if exception rate is high
@@ -627,10 +924,11 @@ impl ObjectInfoCache {
         }
 
         debug!(
-            "Cache health check: {} entries, {} MB, hit rate: {:.2}%",
+            "Cache health check: {} entries, {} MB, hit rate: {:.2}%, {} invalidated keys",
             entry_count,
             memory_usage_mb,
-            stats.hit_rate() * 100.0
+            stats.hit_rate() * 100.0,
+            stats.invalidated_keys
         );
     }
 
@@ -639,19 +937,86 @@ impl ObjectInfoCache {
     /// Get approximate memory usage per entry
     pub fn avg_entry_size(&self) -> usize {
         let entry_count: u64 = self.entry_count();
-        
-        if entry_count == 0 {
-            0
-        } else {
-            (self.weighted_size() / entry_count) as usize
-        }
+
+        self.weighted_size()
+            .checked_div(entry_count)
+            .unwrap_or(0) as usize
     }
 
     #[must_use]
     #[expect(clippy::cast_precision_loss, reason = "Expected accuracy loss")]
-    /// Check if cache is near capacity
+    /// Check if cache is near its memory budget
     pub fn is_near_capacity(&self) -> bool {
-        self.entry_count() as f64 > self.config.max_capacity as f64 * 0.9
+        self.weighted_size() as f64 > self.memory_budget() as f64 * NEAR_BUDGET_RATIO
+    }
+
+    /// Lower the memory budget to `target_bytes` (memory pressure), below
+    /// the configured one until `restore_budget`. The entry cache is rebuilt
+    /// with the smaller capacity, so it also stays within it afterwards.
+    /// Snapshot entries awaiting validation go first, then files, then
+    /// directories whose sizes are expensive to recompute. Returns the bytes
+    /// freed.
+    pub async fn shrink_to(&self, target_bytes: u64) -> u64 {
+        self.restored.clear();
+
+        let freed: u64 = self.rebuild(target_bytes).await;
+
+        info!(
+            "Shrank cache by ~{} KB to a budget of ~{} KB",
+            freed / 1024,
+            target_bytes / 1024
+        );
+
+        freed
+    }
+
+    /// Return to the configured memory budget after `shrink_to`.
+    pub async fn restore_budget(&self) {
+        let configured: u64 = self.config.max_memory_mb.saturating_mul(1024 * 1024);
+
+        if self.memory_budget() < configured {
+            self.rebuild(configured).await;
+            info!("Cache budget restored to {} MB", self.config.max_memory_mb);
+        }
+    }
+
+    /// Replace the entry cache by one of `capacity` bytes, carrying over
+    /// directories first, then files, while they fit. Returns the bytes
+    /// dropped.
+    async fn rebuild(&self, capacity: u64) -> u64 {
+        let old: Cache<ObjectKey, CacheEntry> = self.inner();
+        old.run_pending_tasks().await;
+
+        let mut survivors: Vec<(ObjectKey, CacheEntry, bool, u32)> = old
+            .iter()
+            .map(|(key, entry)| {
+                let is_dir: bool = matches!(&entry, CacheEntry::Success(info) if info.is_dir);
+                let weight: u32 = entry_weight(&key, &entry);
+                (ObjectKey::clone(&key), entry, is_dir, weight)
+            })
+            .collect();
+
+        survivors.sort_by_key(|(_, _, is_dir, _)| !*is_dir);
+
+        let new: Cache<ObjectKey, CacheEntry> = Self::build_inner(&self.config, &self.stats, capacity);
+        let (mut kept, mut freed): (u64, u64) = (0, 0);
+
+        for (key, entry, _, weight) in survivors {
+            if kept + u64::from(weight) > capacity {
+                freed += u64::from(weight);
+                continue;
+            }
+
+            new.insert(key, entry).await;
+            kept += u64::from(weight);
+        }
+
+        new.run_pending_tasks().await;
+
+        *self.inner.write().unwrap_or_else(PoisonError::into_inner) = new;
+        self.budget.store(capacity, Ordering::Relaxed);
+
+        freed
     }
 }
 
@@ -669,7 +1034,7 @@ impl ObjectInfoCache {
 
         // Warm parent directory first
         if let Some(parent) = base_path.parent() {
-            let parent_key: Arc<str> = Self::path_to_key(parent);
+            let parent_key: ObjectKey = Self::path_to_key(parent);
             if self.get(&parent_key).await.is_none() {
                 match ObjectInfo::from_path_direct(parent).await {
                     Ok(info) => {
@@ -697,9 +1062,12 @@ impl ObjectInfoCache {
 
             match tokio::fs::read_dir(&current_path).await {
                 Ok(mut entries) => {
+                    let mut listing: Vec<PathBuf> = Vec::new();
+
                     while let Some(entry) = entries.next_entry().await? {
                         let entry_path: PathBuf = entry.path();
-                        let entry_key: Arc<str> = Self::path_to_key(&entry_path);
+                        let entry_key: ObjectKey = Self::path_to_key(&entry_path);
+                        listing.push(entry_path.clone());
 
                         if self.get(&entry_key).await.is_none() {
                             match ObjectInfo::from_path_direct(&entry_path).await {
@@ -721,6 +1089,10 @@ impl ObjectInfoCache {
                             }
                         }
                     }
+
+                    self.listings
+                        .insert(Self::path_to_key(&current_path), Arc::from(listing))
+                        .await;
                 }
 
                 Err(e) => warn!(
@@ -741,6 +1113,19 @@ impl ObjectInfoCache {
         Ok(warmed_count)
     }
 
+    /// Children of a directory read by a previous warm, if the listing and
+    /// every child entry are still cached.
+    pub async fn cached_listing<P: AsRef<Path>>(&self, dir: P) -> Option<Vec<ObjectInfo>> {
+        let listing: Arc<[PathBuf]> = self.listings.get(&Self::path_to_key(dir)).await?;
+        let mut children: Vec<ObjectInfo> = Vec::with_capacity(listing.len());
+
+        for path in listing.iter() {
+            children.push(self.get(&Self::path_to_key(path)).await?);
+        }
+
+        Some(children)
+    }
+
     /// Smart warming based on navigation patterns - optimized for UI responsiveness
     #[instrument(skip(self), fields(current_path = %current_path.as_ref().display()))]
     pub async fn warm_for_navigation<P: AsRef<Path>>(
@@ -786,4 +1171,141 @@ impl std::fmt::Debug for ObjectInfoCache {
             .field("entry_count", &self.entry_count())
             .finish()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(path: &str, is_dir: bool) -> ObjectInfo {
+        ObjectInfo {
+            path: PathBuf::from(path),
+            is_dir,
+            metadata_loaded: true,
+            ..ObjectInfo::default()
+        }
+    }
+
+    async fn cached(cache: &ObjectInfoCache, path: &str) -> bool {
+        cache.get(&ObjectInfoCache::path_to_key(path)).await.is_some()
+    }
+
+    #[tokio::test]
+    async fn invalidate_path_drops_everything_below_a_removed_directory() {
+        let cache: ObjectInfoCache = ObjectInfoCache::new();
+        let root: &str = "/nonexistent-fsm-test";
+
+        let entries: [(String, bool); 4] = [
+            (format!("{root}/d"), true),
+            (format!("{root}/d/a"), false),
+            (format!("{root}/d/sub/b"), false),
+            (format!("{root}/dx"), false),
+        ];
+
+        for (path, is_dir) in entries {
+            cache.insert_path(&path, object(&path, is_dir)).await;
+        }
+        cache.restore([object(&format!("{root}/d/sub"), true)]);
+
+        let dropped: u64 = cache.invalidate_path(format!("{root}/d")).await;
+
+        assert_eq!(dropped, 4);
+        assert!(!cached(&cache, &format!("{root}/d/a")).await);
+        assert!(!cached(&cache, &format!("{root}/d/sub/b")).await);
+        assert_eq!(cache.restored_len(), 0);
+
+        // A sibling sharing the name's prefix is not below it
+        assert!(cached(&cache, &format!("{root}/dx")).await);
+    }
+
+    #[tokio::test]
+    async fn shrink_to_keeps_the_cache_within_the_lower_budget() {
+        let cache: ObjectInfoCache = ObjectInfoCache::new();
+
+        for i in 0..200 {
+            let path: String = format!("/nonexistent-fsm-test/f{i}");
+            cache.insert_path(&path, object(&path, false)).await;
+        }
+        cache.insert_path("/nonexistent-fsm-test", object("/nonexistent-fsm-test", true)).await;
+        cache.run_pending_tasks().await;
+
+        let target: u64 = cache.weighted_size() / 4;
+        cache.shrink_to(target).await;
+
+        assert_eq!(cache.memory_budget(), target);
+        assert!(cache.weighted_size() <= target);
+        assert!(cached(&cache, "/nonexistent-fsm-test").await, "directories are kept first");
+
+        // Later inserts are evicted down to the lowered budget too
+        for i in 200..400 {
+            let path: String = format!("/nonexistent-fsm-test/f{i}");
+            cache.insert_path(&path, object(&path, false)).await;
+        }
+        cache.run_pending_tasks().await;
+
+        assert!(cache.weighted_size() <= target);
+
+        cache.restore_budget().await;
+
+        assert_eq!(cache.memory_budget(), cache.config().max_memory_mb * 1024 * 1024);
+        assert!(cached(&cache, "/nonexistent-fsm-test").await);
+    }
+
+    #[tokio::test]
+    async fn invalidate_paths_drops_a_whole_batch_and_its_ancestors() {
+        let cache: ObjectInfoCache = ObjectInfoCache::new();
+        let root: &str = "/nonexistent-fsm-test";
+
+        let entries: [(String, bool); 5] = [
+            (root.to_string(), true),
+            (format!("{root}/a"), true),
+            (format!("{root}/a/x"), false),
+            (format!("{root}/b/y"), false),
+            (format!("{root}/c"), false),
+        ];
+
+        for (path, is_dir) in entries {
+            cache.insert_path(&path, object(&path, is_dir)).await;
+        }
+
+        let (a, b): (PathBuf, PathBuf) = (PathBuf::from(format!("{root}/a")), PathBuf::from(format!("{root}/b")));
+        let dropped: u64 = cache.invalidate_paths([a.as_path(), b.as_path()]).await;
+
+        assert_eq!(dropped, 4);
+        assert!(!cached(&cache, root).await);
+        assert!(!cached(&cache, &format!("{root}/a/x")).await);
+        assert!(!cached(&cache, &format!("{root}/b/y")).await);
+        assert!(cached(&cache, &format!("{root}/c")).await);
+    }
+
+    #[test]
+    fn entry_weight_counts_the_path_on_the_heap() {
+        let short: String = "/nonexistent-fsm-test/a".to_string();
+        let long: String = format!("/nonexistent-fsm-test/{}", "a".repeat(1000));
+
+        let weight = |path: &str| -> u32 {
+            entry_weight(&ObjectInfoCache::path_to_key(path), &CacheEntry::Success(object(path, false)))
+        };
+
+        assert!(weight(&long) >= weight(&short) + 1998, "key and path both grow");
+        assert!(entry_weight(&ObjectInfoCache::path_to_key(&short), &CacheEntry::Failed) < weight(&short));
+    }
+
+    #[tokio::test]
+    async fn eviction_keeps_the_cache_within_max_memory_mb() {
+        let cache: ObjectInfoCache = ObjectInfoCache::with_config(CacheConfig {
+            max_memory_mb: 1,
+            ..CacheConfig::default()
+        });
+        let padding: String = "p".repeat(500);
+
+        for i in 0..3000 {
+            let path: String = format!("/nonexistent-fsm-test/{padding}{i}");
+            cache.insert_path(&path, object(&path, false)).await;
+        }
+        cache.run_pending_tasks().await;
+
+        assert!(cache.weighted_size() <= 1024 * 1024);
+        assert!(cache.entry_count() < 3000);
+        assert!(cache.is_near_capacity());
+    }
+}
@@ -0,0 +1,352 @@
+//! ``src/cache/disk_cache.rs``
+//! ============================================================================
+//! # Disk Snapshot of the `ObjectInfoCache`
+//!
+//! Directory sizes found by the size task are expensive to rebuild, so the
+//! cache is written to the XDG cache directory on shutdown and every
+//! `CacheConfig::persist_interval`, and read back at startup. Loaded entries
+//! are not trusted blindly: they wait in the cache until first looked up and
+//! are only served if their mtime (a directory's own mtime for directories)
+//! still matches the disk.
+//!
+//! ## Format
+//! A fixed header (magic, format version, entry count, payload length and
+//! CRC-32 of the payload) followed by the bincode-encoded entries, most
+//! valuable first, cut off at `CacheConfig::persist_max_mb`. A file with
+//! another magic or version, a short payload or a bad checksum is ignored
+//! and replaced by the next save. Writes go through `write_versioned`, so a
+//! crash never leaves a half-written snapshot behind and an older snapshot
+//! never replaces a newer one.
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bincode::config::{self, Configuration};
+use thiserror::Error;
+use tokio::sync::watch;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::cache::cache_manager::ObjectInfoCache;
+use crate::fs::object_info::ObjectInfo;
+use crate::util::persist::{next_version, write_versioned};
+
+/// File signature.
+const MAGIC: &[u8; 8] = b"FSMCACHE";
+
+/// How long shutdown waits for a startup load still in progress before it
+/// gives up on saving.
+pub const SHUTDOWN_LOAD_WAIT: Duration = Duration::from_secs(2);
+
+/// Bumped whenever the encoding of `ObjectInfo` changes.
+pub const FORMAT_VERSION: u32 = 3;
+
+/// Magic, version, entry count, payload length, checksum.
+const HEADER_LEN: usize = 8 + 4 + 8 + 8 + 4;
+
+/// Shortest allowed interval between periodic saves.
+const MIN_SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Reasons a snapshot cannot be used.
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("not a cache snapshot")]
+    BadMagic,
+
+    #[error("unsupported snapshot version {0} (expected {FORMAT_VERSION})")]
+    UnsupportedVersion(u32),
+
+    #[error("snapshot is truncated")]
+    Truncated,
+
+    #[error("snapshot checksum mismatch")]
+    Checksum,
+
+    #[error("corrupt snapshot entry: {0}")]
+    Decode(String),
+}
+
+const fn bincode_config() -> Configuration {
+    config::standard()
+}
+
+/// Encode `entries` in order until the file would exceed `max_bytes`.
+/// Entries that cannot be encoded are skipped. Returns the file contents
+/// and the number of entries written.
+#[must_use]
+pub fn encode(entries: &[ObjectInfo], max_bytes: usize) -> (Vec<u8>, usize) {
+    let budget: usize = max_bytes.saturating_sub(HEADER_LEN);
+    let mut payload: Vec<u8> = Vec::new();
+    let mut count: usize = 0;
+
+    for info in entries {
+        let Ok(bytes) = bincode::serde::encode_to_vec(info, bincode_config()) else {
+            continue;
+        };
+
+        if payload.len() + bytes.len() > budget {
+            break;
+        }
+
+        payload.extend_from_slice(&bytes);
+        count += 1;
+    }
+
+    let mut out: Vec<u8> = Vec::with_capacity(HEADER_LEN + payload.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    out.extend_from_slice(&(count as u64).to_le_bytes());
+    out.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    out.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+    out.extend_from_slice(&payload);
+
+    (out, count)
+}
+
+/// Decode a snapshot, rejecting it as a whole if anything is off.
+pub fn decode(bytes: &[u8]) -> Result<Vec<ObjectInfo>, SnapshotError> {
+    let (magic, rest) = bytes.split_first_chunk::<8>().ok_or(SnapshotError::Truncated)?;
+
+    if magic != MAGIC {
+        return Err(SnapshotError::BadMagic);
+    }
+
+    let (version, rest) = rest.split_first_chunk::<4>().ok_or(SnapshotError::Truncated)?;
+    let version: u32 = u32::from_le_bytes(*version);
+
+    if version != FORMAT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion(version));
+    }
+
+    let (count, rest) = rest.split_first_chunk::<8>().ok_or(SnapshotError::Truncated)?;
+    let (len, rest) = rest.split_first_chunk::<8>().ok_or(SnapshotError::Truncated)?;
+    let (checksum, payload) = rest.split_first_chunk::<4>().ok_or(SnapshotError::Truncated)?;
+
+    if payload.len() as u64 != u64::from_le_bytes(*len) {
+        return Err(SnapshotError::Truncated);
+    }
+
+    if crc32fast::hash(payload) != u32::from_le_bytes(*checksum) {
+        return Err(SnapshotError::Checksum);
+    }
+
+    let mut entries: Vec<ObjectInfo> = Vec::new();
+    let mut offset: usize = 0;
+
+    while offset < payload.len() {
+        let (info, read): (ObjectInfo, usize) =
+            bincode::serde::decode_from_slice(&payload[offset..], bincode_config())
+                .map_err(|e: bincode::error::DecodeError| -> SnapshotError {
+                    SnapshotError::Decode(e.to_string())
+                })?;
+
+        entries.push(info);
+        offset += read;
+    }
+
+    if entries.len() as u64 != u64::from_le_bytes(*count) {
+        return Err(SnapshotError::Decode("entry count mismatch".to_string()));
+    }
+
+    Ok(entries)
+}
+
+/// Read the snapshot at `path` and queue its entries in `cache`. A missing
+/// file is not an error. Returns the number of entries queued.
+pub async fn load(cache: &ObjectInfoCache, path: &Path) -> Result<usize, SnapshotError> {
+    let bytes: Vec<u8> = match tokio::fs::read(path).await {
+        Ok(bytes) => bytes,
+
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(0),
+
+        Err(e) => return Err(e.into()),
+    };
+
+    let entries: Vec<ObjectInfo> = tokio::task::spawn_blocking(move || decode(&bytes))
+        .await
+        .map_err(std::io::Error::other)??;
+
+    Ok(cache.restore(entries))
+}
+
+/// Write the cache to `path`, limited to `persist_max_mb`. Returns the
+/// number of entries written; none if a later save (the periodic one racing
+/// shutdown) got there first.
+pub async fn save(cache: &ObjectInfoCache, path: &Path) -> Result<usize, SnapshotError> {
+    let version: u64 = next_version(path);
+    let max_bytes: usize = usize::try_from(cache.config().persist_max_mb.saturating_mul(1024 * 1024))
+        .unwrap_or(usize::MAX);
+    let cache: ObjectInfoCache = cache.clone();
+
+    let (bytes, count) = tokio::task::spawn_blocking(move || {
+        encode(&cache.persistable_entries(), max_bytes)
+    })
+    .await
+    .map_err(std::io::Error::other)?;
+
+    if !write_versioned(path, version, &bytes).await? {
+        return Ok(0);
+    }
+
+    Ok(count)
+}
+
+/// Load the snapshot at `path` in the background, then write it back every
+/// `persist_interval` for as long as the runtime lives. The returned
+/// receiver turns `true` once the load is over (restored or rejected); a
+/// save before that would replace the snapshot with this session's entries
+/// alone.
+pub fn spawn_persistence(cache: Arc<ObjectInfoCache>, path: PathBuf) -> watch::Receiver<bool> {
+    let (loaded_tx, loaded_rx) = watch::channel(false);
+
+    tokio::spawn(async move {
+        match load(&cache, &path).await {
+            Ok(count) => info!("Restored {} cache entries from {}", count, path.display()),
+
+            Err(e) => warn!("Ignoring cache snapshot {}: {}", path.display(), e),
+        }
+
+        let _ = loaded_tx.send(true);
+
+        let period: Duration = cache.config().persist_interval.max(MIN_SAVE_INTERVAL);
+        let mut interval: tokio::time::Interval = tokio::time::interval_at(Instant::now() + period, period);
+
+        loop {
+            interval.tick().await;
+
+            match save(&cache, &path).await {
+                Ok(count) => debug!("Saved {} cache entries to {}", count, path.display()),
+
+                Err(e) => warn!("Failed to save cache snapshot: {}", e),
+            }
+        }
+    });
+
+    loaded_rx
+}
+
+/// Wait up to `timeout` for the load started by `spawn_persistence`.
+/// Returns whether it finished.
+pub async fn wait_for_load(loaded: &mut watch::Receiver<bool>, timeout: Duration) -> bool {
+    tokio::time::timeout(timeout, loaded.wait_for(|done: &bool| -> bool { *done }))
+        .await
+        .is_ok_and(|result| -> bool { result.is_ok() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, size: u64) -> ObjectInfo {
+        ObjectInfo {
+            path: Path::new("/data").join(name),
+            name: name.into(),
+            size,
+            is_dir: true,
+            metadata_loaded: true,
+            ..ObjectInfo::default()
+        }
+    }
+
+    fn snapshot() -> Vec<u8> {
+        encode(&[entry("a", 1), entry("b", 2)], usize::MAX).0
+    }
+
+    #[test]
+    fn decode_reads_back_what_encode_wrote() {
+        let entries: Vec<ObjectInfo> = vec![entry("a", 1), entry("b", 2), entry("c", 3)];
+        let (bytes, count) = encode(&entries, usize::MAX);
+
+        assert_eq!(count, 3);
+        assert_eq!(decode(&bytes).expect("valid snapshot"), entries);
+    }
+
+    #[test]
+    fn encode_stops_at_the_size_limit() {
+        let entries: Vec<ObjectInfo> = vec![entry("a", 1), entry("b", 2), entry("c", 3)];
+        let (full, _) = encode(&entries, usize::MAX);
+        let one_short: usize = full.len() - 1;
+
+        let (bytes, count) = encode(&entries, one_short);
+
+        assert_eq!(count, 2);
+        assert!(bytes.len() <= one_short);
+        assert_eq!(decode(&bytes).expect("valid snapshot"), entries[..2]);
+
+        let (bytes, count) = encode(&entries, 0);
+        assert_eq!(count, 0);
+        assert!(decode(&bytes).expect("valid snapshot").is_empty());
+    }
+
+    #[test]
+    fn decode_rejects_another_magic() {
+        let mut bytes: Vec<u8> = snapshot();
+        bytes[0] ^= 0xFF;
+
+        assert!(matches!(decode(&bytes), Err(SnapshotError::BadMagic)));
+    }
+
+    #[test]
+    fn decode_rejects_another_version() {
+        let mut bytes: Vec<u8> = snapshot();
+        bytes[8..12].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+
+        assert!(matches!(decode(&bytes), Err(SnapshotError::UnsupportedVersion(v)) if v == FORMAT_VERSION + 1));
+    }
+
+    #[test]
+    fn decode_rejects_truncated_files() {
+        let bytes: Vec<u8> = snapshot();
+
+        for len in [0, 4, 10, HEADER_LEN - 1, HEADER_LEN, bytes.len() - 1] {
+            assert!(matches!(decode(&bytes[..len]), Err(SnapshotError::Truncated)), "length {len}");
+        }
+    }
+
+    #[test]
+    fn decode_rejects_a_flipped_payload_byte() {
+        let mut bytes: Vec<u8> = snapshot();
+        let last: usize = bytes.len() - 1;
+        bytes[last] ^= 0x01;
+
+        assert!(matches!(decode(&bytes), Err(SnapshotError::Checksum)));
+    }
+
+    #[test]
+    fn decode_rejects_a_wrong_entry_count() {
+        let mut bytes: Vec<u8> = snapshot();
+        bytes[12..20].copy_from_slice(&3_u64.to_le_bytes());
+
+        assert!(matches!(decode(&bytes), Err(SnapshotError::Decode(_))));
+    }
+
+    #[test]
+    fn decode_rejects_an_undecodable_payload() {
+        // Checksummed garbage: passes the header checks, fails bincode
+        let payload: &[u8] = &[0xFF; 16];
+        let mut bytes: Vec<u8> = Vec::with_capacity(HEADER_LEN + payload.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&1_u64.to_le_bytes());
+        bytes.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&crc32fast::hash(payload).to_le_bytes());
+        bytes.extend_from_slice(payload);
+
+        assert!(matches!(decode(&bytes), Err(SnapshotError::Decode(_))));
+    }
+
+    #[tokio::test]
+    async fn wait_for_load_reports_whether_the_load_finished() {
+        let (loaded_tx, mut loaded) = watch::channel(false);
+
+        assert!(!wait_for_load(&mut loaded, Duration::from_millis(10)).await);
+
+        loaded_tx.send(true).expect("receiver alive");
+        assert!(wait_for_load(&mut loaded, Duration::from_millis(10)).await);
+    }
+}
@@ -18,8 +18,10 @@
 //! config.save().await?;
 //! ```
 
+use chrono::format::{Item, StrftimeItems};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::time::Duration;
 use tracing::info;
@@ -56,6 +58,307 @@ pub enum Keymap {
     Custom(String),
 }
 
+/// Layout of the main file view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutMode {
+    /// One table per pane.
+    #[default]
+    Table,
+    
+    /// Ranger-style parent / current / preview columns.
+    Miller,
+}
+
+impl std::fmt::Display for LayoutMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Table => write!(f, "table"),
+            
+            Self::Miller => write!(f, "miller"),
+        }
+    }
+}
+
+impl std::str::FromStr for LayoutMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "table" => Ok(Self::Table),
+            
+            "miller" | "columns" => Ok(Self::Miller),
+            
+            other => Err(anyhow::anyhow!("Unknown layout '{other}' (expected table or miller)")),
+        }
+    }
+}
+
+/// Columns of the table layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableColumn {
+    /// Icon and name (tree guides in tree view).
+    Name,
+    
+    /// Extension for files, otherwise the entry type.
+    Type,
+    
+    /// Number of entries in a directory.
+    Items,
+    
+    /// File size.
+    Size,
+    
+    /// Modification time.
+    Modified,
+    
+    /// Creation (birth) time.
+    Created,
+    
+    /// Last access time.
+    Accessed,
+    
+    /// Unix permission bits (`rwxr-xr-x`).
+    Permissions,
+    
+    /// Owning user and group.
+    Owner,
+}
+
+impl TableColumn {
+    /// Every column, in the order new columns are placed in.
+    pub const ALL: [Self; 9] = [
+        Self::Name,
+        Self::Type,
+        Self::Items,
+        Self::Size,
+        Self::Permissions,
+        Self::Owner,
+        Self::Created,
+        Self::Accessed,
+        Self::Modified,
+    ];
+
+    #[must_use]
+    /// Header text.
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Name => "Name",
+            Self::Type => "Type",
+            Self::Items => "Items",
+            Self::Size => "Size",
+            Self::Modified => "Modified",
+            Self::Created => "Created",
+            Self::Accessed => "Accessed",
+            Self::Permissions => "Mode",
+            Self::Owner => "Owner",
+        }
+    }
+
+    #[must_use]
+    /// True for the timestamp columns.
+    pub const fn is_time(self) -> bool {
+        matches!(self, Self::Modified | Self::Created | Self::Accessed)
+    }
+}
+
+impl std::fmt::Display for TableColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s: &str = match self {
+            Self::Name => "name",
+            
+            Self::Type => "type",
+            
+            Self::Items => "items",
+            
+            Self::Size => "size",
+            
+            Self::Modified => "modified",
+            
+            Self::Created => "created",
+            
+            Self::Accessed => "accessed",
+            
+            Self::Permissions => "permissions",
+            
+            Self::Owner => "owner",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+impl std::str::FromStr for TableColumn {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "name" => Ok(Self::Name),
+            
+            "type" | "ext" => Ok(Self::Type),
+            
+            "items" => Ok(Self::Items),
+            
+            "size" => Ok(Self::Size),
+            
+            "modified" | "mtime" => Ok(Self::Modified),
+            
+            "created" | "btime" => Ok(Self::Created),
+            
+            "accessed" | "atime" => Ok(Self::Accessed),
+            
+            "permissions" | "perms" | "mode" => Ok(Self::Permissions),
+            
+            "owner" | "user" => Ok(Self::Owner),
+            
+            other => Err(anyhow::anyhow!(
+                "Unknown column '{other}' (expected name, type, items, size, modified, created, accessed, perms or owner)"
+            )),
+        }
+    }
+}
+
+/// How a column renders its value. Date formats apply to the time columns,
+/// size formats to the size column; anything else renders as `Default`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ColumnFormat {
+    /// `%d/%m/%Y %I:%M:%S %p` for times, IEC units for sizes.
+    #[default]
+    Default,
+    
+    /// Age of the timestamp ("3 days ago").
+    Relative,
+    
+    /// `%Y-%m-%d %H:%M`.
+    Iso,
+    
+    /// Any other `strftime` pattern.
+    Strftime(String),
+    
+    /// Decimal units (kB, MB).
+    Si,
+    
+    /// Binary units (KiB, MiB).
+    Iec,
+    
+    /// Exact byte count.
+    Bytes,
+}
+
+impl std::fmt::Display for ColumnFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            
+            Self::Relative => write!(f, "relative"),
+            
+            Self::Iso => write!(f, "iso"),
+            
+            Self::Strftime(pattern) => write!(f, "{pattern}"),
+            
+            Self::Si => write!(f, "si"),
+            
+            Self::Iec => write!(f, "iec"),
+            
+            Self::Bytes => write!(f, "bytes"),
+        }
+    }
+}
+
+impl std::str::FromStr for ColumnFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "" | "default" => Ok(Self::Default),
+            
+            "relative" | "rel" | "ago" => Ok(Self::Relative),
+            
+            "iso" => Ok(Self::Iso),
+            
+            "si" => Ok(Self::Si),
+            
+            "iec" => Ok(Self::Iec),
+            
+            "bytes" => Ok(Self::Bytes),
+            
+            // Rejected here: chrono panics when formatting with a bad pattern
+            pattern if pattern.contains('%') => {
+                if StrftimeItems::new(pattern).any(|item: Item<'_>| -> bool { matches!(item, Item::Error) }) {
+                    return Err(anyhow::anyhow!("Invalid date pattern '{pattern}'"));
+                }
+
+                Ok(Self::Strftime(pattern.to_string()))
+            }
+            
+            other => Err(anyhow::anyhow!(
+                "Unknown format '{other}' (expected relative, iso, a strftime pattern, si, iec or bytes)"
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for ColumnFormat {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<ColumnFormat> for String {
+    fn from(format: ColumnFormat) -> Self {
+        format.to_string()
+    }
+}
+
+/// One column of the table layout. The order of `Config::columns` is the
+/// display order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnSpec {
+    pub column: TableColumn,
+    
+    /// Hidden columns keep their width and format for when they are shown
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    
+    /// Fixed width in cells; the column's own default if unset (the name
+    /// column fills the remaining space)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u16>,
+    
+    #[serde(default)]
+    pub format: ColumnFormat,
+}
+
+const fn default_visible() -> bool {
+    true
+}
+
+impl ColumnSpec {
+    #[must_use]
+    /// Visible column with default width and format.
+    pub const fn new(column: TableColumn) -> Self {
+        Self {
+            column,
+            visible: true,
+            width: None,
+            format: ColumnFormat::Default,
+        }
+    }
+
+    #[must_use]
+    /// The classic layout: name, type, items, size and modification time.
+    pub fn defaults() -> Vec<Self> {
+        [TableColumn::Name, TableColumn::Type, TableColumn::Items, TableColumn::Size, TableColumn::Modified]
+            .into_iter()
+            .map(Self::new)
+            .collect()
+    }
+}
+
 /// Profiling configuration for performance monitoring
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProfilingConfig {
@@ -89,9 +392,12 @@ impl Default for ProfilingConfig {
 
 /// Cache configuration with sensible defaults - embedded in main Config
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CacheConfig {
-    /// Maximum number of entries
-    pub max_capacity: u64,
+    /// Deprecated and ignored (a warning is logged when set): the cache is
+    /// bounded by `max_memory_mb`. Kept so existing config files still load.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_capacity: Option<u64>,
     
     /// Time-to-live for entries
     #[serde(with = "humantime_serde")]
@@ -101,7 +407,8 @@ pub struct CacheConfig {
     #[serde(with = "humantime_serde")]
     pub tti: Duration,
     
-    /// Maximum memory usage estimate (MB)
+    /// Memory budget for cached entries (MB), enforced by evicting on each
+    /// entry's estimated size
     pub max_memory_mb: u64,
     
     /// Enable cache statistics
@@ -109,17 +416,54 @@ pub struct CacheConfig {
     
     /// Number of shards for concurrent access (power of 2)
     pub num_shards: usize,
+    
+    /// Keep a snapshot of the cache on disk between sessions
+    pub persist: bool,
+    
+    /// Maximum size of the on-disk snapshot (MB)
+    pub persist_max_mb: u64,
+    
+    /// How often the snapshot is written while running
+    #[serde(with = "humantime_serde")]
+    pub persist_interval: Duration,
 }
 
 impl Default for CacheConfig {
     fn default() -> Self {
         Self {
-            max_capacity: 32_768,
+            max_capacity: None,
             ttl: Duration::from_secs(1800), // 30 minutes
             tti: Duration::from_secs(600),  // 10 minutes
             max_memory_mb: 256,
             enable_stats: true,
             num_shards: 64, // Good default for concurrent access
+            persist: true,
+            persist_max_mb: 16,
+            persist_interval: Duration::from_secs(300), // 5 minutes
+        }
+    }
+}
+
+/// Ordering rules shared by every sort mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SortConfig {
+    /// List directories before files regardless of the sort key
+    pub dirs_first: bool,
+    
+    /// Compare digit runs by value (`file2` before `file10`)
+    pub natural: bool,
+    
+    /// Distinguish upper and lower case when comparing names
+    pub case_sensitive: bool,
+}
+
+impl Default for SortConfig {
+    fn default() -> Self {
+        Self {
+            dirs_first: true,
+            natural: true,
+            case_sensitive: false,
         }
     }
 }
@@ -139,6 +483,25 @@ pub struct Config {
     pub show_hidden: bool,
     
     pub editor_cmd: String,
+    
+    #[serde(default)]
+    pub layout: LayoutMode, // Table or Miller columns
+    
+    #[serde(default)]
+    pub sort: SortConfig, // Name ordering and directory grouping
+    
+    #[serde(default)]
+    pub sort_comparators: BTreeMap<String, Vec<String>>, // Named key lists for `:sort <name>`
+    
+    #[serde(default = "ColumnSpec::defaults")]
+    pub columns: Vec<ColumnSpec>, // Table columns in display order, with widths and formats
+    
+    #[serde(default = "default_mouse")]
+    pub mouse: bool, // Capture the mouse; off leaves text selection to the terminal
+}
+
+const fn default_mouse() -> bool {
+    true
 }
 
 impl Default for Config {
@@ -150,6 +513,11 @@ impl Default for Config {
             profiling: ProfilingConfig::default(), // Default profiling config (disabled)
             show_hidden: false,
             editor_cmd: "code".to_string(),
+            layout: LayoutMode::default(),
+            sort: SortConfig::default(),
+            sort_comparators: BTreeMap::new(),
+            columns: ColumnSpec::defaults(),
+            mouse: true,
         }
     }
 }
@@ -204,6 +572,25 @@ impl Config {
         Ok(proj_dirs.config_dir().join("config.toml"))
     }
 
+    /// Returns the bookmarks file path, stored next to `config.toml`.
+    pub fn bookmarks_path() -> anyhow::Result<PathBuf> {
+        Ok(Self::config_dir()?.join("bookmarks.toml"))
+    }
+
+    /// Returns the frecency database path, stored in the data directory.
+    pub fn frecency_path() -> anyhow::Result<PathBuf> {
+        let proj_dirs = ProjectDirs::from("org", "example", "FileManager")
+            .ok_or_else(|| anyhow::anyhow!("Could not determine data directory."))?;
+        Ok(proj_dirs.data_dir().join("frecency.toml"))
+    }
+
+    /// Returns the cache snapshot path, stored in the cache directory.
+    pub fn cache_snapshot_path() -> anyhow::Result<PathBuf> {
+        let proj_dirs = ProjectDirs::from("org", "example", "FileManager")
+            .ok_or_else(|| anyhow::anyhow!("Could not determine cache directory."))?;
+        Ok(proj_dirs.cache_dir().join("objects.bin"))
+    }
+
     /// Returns the config directory (without filename), if you need to display or manipulate it.
     pub fn config_dir() -> anyhow::Result<PathBuf> {
         let proj_dirs = ProjectDirs::from("org", "example", "FileManager")
@@ -211,3 +598,67 @@ impl Config {
         Ok(proj_dirs.config_dir().to_path_buf())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Columns {
+        columns: Vec<ColumnSpec>,
+    }
+
+    #[test]
+    fn column_names_and_aliases_parse() {
+        assert_eq!("MTIME".parse::<TableColumn>().expect("alias"), TableColumn::Modified);
+        assert_eq!("perms".parse::<TableColumn>().expect("alias"), TableColumn::Permissions);
+        assert!("colour".parse::<TableColumn>().is_err());
+
+        for column in TableColumn::ALL {
+            assert_eq!(column.to_string().parse::<TableColumn>().expect("round trip"), column);
+        }
+    }
+
+    #[test]
+    fn column_formats_parse_and_reject_bad_date_patterns() {
+        assert_eq!("".parse::<ColumnFormat>().expect("empty"), ColumnFormat::Default);
+        assert_eq!("ago".parse::<ColumnFormat>().expect("alias"), ColumnFormat::Relative);
+        assert_eq!(
+            "%Y %b".parse::<ColumnFormat>().expect("pattern"),
+            ColumnFormat::Strftime("%Y %b".to_string())
+        );
+        assert!("%Q".parse::<ColumnFormat>().is_err());
+        assert!("fancy".parse::<ColumnFormat>().is_err());
+    }
+
+    #[test]
+    fn column_specs_read_from_toml_with_defaults() {
+        let text: &str = r#"
+            [[columns]]
+            column = "name"
+
+            [[columns]]
+            column = "size"
+            format = "si"
+            width = 12
+
+            [[columns]]
+            column = "modified"
+            visible = false
+            format = "%d.%m."
+        "#;
+
+        let parsed: Columns = toml::from_str(text).expect("deserialize");
+
+        assert_eq!(parsed.columns[0], ColumnSpec::new(TableColumn::Name));
+        assert_eq!(parsed.columns[1].format, ColumnFormat::Si);
+        assert_eq!(parsed.columns[1].width, Some(12));
+        assert!(!parsed.columns[2].visible);
+        assert_eq!(parsed.columns[2].format, ColumnFormat::Strftime("%d.%m.".to_string()));
+
+        let text: String = toml::to_string(&parsed).expect("serialize");
+        assert_eq!(toml::from_str::<Columns>(&text).expect("round trip"), parsed);
+
+        assert!(toml::from_str::<Columns>("[[columns]]\ncolumn = \"size\"\nformat = \"%Q\"").is_err());
+    }
+}
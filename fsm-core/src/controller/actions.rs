@@ -6,7 +6,7 @@
 //! internal events that the application can respond to. This provides a single,
 //! clear interface for the `Controller` to process.
 
-use crate::{controller::event_loop::TaskResult, fs::object_info::ObjectInfo, tasks::search_task::RawSearchResult};
+use crate::{config::{ColumnFormat, LayoutMode, TableColumn}, controller::event_loop::TaskResult, fs::{object_info::ObjectInfo, watcher::FsChange}, model::{preview::Preview, tabs::TabId}, tasks::search_task::RawSearchResult};
 use crossterm::event::{KeyEvent, MouseEvent};
 use std::{path::PathBuf};
 
@@ -23,12 +23,19 @@ pub enum InputPromptType {
     CopyDestination,
     MoveDestination,
     RenameFile,
+    /// Confirmation before deleting paths without the trash
+    ConfirmDelete(Vec<PathBuf>),
+    /// Confirmation before trashing marks that lie outside the active directory
+    ConfirmTrashMarked,
 }
 
 /// Represents a high-level action that the application can perform.
 /// This abstracts away raw terminal events into meaningful commands.
 #[derive(Debug, Clone)]
 pub enum Action {
+    /// Bookmark the active pane's directory (default name: directory name).
+    AddBookmark(Option<String>),
+    
     /// Batch update `ObjectInfo` entries (reduces event queue saturation)
     BatchUpdateObjectInfo
     {
@@ -41,6 +48,15 @@ pub enum Action {
         operation_id: String,
     },
 
+    /// Unmark every marked entry.
+    ClearMarks,
+    
+    /// Clear the active pane's filter (and close the quick-filter bar).
+    ClearQuickFilter,
+    
+    /// Close the active tab.
+    CloseTab,
+    
     /// Close the currently active overlay.
     CloseOverlay,
     
@@ -54,6 +70,9 @@ pub enum Action {
         dest: PathBuf,
     },
     
+    /// Copy the visual range, or else every marked entry, into a directory as one operation.
+    CopyMarked(PathBuf),
+    
     CreateDirectory,
     
     CreateDirectoryWithName(String),
@@ -62,13 +81,22 @@ pub enum Action {
     
     CreateFileWithName(String),
     
+    /// Move the selected entry to the trash.
     Delete,
     
+    /// Ask before deleting the selected entry permanently (bypassing the trash).
+    DeletePermanently,
+    
+    /// Delete confirmed entries for good as one operation.
+    DeletePaths(Vec<PathBuf>),
+    
     /// Direct content search with pattern (no overlay).
     DirectContentSearch(String),
     
     /// Handle streaming directory scan updates
     DirectoryScanUpdate {
+        tab: TabId,
+        scan: u64,
         path: PathBuf,
         update: crate::fs::dir_scanner::ScanUpdate,
     },
@@ -87,15 +115,45 @@ pub enum Action {
     
     Filter(String),
     
+    /// Remove a directory from the frecency database.
+    ForgetFrecent(PathBuf),
+    
+    /// Coalesced filesystem changes inside a watched directory.
+    FsChanged {
+        dir: PathBuf,
+        changes: Vec<FsChange>,
+    },
+    
     /// Go to parent directory.
     GoToParent,
     
     /// Navigate to specified path.
     GoToPath(String),
     
+    /// Flip the marks of the entries in the active pane.
+    InvertMarks,
+    
+    /// Jump the active pane to a named bookmark.
+    JumpToBookmark(String),
+    
+    /// Jump to the best frecency match for the given fragments (`z`).
+    JumpToFrecent(String),
+    
+    /// Jump to a directory picked from the frecency overlay.
+    JumpToFrecentPath(PathBuf),
+    
+    /// Jump to an entry of the active pane's navigation history.
+    JumpToHistory(usize),
+    
     /// A keyboard event.
     Key(KeyEvent),
     
+    /// Mark every entry in the active pane.
+    MarkAll,
+    
+    /// Mark the entries of the visual range and leave visual mode.
+    MarkVisualRange,
+    
     /// A mouse event.
     Mouse(MouseEvent),
     
@@ -105,12 +163,30 @@ pub enum Action {
         dest: PathBuf,
     },
     
+    /// Move the visual range, or else every marked entry, into a directory as one operation.
+    MoveMarked(PathBuf),
+    
     /// Move selection down.
     MoveSelectionDown,
     
+    /// Move the active tab left (negative) or right within the tab bar.
+    MoveTab(isize),
+    
     /// Move selection up.
     MoveSelectionUp,
     
+    /// Go back to the previous directory in the pane's history.
+    NavigateBack,
+    
+    /// Go forward to the next directory in the pane's history.
+    NavigateForward,
+    
+    /// Open a new tab in the focused pane's directory.
+    NewTab,
+    
+    /// Cycle to the next tab.
+    NextTab,
+    
     /// No operation. Used when an event is consumed but no state change is needed.
     NoOp,
     
@@ -123,12 +199,30 @@ pub enum Action {
     /// Page up (move selection up by viewport height).
     PageUp,
     
+    /// Cycle to the previous tab.
+    PrevTab,
+    
+    /// Background-loaded Miller column contents for a path.
+    PreviewLoaded(PathBuf, Preview),
+    
+    /// Remove a trashed entry for good (by its `.trashinfo` path).
+    PurgeTrashed(PathBuf),
+    
+    /// Filter the active pane with quick-filter syntax as it is typed.
+    QuickFilter(String),
+    
     /// Quit the application.
     Quit,
     
+    /// Redo the last `n` undone file operations.
+    Redo(usize),
+    
     /// Reload the current directory.
     ReloadDirectory,
     
+    /// Remove a named bookmark.
+    RemoveBookmark(String),
+    
     /// Rename file/directory
     Rename {
         source: PathBuf,
@@ -138,23 +232,41 @@ pub enum Action {
     /// Rename selected entry.
     RenameEntry(String),
     
+    /// Rename the visual range, or else every marked entry, after a `{name}`/`{ext}`/`{n}` pattern as one operation.
+    RenameMarked(String),
+    
+    /// Name the active tab (empty: revert to the directory name).
+    RenameTab(String),
+    
     /// A terminal resize event.
     Resize(u16, u16),
     
+    /// Move a trashed entry back to its original path (by its `.trashinfo` path).
+    RestoreTrashed(PathBuf),
+    
     /// Jump to first entry.
     SelectFirst,
     
     /// Jump to last entry.
     SelectLast,
     
-    /// Show filename search results.
-    ShowFilenameSearchResults(Vec<ObjectInfo>),
+    /// Show a table column with the given value format.
+    SetColumnFormat {
+        column: TableColumn,
+        format: ColumnFormat,
+    },
+    
+    /// Switch the main view layout.
+    SetLayout(LayoutMode),
+    
+    /// Show filename search results in the tab that started the search.
+    ShowFilenameSearchResults(TabId, Vec<ObjectInfo>),
     
     /// Show input prompt for file/directory creation.
     ShowInputPrompt(InputPromptType),
     
-    /// Show raw ripgrep search results.
-    ShowRawSearchResults(RawSearchResult),
+    /// Show raw ripgrep search results in the tab that started the search.
+    ShowRawSearchResults(TabId, RawSearchResult),
     
     /// Show rich content search results with line numbers and context (deprecated).
     ShowRichSearchResults(Vec<String>),
@@ -165,29 +277,93 @@ pub enum Action {
     /// Simulate a loading state (for demo/testing).
     SimulateLoading,
     
+    /// Sort the active pane by a mode or key name; empty cycles modes.
     Sort(String),
     
     /// Submit input prompt with user input.
     SubmitInputPrompt(String),
     
+    /// Move focus to the other pane (dual-pane mode).
+    SwitchPane,
+    
+    /// Activate the tab at the given index.
+    SwitchTab(usize),
+    
     /// A result from a background task.
     TaskResult(TaskResult),
     
     /// An internal tick event for periodic updates.
     Tick,
     
+    /// Toggle the bookmark overlay.
+    ToggleBookmarks,
+    
+    /// Show or hide a table column.
+    ToggleColumn(TableColumn),
+    
     /// Toggle the content search overlay.
     ToggleContentSearch,
     
+    /// Toggle the cache and runtime diagnostics overlay.
+    ToggleDiagnostics,
+    
+    /// Toggle listing directories before files in every pane.
+    ToggleDirsFirst,
+    
+    /// Open or close the second pane.
+    ToggleDualPane,
+    
     /// Toggle the file name search overlay.
     ToggleFileNameSearch,
     
+    /// Toggle the frecency overlay, prefilled with the given query.
+    ToggleFrecency(String),
+    
     /// Toggle the help overlay visibility.
     ToggleHelp,
     
+    /// Toggle the navigation history overlay.
+    ToggleHistory,
+    
+    /// Toggle between the table and Miller layouts.
+    ToggleLayout,
+    
+    /// Toggle the mark of the selected entry and select the next one.
+    ToggleMark,
+    
+    /// Open or close the quick-filter bar.
+    ToggleQuickFilter,
+    
     /// Toggle showing hidden files.
     ToggleShowHidden,
     
+    /// Toggle the trash browser overlay.
+    ToggleTrash,
+    
+    /// Switch the active pane between flat list and tree view.
+    ToggleTreeView,
+    
+    /// Toggle the undo history overlay.
+    ToggleUndoHistory,
+    
+    /// Enter visual mode anchored at the selected entry, or leave it.
+    ToggleVisualMode,
+    
+    /// Move the visual range, or else every marked entry, to the trash as one operation.
+    TrashMarked,
+    
+    /// Collapse the selected tree directory or move to its parent.
+    TreeCollapse,
+    
+    /// Expand the selected tree directory, loading its children.
+    TreeExpand,
+    
+    /// Undo the last `n` file operations.
+    Undo(usize),
+    
+    /// An undo or redo run finished on its background task.
+    UndoFinished(crate::model::undo::Outcome),
+    
     /// Updates an `ObjectInfo` in the state (e.g., from a background task).
     UpdateObjectInfo {
         parent_dir: PathBuf,
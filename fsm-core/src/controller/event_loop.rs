@@ -12,17 +12,32 @@
 //! - Extensive logging and debugging support
 
 use crate::{controller::actions::{Action, InputPromptType}, logging::ProfilingData};
+use crate::config::LayoutMode;
 use crate::fs::dir_scanner::ScanUpdate;
 use crate::fs::object_info::ObjectInfo;
+use crate::fs::trash::TrashEntry;
 use crate::model::app_state::AppState;
 use crate::model::command_palette::CommandAction;
-use crate::model::fs_state::{EntryFilter, EntrySort, PaneState};
-use crate::model::ui_state::{LoadingState, NotificationLevel, RedrawFlag, UIMode, UIOverlay};
+use crate::model::diagnostics::{DIAGNOSTICS_REFRESH_INTERVAL, DiagnosticsSnapshot};
+use crate::model::fs_state::{EntryFilter, PaneState};
+use crate::model::bookmarks::BookmarkView;
+use crate::model::frecency::FrecencyEntry;
+use crate::model::hit_map::ListHit;
+use crate::model::tabs::TabId;
+use crate::model::tree::TreeState;
+use crate::model::ui_state::{
+    LoadingState, NotificationLevel, PendingKey, RedrawFlag, UIMode, UIOverlay, UIState,
+};
 use crate::tasks::file_ops_task::{FileOperation, FileOperationTask};
 use crate::tasks::search_task::RawSearchResult;
 use crate::tasks::size_task as FileSizeOperator; 
-use crossterm::event::{Event as TermEvent, EventStream, KeyCode, KeyModifiers};
+use crate::util::debounce::Throttler;
+use crossterm::event::{
+    Event as TermEvent, EventStream, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
 use futures::StreamExt;
+use std::ffi::OsString;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -32,6 +47,9 @@ use tokio::sync::{Mutex, MutexGuard, mpsc};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, span::Entered, trace, warn, Span};
 
+/// Rows one notch of the mouse wheel scrolls a pane.
+const WHEEL_SCROLL_LINES: isize = 3;
+
 /// Enhanced task result with performance metrics
 #[derive(Debug, Clone)]
 pub enum TaskResult {
@@ -94,6 +112,13 @@ pub struct EventLoop {
     event_count: u64,
     last_performance_check: Instant,
     avg_response_time: f64,
+    /// Slowest dispatch (ms) since the last diagnostics snapshot
+    peak_response_time: f64,
+    // Diagnostics overlay
+    diagnostics_throttle: Throttler,
+    /// Wakes the loop while the overlay is open, so it refreshes when idle
+    diagnostics_tick: tokio::time::Interval,
+    diagnostics_visible: bool,
 }
 
 impl EventLoop {
@@ -104,6 +129,11 @@ impl EventLoop {
         action_rx: mpsc::UnboundedReceiver<Action>,
     ) -> Self {
         info!("Initializing enhanced event loop controller with performance monitoring");
+
+        let mut diagnostics_tick: tokio::time::Interval =
+            tokio::time::interval(DIAGNOSTICS_REFRESH_INTERVAL / 2);
+        diagnostics_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
         Self {
             app,
             task_rx,
@@ -112,6 +142,10 @@ impl EventLoop {
             event_count: 0,
             last_performance_check: Instant::now(),
             avg_response_time: 0.0,
+            peak_response_time: 0.0,
+            diagnostics_throttle: Throttler::new(DIAGNOSTICS_REFRESH_INTERVAL),
+            diagnostics_tick,
+            diagnostics_visible: false,
         }
     }
 
@@ -154,6 +188,8 @@ impl EventLoop {
                 .mul_add(0.9, time_ms * 0.1);
         }
 
+        self.peak_response_time = self.peak_response_time.max(time_ms);
+
         // Log performance warnings with profiling data
         if time_ms > 16.0 {
             // 60fps threshold - collect profiling data for slow events
@@ -187,10 +223,73 @@ impl EventLoop {
         }
     }
 
+    /// Dispatch an action, recording how long it took for the performance
+    /// figures and the diagnostics overlay.
+    pub async fn process_action(&mut self, action: Action) {
+        let toggles_diagnostics: bool = matches!(action, Action::ToggleDiagnostics);
+        let start_time: Instant = Instant::now();
+
+        self.dispatch_action(action).await;
+        self.update_performance_metrics(start_time.elapsed());
+
+        // Fill a freshly opened overlay right away
+        if toggles_diagnostics {
+            self.diagnostics_throttle.reset();
+        }
+
+        self.refresh_diagnostics().await;
+    }
+
+    /// Collect a new diagnostics snapshot, at most once per
+    /// `DIAGNOSTICS_REFRESH_INTERVAL` and only while the overlay is open.
+    async fn refresh_diagnostics(&mut self) {
+        if !self.diagnostics_throttle.should_trigger() {
+            return;
+        }
+
+        let app: MutexGuard<'_, AppState> = self.app.lock().await;
+        self.diagnostics_visible = app.ui.overlay == UIOverlay::Diagnostics;
+
+        drop(app);
+
+        if !self.diagnostics_visible {
+            return;
+        }
+
+        // Process figures refresh sysinfo, so take them outside the lock
+        let memory_kb: Option<i64> = ProfilingData::get_current_memory_kb();
+        let cpu_percent: Option<f32> = ProfilingData::get_cpu_usage_percent();
+
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+
+        let snapshot: DiagnosticsSnapshot = DiagnosticsSnapshot {
+            cache: app.cache.stats(),
+            cache_entries: app.cache.entry_count(),
+            cache_bytes: app.cache.weighted_size(),
+            cache_budget: app.cache.memory_budget(),
+            cache_restored: app.cache.restored_len(),
+            events: self.event_count,
+            avg_latency: Duration::from_secs_f64(self.avg_response_time.max(0.0) / 1000.0),
+            peak_latency: Duration::from_secs_f64(self.peak_response_time.max(0.0) / 1000.0),
+            pending_tasks: app.tasks.values().filter(|task| !task.is_completed).count(),
+            file_operations: app.ui.active_file_operations.values().cloned().collect(),
+            memory_kb,
+            cpu_percent,
+            task_queue: self.task_rx.len(),
+            action_queue: self.action_rx.len(),
+            uptime: app.started_at.elapsed(),
+        };
+
+        app.ui.diagnostics = Some(snapshot);
+        app.ui.request_redraw(RedrawFlag::Overlay);
+
+        drop(app);
+
+        self.peak_response_time = 0.0;
+    }
+
     /// Enhanced event loop with performance monitoring
     pub async fn next_action(&mut self) -> Option<Action> {
-        let start_time = Instant::now();
-
         let action = tokio::select! {
             Some(Ok(event)) = self.event_stream.next() => {
                 trace!("Terminal event received: {:?}", event);
@@ -209,13 +308,16 @@ impl EventLoop {
                 Some(action)
             }
 
+            _ = self.diagnostics_tick.tick(), if self.diagnostics_visible => {
+                Some(Action::Tick)
+            }
+
             else => {
                 info!("Event loop terminated - no more events");
                 None
             }
         };
 
-        self.update_performance_metrics(start_time.elapsed());
         action
     }
 
@@ -256,31 +358,7 @@ impl EventLoop {
                 }
 
                 // Route to specialized handlers
-                match current_mode {
-                    UIMode::Command => self.handle_command_mode_keys(key_event).await,
-                    _ => match current_overlay {
-                        UIOverlay::None => self.handle_navigation_mode_keys(key_event).await,
-
-                        UIOverlay::FileNameSearch => {
-                            self.handle_filename_search_keys(key_event).await
-                        }
-
-                        UIOverlay::ContentSearch => {
-                            self.handle_content_search_keys(key_event).await
-                        }
-
-                        UIOverlay::Prompt => self.handle_prompt_keys(key_event).await,
-
-                        UIOverlay::SearchResults => {
-                            self.handle_search_results_keys(key_event).await
-                        }
-
-                        _ => {
-                            debug!("Ignoring key in overlay mode: {:?}", current_overlay);
-                            Action::NoOp
-                        }
-                    },
-                }
+                self.route_key(key_event, current_mode, current_overlay).await
             }
 
             TermEvent::Mouse(mouse_event) => {
@@ -300,6 +378,61 @@ impl EventLoop {
         }
     }
 
+    /// Hand a key to the handler of the current mode or overlay.
+    async fn route_key(
+        &self,
+        key_event: KeyEvent,
+        current_mode: UIMode,
+        current_overlay: UIOverlay,
+    ) -> Action {
+        match current_mode {
+            UIMode::Command => self.handle_command_mode_keys(key_event).await,
+
+            UIMode::Visual if current_overlay == UIOverlay::None => {
+                self.handle_visual_mode_keys(key_event).await
+            }
+
+            _ => match current_overlay {
+                UIOverlay::None => self.handle_navigation_mode_keys(key_event).await,
+
+                UIOverlay::FileNameSearch => {
+                    self.handle_filename_search_keys(key_event).await
+                }
+
+                UIOverlay::ContentSearch => {
+                    self.handle_content_search_keys(key_event).await
+                }
+
+                UIOverlay::Prompt => self.handle_prompt_keys(key_event).await,
+
+                UIOverlay::SearchResults => {
+                    self.handle_search_results_keys(key_event).await
+                }
+
+                UIOverlay::History => self.handle_history_keys(key_event).await,
+
+                UIOverlay::Bookmarks => self.handle_bookmarks_keys(key_event).await,
+
+                UIOverlay::Frecency => self.handle_frecency_keys(key_event).await,
+
+                UIOverlay::Trash => self.handle_trash_keys(key_event).await,
+
+                UIOverlay::UndoHistory => self.handle_undo_history_keys(key_event).await,
+
+                UIOverlay::QuickFilter => self.handle_quick_filter_keys(key_event).await,
+
+                UIOverlay::Diagnostics if key_event.code == KeyCode::F(12) => {
+                    Action::ToggleDiagnostics
+                }
+
+                _ => {
+                    debug!("Ignoring key in overlay mode: {:?}", current_overlay);
+                    Action::NoOp
+                }
+            },
+        }
+    }
+
     /// Enhanced escape key handling with context awareness
     async fn handle_escape_key(
         &self,
@@ -311,6 +444,11 @@ impl EventLoop {
         {
             let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
 
+            if app.ui.pending_key.take().is_some() {
+                debug!("Escape: cancelled pending mark key");
+                return Action::NoOp;
+            }
+
             if !app.ui.active_file_operations.is_empty() {
                 let cancelled_count: usize = app.ui.cancel_all_operations();
 
@@ -346,11 +484,21 @@ impl EventLoop {
             return Action::NoOp;
         }
 
+        if overlay == UIOverlay::QuickFilter {
+            debug!("Escape: clearing quick filter");
+            return Action::ClearQuickFilter;
+        }
+
         if overlay != UIOverlay::None {
             debug!("Escape: closing overlay {:?}", overlay);
             return Action::CloseOverlay;
         }
 
+        if mode == UIMode::Visual {
+            debug!("Escape: leaving visual mode");
+            return Action::ToggleVisualMode;
+        }
+
         if mode == UIMode::Command {
             debug!("Escape: checking command completions");
             let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
@@ -370,6 +518,22 @@ impl EventLoop {
             return Action::ExitCommandMode;
         }
 
+        let app: MutexGuard<'_, AppState> = self.app.lock().await;
+        let filtered: bool = app.fs.active_pane().filter != EntryFilter::All;
+        let marked: bool = !app.fs.marked.is_empty();
+
+        drop(app);
+
+        if filtered {
+            debug!("Escape: clearing pane filter");
+            return Action::ClearQuickFilter;
+        }
+
+        if marked {
+            debug!("Escape: clearing marks");
+            return Action::ClearMarks;
+        }
+
         debug!("Escape: requesting application quit");
         Action::Quit
     }
@@ -510,7 +674,6 @@ impl EventLoop {
         })
     }
 
-    #[allow(clippy::unused_async)]
     /// Enhanced navigation with improved responsiveness
     async fn handle_navigation_mode_keys(&self, key: crossterm::event::KeyEvent) -> Action {
         trace!(
@@ -518,6 +681,27 @@ impl EventLoop {
             key.code, key.modifiers
         );
 
+        // Second key of a mark sequence (`M<key>` / `'<key>`)
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        let pending: Option<PendingKey> = app.ui.pending_key.take();
+
+        drop(app);
+
+        if let Some(pending) = pending {
+            let KeyCode::Char(mark) = key.code else {
+                debug!("Mark sequence cancelled by {:?}", key.code);
+                return Action::NoOp;
+            };
+
+            return match pending {
+                PendingKey::SetMark => Action::AddBookmark(Some(mark.to_string())),
+
+                PendingKey::JumpToMark => Action::JumpToBookmark(mark.to_string()),
+
+                PendingKey::PurgeTrash => Action::NoOp,
+            };
+        }
+
         match (key.code, key.modifiers) {
             // Core command access
             (KeyCode::Char(':'), _) => {
@@ -548,11 +732,112 @@ impl EventLoop {
                 Action::ShowInputPrompt(InputPromptType::MoveDestination)
             }
 
+            (KeyCode::Char('r'), KeyModifiers::CONTROL) => Action::Redo(1),
+
             (KeyCode::Char('r'), _) => {
                 info!("Rename file - requesting new name");
                 Action::ShowInputPrompt(InputPromptType::RenameFile)
             }
 
+            (KeyCode::Char('D'), _) | (KeyCode::Delete, KeyModifiers::SHIFT) => {
+                info!("Delete permanently - requesting confirmation");
+                Action::DeletePermanently
+            }
+
+            (KeyCode::Char('d'), _) | (KeyCode::Delete, _) => {
+                info!("Moving selected entry to trash");
+                Action::Delete
+            }
+
+            // Undo/redo
+            (KeyCode::Char('u'), _) => Action::Undo(1),
+
+            (KeyCode::Char('U'), _) => {
+                info!("Toggling undo history overlay");
+                Action::ToggleUndoHistory
+            }
+
+            // Marks
+            (KeyCode::Char('V'), _) => {
+                info!("Entering visual mode");
+                Action::ToggleVisualMode
+            }
+
+            (KeyCode::Char(' '), _) => Action::ToggleMark,
+
+            (KeyCode::Char('a'), _) => Action::MarkAll,
+
+            (KeyCode::Char('*'), _) => Action::InvertMarks,
+
+            (KeyCode::Char('A'), _) => Action::ClearMarks,
+
+            // History navigation
+            (KeyCode::Left, KeyModifiers::ALT) | (KeyCode::Char('['), _) => Action::NavigateBack,
+
+            (KeyCode::Right, KeyModifiers::ALT) | (KeyCode::Char(']'), _) => {
+                Action::NavigateForward
+            }
+
+            (KeyCode::Char('H'), _) => {
+                info!("Toggling history overlay");
+                Action::ToggleHistory
+            }
+
+            // Bookmarks
+            (KeyCode::Char('M'), _) | (KeyCode::Char('\''), _) => {
+                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+                app.ui.pending_key = Some(if key.code == KeyCode::Char('M') {
+                    PendingKey::SetMark
+                } else {
+                    PendingKey::JumpToMark
+                });
+
+                drop(app);
+
+                Action::NoOp
+            }
+
+            (KeyCode::Char('b'), _) => {
+                info!("Toggling bookmark overlay");
+                Action::ToggleBookmarks
+            }
+
+            (KeyCode::Char('z'), _) => {
+                info!("Toggling frecency overlay");
+                Action::ToggleFrecency(String::new())
+            }
+
+            (KeyCode::Char('f'), KeyModifiers::NONE) => {
+                info!("Opening quick filter");
+                Action::ToggleQuickFilter
+            }
+
+            // Sorting
+            (KeyCode::Char('s'), KeyModifiers::NONE) => Action::Sort(String::new()),
+
+            // Tabs
+            (KeyCode::Char('t'), KeyModifiers::CONTROL) => {
+                info!("Opening new tab");
+                Action::NewTab
+            }
+
+            (KeyCode::Char('w'), KeyModifiers::ALT) => {
+                info!("Closing tab");
+                Action::CloseTab
+            }
+
+            (KeyCode::PageDown, KeyModifiers::CONTROL) | (KeyCode::Char('}'), _) => Action::NextTab,
+
+            (KeyCode::PageUp, KeyModifiers::CONTROL) | (KeyCode::Char('{'), _) => Action::PrevTab,
+
+            (KeyCode::Char('>'), _) => Action::MoveTab(1),
+
+            (KeyCode::Char('<'), _) => Action::MoveTab(-1),
+
+            (KeyCode::Char(c @ '1'..='9'), KeyModifiers::ALT) => {
+                Action::SwitchTab(c as usize - '1' as usize)
+            }
+
             // Navigation keys
             (KeyCode::Up, _) => Action::MoveSelectionUp,
 
@@ -570,6 +855,29 @@ impl EventLoop {
 
             (KeyCode::Backspace, _) => Action::GoToParent,
 
+            // Tree view
+            (KeyCode::Char('T'), _) => {
+                info!("Toggling tree view");
+                Action::ToggleTreeView
+            }
+
+            (KeyCode::Right, KeyModifiers::NONE) => Action::TreeExpand,
+
+            (KeyCode::Char('L'), _) => {
+                info!("Toggling layout");
+                Action::ToggleLayout
+            }
+
+            (KeyCode::Left, KeyModifiers::NONE) => Action::TreeCollapse,
+
+            // Dual-pane mode
+            (KeyCode::Tab, _) => Action::SwitchPane,
+
+            (KeyCode::Char('w'), KeyModifiers::CONTROL) => {
+                info!("Toggling dual-pane mode");
+                Action::ToggleDualPane
+            }
+
             // System controls
             (KeyCode::Char('q'), _) => {
                 info!("Quit requested");
@@ -581,6 +889,11 @@ impl EventLoop {
 
             (KeyCode::Char('l'), KeyModifiers::CONTROL) => Action::SimulateLoading,
 
+            (KeyCode::F(12), _) => {
+                info!("Toggling diagnostics overlay");
+                Action::ToggleDiagnostics
+            }
+
             _ => {
                 trace!("Unhandled navigation key: {:?}", key);
                 Action::Key(key)
@@ -987,101 +1300,518 @@ impl EventLoop {
         }
     }
 
-    /// Enhanced command action mapping
-    fn map_command_action_to_action(cmd_action: CommandAction) -> Action {
-        debug!("Mapping command action: {:?}", cmd_action);
+    /// History overlay navigation
+    async fn handle_history_keys(&self, key: crossterm::event::KeyEvent) -> Action {
+        trace!("History overlay key: {:?}", key.code);
 
-        let action: Action = match cmd_action {
-            CommandAction::OpenConfig => {
-                info!("Command: open config (opening system config file)");
-                // TODO: Open actual config file
-                Action::ExitCommandMode
-            }
+        match key.code {
+            KeyCode::Up => {
+                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+                app.ui.overlay_select_prev();
 
-            CommandAction::Reload => {
-                info!("Command: reload directory");
-                Action::ReloadDirectory
-            }
+                drop(app);
 
-            CommandAction::NewFile => {
-                info!("Command: create new file");
-                Action::CreateFile
+                Action::NoOp
             }
 
-            CommandAction::NewFolder => {
-                info!("Command: create new folder");
-                Action::CreateDirectory
+            KeyCode::Down => {
+                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+                let len: usize = app.fs.active_pane().history_entries().len();
+                app.ui.overlay_select_next(len);
+
+                drop(app);
+
+                Action::NoOp
             }
 
-            CommandAction::NewFileWithName(name) => {
-                info!("Command: create new file '{}'", name);
-                Action::CreateFileWithName(name)
+            KeyCode::Enter => {
+                let app: MutexGuard<'_, AppState> = self.app.lock().await;
+                let index: usize = app.ui.overlay_selected;
+
+                drop(app);
+
+                debug!("History overlay: jumping to entry {}", index);
+                Action::JumpToHistory(index)
             }
 
-            CommandAction::NewFolderWithName(name) => {
-                info!("Command: create new folder '{}'", name);
-                Action::CreateDirectoryWithName(name)
+            KeyCode::Char('H') => Action::ToggleHistory,
+
+            _ => {
+                trace!("History overlay: ignoring key {:?}", key.code);
+                Action::NoOp
             }
+        }
+    }
 
-            CommandAction::SearchContent => {
-                info!("Command: toggle content search");
-                Action::ToggleContentSearch
+    /// Trash overlay: Enter or `r` restores, Delete or `x` twice purges
+    async fn handle_trash_keys(&self, key: crossterm::event::KeyEvent) -> Action {
+        trace!("Trash overlay key: {:?}", key.code);
+
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        let purge_pending: bool = app.ui.pending_key.take() == Some(PendingKey::PurgeTrash);
+        let selected: Option<PathBuf> = app
+            .ui
+            .trash_entries
+            .get(app.ui.overlay_selected)
+            .map(|entry: &TrashEntry| entry.info_path.clone());
+
+        let action: Action = match key.code {
+            KeyCode::Up => {
+                app.ui.overlay_select_prev();
+                Action::NoOp
             }
 
-            CommandAction::SearchContentWithPattern(pattern) => {
-                info!("Command: direct content search for '{}'", pattern);
-                Action::DirectContentSearch(pattern)
+            KeyCode::Down => {
+                let len: usize = app.ui.trash_entries.len();
+                app.ui.overlay_select_next(len);
+                Action::NoOp
             }
 
-            CommandAction::Custom(cmd) => {
-                info!("Command: custom command '{}' not implemented", cmd);
-                Action::ExitCommandMode
+            KeyCode::Enter | KeyCode::Char('r') => selected.map_or(Action::NoOp, Action::RestoreTrashed),
+
+            KeyCode::Delete | KeyCode::Char('x') => match selected {
+                Some(info_path) if purge_pending => Action::PurgeTrashed(info_path),
+
+                Some(_) => {
+                    app.ui.pending_key = Some(PendingKey::PurgeTrash);
+                    app.ui.show_warning("Press again to purge for good".to_string());
+                    Action::NoOp
+                }
+
+                None => Action::NoOp,
+            },
+
+            _ => {
+                trace!("Trash overlay: ignoring key {:?}", key.code);
+                Action::NoOp
             }
         };
 
-        debug!("Command action mapped to: {:?}", action);
+        app.ui.request_redraw(RedrawFlag::Overlay);
+
+        drop(app);
+
         action
     }
 
-    /// Enhanced action dispatcher with comprehensive error handling
-    pub async fn dispatch_action(&self, action: Action) {
-        let span: Span = tracing::info_span!(
-            "action_dispatch",
-            action = ?action,
-            operation_type = "action_dispatch"
-        );
-        
-        let guard: Entered<'_> = span.enter();
-        
-        let start_time: Instant = Instant::now();
-        
-        // Drop the span guard before any async operations
-        drop(guard);
+    /// Visual mode: movement extends the range from the anchor. Space marks
+    /// it; copy, move and delete act on the range alone, which stays open
+    /// until the operation is submitted.
+    async fn handle_visual_mode_keys(&self, key: crossterm::event::KeyEvent) -> Action {
+        trace!("Visual mode key: {:?}", key.code);
 
-        match action {
-            // Batch update actions
-            Action::BatchUpdateObjectInfo 
-            { 
-                parent_dir, 
-                objects 
-            } => {
-                trace!("Batch updating {0} object infos for {parent_dir:?}", objects.len()); 
-                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        match (key.code, key.modifiers) {
+            (KeyCode::Up, _) => Action::MoveSelectionUp,
 
-                app.update_object_info_batch(&parent_dir, objects).await;
-                app.ui.request_redraw(RedrawFlag::Main);
-            }
+            (KeyCode::Down, _) => Action::MoveSelectionDown,
 
-            // UI actions
-            Action::ToggleHelp
+            (KeyCode::PageUp, _) => Action::PageUp,
+
+            (KeyCode::PageDown, _) => Action::PageDown,
+
+            (KeyCode::Home, _) => Action::SelectFirst,
+
+            (KeyCode::End, _) => Action::SelectLast,
+
+            (KeyCode::Char('V'), _) => Action::ToggleVisualMode,
+
+            (KeyCode::Char(' '), _) => Action::MarkVisualRange,
+
+            (KeyCode::Char('c'), _) => Action::ShowInputPrompt(InputPromptType::CopyDestination),
+
+            (KeyCode::Char('m'), _) => Action::ShowInputPrompt(InputPromptType::MoveDestination),
+
+            (KeyCode::Char('D'), _) | (KeyCode::Delete, KeyModifiers::SHIFT) => Action::DeletePermanently,
+
+            (KeyCode::Char('d') | KeyCode::Delete, _) => Action::Delete,
+
+            _ => {
+                trace!("Visual mode: ignoring key {:?}", key.code);
+                Action::NoOp
+            }
+        }
+    }
+
+    /// Undo history overlay: Enter undoes or redoes up to the highlighted
+    /// operation, `u` / Ctrl+R step once
+    async fn handle_undo_history_keys(&self, key: crossterm::event::KeyEvent) -> Action {
+        trace!("Undo history overlay key: {:?}", key.code);
+
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        let undone: usize = app.redo_history.len();
+        let rows: usize = undone + app.history.len();
+        let row: usize = app.ui.overlay_selected;
+
+        let action: Action = match (key.code, key.modifiers) {
+            (KeyCode::Up, _) => {
+                app.ui.overlay_select_prev();
+                Action::NoOp
+            }
+
+            (KeyCode::Down, _) => {
+                app.ui.overlay_select_next(rows);
+                Action::NoOp
+            }
+
+            // Undone rows redo up to themselves, done rows undo down to themselves
+            (KeyCode::Enter, _) if row < undone => Action::Redo(undone - row),
+
+            (KeyCode::Enter, _) if row < rows => Action::Undo(row - undone + 1),
+
+            (KeyCode::Char('r'), KeyModifiers::CONTROL) => Action::Redo(1),
+
+            (KeyCode::Char('u'), _) => Action::Undo(1),
+
+            (KeyCode::Char('U'), _) => Action::ToggleUndoHistory,
+
+            _ => {
+                trace!("Undo history overlay: ignoring key {:?}", key.code);
+                Action::NoOp
+            }
+        };
+
+        app.ui.request_redraw(RedrawFlag::Overlay);
+
+        drop(app);
+
+        action
+    }
+
+    /// Quick-filter bar: the pane narrows as you type, Enter keeps the
+    /// filter, Esc clears it. Up/Down move the pane selection.
+    async fn handle_quick_filter_keys(&self, key: crossterm::event::KeyEvent) -> Action {
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+
+        let action: Action = match key.code {
+            KeyCode::Char(c) => {
+                app.ui.input.push(c);
+                Action::QuickFilter(app.ui.input.clone())
+            }
+
+            KeyCode::Backspace => {
+                app.ui.input.pop();
+                Action::QuickFilter(app.ui.input.clone())
+            }
+
+            KeyCode::Enter => Action::CloseOverlay,
+
+            KeyCode::Up => Action::MoveSelectionUp,
+
+            KeyCode::Down => Action::MoveSelectionDown,
+
+            _ => Action::NoOp,
+        };
+
+        drop(app);
+
+        action
+    }
+
+    /// Bookmark overlay: type to filter, Enter to jump, Delete to remove
+    async fn handle_bookmarks_keys(&self, key: crossterm::event::KeyEvent) -> Action {
+        trace!("Bookmark overlay key: {:?}", key.code);
+
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+
+        match key.code {
+            KeyCode::Up => {
+                app.ui.overlay_select_prev();
+                app.ui.request_redraw(RedrawFlag::Overlay);
+
+                Action::NoOp
+            }
+
+            KeyCode::Down => {
+                let len: usize = app.bookmarks.filtered(&app.ui.input).len();
+                app.ui.overlay_select_next(len);
+                app.ui.request_redraw(RedrawFlag::Overlay);
+
+                Action::NoOp
+            }
+
+            KeyCode::Enter | KeyCode::Delete => {
+                let selected: Option<String> = app
+                    .bookmarks
+                    .filtered(&app.ui.input)
+                    .get(app.ui.overlay_selected)
+                    .map(|b: &BookmarkView<'_>| -> String { b.name.to_string() });
+
+                drop(app);
+
+                match (key.code, selected) {
+                    (KeyCode::Enter, Some(name)) => Action::JumpToBookmark(name),
+
+                    (KeyCode::Delete, Some(name)) => Action::RemoveBookmark(name),
+
+                    _ => Action::NoOp,
+                }
+            }
+
+            KeyCode::Backspace => {
+                app.ui.input.pop();
+                app.ui.overlay_selected = 0;
+                app.ui.request_redraw(RedrawFlag::Overlay);
+
+                Action::NoOp
+            }
+
+            KeyCode::Char(c) => {
+                app.ui.input.push(c);
+                app.ui.overlay_selected = 0;
+                app.ui.request_redraw(RedrawFlag::Overlay);
+
+                Action::NoOp
+            }
+
+            _ => {
+                trace!("Bookmark overlay: ignoring key {:?}", key.code);
+                Action::NoOp
+            }
+        }
+    }
+
+    /// Frecency overlay: type fragments to narrow, Enter to jump, Delete to forget
+    async fn handle_frecency_keys(&self, key: crossterm::event::KeyEvent) -> Action {
+        trace!("Frecency overlay key: {:?}", key.code);
+
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+
+        match key.code {
+            KeyCode::Up => {
+                app.ui.overlay_select_prev();
+                app.ui.request_redraw(RedrawFlag::Overlay);
+
+                Action::NoOp
+            }
+
+            KeyCode::Down => {
+                let len: usize = app.frecency.ranked(&app.ui.input).len();
+                app.ui.overlay_select_next(len);
+                app.ui.request_redraw(RedrawFlag::Overlay);
+
+                Action::NoOp
+            }
+
+            KeyCode::Enter | KeyCode::Delete => {
+                let selected: Option<PathBuf> = app
+                    .frecency
+                    .ranked(&app.ui.input)
+                    .get(app.ui.overlay_selected)
+                    .map(|(_, e): &(f64, &FrecencyEntry)| -> PathBuf { e.path.clone() });
+
+                drop(app);
+
+                match (key.code, selected) {
+                    (KeyCode::Enter, Some(path)) => Action::JumpToFrecentPath(path),
+
+                    (KeyCode::Delete, Some(path)) => Action::ForgetFrecent(path),
+
+                    _ => Action::NoOp,
+                }
+            }
+
+            KeyCode::Backspace => {
+                app.ui.input.pop();
+                app.ui.overlay_selected = 0;
+                app.ui.request_redraw(RedrawFlag::Overlay);
+
+                Action::NoOp
+            }
+
+            KeyCode::Char(c) => {
+                app.ui.input.push(c);
+                app.ui.overlay_selected = 0;
+                app.ui.request_redraw(RedrawFlag::Overlay);
+
+                Action::NoOp
+            }
+
+            _ => {
+                trace!("Frecency overlay: ignoring key {:?}", key.code);
+                Action::NoOp
+            }
+        }
+    }
+
+    /// Enhanced command action mapping
+    fn map_command_action_to_action(cmd_action: CommandAction) -> Action {
+        debug!("Mapping command action: {:?}", cmd_action);
+
+        let action: Action = match cmd_action {
+            CommandAction::OpenConfig => {
+                info!("Command: open config (opening system config file)");
+                // TODO: Open actual config file
+                Action::ExitCommandMode
+            }
+
+            CommandAction::Reload => {
+                info!("Command: reload directory");
+                Action::ReloadDirectory
+            }
+
+            CommandAction::NewFile => {
+                info!("Command: create new file");
+                Action::CreateFile
+            }
+
+            CommandAction::NewFolder => {
+                info!("Command: create new folder");
+                Action::CreateDirectory
+            }
+
+            CommandAction::NewFileWithName(name) => {
+                info!("Command: create new file '{}'", name);
+                Action::CreateFileWithName(name)
+            }
+
+            CommandAction::NewFolderWithName(name) => {
+                info!("Command: create new folder '{}'", name);
+                Action::CreateDirectoryWithName(name)
+            }
+
+            CommandAction::SearchContent => {
+                info!("Command: toggle content search");
+                Action::ToggleContentSearch
+            }
+
+            CommandAction::SearchContentWithPattern(pattern) => {
+                info!("Command: direct content search for '{}'", pattern);
+                Action::DirectContentSearch(pattern)
+            }
+
+            CommandAction::AddBookmark(name) => {
+                info!("Command: add bookmark {:?}", name);
+                Action::AddBookmark(name)
+            }
+
+            CommandAction::RemoveBookmark(name) => {
+                info!("Command: remove bookmark '{}'", name);
+                Action::RemoveBookmark(name)
+            }
+
+            CommandAction::ListBookmarks => {
+                info!("Command: list bookmarks");
+                Action::ToggleBookmarks
+            }
+
+            CommandAction::NewTab => Action::NewTab,
+
+            CommandAction::CloseTab => Action::CloseTab,
+
+            CommandAction::NextTab => Action::NextTab,
+
+            CommandAction::PrevTab => Action::PrevTab,
+
+            CommandAction::MoveTab(delta) => Action::MoveTab(delta),
+
+            CommandAction::RenameTab(name) => {
+                info!("Command: rename tab to '{}'", name);
+                Action::RenameTab(name)
+            }
+
+            CommandAction::ToggleTree => Action::ToggleTreeView,
+
+            CommandAction::Sort(criteria) => {
+                info!("Command: sort '{}'", criteria);
+                Action::Sort(criteria)
+            }
+
+            CommandAction::ToggleDirsFirst => Action::ToggleDirsFirst,
+
+            CommandAction::ShowDiagnostics => Action::ToggleDiagnostics,
+
+            CommandAction::ShowTrash => Action::ToggleTrash,
+
+            CommandAction::Undo => Action::Undo(1),
+
+            CommandAction::Redo => Action::Redo(1),
+
+            CommandAction::ShowUndoHistory => Action::ToggleUndoHistory,
+
+            CommandAction::ToggleColumn(column) => Action::ToggleColumn(column),
+
+            CommandAction::SetColumnFormat(column, format) => Action::SetColumnFormat { column, format },
+
+            CommandAction::Filter(query) => {
+                info!("Command: filter '{}'", query);
+                Action::QuickFilter(query)
+            }
+
+            CommandAction::SetLayout(layout) => layout.map_or(Action::ToggleLayout, Action::SetLayout),
+
+            CommandAction::FrecencyJump(query) => {
+                info!("Command: frecency jump '{}'", query);
+                Action::JumpToFrecent(query)
+            }
+
+            CommandAction::ShowFrecency(query) => {
+                info!("Command: show frecency candidates for '{}'", query);
+                Action::ToggleFrecency(query)
+            }
+
+            CommandAction::Custom(cmd) => {
+                info!("Command: custom command '{}' not implemented", cmd);
+                Action::ExitCommandMode
+            }
+        };
+
+        debug!("Command action mapped to: {:?}", action);
+        action
+    }
+
+    /// Enhanced action dispatcher with comprehensive error handling
+    pub async fn dispatch_action(&self, action: Action) {
+        let span: Span = tracing::info_span!(
+            "action_dispatch",
+            action = ?action,
+            operation_type = "action_dispatch"
+        );
+        
+        let guard: Entered<'_> = span.enter();
+        
+        let start_time: Instant = Instant::now();
+        
+        // Drop the span guard before any async operations
+        drop(guard);
+
+        match action {
+            // Batch update actions
+            Action::BatchUpdateObjectInfo 
+            { 
+                parent_dir, 
+                objects 
+            } => {
+                trace!("Batch updating {0} object infos for {parent_dir:?}", objects.len()); 
+                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+
+                app.update_object_info_batch(&parent_dir, objects).await;
+                app.ui.request_redraw(RedrawFlag::Main);
+            }
+
+            // UI actions
+            Action::ToggleHelp
             | Action::EnterCommandMode
             | Action::ExitCommandMode
             | Action::ToggleFileNameSearch
             | Action::ToggleContentSearch
             | Action::CloseOverlay
+            | Action::ToggleHistory
+            | Action::ToggleDiagnostics
             | Action::ToggleShowHidden
+            | Action::ToggleLayout
+            | Action::SetLayout(_)
+            | Action::ToggleColumn(_)
+            | Action::SetColumnFormat { .. }
             | Action::SimulateLoading => self.dispatch_ui_action(action).await,
 
+            // Quick filter
+            Action::ToggleQuickFilter
+            | Action::QuickFilter(_)
+            | Action::ClearQuickFilter => self.dispatch_filter_action(action).await,
+
+            // Sorting
+            Action::Sort(_) | Action::ToggleDirsFirst => self.dispatch_sort_action(action).await,
+
             // Navigation
             Action::MoveSelectionUp
             | Action::MoveSelectionDown
@@ -1090,7 +1820,57 @@ impl EventLoop {
             | Action::SelectFirst
             | Action::SelectLast
             | Action::EnterSelected
-            | Action::GoToParent => self.dispatch_navigation_action(action).await,
+            | Action::GoToParent
+            | Action::SwitchPane
+            | Action::ToggleDualPane
+            | Action::NavigateBack
+            | Action::NavigateForward
+            | Action::JumpToHistory(_)
+            | Action::ToggleTreeView
+            | Action::TreeExpand
+            | Action::TreeCollapse => self.dispatch_navigation_action(action).await,
+
+            // Bookmarks
+            Action::AddBookmark(_)
+            | Action::RemoveBookmark(_)
+            | Action::JumpToBookmark(_)
+            | Action::ToggleBookmarks => self.dispatch_bookmark_action(action).await,
+
+            // Tabs
+            Action::NewTab
+            | Action::CloseTab
+            | Action::NextTab
+            | Action::PrevTab
+            | Action::SwitchTab(_)
+            | Action::MoveTab(_)
+            | Action::RenameTab(_) => self.dispatch_tab_action(action).await,
+
+            // Frecency
+            Action::JumpToFrecent(_)
+            | Action::JumpToFrecentPath(_)
+            | Action::ForgetFrecent(_)
+            | Action::ToggleFrecency(_) => self.dispatch_frecency_action(action).await,
+
+            // Trash
+            Action::Delete
+            | Action::DeletePermanently
+            | Action::RestoreTrashed(_)
+            | Action::PurgeTrashed(_)
+            | Action::ToggleTrash => self.dispatch_trash_action(action).await,
+
+            // Undo/redo
+            Action::Undo(_)
+            | Action::Redo(_)
+            | Action::UndoFinished(_)
+            | Action::ToggleUndoHistory => self.dispatch_undo_action(action).await,
+
+            // Marks
+            Action::ToggleMark
+            | Action::MarkAll
+            | Action::InvertMarks
+            | Action::ClearMarks
+            | Action::ToggleVisualMode
+            | Action::MarkVisualRange => self.dispatch_mark_action(action).await,
 
             // Command-driven actions
             Action::CreateFile
@@ -1098,7 +1878,6 @@ impl EventLoop {
             | Action::CreateFileWithName(_)
             | Action::CreateDirectoryWithName(_)
             | Action::ReloadDirectory
-            | Action::Delete
             | Action::RenameEntry(_)
             | Action::GoToPath(_) => self.dispatch_command_action(action).await,
 
@@ -1107,15 +1886,17 @@ impl EventLoop {
             | Action::ContentSearch(_)
             | Action::DirectContentSearch(_)
             | Action::ShowSearchResults(_)
-            | Action::ShowFilenameSearchResults(_)
+            | Action::ShowFilenameSearchResults(..)
             | Action::ShowRichSearchResults(_)
-            | Action::ShowRawSearchResults(_)
+            | Action::ShowRawSearchResults(..)
             | Action::OpenFile(_, _) => self.dispatch_search_action(action).await,
 
             // Task/Update results
             Action::TaskResult(_)
             | Action::DirectoryScanUpdate { .. }
-            | Action::UpdateObjectInfo { .. } => self.dispatch_task_update_action(action).await,
+            | Action::UpdateObjectInfo { .. }
+            | Action::FsChanged { .. }
+            | Action::PreviewLoaded(..) => self.dispatch_task_update_action(action).await,
 
             // Input prompts
             Action::ShowInputPrompt(_) | Action::SubmitInputPrompt(_) => {
@@ -1128,8 +1909,15 @@ impl EventLoop {
             | Action::Rename { .. }
             | Action::CancelFileOperation { .. } => self.dispatch_file_op_action(action).await,
 
+            // File operations on the marked set
+            Action::CopyMarked(_)
+            | Action::MoveMarked(_)
+            | Action::RenameMarked(_)
+            | Action::TrashMarked
+            | Action::DeletePaths(_) => self.handle_marked_operation(action).await,
+
             // Legacy/Misc
-            Action::Sort(_) | Action::Filter(_) => {
+            Action::Filter(_) => {
                 self.dispatch_legacy_action(action).await;
             }
 
@@ -1152,18 +1940,147 @@ impl EventLoop {
                 }
             }
 
-            Action::Key(_) | Action::Mouse(_) | Action::Resize(..) | Action::NoOp => {
+            Action::Mouse(event) => self.dispatch_mouse_action(event).await,
+
+            Action::Key(_) | Action::Resize(..) | Action::NoOp => {
                 let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
                 app.ui.request_redraw(RedrawFlag::All);
             }
         }
 
+        // Keep the Miller side columns in step with selection and directory changes
+        self.app.lock().await.refresh_previews();
+
         let execution_time: Duration = start_time.elapsed();
         if execution_time.as_millis() > 10 {
             debug!("Action dispatch took {:.2}ms", execution_time.as_millis());
         }
     }
 
+    /// Mouse input, mapped through the areas recorded by the last frame. In
+    /// the panes a click focuses the pane and selects the row under it, a
+    /// double click enters or opens it, and the wheel scrolls the pane under
+    /// the pointer. Open overlays and command completions take clicks and
+    /// wheel turns for their own lists.
+    async fn dispatch_mouse_action(&self, event: MouseEvent) {
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        let mode: UIMode = app.ui.mode;
+        let overlay: UIOverlay = app.ui.overlay;
+
+        let action: Action = if mode != UIMode::Command
+            && matches!(overlay, UIOverlay::None | UIOverlay::QuickFilter)
+        {
+            let action: Action = Self::pane_mouse(&mut app, event);
+
+            drop(app);
+
+            action
+        } else {
+            let list: Option<ListHit> = if mode == UIMode::Command {
+                app.ui.hits.completions
+            } else {
+                app.ui.hits.overlay_list
+            };
+
+            let key: Option<KeyCode> = Self::list_mouse(&mut app.ui, list, event);
+
+            drop(app);
+
+            // The list's own key handler does the rest
+            match key {
+                Some(code) => self.route_key(KeyEvent::from(code), mode, overlay).await,
+
+                None => Action::NoOp,
+            }
+        };
+
+        if !matches!(action, Action::NoOp) {
+            debug!("Mouse event mapped to action: {:?}", action);
+            Box::pin(self.dispatch_action(action)).await;
+        }
+    }
+
+    /// Clicks and wheel turns over the panes.
+    fn pane_mouse(app: &mut AppState, event: MouseEvent) -> Action {
+        let Some(hit) = app.ui.hits.pane_at(event.column, event.row).copied() else {
+            return Action::NoOp;
+        };
+
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let double: bool = app.ui.register_click(event.column, event.row);
+
+                // A visual range stays in its pane and directory
+                let visual: bool = app.ui.is_in_visual_mode();
+                if visual && hit.pane != app.fs.active_pane {
+                    return Action::NoOp;
+                }
+
+                app.focus_pane(hit.pane);
+                app.ui.request_redraw(RedrawFlag::Main);
+
+                let Some(idx) = hit.rows.item_at(event.column, event.row) else {
+                    return Action::NoOp;
+                };
+
+                app.fs.active_pane_mut().select_index(idx);
+                app.ui.set_selected(Some(idx));
+
+                if double && !visual { Action::EnterSelected } else { Action::NoOp }
+            }
+
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                let delta: isize = if event.kind == MouseEventKind::ScrollUp {
+                    -WHEEL_SCROLL_LINES
+                } else {
+                    WHEEL_SCROLL_LINES
+                };
+
+                app.fs.panes[hit.pane].scroll_viewport(delta);
+
+                if hit.pane == app.fs.active_pane {
+                    app.ui.selected = app.fs.active_pane().selected;
+                }
+
+                app.ui.request_redraw(RedrawFlag::Main);
+
+                Action::NoOp
+            }
+
+            _ => Action::NoOp,
+        }
+    }
+
+    /// Clicks and wheel turns while an overlay or the command line is open:
+    /// a click highlights the item under it and a double click activates
+    /// it. Returns the key that finishes the job, if any.
+    fn list_mouse(ui: &mut UIState, list: Option<ListHit>, event: MouseEvent) -> Option<KeyCode> {
+        match event.kind {
+            MouseEventKind::ScrollUp => Some(KeyCode::Up),
+
+            MouseEventKind::ScrollDown => Some(KeyCode::Down),
+
+            MouseEventKind::Down(MouseButton::Left) => {
+                let double: bool = ui.register_click(event.column, event.row);
+                let idx: usize = list?.item_at(event.column, event.row)?;
+
+                // Completions are applied with Tab, overlay items with Enter
+                if ui.mode == UIMode::Command {
+                    ui.command_palette.completion_index = idx;
+                    ui.request_redraw(RedrawFlag::Command);
+
+                    return double.then_some(KeyCode::Tab);
+                }
+
+                ui.select_overlay_item(idx);
+
+                double.then_some(KeyCode::Enter)
+            }
+
+            _ => None,
+        }
+    }
+
     #[expect(clippy::cognitive_complexity, reason = "Probably refactor later")]
     async fn dispatch_ui_action(&self, action: Action) {
         match action {
@@ -1240,72 +2157,425 @@ impl EventLoop {
                 info!("Closed overlay: {:?}", previous_overlay);
             }
             
+            Action::ToggleHistory => {
+                debug!("Toggling history overlay");
+                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+                let current: usize = app.fs.active_pane().back_stack.len();
+                app.ui.toggle_history_overlay(current);
+
+                info!("History overlay toggled to: {:?}", app.ui.overlay);
+
+                drop(app);
+            }
+
+            Action::ToggleDiagnostics => {
+                debug!("Toggling diagnostics overlay");
+                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+                app.ui.toggle_diagnostics_overlay();
+
+                info!("Diagnostics overlay toggled to: {:?}", app.ui.overlay);
+
+                drop(app);
+            }
+
             Action::ToggleShowHidden => {
                 debug!("Toggling hidden files visibility");
                 let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
                 app.ui.toggle_show_hidden();
                 app.ui.request_redraw(RedrawFlag::All);
             }
+
+            Action::ToggleLayout => {
+                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+                app.toggle_layout();
+
+                info!("Layout toggled to: {}", app.ui.layout);
+            }
+
+            Action::SetLayout(layout) => {
+                info!("Setting layout: {}", layout);
+                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+                app.ui.exit_command_mode();
+                app.set_layout(layout);
+            }
+
+            Action::ToggleColumn(column) => {
+                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+                app.ui.exit_command_mode();
+
+                let shown: bool = app.ui.toggle_column(column);
+                info!("Column {} shown: {}", column, shown);
+                app.set_status(format!("Column {column}: {}", if shown { "on" } else { "off" }));
+            }
+
+            Action::SetColumnFormat { column, format } => {
+                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+                app.ui.exit_command_mode();
+
+                info!("Column {} format: {}", column, format);
+                app.set_status(format!("Column {column}: {format}"));
+                app.ui.set_column_format(column, format);
+            }
             Action::SimulateLoading => {
                 debug!("Simulating loading state");
                 let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
 
-                app.ui.loading = Some(LoadingState {
-                    message: "Simulated loading...".into(),
-                    progress: None,
-                    spinner_frame: 0,
-                    current_item: Some("demo.txt".into()),
-                    completed: Some(0),
-                    total: Some(100),
-                });
+                app.ui.loading = Some(LoadingState {
+                    message: "Simulated loading...".into(),
+                    progress: None,
+                    spinner_frame: 0,
+                    current_item: Some("demo.txt".into()),
+                    completed: Some(0),
+                    total: Some(100),
+                });
+
+                app.ui.overlay = UIOverlay::Loading;
+                app.ui.request_redraw(RedrawFlag::All);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    async fn dispatch_navigation_action(&self, action: Action) {
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        match action {
+            Action::MoveSelectionUp => {
+                debug!("Moving selection up");
+                app.fs.active_pane_mut().move_selection_up();
+                app.ui.selected = app.fs.active_pane().selected;
+            }
+            Action::MoveSelectionDown => {
+                debug!("Moving selection down");
+                app.fs.active_pane_mut().move_selection_down();
+                app.ui.selected = app.fs.active_pane().selected;
+            }
+            Action::PageUp => {
+                debug!("Page up");
+                app.fs.active_pane_mut().page_up();
+                app.ui.selected = app.fs.active_pane().selected;
+            }
+            Action::PageDown => {
+                debug!("Page down");
+                app.fs.active_pane_mut().page_down();
+                app.ui.selected = app.fs.active_pane().selected;
+            }
+            Action::SelectFirst => {
+                debug!("Selecting first entry");
+                app.fs.active_pane_mut().select_first();
+                app.ui.selected = app.fs.active_pane().selected;
+            }
+            Action::SelectLast => {
+                debug!("Selecting last entry");
+                app.fs.active_pane_mut().select_last();
+                app.ui.selected = app.fs.active_pane().selected;
+            }
+            Action::EnterSelected => {
+                debug!("Entering selected item");
+                app.enter_selected_directory().await;
+            }
+            Action::GoToParent => {
+                info!("Going to parent directory");
+                app.go_to_parent_directory().await;
+            }
+            Action::SwitchPane => {
+                debug!("Switching pane focus");
+                app.switch_pane();
+            }
+            Action::ToggleDualPane => {
+                info!("Toggling dual-pane mode");
+                app.toggle_dual_pane();
+            }
+            Action::NavigateBack => {
+                info!("Navigating back");
+                app.navigate_back().await;
+            }
+            Action::NavigateForward => {
+                info!("Navigating forward");
+                app.navigate_forward().await;
+            }
+            Action::JumpToHistory(index) => {
+                info!("Jumping to history entry {}", index);
+                app.ui.close_all_overlays();
+                app.jump_to_history(index).await;
+            }
+            Action::ToggleTreeView => {
+                info!("Toggling tree view");
+                app.ui.exit_command_mode();
+                app.toggle_tree_view();
+            }
+            Action::TreeExpand => {
+                debug!("Expanding tree directory");
+                if app.fs.active_pane().is_tree_view() {
+                    app.expand_tree_selection().await;
+                } else if app.ui.layout == LayoutMode::Miller
+                    && app.fs.active_pane().selected_entry().is_some_and(|e: &ObjectInfo| -> bool { e.is_dir })
+                {
+                    // Miller columns move right into the selected directory
+                    app.enter_selected_directory().await;
+                }
+            }
+            Action::TreeCollapse => {
+                debug!("Collapsing tree directory");
+                if app.fs.active_pane().is_tree_view() {
+                    app.collapse_tree_selection();
+                } else if app.ui.layout == LayoutMode::Miller {
+                    app.go_to_parent_directory().await;
+                }
+            }
+            _ => unreachable!(),
+        }
+        app.ui.request_redraw(RedrawFlag::All);
+    }
+
+    async fn dispatch_bookmark_action(&self, action: Action) {
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        match action {
+            Action::AddBookmark(name) => {
+                info!("Adding bookmark {:?}", name);
+                app.ui.exit_command_mode();
+                app.add_bookmark(name).await;
+            }
+            Action::RemoveBookmark(name) => {
+                info!("Removing bookmark '{}'", name);
+                if app.ui.is_in_command_mode() {
+                    app.ui.exit_command_mode();
+                }
+                app.remove_bookmark(&name).await;
+
+                let len: usize = app.bookmarks.filtered(&app.ui.input).len();
+                app.ui.overlay_selected = app.ui.overlay_selected.min(len.saturating_sub(1));
+            }
+            Action::JumpToBookmark(name) => {
+                info!("Jumping to bookmark '{}'", name);
+                app.ui.close_all_overlays();
+                app.jump_to_bookmark(&name).await;
+            }
+            Action::ToggleBookmarks => {
+                app.ui.exit_command_mode();
+                app.toggle_bookmarks_overlay().await;
+                info!("Bookmark overlay toggled to: {:?}", app.ui.overlay);
+            }
+            _ => unreachable!(),
+        }
+        app.ui.request_redraw(RedrawFlag::All);
+    }
+
+    async fn dispatch_filter_action(&self, action: Action) {
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        match action {
+            Action::ToggleQuickFilter => {
+                app.ui.exit_command_mode();
+                app.toggle_quick_filter();
+                info!("Quick filter toggled to: {:?}", app.ui.overlay);
+            }
+            Action::QuickFilter(query) => {
+                debug!("Quick filter: '{}'", query);
+                app.filter_entries(&query);
+            }
+            Action::ClearQuickFilter => {
+                info!("Clearing quick filter");
+                app.clear_quick_filter();
+            }
+            _ => unreachable!(),
+        }
+        app.ui.request_redraw(RedrawFlag::All);
+    }
+
+    async fn dispatch_sort_action(&self, action: Action) {
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        if app.ui.is_in_command_mode() {
+            app.ui.exit_command_mode();
+        }
+        match action {
+            Action::Sort(criteria) if criteria.trim().is_empty() => {
+                app.cycle_sort();
+            }
+            Action::Sort(criteria) => {
+                app.sort_entries(&criteria);
+            }
+            Action::ToggleDirsFirst => {
+                info!("Toggling directories first");
+                app.toggle_dirs_first();
+            }
+            _ => unreachable!(),
+        }
+        app.ui.request_redraw(RedrawFlag::All);
+    }
+
+    async fn dispatch_tab_action(&self, action: Action) {
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        if app.ui.is_in_command_mode() {
+            app.ui.exit_command_mode();
+        }
+        match action {
+            Action::NewTab => {
+                info!("Opening new tab");
+                app.open_tab().await;
+            }
+            Action::CloseTab => {
+                info!("Closing tab {}", app.tabs.active_id());
+                app.close_tab().await;
+            }
+            Action::NextTab => {
+                app.cycle_tab(1).await;
+            }
+            Action::PrevTab => {
+                app.cycle_tab(-1).await;
+            }
+            Action::SwitchTab(index) => {
+                info!("Switching to tab {}", index + 1);
+                app.switch_tab(index).await;
+            }
+            Action::MoveTab(delta) => {
+                debug!("Moving tab by {}", delta);
+                app.move_tab(delta);
+            }
+            Action::RenameTab(name) => {
+                info!("Renaming tab to '{}'", name);
+                app.rename_tab(&name);
+            }
+            _ => unreachable!(),
+        }
+        app.ui.request_redraw(RedrawFlag::All);
+    }
+
+    async fn dispatch_frecency_action(&self, action: Action) {
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        match action {
+            Action::JumpToFrecent(query) => {
+                info!("Frecency jump for '{}'", query);
+                app.ui.exit_command_mode();
+                app.jump_to_frecent(&query).await;
+            }
+            Action::JumpToFrecentPath(path) => {
+                info!("Jumping to frecent directory {}", path.display());
+                app.ui.close_all_overlays();
+                app.jump_to_frecent_path(path).await;
+            }
+            Action::ForgetFrecent(path) => {
+                info!("Forgetting frecent directory {}", path.display());
+                app.frecency.remove(&path);
+                app.frecency.save_in_background();
+
+                let len: usize = app.frecency.ranked(&app.ui.input).len();
+                app.ui.overlay_selected = app.ui.overlay_selected.min(len.saturating_sub(1));
+            }
+            Action::ToggleFrecency(query) => {
+                app.ui.exit_command_mode();
+                app.ui.toggle_frecency_overlay(&query);
+                info!("Frecency overlay toggled to: {:?}", app.ui.overlay);
+            }
+            _ => unreachable!(),
+        }
+        app.ui.request_redraw(RedrawFlag::All);
+    }
+
+    async fn dispatch_trash_action(&self, action: Action) {
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        match action {
+            Action::Delete => {
+                app.ui.exit_command_mode();
+
+                // Marks the pane doesn't show must not be trashed unseen
+                if !app.ui.is_in_visual_mode() && app.count_outside_cwd(&app.fs.marked) > 0 {
+                    info!("Asking before trashing marks outside {}", app.fs.active_pane().cwd.display());
+                    app.ui.show_input_prompt(InputPromptType::ConfirmTrashMarked);
+                } else if app.has_selection() {
+                    drop(app);
+                    Box::pin(self.dispatch_action(Action::TrashMarked)).await;
+                    return;
+                } else if let Some(source) = Self::extract_selected_file_path(&app) {
+                    drop(app);
+                    self.handle_trash_operation(source).await;
+                    return;
+                }
+            }
+            Action::DeletePermanently => {
+                app.ui.exit_command_mode();
+
+                let paths: Vec<PathBuf> = if app.has_selection() {
+                    app.selection_paths()
+                } else {
+                    Self::extract_selected_file_path(&app).into_iter().collect()
+                };
 
-                app.ui.overlay = UIOverlay::Loading;
-                app.ui.request_redraw(RedrawFlag::All);
+                if !paths.is_empty() {
+                    info!("Asking before deleting {} entries permanently", paths.len());
+                    app.ui.show_input_prompt(InputPromptType::ConfirmDelete(paths));
+                }
+            }
+            Action::RestoreTrashed(info_path) => {
+                info!("Restoring trashed entry {}", info_path.display());
+                app.restore_trashed(&info_path).await;
+            }
+            Action::PurgeTrashed(info_path) => {
+                info!("Purging trashed entry {}", info_path.display());
+                app.purge_trashed(&info_path).await;
+            }
+            Action::ToggleTrash => {
+                app.ui.exit_command_mode();
+                app.toggle_trash_overlay().await;
+                info!("Trash overlay toggled to: {:?}", app.ui.overlay);
             }
             _ => unreachable!(),
         }
+        app.ui.request_redraw(RedrawFlag::All);
     }
 
-    async fn dispatch_navigation_action(&self, action: Action) {
+    async fn dispatch_undo_action(&self, action: Action) {
         let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
         match action {
-            Action::MoveSelectionUp => {
-                debug!("Moving selection up");
-                app.fs.active_pane_mut().move_selection_up();
-                app.ui.selected = app.fs.active_pane().selected;
+            Action::Undo(steps) => {
+                info!("Undoing {} operation(s)", steps);
+                app.ui.exit_command_mode();
+                app.undo(steps);
             }
-            Action::MoveSelectionDown => {
-                debug!("Moving selection down");
-                app.fs.active_pane_mut().move_selection_down();
-                app.ui.selected = app.fs.active_pane().selected;
+            Action::Redo(steps) => {
+                info!("Redoing {} operation(s)", steps);
+                app.ui.exit_command_mode();
+                app.redo(steps);
             }
-            Action::PageUp => {
-                debug!("Page up");
-                app.fs.active_pane_mut().page_up();
-                app.ui.selected = app.fs.active_pane().selected;
+            Action::UndoFinished(outcome) => {
+                info!("{} run finished, {} applied", outcome.direction.verb(), outcome.applied.len());
+                app.finish_undo(outcome).await;
             }
-            Action::PageDown => {
-                debug!("Page down");
-                app.fs.active_pane_mut().page_down();
-                app.ui.selected = app.fs.active_pane().selected;
+            Action::ToggleUndoHistory => {
+                app.ui.exit_command_mode();
+                let next_undo: usize = app.redo_history.len();
+                app.ui.toggle_undo_history_overlay(next_undo);
+                info!("Undo history overlay toggled to: {:?}", app.ui.overlay);
             }
-            Action::SelectFirst => {
-                debug!("Selecting first entry");
-                app.fs.active_pane_mut().select_first();
-                app.ui.selected = app.fs.active_pane().selected;
+            _ => unreachable!(),
+        }
+        app.ui.request_redraw(RedrawFlag::All);
+    }
+
+    async fn dispatch_mark_action(&self, action: Action) {
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        match action {
+            Action::ToggleMark => {
+                app.toggle_mark_selected();
+                debug!("{} entries marked", app.fs.marked.len());
             }
-            Action::SelectLast => {
-                debug!("Selecting last entry");
-                app.fs.active_pane_mut().select_last();
-                app.ui.selected = app.fs.active_pane().selected;
+            Action::MarkAll => {
+                app.mark_all();
+                info!("Marked all entries, {} marked", app.fs.marked.len());
             }
-            Action::EnterSelected => {
-                debug!("Entering selected item");
-                app.enter_selected_directory().await;
+            Action::InvertMarks => {
+                app.invert_marks();
+                info!("Inverted marks, {} marked", app.fs.marked.len());
             }
-            Action::GoToParent => {
-                info!("Going to parent directory");
-                app.go_to_parent_directory().await;
+            Action::ClearMarks => {
+                info!("Clearing {} marks", app.fs.marked.len());
+                app.clear_marks();
+            }
+            Action::ToggleVisualMode => {
+                app.toggle_visual_mode();
+                info!("Visual mode: {}", app.ui.is_in_visual_mode());
+            }
+            Action::MarkVisualRange => {
+                let count: usize = app.mark_visual_range();
+                info!("Marked {count} entries of the visual range");
             }
             _ => unreachable!(),
         }
@@ -1335,10 +2605,6 @@ impl EventLoop {
                 info!("Reloading directory (command-driven)");
                 app.reload_directory().await;
             }
-            Action::Delete => {
-                info!("Delete action triggered - this should now be command-driven");
-                app.delete_entry().await;
-            }
             Action::RenameEntry(new_name) => {
                 info!("Renaming selected entry to '{}'", new_name);
                 app.rename_selected_entry(new_name).await;
@@ -1382,17 +2648,26 @@ impl EventLoop {
             Action::ShowSearchResults(results) => {
                 self.handle_show_search_results(results).await;
             }
-            Action::ShowFilenameSearchResults(results) => {
+            Action::ShowFilenameSearchResults(tab, results) => {
                 info!("Showing {} filename search results", results.len());
                 let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+
+                if tab != app.tabs.active_id() {
+                    if let Some(background) = app.tabs.background_mut(tab) {
+                        debug!("Storing filename search results for background tab {}", tab);
+                        background.ui.filename_search_results = results;
+                    }
+                    return;
+                }
+
                 app.ui.filename_search_results.clone_from(&results);
                 app.ui.request_redraw(RedrawFlag::All);
             }
             Action::ShowRichSearchResults(results) => {
                 self.handle_show_rich_search_results(results).await;
             }
-            Action::ShowRawSearchResults(results) => {
-                self.handle_show_raw_search_results(results).await;
+            Action::ShowRawSearchResults(tab, results) => {
+                self.handle_show_raw_search_results(tab, results).await;
             }
             Action::OpenFile(path, line_number) => {
                 self.handle_open_file(path, line_number).await;
@@ -1427,9 +2702,18 @@ impl EventLoop {
         app.ui.request_redraw(RedrawFlag::All);
     }
 
-    async fn handle_show_raw_search_results(&self, results: RawSearchResult) {
+    async fn handle_show_raw_search_results(&self, tab: TabId, results: RawSearchResult) {
         info!("Showing {} raw search results", results.lines.len());
         let mut app = self.app.lock().await;
+
+        if tab != app.tabs.active_id() {
+            if let Some(background) = app.tabs.background_mut(tab) {
+                debug!("Storing content search results for background tab {}", tab);
+                background.ui.raw_search_results = Some(results);
+                background.ui.raw_search_selected = 0;
+            }
+            return;
+        }
         app.ui.raw_search_results = Some(results);
         app.ui.raw_search_selected = 0;
 
@@ -1442,22 +2726,22 @@ impl EventLoop {
 
     async fn handle_open_file(&self, path: PathBuf, line_number: Option<usize>) {
         info!("Opening file {:?} at line {:?}", path, line_number);
-        let path_str: String = path.to_string_lossy().to_string();
-
         let mut cmd: Command = Command::new("code");
 
         if let Some(line) = line_number {
-            let goto_arg = format!("{path_str}:{line}");
-            debug!("Using VS Code --goto argument: '{}'", goto_arg);
+            // Built as an OS string so paths that are not UTF-8 stay intact
+            let mut goto_arg: OsString = path.clone().into_os_string();
+            goto_arg.push(format!(":{line}"));
+            debug!("Using VS Code --goto argument: {:?}", goto_arg);
             cmd.arg("--goto").arg(goto_arg);
         } else {
             debug!("Opening file without line number");
-            cmd.arg(&path_str);
+            cmd.arg(&path);
         }
 
         match cmd.spawn() {
             Ok(_) => {
-                info!("Successfully launched VS Code for file: {}", path_str);
+                info!("Successfully launched VS Code for file: {}", path.display());
                 let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
                 app.ui.close_all_overlays();
                 app.ui.request_redraw(RedrawFlag::All);
@@ -1476,8 +2760,8 @@ impl EventLoop {
             Action::TaskResult(task_result) => {
                 self.handle_task_result(task_result).await;
             }
-            Action::DirectoryScanUpdate { path, update } => {
-                self.handle_directory_scan_update(path, update).await;
+            Action::DirectoryScanUpdate { tab, scan, path, update } => {
+                self.handle_directory_scan_update(tab, scan, path, update).await;
             }
             Action::UpdateObjectInfo { parent_dir, info } => {
                 trace!("Updating object info for {:?}", info.path);
@@ -1485,6 +2769,26 @@ impl EventLoop {
                 app.update_object_info(&parent_dir, &info);
                 app.ui.request_redraw(RedrawFlag::All);
             }
+            Action::FsChanged { dir, changes } => {
+                trace!("{} filesystem change(s) in {:?}", changes.len(), dir);
+                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+                let changed_dirs: Vec<ObjectInfo> = app.apply_fs_changes(&dir, &changes);
+                let action_tx: UnboundedSender<Action> = app.action_tx.clone();
+
+                for entry in changed_dirs {
+                    FileSizeOperator::calculate_size_task(
+                        dir.clone(),
+                        entry,
+                        action_tx.clone(),
+                        app.cache.clone(),
+                    );
+                }
+            }
+            Action::PreviewLoaded(path, preview) => {
+                trace!("Preview loaded for {:?}", path);
+                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+                app.set_preview(path, preview);
+            }
             _ => unreachable!(),
         }
     }
@@ -1592,7 +2896,6 @@ impl EventLoop {
         );
     }
 
-    #[allow(clippy::unused_async)]
     async fn handle_file_op_complete(
         &self,
         app: &mut AppState,
@@ -1601,10 +2904,36 @@ impl EventLoop {
     ) {
         app.ui.remove_operation(&operation_id);
 
+        let origin: Option<TabId> = app.file_op_tabs.remove(&operation_id);
+
         match result {
             Ok(()) => {
                 info!("File operation {} completed successfully", operation_id);
-                app.ui.show_info("File operation completed".to_string());
+
+                match origin {
+                    Some(tab) if tab != app.tabs.active_id() => {
+                        // Refresh the originating tab once it is shown again
+                        let title: String = app
+                            .tabs
+                            .position(tab)
+                            .map(|idx: usize| -> String { app.tab_title(idx) })
+                            .unwrap_or_default();
+
+                        if let Some(background) = app.tabs.background_mut(tab) {
+                            background.needs_reload = true;
+                        }
+
+                        app.ui
+                            .show_info(format!("File operation completed (tab '{title}')"));
+                    }
+
+                    Some(_) => {
+                        app.reload_directory().await;
+                        app.ui.show_info("File operation completed".to_string());
+                    }
+
+                    None => app.ui.show_info("File operation completed".to_string()),
+                }
             }
             Err(e) => {
                 if e.to_string().contains("Cancelled") {
@@ -1670,31 +2999,167 @@ impl EventLoop {
         }
     }
 
-    async fn handle_directory_scan_update(&self, path: PathBuf, update: ScanUpdate) {
-        debug!("Directory scan update for path: {:?}", path);
+    async fn handle_directory_scan_update(&self, tab: TabId, scan: u64, path: PathBuf, update: ScanUpdate) {
+        debug!("Directory scan update for path: {:?} (tab {}, scan {})", path, tab, scan);
         let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        let is_active_tab: bool = tab == app.tabs.active_id();
 
-        if app.fs.active_pane().cwd == path {
-            match update {
-                ScanUpdate::Entry(entry) => {
-                    trace!("Adding incremental entry: {:?}", entry.name);
-                    app.fs.active_pane_mut().add_incremental_entry(entry);
-                    app.ui.request_redraw(RedrawFlag::All);
+        let Some(fs) = app.fs_for_tab_mut(tab) else {
+            trace!("Dropping scan update for closed tab {}", tab);
+            return;
+        };
+
+        // Route the update to the pane this scan is loading; updates of a
+        // scan that was superseded (e.g. by a reload) match no pane
+        let targets: Vec<usize> = fs
+            .panes
+            .iter()
+            .enumerate()
+            .filter(|(_, pane): &(usize, &PaneState)| -> bool 
+                {
+                    pane.cwd == path && pane.is_incremental_loading && pane.scan_id == Some(scan)
                 }
-                ScanUpdate::Completed(count) => {
-                    self.handle_scan_completed(&mut app, path, count).await;
+            )
+            .map(|(idx, _): (usize, &PaneState)| -> usize { idx })
+            .collect();
+
+        if targets.is_empty() {
+            self.handle_tree_scan_update(&mut app, tab, path, update).await;
+            return;
+        }
+
+        match update {
+            ScanUpdate::Entries(entries) => {
+                trace!("Adding {} incremental entries", entries.len());
+                for &idx in &targets {
+                    for entry in &entries {
+                        fs.panes[idx].add_incremental_entry(entry.clone());
+                    }
                 }
-                ScanUpdate::Error(e) => {
-                    warn!("Directory scan error: {}", e);
-                    let current_pane: &mut PaneState = app.fs.active_pane_mut();
-                    current_pane.is_loading = false;
-                    current_pane.is_incremental_loading = false;
-                    let err_msg: String = format!("Error scanning directory: {e}");
-                    current_pane.last_error = Some(err_msg.clone());
-                    app.set_error(err_msg);
+                if is_active_tab {
                     app.ui.request_redraw(RedrawFlag::All);
                 }
             }
+            ScanUpdate::Completed(count) => {
+                self.handle_scan_completed(&mut app, tab, &targets, path, count).await;
+            }
+            ScanUpdate::Error(e) => {
+                warn!("Directory scan error: {}", e);
+                let err_msg: String = format!("Error scanning directory: {e}");
+                for &idx in &targets {
+                    fs.panes[idx].abort_incremental_loading(err_msg.clone());
+                }
+                app.set_error(err_msg);
+                app.ui.request_redraw(RedrawFlag::All);
+            }
+        }
+    }
+
+    /// Scan updates for directories expanded in tree view.
+    async fn handle_tree_scan_update(
+        &self,
+        app: &mut AppState,
+        tab: TabId,
+        path: PathBuf,
+        update: ScanUpdate,
+    ) {
+        let is_active_tab: bool = tab == app.tabs.active_id();
+
+        let Some(fs) = app.fs_for_tab_mut(tab) else {
+            return;
+        };
+
+        let targets: Vec<usize> = fs
+            .panes
+            .iter()
+            .enumerate()
+            .filter(|(_, pane): &(usize, &PaneState)| -> bool {
+                pane.tree
+                    .as_ref()
+                    .is_some_and(|t: &TreeState| -> bool { t.loading.contains(&path) })
+            })
+            .map(|(idx, _): (usize, &PaneState)| -> usize { idx })
+            .collect();
+
+        if targets.is_empty() {
+            trace!("No pane is waiting for scan updates of {:?}", path);
+            return;
+        }
+
+        match update {
+            ScanUpdate::Entries(entries) => {
+                for &idx in &targets {
+                    for entry in &entries {
+                        fs.panes[idx].add_tree_child(&path, entry.clone());
+                    }
+                }
+            }
+
+            ScanUpdate::Completed(count) => {
+                debug!("Tree children of {:?} loaded: {} entries", path, count);
+
+                let mut child_dirs: Vec<ObjectInfo> = Vec::new();
+                let mut tree_loads: Vec<PathBuf> = Vec::new();
+
+                for &idx in &targets {
+                    let pane: &mut PaneState = &mut fs.panes[idx];
+                    pane.complete_tree_children(&path);
+
+                    // Nested subtrees that were expanded before a reload
+                    for dir in pane.take_tree_pending_loads() {
+                        if !tree_loads.contains(&dir) {
+                            tree_loads.push(dir);
+                        }
+                    }
+
+                    if child_dirs.is_empty()
+                        && let Some(level) = pane
+                            .tree
+                            .as_ref()
+                            .and_then(|t: &TreeState| t.levels.get(&path))
+                    {
+                        child_dirs = level
+                            .iter()
+                            .filter(|e: &&ObjectInfo| -> bool { e.is_dir })
+                            .cloned()
+                            .collect();
+                    }
+                }
+
+                if is_active_tab {
+                    app.ui.selected = app.fs.active_pane().selected;
+                }
+
+                let action_tx: UnboundedSender<Action> = app.action_tx.clone();
+
+                for entry in child_dirs {
+                    FileSizeOperator::calculate_size_task(
+                        path.clone(),
+                        entry,
+                        action_tx.clone(),
+                        app.cache.clone(),
+                    );
+                }
+
+                for dir in tree_loads {
+                    app.spawn_directory_scan(tab, dir).await;
+                }
+            }
+
+            ScanUpdate::Error(e) => {
+                warn!("Tree scan error for {:?}: {}", path, e);
+                let err_msg: String = format!("Error scanning directory: {e}");
+
+                for &idx in &targets {
+                    fs.panes[idx].fail_tree_children(&path, err_msg.clone());
+                }
+
+                app.set_error(err_msg);
+            }
+        }
+
+        if is_active_tab {
+            app.ui.request_redraw(RedrawFlag::All);
         }
     }
 
@@ -1713,22 +3178,70 @@ impl EventLoop {
     async fn handle_show_input_prompt(&self, prompt_type: InputPromptType) {
         info!("Showing input prompt: {:?}", prompt_type);
         let mut app = self.app.lock().await;
+
+        // In dual-pane mode the other pane is the natural copy/move target
+        let default_dest: Option<String> = match prompt_type {
+            InputPromptType::CopyDestination | InputPromptType::MoveDestination => app
+                .fs
+                .inactive_pane()
+                .map(|pane: &PaneState| -> String { pane.cwd.display().to_string() }),
+
+            _ => None,
+        };
+
         app.ui.show_input_prompt(prompt_type);
+
+        if let Some(dest) = default_dest {
+            debug!("Prefilling destination with other pane: {}", dest);
+            app.ui.set_input(dest);
+        }
+
         app.ui.request_redraw(RedrawFlag::All);
     }
 
-    #[allow(clippy::unused_async)]
-    async fn handle_scan_completed(&self, app: &mut AppState, path: PathBuf, count: usize) {
+    async fn handle_scan_completed(
+        &self,
+        app: &mut AppState,
+        tab: TabId,
+        panes: &[usize],
+        path: PathBuf,
+        count: usize,
+    ) {
         info!("Directory scan completed with {} entries", count);
-        let entries: Vec<ObjectInfo> = app.fs.active_pane().entries.clone();
 
-        app.fs
-            .active_pane_mut()
-            .complete_incremental_loading(entries);
-        app.fs.add_recent_dir(path.clone());
+        let Some(fs) = app.fs_for_tab_mut(tab) else {
+            return;
+        };
+
+        for &idx in panes {
+            fs.panes[idx].complete_incremental_loading();
+        }
+
+        // Expanded subtrees of a reloaded tree are scanned again
+        let mut tree_loads: Vec<PathBuf> = Vec::new();
+        for &idx in panes {
+            for dir in fs.panes[idx].take_tree_pending_loads() {
+                if !tree_loads.contains(&dir) {
+                    tree_loads.push(dir);
+                }
+            }
+        }
+
+        // Only top-level entries belong to this scan (tree rows are nested)
+        let entries_for_size: Vec<ObjectInfo> = fs.panes[panes[0]]
+            .entries
+            .iter()
+            .chain(&fs.panes[panes[0]].filtered_out)
+            .filter(|e: &&ObjectInfo| -> bool { e.path.parent() == Some(path.as_path()) })
+            .cloned()
+            .collect();
+        let active_pane_done: bool = panes.contains(&fs.active_pane);
+
+        if tab == app.tabs.active_id() && active_pane_done {
+            app.ui.selected = app.fs.active_pane().selected;
+        }
 
         let action_tx: UnboundedSender<Action> = app.action_tx.clone();
-        let entries_for_size: Vec<ObjectInfo> = app.fs.active_pane().entries.clone();
 
         for entry in entries_for_size {
             if entry.is_dir {
@@ -1741,6 +3254,10 @@ impl EventLoop {
             }
         }
 
+        for dir in tree_loads {
+            app.spawn_directory_scan(tab, dir).await;
+        }
+
         app.ui.request_redraw(RedrawFlag::All);
     }
 
@@ -1788,6 +3305,14 @@ impl EventLoop {
             Some(InputPromptType::RenameFile) => {
                 self.process_rename_file_prompt(app, input).await;
             }
+
+            Some(InputPromptType::ConfirmDelete(paths)) => {
+                self.process_confirm_delete_prompt(app, paths, &input).await;
+            }
+
+            Some(InputPromptType::ConfirmTrashMarked) => {
+                self.process_confirm_trash_marked_prompt(app, &input).await;
+            }
             
             None => {
                 drop(app);
@@ -1843,11 +3368,19 @@ impl EventLoop {
     async fn process_copy_destination_prompt(&self, app: MutexGuard<'_, AppState>, input: String) {
         info!("Processing copy destination prompt with input: '{}'", input);
 
+        let dest: PathBuf = app.resolve_input_path(&input);
+
+        if app.has_selection() {
+            drop(app);
+            Box::pin(self.dispatch_action(Action::CopyMarked(dest))).await;
+            return;
+        }
+
         let source_path = Self::extract_selected_file_path(&app);
         drop(app);
         
         if let Some(source_path) = source_path {
-            self.execute_copy_operation(source_path, input).await;
+            self.execute_copy_operation(source_path, dest).await;
         } else {
             self.show_copy_error().await;
         }
@@ -1856,11 +3389,19 @@ impl EventLoop {
     async fn process_move_destination_prompt(&self, app: MutexGuard<'_, AppState>, input: String) {
         info!("Processing move destination prompt with input: '{}'", input);
 
+        let dest: PathBuf = app.resolve_input_path(&input);
+
+        if app.has_selection() {
+            drop(app);
+            Box::pin(self.dispatch_action(Action::MoveMarked(dest))).await;
+            return;
+        }
+
         let source_path = Self::extract_selected_file_path(&app);
         drop(app);
         
         if let Some(source_path) = source_path {
-            self.execute_move_operation(source_path, input).await;
+            self.execute_move_operation(source_path, dest).await;
         } else {
             self.show_move_error().await;
         }
@@ -1869,6 +3410,13 @@ impl EventLoop {
     async fn process_rename_file_prompt(&self, app: MutexGuard<'_, AppState>, input: String) {
         info!("Processing rename file prompt with input: '{}'", input);
 
+        if app.has_selection() {
+            let action: Action = Action::RenameMarked(input);
+            drop(app);
+            Box::pin(self.dispatch_action(action)).await;
+            return;
+        }
+
         let source_path = Self::extract_selected_file_path(&app);
         drop(app);
         
@@ -1879,6 +3427,43 @@ impl EventLoop {
         }
     }
 
+    /// Anything but an explicit yes keeps the entry.
+    async fn process_confirm_delete_prompt(&self, mut app: MutexGuard<'_, AppState>, paths: Vec<PathBuf>, input: &str) {
+        if matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+            info!("Deleting {} entries permanently", paths.len());
+
+            // The paths were taken from the visual range when it asked
+            if app.ui.is_in_visual_mode() {
+                app.clear_selection();
+            }
+
+            drop(app);
+            Box::pin(self.dispatch_action(Action::DeletePaths(paths))).await;
+            return;
+        }
+
+        if let [path] = paths.as_slice() {
+            app.set_status(format!("Kept {}", path.display()));
+        } else {
+            app.set_status(format!("Kept {} entries", paths.len()));
+        }
+
+        app.ui.request_redraw(RedrawFlag::All);
+    }
+
+    /// Anything but an explicit yes keeps the marks and trashes nothing.
+    async fn process_confirm_trash_marked_prompt(&self, mut app: MutexGuard<'_, AppState>, input: &str) {
+        if matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+            drop(app);
+            Box::pin(self.dispatch_action(Action::TrashMarked)).await;
+            return;
+        }
+
+        let kept: usize = app.fs.marked.len();
+        app.set_status(format!("Kept {kept} marked entries"));
+        app.ui.request_redraw(RedrawFlag::All);
+    }
+
     fn extract_selected_file_path(app: &MutexGuard<'_, AppState>) -> Option<PathBuf> {
         app.fs.active_pane().selected.and_then(|selected_idx| {
             app.fs
@@ -1892,9 +3477,8 @@ impl EventLoop {
     async fn execute_copy_operation(
         &self,
         source_path: PathBuf,
-        input: String,
+        dest_path: PathBuf,
     ) {
-        let dest_path = PathBuf::from(input);
         Box::pin(self.dispatch_action(Action::Copy {
             source: source_path,
             dest: dest_path,
@@ -1905,10 +3489,8 @@ impl EventLoop {
     async fn execute_move_operation(
         &self,
         source_path: PathBuf,
-        input: String,
+        dest_path: PathBuf,
     ) {
-        let dest_path = PathBuf::from(input);
-
         Box::pin(self.dispatch_action(Action::Move {
             source: source_path,
             dest: dest_path,
@@ -2019,12 +3601,29 @@ impl EventLoop {
         self.spawn_file_operation_task(task, "Move").await;
     }
 
+    async fn handle_trash_operation(&self, source: PathBuf) {
+        info!("Moving {:?} to trash", source);
+
+        let operation = FileOperation::Trash { source: source.clone() };
+
+        let task = self.create_and_spawn_file_operation_task(operation).await;
+
+        let message = format!(
+            "Moving {} to trash",
+            source.file_name().unwrap_or_default().to_string_lossy()
+        );
+
+        self.show_operation_info(message).await;
+
+        self.spawn_file_operation_task(task, "Trash").await;
+    }
+
     async fn handle_rename_operation(&self, source: PathBuf, new_name: String) {
         info!("Starting rename operation: {:?} -> {}", source, new_name);
 
         let operation = FileOperation::Rename {
             source: source.clone(),
-            new_name: new_name.clone(),
+            new_name: OsString::from(&new_name),
         };
 
         let task = self.create_and_spawn_file_operation_task(operation).await;
@@ -2040,6 +3639,102 @@ impl EventLoop {
         self.spawn_file_operation_task(task, "Rename").await;
     }
 
+    /// Run one operation on every entry of the visual range, or else on
+    /// every marked entry (one task, one undo step), and drop that selection.
+    /// Confirmed permanent deletes bring their own entries and only unmark
+    /// those.
+    async fn handle_marked_operation(&self, action: Action) {
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+        let confirmed: bool = matches!(action, Action::DeletePaths(_));
+        let marked: Vec<PathBuf> = match &action {
+            Action::DeletePaths(paths) => paths.clone(),
+
+            _ => app.selection_paths(),
+        };
+
+        drop(app);
+
+        // Marks of entries that are gone since are skipped
+        let mut sources: Vec<PathBuf> = Vec::with_capacity(marked.len());
+        for path in marked {
+            if tokio::fs::symlink_metadata(&path).await.is_ok() {
+                sources.push(path);
+            }
+        }
+
+        let operation: Result<FileOperation, String> = match action {
+            _ if sources.is_empty() => Err("No marked or selected entries".to_string()),
+
+            Action::CopyMarked(dest) | Action::MoveMarked(dest)
+                if !tokio::fs::metadata(&dest).await.is_ok_and(|meta: std::fs::Metadata| -> bool { meta.is_dir() }) =>
+            {
+                Err(format!("{} is not a directory", dest.display()))
+            }
+
+            Action::CopyMarked(dest) => Ok(FileOperation::Batch(
+                sources
+                    .into_iter()
+                    .map(|source: PathBuf| -> FileOperation { FileOperation::Copy { source, dest: dest.clone() } })
+                    .collect(),
+            )),
+
+            Action::MoveMarked(dest) => Ok(FileOperation::Batch(
+                sources
+                    .into_iter()
+                    .map(|source: PathBuf| -> FileOperation { FileOperation::Move { source, dest: dest.clone() } })
+                    .collect(),
+            )),
+
+            Action::RenameMarked(pattern) => FileOperation::batch_rename(&sources, pattern.trim()),
+
+            Action::TrashMarked => Ok(FileOperation::Batch(
+                sources
+                    .into_iter()
+                    .map(|source: PathBuf| -> FileOperation { FileOperation::Trash { source } })
+                    .collect(),
+            )),
+
+            Action::DeletePaths(_) => Ok(FileOperation::Batch(
+                sources
+                    .into_iter()
+                    .map(|source: PathBuf| -> FileOperation { FileOperation::Delete { source } })
+                    .collect(),
+            )),
+
+            _ => unreachable!(),
+        };
+
+        let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
+
+        let operation: FileOperation = match operation {
+            Ok(operation) => operation,
+
+            Err(e) => {
+                app.ui.show_error(e);
+                app.ui.request_redraw(RedrawFlag::All);
+                return;
+            }
+        };
+
+        info!("Starting batch operation: {}", operation);
+
+        if confirmed {
+            for step in operation.steps() {
+                app.fs.marked.remove(step.source());
+            }
+        } else {
+            app.clear_selection();
+        }
+        drop(app);
+
+        let message: String = format!("{operation}...");
+        let task: FileOperationTask = self.create_and_spawn_file_operation_task(operation).await;
+
+        self.show_operation_info(message).await;
+
+        self.spawn_file_operation_task(task, "Batch").await;
+    }
+
     async fn handle_cancel_file_operation(&self, operation_id: String) {
         info!("Cancelling file operation: {operation_id}");
 
@@ -2086,6 +3781,10 @@ impl EventLoop {
         let mut app = self.app.lock().await;
         app.ui
             .store_cancel_token(operation_id.to_string(), cancel_token);
+
+        // Completion is reported back to the tab the operation started in
+        let tab: TabId = app.tabs.active_id();
+        app.file_op_tabs.insert(operation_id.to_string(), tab);
     }
 
     async fn show_operation_info(&self, message: String) {
@@ -2097,57 +3796,45 @@ impl EventLoop {
     #[allow(clippy::unused_async)]
     async fn spawn_file_operation_task(&self, task: FileOperationTask, operation_name: &str) {
         let operation_name = operation_name.to_string();
+        let app: Arc<Mutex<AppState>> = self.app.clone();
 
         tokio::spawn(async move {
-            if let Err(e) = task.execute().await {
-                eprintln!("{operation_name} operation failed: {e}");
+            match task.execute().await {
+                Ok(()) => {}
+
+                Err(crate::error::AppError::Io(e)) if e.kind() == std::io::ErrorKind::Interrupted => {
+                    debug!("{operation_name} operation was cancelled");
+                }
+
+                Err(e) => {
+                    warn!("{operation_name} operation failed: {e}");
+
+                    let mut app: MutexGuard<'_, AppState> = app.lock().await;
+                    app.ui.show_error(format!("{operation_name} operation failed: {e}"));
+                    app.ui.request_redraw(RedrawFlag::All);
+                }
             }
         });
     }
 
     async fn dispatch_legacy_action(&self, action: Action) {
         match action {
-            Action::Sort(_) => {
-                info!("Sort action should now be command-driven (:sort)");
-                let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
-                let active_pane: &mut PaneState = app.fs.active_pane_mut();
-
-                active_pane.sort = match active_pane.sort {
-                    EntrySort::NameAsc => EntrySort::NameDesc,
-
-                    EntrySort::NameDesc => EntrySort::SizeAsc,
-
-                    EntrySort::SizeAsc => EntrySort::SizeDesc,
-
-                    EntrySort::SizeDesc => EntrySort::ModifiedAsc,
-
-                    EntrySort::ModifiedAsc => EntrySort::ModifiedDesc,
-
-                    EntrySort::ModifiedDesc | EntrySort::Custom(_) => EntrySort::NameAsc,
-                };
-
-                let sort_criteria: String = active_pane.sort.to_string();
-                app.sort_entries(&sort_criteria);
-                app.ui.request_redraw(RedrawFlag::All);
-            }
-
             Action::Filter(_) => {
                 info!("Filter action should now be command-driven (:filter)");
                 let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
-                let active_pane: &mut PaneState = app.fs.active_pane_mut();
 
-                active_pane.filter = match active_pane.filter {
+                let filter: EntryFilter = match app.fs.active_pane().filter {
                     EntryFilter::All => EntryFilter::FilesOnly,
                     EntryFilter::FilesOnly => EntryFilter::DirsOnly,
                     EntryFilter::DirsOnly
                     | EntryFilter::Extension(_)
                     | EntryFilter::Pattern(_)
+                    | EntryFilter::Glob(_)
+                    | EntryFilter::Regex(_)
                     | EntryFilter::Custom(_) => EntryFilter::All,
                 };
 
-                let filter_criteria: String = active_pane.filter.to_string();
-                app.filter_entries(&filter_criteria);
-                app.ui.request_redraw(RedrawFlag::All);
+                app.set_pane_filter(filter);
             }
             _ => unreachable!(),
         }
@@ -4,22 +4,87 @@
 //!
 //! Provides an asynchronous function to scan a directory and return a sorted
 //! list of `ObjectInfo` entries. Designed for non-blocking UI updates.
+//!
+//! Directories are read with blocking `readdir` calls on the blocking pool and
+//! streamed in chunks; metadata is stat'ed afterwards in parallel.
 
 use crate::{cache::cache_manager::ObjectInfoCache,
-    config::ProfilingConfig, 
+    config::{ProfilingConfig, SortConfig}, 
     controller::actions::Action, 
     logging::ProfilingData, 
-    tasks::metadata_task::batch_load_metadata_task
+    tasks::metadata_task::{batch_load_metadata_task, load_metadata_parallel}
 };
 use crate::error::AppError;
 use crate::fs::object_info::{LightObjectInfo, ObjectInfo};
-use std::{cmp::Ordering, ffi::OsStr, path::{Path, PathBuf}, sync::Arc, time::Duration};
-use std::time::{Instant, SystemTime};
-use tokio::{fs::{self, DirEntry, ReadDir}, sync::mpsc::{UnboundedReceiver, UnboundedSender}};
-use tokio::sync::mpsc;
-use tokio::task::JoinHandle;
+use crate::model::fs_state::{EntrySort, sort_objects};
+use std::{path::{Path, PathBuf}, sync::Arc, time::Duration};
+use std::time::Instant;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::{JoinError, JoinHandle};
 use tracing::{info, instrument, Span};
 
+/// Entries per `ScanUpdate::Entries` chunk.
+pub const SCAN_CHUNK_SIZE: usize = 512;
+
+/// Entries stat'ed per parallel metadata batch.
+const METADATA_BATCH_SIZE: usize = 1024;
+
+/// Reads a directory with blocking `readdir` calls and hands the entries to
+/// `on_chunk` in chunks of `chunk_size`.
+///
+/// File types come from `readdir` itself, so no entry is stat'ed except
+/// symlinks. Entries that vanish mid-scan are skipped. Reading stops early
+/// when `on_chunk` returns false. Returns the number of entries delivered.
+pub fn read_dir_chunks(
+    path: &Path,
+    show_hidden: bool,
+    chunk_size: usize,
+    mut on_chunk: impl FnMut(Vec<LightObjectInfo>) -> bool,
+) -> std::io::Result<usize> {
+    let chunk_size: usize = chunk_size.max(1);
+    let mut chunk: Vec<LightObjectInfo> = Vec::with_capacity(chunk_size);
+    let mut delivered: usize = 0;
+
+    for entry in std::fs::read_dir(path)? {
+        let entry: std::fs::DirEntry = match entry {
+            Ok(entry) => entry,
+
+            Err(e) => {
+                tracing::debug!(path = %path.display(), error = %e, "Skipping unreadable entry");
+                continue;
+            }
+        };
+
+        if !show_hidden && entry.file_name().as_encoded_bytes().starts_with(b".") {
+            continue;
+        }
+
+        match LightObjectInfo::from_dir_entry(&entry) {
+            Ok(light_info) => chunk.push(light_info),
+
+            Err(e) => {
+                tracing::debug!(path = %entry.path().display(), error = %e, "Skipping vanished entry");
+                continue;
+            }
+        }
+
+        if chunk.len() == chunk_size {
+            delivered += chunk.len();
+
+            if !on_chunk(std::mem::replace(&mut chunk, Vec::with_capacity(chunk_size))) {
+                return Ok(delivered);
+            }
+        }
+    }
+
+    if !chunk.is_empty() {
+        delivered += chunk.len();
+        on_chunk(chunk);
+    }
+
+    Ok(delivered)
+}
+
 /// Scans the given directory asynchronously and returns a sorted list of `ObjectInfo`.
 ///
 /// # Arguments
@@ -27,7 +92,10 @@ use tracing::{info, instrument, Span};
 /// * `show_hidden` - Whether to include hidden files/directories (starting with '.').
 /// * `profiling_config` - The configuration for performance profiling.
 /// * `cache` - The `ObjectInfo` cache for performance optimization
-#[expect(clippy::too_many_lines, reason = "Expected verbosity due to logging")]
+///
+/// Entries are read with blocking `readdir` calls and the cache misses are
+/// stat'ed in parallel.
+#[expect(clippy::cast_precision_loss, reason = "Expected loss")]
 #[instrument(
     skip(profiling_config, cache),
     fields(
@@ -69,80 +137,51 @@ pub async fn scan_dir(
     let start_time: Instant = Instant::now();
     let start_mem: Option<i64> = ProfilingData::get_current_memory_kb();
 
-    let mut entries: Vec<ObjectInfo> = Vec::new();
-    let mut read_dir: ReadDir = fs::read_dir(path).await?;
-
-    // Cache performance tracking
-    let mut cache_hits: u32 = 0;
-    let mut cache_misses: u32 = 0;
-
-    while let Some(entry) = read_dir.next_entry().await? {
-        let entry_path: PathBuf = entry.path();
+    let dir: PathBuf = path.to_path_buf();
+    let light_entries: Vec<LightObjectInfo> = tokio::task::spawn_blocking(
+        move || -> std::io::Result<Vec<LightObjectInfo>> {
+            let mut light_entries: Vec<LightObjectInfo> = Vec::new();
 
-        let file_name: &str = entry_path
-            .file_name()
-            .and_then(|s: &std::ffi::OsStr| s.to_str())
-            .unwrap_or("");
+            read_dir_chunks(&dir, show_hidden, SCAN_CHUNK_SIZE, |chunk: Vec<LightObjectInfo>| -> bool {
+                light_entries.extend(chunk);
+                true
+            })?;
 
-        if !show_hidden && file_name.starts_with('.') {
-            continue;
+            Ok(light_entries)
         }
+    )
+    .await
+    .map_err(std::io::Error::other)??;
 
-        // Cache-integrated ObjectInfo loading with performance tracking
-        let cache_lookup_start = Instant::now();
+    // Cached entries are used as they are; only misses are stat'ed
+    let mut entries: Vec<ObjectInfo> = Vec::with_capacity(light_entries.len());
+    let mut misses: Vec<LightObjectInfo> = Vec::new();
 
-        // Cache-integrated ObjectInfo loading
-        match cache.get_or_load_path(
-            &entry_path, 
-            || 
-            { 
-                cache_misses += 1;
+    for light_info in light_entries {
+        match cache.get_by_path(&light_info.path).await {
+            Some(info) => entries.push(info),
 
-                tracing::debug!(
-                    path = %entry_path.display(),
-                    "Cache miss - loading from filesystem"
-                );
+            None => misses.push(light_info),
+        }
+    }
 
-                ObjectInfo::from_path_direct(&entry_path)
-            }
-        )
-        .await 
-        {
-            Ok(info) => {
-                let lookup_duration = cache_lookup_start.elapsed();
-
-                if lookup_duration.as_micros() < 100
-                {
-                    // Fast lookup indicates cache hit
-                    cache_hits += 1;
-
-                    tracing::debug!(
-                        path = %entry_path.display(),
-                        lookup_time_us = lookup_duration.as_micros(),
-                        "Cache hit - fast retrieval"
-                    );
-                }
+    let cache_hits: usize = entries.len();
+    let cache_misses: usize = misses.len();
 
-                entries.push(info);
-            }
-            
-            Err(e) => {
-                // Log the error but continue processing other entries
-                tracing::debug!("Failed to get ObjectInfo for {entry_path:?}: {e}");
-            }
-        }
+    let loaded: Vec<ObjectInfo> = tokio::task::spawn_blocking(
+        move || -> Vec<ObjectInfo> { load_metadata_parallel(misses) }
+    )
+    .await
+    .map_err(std::io::Error::other)?;
+
+    for info in &loaded {
+        cache.insert_path(&info.path, info.clone()).await;
     }
 
-    // Sort entries: directories first, then alphabetically by name
-    entries.sort_by(|a: &ObjectInfo, b: &ObjectInfo| {
-        if a.is_dir && !b.is_dir {
-            Ordering::Less
-        } else if !a.is_dir && b.is_dir {
-            Ordering::Greater
-        } else {
-            a.name.cmp(&b.name)
-        }
-    });
+    entries.extend(loaded);
+
+    // Sort entries: directories first, then naturally by name
+    sort_objects(&mut entries, &EntrySort::NameAsc, &SortConfig::default());
 
     let duration: Duration = start_time.elapsed();
     let profiling_data: ProfilingData = ProfilingData::collect_profiling_data_conditional(
@@ -161,9 +200,9 @@ pub async fn scan_dir(
     }
 
     // Calculate cache efficiency
-    let total_lookups: u32 = cache_hits + cache_misses;
+    let total_lookups: usize = cache_hits + cache_misses;
     let cache_hit_rate: f64 = if total_lookups > 0 {
-        (f64::from(cache_hits) / f64::from(total_lookups)) * 100.0
+        (cache_hits as f64 / total_lookups as f64) * 100.0
     } else {
         0.0
     };
@@ -203,8 +242,8 @@ pub async fn scan_dir(
 /// Represents a scanning progress update
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ScanUpdate {
-    /// A new entry was discovered
-    Entry(ObjectInfo),
+    /// A chunk of newly discovered entries, in `readdir` order
+    Entries(Vec<ObjectInfo>),
     /// Scanning completed with final count
     Completed(usize),
     /// An error occurred while scanning
@@ -216,7 +255,7 @@ pub enum ScanUpdate {
 /// # Arguments
 /// * `path` - The path to the directory to scan
 /// * `show_hidden` - Whether to include hidden files/directories
-/// * `batch_size` - Number of entries to process before yielding (for responsiveness)
+/// * `batch_size` - Number of entries per `ScanUpdate::Entries` chunk
 /// * `action_tx` - Channel to send metadata loading tasks
 /// * `profiling_config` - The configuration for performance profiling.
 /// * `cache` - The `ObjectInfo` cache for performance optimization.
@@ -327,7 +366,7 @@ impl DirectoryScanner {
         self.perform_quick_scan(&mut entries, &mut light_entries).await?;
         let phase1_duration = phase1_start.elapsed();
 
-        // Sort entries by directory first, then alphabetically
+        // Sort entries by directory first, then naturally by name
         Self::sort_entries(&mut entries);
 
         // Send completion notification
@@ -394,50 +433,58 @@ impl DirectoryScanner {
         entries: &mut Vec<ObjectInfo>,
         light_entries: &mut Vec<LightObjectInfo>,
     ) -> Result<(), AppError> {
-        let mut read_dir: ReadDir = self.initialize_directory_reader().await?;
+        // The blocking reader runs at most a few chunks ahead of the stream
+        let (chunk_tx, mut chunk_rx) = mpsc::channel::<Vec<LightObjectInfo>>(4);
+        let path: PathBuf = self.path.clone();
+        let show_hidden: bool = self.show_hidden;
+        let chunk_size: usize = self.batch_size.max(1);
+
+        let reader: JoinHandle<std::io::Result<usize>> = tokio::task::spawn_blocking(
+            move || -> std::io::Result<usize> {
+                read_dir_chunks(&path, show_hidden, chunk_size, |chunk: Vec<LightObjectInfo>| -> bool {
+                    chunk_tx.blocking_send(chunk).is_ok()
+                })
+            }
+        );
+
         let mut processed: u64 = 0u64;
         let mut cache_optimized: u64 = 0u64;
 
-        while let Some(entry_result) = read_dir.next_entry().await.transpose() {
-            let entry: DirEntry = match entry_result {
-                Ok(e) => e,
+        while let Some(chunk) = chunk_rx.recv().await {
+            let mut placeholders: Vec<ObjectInfo> = Vec::with_capacity(chunk.len());
 
-                Err(e) => {
-                    let app_error: AppError = AppError::from(e);
-
-                    let _ = self.tx.send(ScanUpdate::Error(app_error.to_string()));
-
-                    continue;
+            for light_info in chunk {
+                // Entries with cached metadata skip the metadata phase
+                if let Some(cached_info) = self.cache.get_by_path(&light_info.path).await {
+                    placeholders.push(cached_info);
+                    cache_optimized += 1;
+                } else {
+                    placeholders.push(light_info.placeholder());
+                    light_entries.push(light_info);
                 }
-            };
+            }
 
-            if self.should_skip_entry(&entry) {
-                continue;
+            processed += placeholders.len() as u64;
+            entries.extend(placeholders.iter().cloned());
+
+            if self.tx.send(ScanUpdate::Entries(placeholders)).is_err() {
+                // Receiver dropped; closing the channel stops the reader
+                break;
             }
+        }
 
-            let was_cache_optimized = self
-                .process_directory_entry(
-                    entry,
-                    entries,
-                    light_entries
-                )
-                .await;
+        drop(chunk_rx);
 
-            if was_cache_optimized.0 {
-                processed += 1;
+        let read_result: std::io::Result<usize> = reader
+            .await
+            .unwrap_or_else(|e: JoinError| -> std::io::Result<usize> { Err(std::io::Error::other(e)) });
 
-                if was_cache_optimized.1 {
-                    cache_optimized += 1;
-                }
+        if let Err(e) = read_result {
+            let app_error: AppError = AppError::from(e);
 
-                // Yield control periodically for responsiveness
-                if processed.is_multiple_of(self.batch_size as u64) {
-                    tokio::task::yield_now().await;
-                }
-            } else {
-                // Receiver dropped, stop scanning
-                break;
-            }
+            let _ = self.tx.send(ScanUpdate::Error(app_error.to_string()));
+
+            return Err(app_error);
         }
 
         // Record cache optimization rate
@@ -459,126 +506,9 @@ impl DirectoryScanner {
         Ok(())
     }
 
-    async fn initialize_directory_reader(&self) -> Result<ReadDir, AppError> {
-        match fs::read_dir(&self.path).await {
-            Ok(read_dir) => Ok(read_dir),
-            
-            Err(e) => {
-                let app_error: AppError = AppError::from(e);
-                
-                let _ = self.tx.send(ScanUpdate::Error(app_error.to_string()));
-                
-                Err(app_error)
-            }
-        }
-    }
-
-    fn should_skip_entry(&self, entry: &DirEntry) -> bool {
-        if self.show_hidden {
-            return false;
-        }
-
-        let entry_path: PathBuf = entry.path();
-        let file_name: &str = entry_path
-            .file_name()
-            .and_then(|s: &OsStr| -> Option<&str> 
-                {
-                    s.to_str()
-                }
-            )
-            .unwrap_or("");
-
-        file_name.starts_with('.')
-    }
-
-    // Returns (success, was_cache_hit)
-    async fn process_directory_entry(
-        &self,
-        entry: DirEntry,
-        entries: &mut Vec<ObjectInfo>,
-        light_entries: &mut Vec<LightObjectInfo>,
-    ) -> (bool, bool) {
-        let entry_path: PathBuf = entry.path();
-
-        match LightObjectInfo::from_path(&entry_path).await {
-            Ok(light_info) => {
-                // Create placeholder with cache check for existing full metadata
-                let cache_check_start = Instant::now();
-                let (placeholder_info, was_cache_hit) = if let Some(cached_info) = self
-                    .cache.get_by_path(&entry_path)
-                    .await
-                     {
-                    let cache_lookup_time = cache_check_start.elapsed();
-                    tracing::debug!(
-                        path = %entry_path.display(),
-                        lookup_time_us = cache_lookup_time.as_micros(),
-                        "Cache hit during streaming scan"
-                    );
-                    (cached_info, true)
-                } else {
-                    let placeholder = ObjectInfo {
-                        path: light_info.path.clone(),
-                        modified: SystemTime::UNIX_EPOCH, // Placeholder
-                        name: light_info.name.clone(),
-                        extension: light_info.extension.clone(),
-                        size: 0,
-                        items_count: 0,
-                        is_dir: light_info.is_dir,
-                        is_symlink: light_info.is_symlink,
-                        metadata_loaded: false,
-                    };
-                    tracing::debug!(
-                        path = %entry_path.display(),
-                        "Cache miss - using placeholder for streaming scan"
-                    );
-                    (placeholder, false)
-                };
-
-                // Send streaming update immediately
-                if self
-                    .tx
-                    .send(
-                        ScanUpdate::Entry(placeholder_info.clone())
-                    )
-                    .is_err()
-                {
-                    return (false, false); // Receiver dropped
-                }
-
-                entries.push(placeholder_info);
-                light_entries.push(light_info);
-
-                (true, was_cache_hit)
-            }
-
-            Err(e) => {
-                tracing::debug!(
-                    path = %entry_path.display(),
-                    error = %e,
-                    "Failed to get basic info for entry"
-                );
-
-                let _ = self.tx.send(ScanUpdate::Error(e.to_string()));
-
-                (true, false)
-            }
-        }
-    }
-
+    /// Default listing order; panes re-sort with their own mode on arrival.
     fn sort_entries(entries: &mut [ObjectInfo]) {
-        entries
-            .sort_by(
-                |a: &ObjectInfo, b: &ObjectInfo| -> Ordering 
-                {
-                    if a.is_dir && !b.is_dir {
-                        Ordering::Less
-                    } else if !a.is_dir && b.is_dir {
-                        Ordering::Greater
-                    } else {
-                        a.name.cmp(&b.name)
-                    }
-                }
-            );
+        sort_objects(entries, &EntrySort::NameAsc, &SortConfig::default());
     }
 
     #[instrument(
@@ -586,7 +516,7 @@ impl DirectoryScanner {
         fields(
             operation_type = "start_background_metadata_loading",
             entry_count = light_entries.len(),
-            batch_size = 5,
+            batch_size = METADATA_BATCH_SIZE,
         )
     )]
     fn start_background_metadata_loading(&self, light_entries: Vec<LightObjectInfo>) {
@@ -595,9 +525,112 @@ impl DirectoryScanner {
                 self.path.clone(),
                 light_entries,
                 self.action_tx.clone(),
-                5, // Metadata batch size
+                METADATA_BATCH_SIZE,
                 self.cache.clone(),
             );
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A directory with `files` empty files, a subdirectory, a hidden file
+    /// and a symlink to the subdirectory.
+    fn fixture(files: usize) -> tempfile::TempDir {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+
+        for n in 0..files {
+            std::fs::write(root.path().join(format!("file{n}.TXT")), "").expect("write file");
+        }
+
+        std::fs::create_dir(root.path().join("sub")).expect("create dir");
+        std::fs::write(root.path().join(".hidden"), "").expect("write file");
+        std::os::unix::fs::symlink(root.path().join("sub"), root.path().join("link")).expect("symlink");
+
+        root
+    }
+
+    fn read_all(path: &Path, show_hidden: bool, chunk_size: usize) -> (Vec<usize>, Vec<LightObjectInfo>) {
+        let mut sizes: Vec<usize> = Vec::new();
+        let mut entries: Vec<LightObjectInfo> = Vec::new();
+
+        read_dir_chunks(path, show_hidden, chunk_size, |chunk: Vec<LightObjectInfo>| -> bool {
+            sizes.push(chunk.len());
+            entries.extend(chunk);
+            true
+        })
+        .expect("read dir");
+
+        (sizes, entries)
+    }
+
+    #[test]
+    fn read_dir_chunks_delivers_full_chunks_then_the_rest() {
+        let root: tempfile::TempDir = fixture(5);
+
+        let (sizes, entries): (Vec<usize>, Vec<LightObjectInfo>) = read_all(root.path(), false, 3);
+
+        assert_eq!(sizes, [3, 3, 1]);
+        assert_eq!(entries.len(), 7);
+
+        let (_, entries): (Vec<usize>, Vec<LightObjectInfo>) = read_all(root.path(), true, 0);
+
+        assert_eq!(entries.len(), 8);
+    }
+
+    #[test]
+    fn read_dir_chunks_stops_when_the_callback_refuses() {
+        let root: tempfile::TempDir = fixture(5);
+        let mut calls: usize = 0;
+
+        let delivered: usize = read_dir_chunks(root.path(), false, 2, |_: Vec<LightObjectInfo>| -> bool {
+            calls += 1;
+            false
+        })
+        .expect("read dir");
+
+        assert_eq!((calls, delivered), (1, 2));
+        assert!(read_dir_chunks(&root.path().join("gone"), false, 2, |_: Vec<LightObjectInfo>| -> bool { true }).is_err());
+    }
+
+    #[test]
+    fn entries_take_their_type_from_readdir_and_follow_symlinks_for_is_dir() {
+        let root: tempfile::TempDir = fixture(1);
+
+        let (_, entries): (Vec<usize>, Vec<LightObjectInfo>) = read_all(root.path(), false, 16);
+        let find = |name: &str| -> &LightObjectInfo {
+            entries
+                .iter()
+                .find(|e: &&LightObjectInfo| -> bool { e.name == name })
+                .expect("entry listed")
+        };
+
+        assert!(find("sub").is_dir && !find("sub").is_symlink);
+        assert!(find("link").is_dir && find("link").is_symlink);
+        assert_eq!(find("file0.TXT").extension.as_deref(), Some("txt"));
+        assert_eq!(find("sub").extension, None);
+    }
+
+    #[tokio::test]
+    async fn scan_dir_lists_sorted_entries_and_fills_the_cache() {
+        let root: tempfile::TempDir = fixture(2);
+        std::fs::remove_file(root.path().join("link")).expect("remove symlink");
+        let cache: ObjectInfoCache = ObjectInfoCache::new();
+        let profiling: ProfilingConfig = ProfilingConfig {
+            enabled: false,
+            ..ProfilingConfig::default()
+        };
+
+        let entries: Vec<ObjectInfo> = scan_dir(root.path(), false, &profiling, &cache).await.expect("scan");
+        let names: Vec<String> = entries
+            .iter()
+            .map(|e: &ObjectInfo| -> String { e.name.to_string_lossy().into_owned() })
+            .collect();
+
+        assert_eq!(names, ["sub", "file0.TXT", "file1.TXT"]);
+        assert!(entries.iter().all(|e: &ObjectInfo| -> bool { e.metadata_loaded }));
+        assert!(cache.get_by_path(&root.path().join("file0.TXT")).await.is_some());
+    }
+}
@@ -4,16 +4,113 @@
 //!
 //! Cross-platform, async-friendly abstraction for a file or directory entry.
 //! Integrates with `ObjectTable` (for TUI), moka cache, and async tasks.
+//!
+//! Paths and names are kept as OS strings, so entries whose names are not
+//! valid UTF-8 (e.g. Latin-1 names from old archives) can still be opened,
+//! copied, moved and deleted. Only display goes through a lossy conversion,
+//! see `ObjectInfo::display_name`.
 
 use compact_str::CompactString;
-use serde::{Deserialize, Serialize};
-use std::{ffi::OsStr, fs::{FileType, Metadata}, time::Duration};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::{ffi::{OsStr, OsString}, fs::{FileType, Metadata}};
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::SystemTime;
 
 use tokio::fs::{self as TokioFs};
 
-use crate::{cache::cache_manager::ObjectInfoCache, AppError};
+use crate::{cache::cache_manager::ObjectInfoCache, fs::owners, util::humanize, AppError};
+
+/// Appended to displayed names that are not valid UTF-8.
+pub const NON_UTF8_MARKER: &str = " [?]";
+
+/// Lowercased extension, used for icons, sorting and filters only (lossy).
+fn lowercase_extension(ext: &OsStr) -> CompactString {
+    CompactString::new(ext.to_string_lossy().to_lowercase())
+}
+
+/// Serialize a path as an OS string, so paths that are not valid UTF-8
+/// survive a round trip (serde's own `Path` impl rejects them).
+fn serialize_os_path<S: Serializer>(path: &Path, serializer: S) -> Result<S::Ok, S::Error> {
+    path.as_os_str().serialize(serializer)
+}
+
+fn deserialize_os_path<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PathBuf, D::Error> {
+    OsString::deserialize(deserializer).map(PathBuf::from)
+}
+
+/// Unix mode, ownership and inode data. All zero on other platforms and
+/// until the entry's metadata is loaded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnixMetadata {
+    pub inode: u64,
+    pub nlink: u64,
+
+    /// File type and permission bits (`st_mode`)
+    pub mode: u32,
+
+    pub uid: u32,
+    pub gid: u32,
+}
+
+impl UnixMetadata {
+    #[cfg(unix)]
+    #[must_use]
+    pub fn from_metadata(metadata: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        Self {
+            inode: metadata.ino(),
+            nlink: metadata.nlink(),
+            mode: metadata.mode(),
+            uid: metadata.uid(),
+            gid: metadata.gid(),
+        }
+    }
+
+    #[cfg(not(unix))]
+    #[must_use]
+    pub fn from_metadata(_metadata: &Metadata) -> Self {
+        Self::default()
+    }
+
+    /// Permission bits in `ls` notation (`rwxr-xr-x`), including setuid,
+    /// setgid and sticky bits.
+    #[must_use]
+    pub fn permissions(&self) -> String {
+        const SPECIAL: [(u32, char, char); 3] = [(0o4000, 's', 'S'), (0o2000, 's', 'S'), (0o1000, 't', 'T')];
+
+        let mut out: String = String::with_capacity(9);
+
+        for (class, (special_bit, with_exec, without_exec)) in SPECIAL.into_iter().enumerate() {
+            let bits: u32 = (self.mode >> (6 - class * 3)) & 0o7;
+
+            out.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+            out.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+
+            out.push(match (self.mode & special_bit != 0, bits & 0o1 != 0) {
+                (true, true) => with_exec,
+                (true, false) => without_exec,
+                (false, true) => 'x',
+                (false, false) => '-',
+            });
+        }
+
+        out
+    }
+
+    /// Name of the owning user (cached, see `fs::owners`).
+    #[must_use]
+    pub fn owner(&self) -> CompactString {
+        owners::user_name(self.uid)
+    }
+
+    /// Name of the owning group (cached, see `fs::owners`).
+    #[must_use]
+    pub fn group(&self) -> CompactString {
+        owners::group_name(self.gid)
+    }
+}
 
 /// Enum for object type, matching the table logic.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -37,13 +134,20 @@ impl std::fmt::Display for ObjectType {
 pub struct ObjectInfo {
     /// LARGEST FIRST (CLAUDE.md Rule 14)
     /// ~24 bytes (largest)
+    #[serde(serialize_with = "serialize_os_path", deserialize_with = "deserialize_os_path")]
     pub path: PathBuf,
 
     /// 16 bytes (no timezone overhead)
     pub modified: SystemTime,
 
-    /// ~24 bytes (stack-optimized) 
-    pub name: CompactString,
+    /// 16 bytes, `UNIX_EPOCH` where the filesystem has no birth time
+    pub created: SystemTime,
+
+    /// 16 bytes, last access (as precise as the mount's atime policy)
+    pub accessed: SystemTime,
+
+    /// 24 bytes, exact bytes of the file name (may not be UTF-8)
+    pub name: OsString,
 
     /// ~24 bytes when Some
     pub extension: Option<CompactString>,
@@ -51,6 +155,7 @@ pub struct ObjectInfo {
     /// 8-BYTE ALIGNED PRIMITIVES
     pub size: u64,                          // 8 bytes
     pub items_count: u64,                   // 8 bytes
+    pub unix: UnixMetadata,                 // 32 bytes
 
     // FLAGS GROUPED (cache-friendly)
     pub is_dir: bool,                       // 1 byte
@@ -63,8 +168,9 @@ pub struct ObjectInfo {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct LightObjectInfo {
     // LARGEST FIRST (CLAUDE.md Rule 14)
+    #[serde(serialize_with = "serialize_os_path", deserialize_with = "deserialize_os_path")]
     pub path: PathBuf,                      // ~24 bytes (largest)
-    pub name: CompactString,                // ~24 bytes (stack-optimized)
+    pub name: OsString,                     // 24 bytes (may not be UTF-8)
     pub extension: Option<CompactString>,   // ~24 bytes when Some
 
     // FLAGS GROUPED (cache-friendly)
@@ -92,16 +198,10 @@ impl LightObjectInfo {
         let metadata = TokioFs::metadata(path).await?;
         let file_type = metadata.file_type();
 
-        let name = CompactString::new(
-            path.file_name()
-                .and_then(OsStr::to_str)
-                .unwrap_or("")
-        );
+        let name: OsString = path.file_name().unwrap_or_default().to_os_string();
 
         let extension = if file_type.is_file() {
-            path.extension()
-                .and_then(OsStr::to_str)
-                .map(|s| CompactString::new(s.to_lowercase()))
+            path.extension().map(lowercase_extension)
         } else {
             None
         };
@@ -115,42 +215,100 @@ impl LightObjectInfo {
         })
     }
 
+    /// Build from a `readdir` entry without a stat: the file type comes from
+    /// `d_type`. Only symlinks are stat'ed, to tell whether they point at a
+    /// directory.
+    pub fn from_dir_entry(entry: &std::fs::DirEntry) -> std::io::Result<Self> {
+        let path: PathBuf = entry.path();
+        let file_type: FileType = entry.file_type()?;
+        let is_symlink: bool = file_type.is_symlink();
+
+        let is_dir: bool = if is_symlink {
+            std::fs::metadata(&path).is_ok_and(|m: Metadata| -> bool { m.is_dir() })
+        } else {
+            file_type.is_dir()
+        };
+
+        let name: OsString = path.file_name().unwrap_or_default().to_os_string();
+
+        let extension = if is_dir {
+            None
+        } else {
+            path.extension().map(lowercase_extension)
+        };
+
+        Ok(Self {
+            path,
+            name,
+            extension,
+            is_dir,
+            is_symlink,
+        })
+    }
+
+    /// Entry shown while its metadata is still loading.
+    #[must_use]
+    pub fn placeholder(&self) -> ObjectInfo {
+        ObjectInfo {
+            path: self.path.clone(),
+            name: self.name.clone(),
+            extension: self.extension.clone(),
+            is_dir: self.is_dir,
+            is_symlink: self.is_symlink,
+            ..ObjectInfo::default()
+        }
+    }
+
     // CONVERSION TO FULL OBJECTINFO
+    // Directory sizes and item counts are left to the size task, which walks
+    // the directory anyway.
     pub async fn into_full_info(self) -> Result<ObjectInfo, AppError> {
-        let metadata = TokioFs::symlink_metadata(&self.path).await?;
+        let metadata: Metadata = TokioFs::symlink_metadata(&self.path).await?;
+
+        Ok(self.with_metadata(&metadata))
+    }
+
+    /// Blocking variant of `into_full_info`, for stat'ing on a thread pool.
+    pub fn into_full_info_sync(self) -> Result<ObjectInfo, AppError> {
+        let metadata: Metadata = std::fs::symlink_metadata(&self.path)?;
 
+        Ok(self.with_metadata(&metadata))
+    }
+
+    fn with_metadata(self, metadata: &Metadata) -> ObjectInfo {
         let size = if self.is_dir { 0 } else { metadata.len() };
-        let items_count = if self.is_dir {
-            // Optimized directory counting
-            match TokioFs::read_dir(&self.path).await {
-                Ok(mut entries) => {
-                    let mut count = 0u64;
-                    while entries.next_entry().await?.is_some() {
-                        count += 1;
-                    }
-                    count
-                }
-                Err(_) => 0,
-            }
-        } else {
-            0
-        };
 
         let modified = metadata
             .modified()
             .unwrap_or(SystemTime::UNIX_EPOCH);
 
-        Ok(ObjectInfo {
+        let created = metadata
+            .created()
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        let accessed = metadata
+            .accessed()
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        // Resolve owner names here, off the UI thread, so rendering the
+        // owner column only hits the cache
+        let unix: UnixMetadata = UnixMetadata::from_metadata(metadata);
+        owners::prefetch(unix.uid, unix.gid);
+
+        ObjectInfo {
             path: self.path,
             modified,
+            created,
+            accessed,
             name: self.name,
             extension: self.extension,
             size,
-            items_count,
+            items_count: 0,
+            unix,
             is_dir: self.is_dir,
             is_symlink: self.is_symlink,
             metadata_loaded: true,
-        })
+        }
     }
 }
 
@@ -168,6 +326,61 @@ impl ObjectInfo {
           }
       }
 
+      /// Copy loaded metadata onto this entry. A plain stat of a directory
+      /// carries no size or item count, so it keeps what the size task found.
+      pub fn apply_metadata(&mut self, info: &Self) {
+          if !info.is_dir || info.size > 0 || info.items_count > 0 {
+              self.size = info.size;
+              self.items_count = info.items_count;
+          }
+
+          self.modified = info.modified;
+          self.created = info.created;
+          self.accessed = info.accessed;
+          self.unix = info.unix;
+          self.metadata_loaded = info.metadata_loaded;
+      }
+
+      /// Estimated memory footprint in bytes: the struct itself plus the
+      /// heap buffers of the path, name and extension (short extensions are
+      /// stored inline).
+      #[must_use]
+      pub fn heap_size(&self) -> usize {
+          let spilled = |s: &CompactString| -> usize {
+              if s.is_heap_allocated() { s.capacity() } else { 0 }
+          };
+
+          std::mem::size_of::<Self>()
+              + self.path.capacity()
+              + self.name.capacity()
+              + self.extension.as_ref().map_or(0, spilled)
+      }
+
+      /// Name for display. Names that are not valid UTF-8 are converted
+      /// lossily and marked with `NON_UTF8_MARKER`.
+      #[must_use]
+      pub fn display_name(&self) -> Cow<'_, str> {
+          match self.name.to_str() {
+              Some(name) => Cow::Borrowed(name),
+
+              None => Cow::Owned(format!("{}{NON_UTF8_MARKER}", self.name.to_string_lossy())),
+          }
+      }
+
+      /// True if the name is not valid UTF-8.
+      #[inline]
+      #[must_use]
+      pub fn has_lossy_name(&self) -> bool {
+          self.name.to_str().is_none()
+      }
+
+      /// Dotfile check on the raw name bytes.
+      #[inline]
+      #[must_use]
+      pub fn is_hidden(&self) -> bool {
+          self.name.as_encoded_bytes().starts_with(b".")
+      }
+
       #[inline]
       #[must_use]
       pub fn size_human(&self) -> String {
@@ -181,18 +394,9 @@ impl ObjectInfo {
       {
             if let Some(cache_ref) = cache
             {
-                let path_str: &str = path.to_str().ok_or_else(
-                    || -> AppError {AppError::FileOperationFailed 
-                    { 
-                        operation: "Invalid Path".into(), 
-                        path: "Invalid Path".into(), 
-                        reason: "path.to_str() failed".into(),
-                    }}
-                )?;
-
                 // Use cache with fallback
                 cache_ref.get_or_load(
-                    path_str.into(), 
+                    ObjectInfoCache::path_to_key(path), 
                     || 
                     Self::from_path_direct(path)
                 ).await 
@@ -209,16 +413,10 @@ impl ObjectInfo {
           let is_dir = file_type.is_dir();
           let is_symlink: bool = file_type.is_symlink();
 
-          let name = CompactString::new(
-              path.file_name()
-                  .and_then(OsStr::to_str)
-                  .unwrap_or("")
-          );
+          let name: OsString = path.file_name().unwrap_or_default().to_os_string();
 
           let extension = if file_type.is_file() {
-              path.extension()
-                  .and_then(OsStr::to_str)
-                  .map(CompactString::new)
+              path.extension().map(lowercase_extension)
           } else {
               None
           };
@@ -226,16 +424,27 @@ impl ObjectInfo {
           let modified = metadata
             .modified()
             .unwrap_or(SystemTime::UNIX_EPOCH);
+
+          let created = metadata
+            .created()
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+          let accessed = metadata
+            .accessed()
+            .unwrap_or(SystemTime::UNIX_EPOCH);
   
           let size = if is_dir { 0 } else { metadata.len() };
 
           Ok(Self {
               path: path.to_path_buf(),
               modified,
+              created,
+              accessed,
               name,
               extension,
               size,
               items_count: 0,
+              unix: UnixMetadata::from_metadata(&metadata),
               is_dir,
               is_symlink,
               metadata_loaded: true,
@@ -247,21 +456,10 @@ impl ObjectInfo {
           Self::from_path_cached(path, None).await
       }
 
-      #[expect(clippy::cast_possible_wrap, reason = "Expected accuracy")]
+      /// Modification time in local time, formatted with a `strftime` pattern.
       #[must_use]
       pub fn format_date(&self, format: &str) -> String {
-          use chrono::{Local, TimeZone};
-
-          let duration: Duration = self.modified
-              .duration_since(UNIX_EPOCH)
-              .unwrap_or_default();
-
-          let datetime: chrono::DateTime<Local> = Local
-              .timestamp_opt(duration.as_secs() as i64, duration.subsec_nanos())
-              .single()
-              .unwrap_or_default();
-
-          datetime.format(format).to_string()
+          humanize::format_time(self.modified, format)
       }
   }
 
@@ -273,13 +471,79 @@ impl ObjectInfo {
         {
             path: PathBuf::new(),
             modified: SystemTime::UNIX_EPOCH,
-            name: CompactString::const_new(""),
+            created: SystemTime::UNIX_EPOCH,
+            accessed: SystemTime::UNIX_EPOCH,
+            name: OsString::new(),
             extension: None,
             size: 0,
             items_count: 0,
+            unix: UnixMetadata::default(),
             is_dir: false,
             is_symlink: false,
             metadata_loaded: false,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode(mode: u32) -> UnixMetadata {
+        UnixMetadata {
+            mode,
+            ..UnixMetadata::default()
+        }
+    }
+
+    #[test]
+    fn permissions_render_like_ls() {
+        assert_eq!(mode(0o100_644).permissions(), "rw-r--r--");
+        assert_eq!(mode(0o040_755).permissions(), "rwxr-xr-x");
+        assert_eq!(mode(0o4755).permissions(), "rwsr-xr-x");
+        assert_eq!(mode(0o2745).permissions(), "rwxr-Sr-x");
+        assert_eq!(mode(0o1777).permissions(), "rwxrwxrwt");
+        assert_eq!(mode(0o1776).permissions(), "rwxrwxrwT");
+        assert_eq!(UnixMetadata::default().permissions(), "---------");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn full_info_carries_mode_links_and_inode() {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let file: PathBuf = root.path().join("file");
+        std::fs::write(&file, "data").expect("write file");
+        std::fs::set_permissions(&file, std::fs::Permissions::from_mode(0o640)).expect("chmod");
+        std::fs::hard_link(&file, root.path().join("link")).expect("hard link");
+
+        let light: LightObjectInfo = LightObjectInfo::from_path(&file).await.expect("light info");
+        assert_eq!(light.placeholder().unix, UnixMetadata::default());
+
+        let info: ObjectInfo = light.into_full_info().await.expect("full info");
+        let metadata: Metadata = std::fs::metadata(&file).expect("stat");
+
+        assert_eq!(info.unix.permissions(), "rw-r-----");
+        assert_eq!(info.unix.nlink, 2);
+        assert_eq!(info.unix.inode, metadata.ino());
+        assert_eq!((info.unix.uid, info.unix.gid), (metadata.uid(), metadata.gid()));
+        assert!(info.metadata_loaded);
+    }
+
+    #[test]
+    fn ids_without_a_name_resolve_to_the_number() {
+        let unknown: u32 = 4_000_000_123;
+
+        assert_eq!(
+            UnixMetadata {
+                uid: unknown,
+                gid: unknown,
+                ..UnixMetadata::default()
+            }
+            .owner(),
+            "4000000123"
+        );
+        assert_eq!(owners::group_name(unknown), "4000000123");
+    }
+}
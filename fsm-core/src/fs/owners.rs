@@ -0,0 +1,137 @@
+//! ``src/fs/owners.rs``
+//! ============================================================================
+//! # Owner Names: Cached uid/gid Resolution
+//!
+//! Maps numeric user and group ids to names through the system database
+//! (`getpwuid_r` / `getgrgid_r`, so NSS sources such as LDAP are honoured).
+//! Lookups can be slow, so every id is resolved once and remembered for the
+//! lifetime of the process. The metadata phase resolves the ids of every
+//! entry it loads, so rendering only ever hits the cache.
+//!
+//! Ids without a name (deleted users, foreign filesystems) resolve to the
+//! number itself. On non-Unix platforms every id does.
+
+use std::sync::LazyLock;
+
+use compact_str::CompactString;
+use dashmap::DashMap;
+
+static USER_NAMES: LazyLock<DashMap<u32, CompactString>> = LazyLock::new(DashMap::new);
+
+static GROUP_NAMES: LazyLock<DashMap<u32, CompactString>> = LazyLock::new(DashMap::new);
+
+/// Name of user `uid`, or the id itself if it has none.
+#[must_use]
+pub fn user_name(uid: u32) -> CompactString {
+    cached(&USER_NAMES, uid, sys::lookup_user)
+}
+
+/// Name of group `gid`, or the id itself if it has none.
+#[must_use]
+pub fn group_name(gid: u32) -> CompactString {
+    cached(&GROUP_NAMES, gid, sys::lookup_group)
+}
+
+/// Resolve both ids ahead of rendering (called from the metadata phase).
+pub fn prefetch(uid: u32, gid: u32) {
+    let _ = user_name(uid);
+    let _ = group_name(gid);
+}
+
+fn cached(
+    names: &DashMap<u32, CompactString>,
+    id: u32,
+    lookup: fn(u32) -> Option<CompactString>,
+) -> CompactString {
+    if let Some(name) = names.get(&id) {
+        return name.clone();
+    }
+
+    let name: CompactString = lookup(id).unwrap_or_else(|| CompactString::from(itoa::Buffer::new().format(id)));
+    names.insert(id, name.clone());
+
+    name
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::ffi::CStr;
+
+    use compact_str::CompactString;
+
+    /// Initial buffer for the string fields of a passwd/group record.
+    const INITIAL_BUFFER: usize = 1024;
+
+    /// Give up on records larger than this (e.g. huge group member lists).
+    const MAX_BUFFER: usize = 1 << 20;
+
+    pub fn lookup_user(uid: u32) -> Option<CompactString> {
+        let mut buffer: Vec<libc::c_char> = vec![0; INITIAL_BUFFER];
+
+        loop {
+            // SAFETY: passwd is plain old data; getpwuid_r fills it and points
+            // its strings into `buffer`, which outlives every use below.
+            let mut record: libc::passwd = unsafe { std::mem::zeroed() };
+            let mut result: *mut libc::passwd = std::ptr::null_mut();
+
+            let status: libc::c_int = unsafe {
+                libc::getpwuid_r(uid, &raw mut record, buffer.as_mut_ptr(), buffer.len(), &raw mut result)
+            };
+
+            if status == libc::ERANGE && buffer.len() < MAX_BUFFER {
+                buffer.resize(buffer.len() * 2, 0);
+                continue;
+            }
+
+            if status != 0 || result.is_null() || record.pw_name.is_null() {
+                return None;
+            }
+
+            // SAFETY: pw_name is a NUL-terminated string inside `buffer`
+            let name: &CStr = unsafe { CStr::from_ptr(record.pw_name) };
+
+            return Some(CompactString::from(name.to_string_lossy()));
+        }
+    }
+
+    pub fn lookup_group(gid: u32) -> Option<CompactString> {
+        let mut buffer: Vec<libc::c_char> = vec![0; INITIAL_BUFFER];
+
+        loop {
+            // SAFETY: as in `lookup_user`, with getgrgid_r and a group record
+            let mut record: libc::group = unsafe { std::mem::zeroed() };
+            let mut result: *mut libc::group = std::ptr::null_mut();
+
+            let status: libc::c_int = unsafe {
+                libc::getgrgid_r(gid, &raw mut record, buffer.as_mut_ptr(), buffer.len(), &raw mut result)
+            };
+
+            if status == libc::ERANGE && buffer.len() < MAX_BUFFER {
+                buffer.resize(buffer.len() * 2, 0);
+                continue;
+            }
+
+            if status != 0 || result.is_null() || record.gr_name.is_null() {
+                return None;
+            }
+
+            // SAFETY: gr_name is a NUL-terminated string inside `buffer`
+            let name: &CStr = unsafe { CStr::from_ptr(record.gr_name) };
+
+            return Some(CompactString::from(name.to_string_lossy()));
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod sys {
+    use compact_str::CompactString;
+
+    pub const fn lookup_user(_uid: u32) -> Option<CompactString> {
+        None
+    }
+
+    pub const fn lookup_group(_gid: u32) -> Option<CompactString> {
+        None
+    }
+}
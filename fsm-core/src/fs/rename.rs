@@ -0,0 +1,108 @@
+//! ``src/fs/rename.rs``
+//! ============================================================================
+//! # Renames That Never Replace
+//!
+//! `rename(2)` silently replaces an existing target, and checking for the
+//! target first leaves a window in which another process can create it.
+//! Operations that are recorded for undo must never destroy anything, so
+//! they rename through [`rename_no_replace`], which asks the kernel to
+//! refuse an existing target (`renameat2` with `RENAME_NOREPLACE`).
+//!
+//! Where that is unavailable (other platforms, filesystems without support)
+//! the target is checked right before the rename instead.
+
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+/// Rename `from` to `to`, failing with `AlreadyExists` if `to` exists.
+/// Moves across filesystems fail with `CrossesDevices`, as with `rename`.
+pub async fn rename_no_replace(from: &Path, to: &Path) -> Result<(), Error> {
+    let (from, to): (PathBuf, PathBuf) = (from.to_path_buf(), to.to_path_buf());
+
+    tokio::task::spawn_blocking(move || sys::rename_no_replace(&from, &to))
+        .await
+        .map_err(Error::other)?
+}
+
+/// Check, then rename: not atomic, for where the kernel cannot refuse.
+fn checked_rename(from: &Path, to: &Path) -> Result<(), Error> {
+    if std::fs::symlink_metadata(to).is_ok() {
+        return Err(Error::from(ErrorKind::AlreadyExists));
+    }
+
+    std::fs::rename(from, to)
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::ffi::CString;
+    use std::io::Error;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    pub fn rename_no_replace(from: &Path, to: &Path) -> Result<(), Error> {
+        let from_c: CString = CString::new(from.as_os_str().as_bytes())?;
+        let to_c: CString = CString::new(to.as_os_str().as_bytes())?;
+
+        // SAFETY: both pointers are NUL-terminated strings that outlive the call
+        let status: libc::c_int = unsafe {
+            libc::renameat2(libc::AT_FDCWD, from_c.as_ptr(), libc::AT_FDCWD, to_c.as_ptr(), libc::RENAME_NOREPLACE)
+        };
+
+        if status == 0 {
+            return Ok(());
+        }
+
+        let err: Error = Error::last_os_error();
+
+        match err.raw_os_error() {
+            // The filesystem (or an old kernel) does not support the flag
+            Some(libc::EINVAL | libc::ENOSYS) => super::checked_rename(from, to),
+
+            _ => Err(err),
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use std::io::Error;
+    use std::path::Path;
+
+    pub fn rename_no_replace(from: &Path, to: &Path) -> Result<(), Error> {
+        super::checked_rename(from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rename_no_replace_keeps_an_existing_target() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let from: PathBuf = root.path().join("from");
+        let to: PathBuf = root.path().join("to");
+        std::fs::write(&from, "from").expect("write file");
+        std::fs::write(&to, "to").expect("write file");
+
+        let err: Error = rename_no_replace(&from, &to).await.expect_err("target exists");
+
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read_to_string(&to).expect("read"), "to");
+        assert_eq!(std::fs::read_to_string(&from).expect("read"), "from");
+    }
+
+    #[tokio::test]
+    async fn rename_no_replace_renames_to_a_free_path() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let from: PathBuf = root.path().join("from");
+        let to: PathBuf = root.path().join("to");
+        std::fs::create_dir(&from).expect("create dir");
+
+        rename_no_replace(&from, &to).await.expect("rename");
+
+        assert!(to.is_dir());
+        assert!(!from.exists());
+    }
+}
@@ -0,0 +1,709 @@
+//! ``src/fs/trash.rs``
+//! ============================================================================
+//! # Trash: freedesktop.org Trash Directories
+//!
+//! Deleting moves entries into a trash directory (Trash specification 1.0)
+//! instead of removing them, so other desktop tools see and restore them too:
+//!
+//! - Entries on the filesystem of the home trash, `$XDG_DATA_HOME/Trash`
+//!   (default `~/.local/share/Trash`), go there.
+//! - Entries on other filesystems go to the trash of their mount point
+//!   ("top directory"): `$topdir/.Trash/$uid` when the administrator provided
+//!   a sticky `$topdir/.Trash`, `$topdir/.Trash-$uid` otherwise.
+//!
+//! An entry trashed as `files/<name>` is described by `info/<name>.trashinfo`
+//! with its original path (percent-encoded; relative to the top directory in
+//! per-mount trashes) and deletion date. The info file is created first and
+//! exclusively, which reserves the name against concurrent trashing.
+//!
+//! Nothing is copied between filesystems: an entry whose filesystem has no
+//! usable trash cannot be trashed and has to be deleted permanently.
+
+use std::cmp::Reverse;
+use std::ffi::{OsStr, OsString};
+use std::fs::Metadata;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDateTime};
+use tokio::fs as TokioFs;
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, trace, warn};
+
+use crate::error::AppError;
+use crate::fs::rename::rename_no_replace;
+
+/// Extension of the files in `info/`.
+const INFO_EXTENSION: &str = "trashinfo";
+
+/// `DeletionDate` format (local time, no zone).
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Give up looking for a free name after this many candidates.
+const MAX_NAME_ATTEMPTS: usize = 10_000;
+
+/// An entry in one of the trash directories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashEntry {
+    /// `info/<name>.trashinfo`; identifies the entry
+    pub info_path: PathBuf,
+
+    /// `files/<name>`
+    pub trashed_path: PathBuf,
+
+    /// Where the entry lived before it was trashed
+    pub original_path: PathBuf,
+
+    /// Local deletion time, if recorded validly
+    pub deleted_at: Option<NaiveDateTime>,
+
+    pub is_dir: bool,
+}
+
+impl TrashEntry {
+    /// Deletion time for display ("?" if unknown).
+    #[must_use]
+    pub fn deleted_label(&self) -> String {
+        self.deleted_at.map_or_else(
+            || "?".to_string(),
+            |date: NaiveDateTime| -> String { date.format("%Y-%m-%d %H:%M").to_string() },
+        )
+    }
+}
+
+/// Move `path` into the trash of its filesystem.
+pub async fn trash(path: &Path) -> Result<TrashEntry, AppError> {
+    let path: PathBuf = std::path::absolute(path)?;
+    let meta: Metadata = TokioFs::symlink_metadata(&path).await?;
+
+    let Some(name) = path.file_name() else {
+        return Err(failure("trash", &path, "cannot trash a filesystem root"));
+    };
+
+    let (trash_dir, top) = trash_dir_for(&path).await?;
+    let files: PathBuf = trash_dir.join("files");
+    let info: PathBuf = trash_dir.join("info");
+
+    for dir in [&files, &info] {
+        create_private_dir(dir)
+            .await
+            .map_err(|e| failure("trash", &path, &format!("no usable trash at {}: {e}", trash_dir.display())))?;
+    }
+
+    // Per-mount trashes record paths relative to their top directory
+    let recorded: &Path = top
+        .as_deref()
+        .and_then(|top: &Path| path.strip_prefix(top).ok())
+        .unwrap_or(&path);
+
+    let (info_path, trashed_path) = reserve(&files, &info, name, recorded, meta.is_dir()).await?;
+
+    if let Err(e) = rename_no_replace(&path, &trashed_path).await {
+        let _ = TokioFs::remove_file(&info_path).await;
+        return Err(e.into());
+    }
+
+    debug!("Trashed {} as {}", path.display(), trashed_path.display());
+
+    Ok(TrashEntry {
+        info_path,
+        trashed_path,
+        original_path: path,
+        deleted_at: Some(Local::now().naive_local()),
+        is_dir: meta.is_dir(),
+    })
+}
+
+/// Every entry of every trash directory of the user, newest first.
+pub async fn list() -> Vec<TrashEntry> {
+    let mut entries: Vec<TrashEntry> = Vec::new();
+
+    for (dir, top) in trash_dirs().await {
+        read_trash_dir(&dir, top.as_deref(), &mut entries).await;
+    }
+
+    entries.sort_by_key(|entry: &TrashEntry| Reverse(entry.deleted_at));
+
+    entries
+}
+
+/// Move `entry` back to its original path. Nothing is overwritten: if the
+/// path is taken again, the entry stays in the trash.
+pub async fn restore(entry: &TrashEntry) -> Result<PathBuf, AppError> {
+    move_back(entry).await?;
+    forget(entry).await;
+
+    debug!("Restored {}", entry.original_path.display());
+
+    Ok(entry.original_path.clone())
+}
+
+/// Restore every entry of `entries`, or none: when one fails, the ones
+/// already moved back return to their place in the trash. Info files are
+/// only removed once all entries are back.
+pub async fn restore_all(entries: &[TrashEntry]) -> Result<(), AppError> {
+    for (idx, entry) in entries.iter().enumerate() {
+        if let Err(e) = move_back(entry).await {
+            for moved in entries[..idx].iter().rev() {
+                if let Err(e) = rename_no_replace(&moved.original_path, &moved.trashed_path).await {
+                    warn!("Could not put {} back into the trash: {}", moved.original_path.display(), e);
+                }
+            }
+
+            return Err(e);
+        }
+    }
+
+    for entry in entries {
+        forget(entry).await;
+    }
+
+    debug!("Restored {} entries", entries.len());
+
+    Ok(())
+}
+
+/// Rename the trashed file to its original path, leaving the info file.
+async fn move_back(entry: &TrashEntry) -> Result<(), AppError> {
+    if TokioFs::symlink_metadata(&entry.original_path).await.is_ok() {
+        return Err(failure("restore", &entry.original_path, "a file with that name already exists"));
+    }
+
+    if let Some(parent) = entry.original_path.parent() {
+        TokioFs::create_dir_all(parent).await?;
+    }
+
+    rename_no_replace(&entry.trashed_path, &entry.original_path).await?;
+
+    Ok(())
+}
+
+/// Drop the info file of a restored entry.
+async fn forget(entry: &TrashEntry) {
+    if let Err(e) = TokioFs::remove_file(&entry.info_path).await {
+        warn!("Restored {} but kept its info file: {}", entry.original_path.display(), e);
+    }
+}
+
+/// Remove `entry` from the trash for good.
+pub async fn purge(entry: &TrashEntry) -> Result<(), AppError> {
+    match TokioFs::symlink_metadata(&entry.trashed_path).await {
+        Ok(meta) if meta.is_dir() => TokioFs::remove_dir_all(&entry.trashed_path).await?,
+
+        Ok(_) => TokioFs::remove_file(&entry.trashed_path).await?,
+
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+
+        Err(e) => return Err(e.into()),
+    }
+
+    match TokioFs::remove_file(&entry.info_path).await {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+
+        _ => Ok(()),
+    }
+}
+
+fn failure(operation: &str, path: &Path, reason: &str) -> AppError {
+    AppError::FileOperationFailed {
+        operation: operation.to_string(),
+        path: path.to_path_buf(),
+        reason: reason.to_string(),
+    }
+}
+
+/// `$XDG_DATA_HOME/Trash`, or `~/.local/share/Trash`.
+fn home_trash() -> Option<PathBuf> {
+    let data_home: PathBuf = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|dir: &PathBuf| dir.is_absolute())
+        .or_else(|| {
+            directories::BaseDirs::new()
+                .map(|dirs: directories::BaseDirs| dirs.home_dir().join(".local/share"))
+        })?;
+
+    Some(data_home.join("Trash"))
+}
+
+/// Trash directory for `path`, with the top directory its info files are
+/// relative to (`None` for the home trash).
+#[cfg(unix)]
+async fn trash_dir_for(path: &Path) -> Result<(PathBuf, Option<PathBuf>), AppError> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let home: PathBuf = home_trash().ok_or_else(|| failure("trash", path, "no home directory"))?;
+    let parent: &Path = path.parent().unwrap_or(path);
+    let device: u64 = TokioFs::metadata(parent).await?.dev();
+
+    if nearest_device(&home).await == Some(device) {
+        return Ok((home, None));
+    }
+
+    let top: PathBuf = mount_point(parent, device).await;
+    let uid: u32 = sys::uid();
+
+    // An administrator-provided shared trash must be a sticky directory
+    let shared: PathBuf = top.join(".Trash");
+    if let Ok(meta) = TokioFs::symlink_metadata(&shared).await
+        && meta.is_dir()
+        && meta.permissions().mode() & 0o1000 != 0
+    {
+        return Ok((shared.join(uid.to_string()), Some(top)));
+    }
+
+    Ok((top.join(format!(".Trash-{uid}")), Some(top)))
+}
+
+#[cfg(not(unix))]
+async fn trash_dir_for(path: &Path) -> Result<(PathBuf, Option<PathBuf>), AppError> {
+    home_trash()
+        .map(|home: PathBuf| (home, None))
+        .ok_or_else(|| failure("trash", path, "no home directory"))
+}
+
+/// Device of `path`, or of its nearest existing ancestor.
+#[cfg(unix)]
+async fn nearest_device(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    for dir in path.ancestors() {
+        if let Ok(meta) = TokioFs::metadata(dir).await {
+            return Some(meta.dev());
+        }
+    }
+
+    None
+}
+
+/// Topmost ancestor of `dir` still on `device`.
+#[cfg(unix)]
+async fn mount_point(dir: &Path, device: u64) -> PathBuf {
+    use std::os::unix::fs::MetadataExt;
+
+    let mut top: &Path = dir;
+
+    while let Some(parent) = top.parent() {
+        match TokioFs::metadata(parent).await {
+            Ok(meta) if meta.dev() == device => top = parent,
+
+            _ => break,
+        }
+    }
+
+    top.to_path_buf()
+}
+
+/// Create `dir` (and missing parents) readable by the owner only.
+async fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    let mut builder: TokioFs::DirBuilder = TokioFs::DirBuilder::new();
+    builder.recursive(true);
+
+    #[cfg(unix)]
+    builder.mode(0o700);
+
+    builder.create(dir).await
+}
+
+/// Pick a free name in `files/` and claim it by creating its info file.
+async fn reserve(
+    files: &Path,
+    info: &Path,
+    name: &OsStr,
+    recorded: &Path,
+    is_dir: bool,
+) -> Result<(PathBuf, PathBuf), AppError> {
+    let contents: String = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        encode_path(recorded),
+        Local::now().format(DATE_FORMAT)
+    );
+
+    for attempt in 1..=MAX_NAME_ATTEMPTS {
+        let candidate: OsString = candidate_name(name, attempt, is_dir);
+        let trashed_path: PathBuf = files.join(&candidate);
+
+        let mut info_name: OsString = candidate;
+        info_name.push(".");
+        info_name.push(INFO_EXTENSION);
+        let info_path: PathBuf = info.join(info_name);
+
+        // A stray file without info still blocks its name
+        if TokioFs::symlink_metadata(&trashed_path).await.is_ok() {
+            continue;
+        }
+
+        let mut file: TokioFs::File = match TokioFs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&info_path)
+            .await
+        {
+            Ok(file) => file,
+
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+
+            Err(e) => return Err(e.into()),
+        };
+
+        if let Err(e) = file.write_all(contents.as_bytes()).await {
+            let _ = TokioFs::remove_file(&info_path).await;
+            return Err(e.into());
+        }
+
+        return Ok((info_path, trashed_path));
+    }
+
+    Err(failure("trash", &files.join(name), "no free name left in the trash"))
+}
+
+/// `name` for the first attempt, then `stem.N.ext` for files with an
+/// extension and `name.N` otherwise.
+fn candidate_name(name: &OsStr, attempt: usize, is_dir: bool) -> OsString {
+    if attempt == 1 {
+        return name.to_os_string();
+    }
+
+    let number: String = attempt.to_string();
+    let path: &Path = Path::new(name);
+
+    let mut candidate: OsString = OsString::new();
+
+    match (path.file_stem(), path.extension()) {
+        (Some(stem), Some(extension)) if !is_dir => {
+            candidate.push(stem);
+            candidate.push(".");
+            candidate.push(&number);
+            candidate.push(".");
+            candidate.push(extension);
+        }
+
+        _ => {
+            candidate.push(name);
+            candidate.push(".");
+            candidate.push(&number);
+        }
+    }
+
+    candidate
+}
+
+/// Trash directories that exist for the user: the home trash and the
+/// per-mount trashes of every mounted filesystem.
+async fn trash_dirs() -> Vec<(PathBuf, Option<PathBuf>)> {
+    let mut dirs: Vec<(PathBuf, Option<PathBuf>)> = Vec::new();
+
+    if let Some(home) = home_trash() {
+        dirs.push((home, None));
+    }
+
+    #[cfg(unix)]
+    {
+        let uid: u32 = sys::uid();
+
+        for top in sys::mount_points().await {
+            for dir in [top.join(".Trash").join(uid.to_string()), top.join(format!(".Trash-{uid}"))] {
+                let exists: bool = TokioFs::metadata(dir.join("info"))
+                    .await
+                    .is_ok_and(|meta: Metadata| meta.is_dir());
+
+                if exists && !dirs.iter().any(|(known, _)| *known == dir) {
+                    dirs.push((dir, Some(top.clone())));
+                }
+            }
+        }
+    }
+
+    dirs
+}
+
+/// Collect the entries of one trash directory. Info files without a trashed
+/// entry (left over by other tools) are skipped.
+async fn read_trash_dir(dir: &Path, top: Option<&Path>, entries: &mut Vec<TrashEntry>) {
+    let Ok(mut info_dir) = TokioFs::read_dir(dir.join("info")).await else {
+        return;
+    };
+
+    while let Ok(Some(item)) = info_dir.next_entry().await {
+        let info_path: PathBuf = item.path();
+
+        if info_path.extension() != Some(OsStr::new(INFO_EXTENSION)) {
+            continue;
+        }
+
+        let Some(name) = info_path.file_stem() else {
+            continue;
+        };
+
+        let trashed_path: PathBuf = dir.join("files").join(name);
+
+        let Ok(meta) = TokioFs::symlink_metadata(&trashed_path).await else {
+            trace!("Skipping orphaned {}", info_path.display());
+            continue;
+        };
+
+        let Ok(contents) = TokioFs::read_to_string(&info_path).await else {
+            continue;
+        };
+
+        let Some((original, deleted_at)) = parse_info(&contents) else {
+            debug!("Skipping malformed {}", info_path.display());
+            continue;
+        };
+
+        let original_path: PathBuf = match top {
+            Some(top) if original.is_relative() => top.join(original),
+
+            _ => original,
+        };
+
+        entries.push(TrashEntry {
+            info_path,
+            trashed_path,
+            original_path,
+            deleted_at,
+            is_dir: meta.is_dir(),
+        });
+    }
+}
+
+/// Original path and deletion date of a `.trashinfo` file.
+fn parse_info(contents: &str) -> Option<(PathBuf, Option<NaiveDateTime>)> {
+    let mut in_section: bool = false;
+    let mut path: Option<PathBuf> = None;
+    let mut deleted_at: Option<NaiveDateTime> = None;
+
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_section = line == "[Trash Info]";
+            continue;
+        }
+
+        if !in_section {
+            continue;
+        }
+
+        if let Some(value) = line.strip_prefix("Path=") {
+            path = Some(decode_path(value));
+        } else if let Some(value) = line.strip_prefix("DeletionDate=") {
+            deleted_at = NaiveDateTime::parse_from_str(value, DATE_FORMAT).ok();
+        }
+    }
+
+    Some((path?, deleted_at))
+}
+
+/// Percent-encode every byte of `path` except unreserved characters and `/`.
+fn encode_path(path: &Path) -> String {
+    let mut encoded: String = String::new();
+
+    for &byte in path.as_os_str().as_encoded_bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~' | b'/') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    encoded
+}
+
+fn decode_path(value: &str) -> PathBuf {
+    let bytes: &[u8] = value.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut i: usize = 0;
+
+    while i < bytes.len() {
+        let escaped: Option<u8> = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex: &str| u8::from_str_radix(hex, 16).ok());
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    sys::path_from_bytes(decoded)
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt;
+    use std::path::PathBuf;
+
+    use tokio::fs as TokioFs;
+
+    pub fn uid() -> u32 {
+        // SAFETY: getuid has no preconditions and cannot fail
+        unsafe { libc::getuid() }
+    }
+
+    pub fn path_from_bytes(bytes: Vec<u8>) -> PathBuf {
+        PathBuf::from(OsString::from_vec(bytes))
+    }
+
+    /// Mount points listed by the kernel (none where `/proc` is missing).
+    pub async fn mount_points() -> Vec<PathBuf> {
+        let Ok(mounts) = TokioFs::read_to_string("/proc/self/mounts").await else {
+            return Vec::new();
+        };
+
+        mounts
+            .lines()
+            .filter_map(|line: &str| line.split(' ').nth(1))
+            .map(|field: &str| path_from_bytes(unescape_octal(field)))
+            .collect()
+    }
+
+    /// Undo the `\040`-style escapes of `/proc/self/mounts`.
+    fn unescape_octal(field: &str) -> Vec<u8> {
+        let bytes: &[u8] = field.as_bytes();
+        let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+        let mut i: usize = 0;
+
+        while i < bytes.len() {
+            let escaped: Option<u8> = (bytes[i] == b'\\')
+                .then(|| field.get(i + 1..i + 4))
+                .flatten()
+                .and_then(|octal: &str| u8::from_str_radix(octal, 8).ok());
+
+            match escaped {
+                Some(byte) => {
+                    out.push(byte);
+                    i += 4;
+                }
+
+                None => {
+                    out.push(bytes[i]);
+                    i += 1;
+                }
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(not(unix))]
+mod sys {
+    use std::path::PathBuf;
+
+    pub fn path_from_bytes(bytes: Vec<u8>) -> PathBuf {
+        PathBuf::from(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_path_escapes_reserved_bytes_only() {
+        assert_eq!(encode_path(Path::new("/home/a-b_c.d~/x")), "/home/a-b_c.d~/x");
+        assert_eq!(encode_path(Path::new("/tmp/a b%c")), "/tmp/a%20b%25c");
+        assert_eq!(encode_path(Path::new("/tmp/é")), "/tmp/%C3%A9");
+    }
+
+    #[test]
+    fn decode_path_reverses_encode_path() {
+        for original in ["/tmp/a b%c", "/tmp/é/[x]#?", "relative/dir name"] {
+            assert_eq!(decode_path(&encode_path(Path::new(original))), PathBuf::from(original));
+        }
+    }
+
+    #[test]
+    fn decode_path_keeps_malformed_escapes() {
+        assert_eq!(decode_path("/tmp/100%"), PathBuf::from("/tmp/100%"));
+        assert_eq!(decode_path("/tmp/%zz"), PathBuf::from("/tmp/%zz"));
+        assert_eq!(decode_path("/tmp/%4"), PathBuf::from("/tmp/%4"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn encode_path_round_trips_non_utf8_bytes() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path: &Path = Path::new(OsStr::from_bytes(b"/tmp/caf\xE9"));
+
+        assert_eq!(encode_path(path), "/tmp/caf%E9");
+        assert_eq!(decode_path("/tmp/caf%E9"), path);
+    }
+
+    #[test]
+    fn parse_info_reads_path_and_date() {
+        let contents: &str = "[Trash Info]\nPath=/home/user/a%20file.txt\nDeletionDate=2024-03-01T12:30:05\n";
+        let (path, deleted_at) = parse_info(contents).expect("valid trashinfo");
+
+        assert_eq!(path, PathBuf::from("/home/user/a file.txt"));
+        assert_eq!(
+            deleted_at,
+            NaiveDateTime::parse_from_str("2024-03-01T12:30:05", DATE_FORMAT).ok()
+        );
+    }
+
+    #[test]
+    fn parse_info_tolerates_a_bad_date() {
+        let (path, deleted_at) = parse_info("[Trash Info]\nPath=a\nDeletionDate=yesterday\n").expect("path present");
+
+        assert_eq!(path, PathBuf::from("a"));
+        assert_eq!(deleted_at, None);
+    }
+
+    #[test]
+    fn parse_info_ignores_keys_outside_its_section() {
+        assert_eq!(parse_info("Path=/outside\n[Other]\nPath=/other\n"), None);
+        assert_eq!(parse_info(""), None);
+
+        let (path, _) = parse_info("[Other]\nPath=/other\n[Trash Info]\nPath=/inside\n").expect("path present");
+        assert_eq!(path, PathBuf::from("/inside"));
+    }
+
+    #[test]
+    fn candidate_name_numbers_before_the_extension_of_files() {
+        assert_eq!(candidate_name(OsStr::new("notes.txt"), 1, false), "notes.txt");
+        assert_eq!(candidate_name(OsStr::new("notes.txt"), 2, false), "notes.2.txt");
+        assert_eq!(candidate_name(OsStr::new("archive.tar.gz"), 3, false), "archive.tar.3.gz");
+    }
+
+    #[test]
+    fn candidate_name_appends_to_directories_and_bare_names() {
+        assert_eq!(candidate_name(OsStr::new("photos.d"), 2, true), "photos.d.2");
+        assert_eq!(candidate_name(OsStr::new("Makefile"), 2, false), "Makefile.2");
+        assert_eq!(candidate_name(OsStr::new(".bashrc"), 4, false), ".bashrc.4");
+    }
+
+    #[tokio::test]
+    async fn restore_all_puts_moved_entries_back_when_one_fails() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let mut entries: Vec<TrashEntry> = Vec::new();
+
+        for name in ["a", "b"] {
+            let entry: TrashEntry = TrashEntry {
+                info_path: root.path().join(format!("{name}.trashinfo")),
+                trashed_path: root.path().join(format!("{name}.trashed")),
+                original_path: root.path().join(name),
+                deleted_at: None,
+                is_dir: false,
+            };
+            TokioFs::write(&entry.info_path, "").await.expect("write file");
+            entries.push(entry);
+        }
+
+        // Only the first one is still in the trash
+        TokioFs::write(&entries[0].trashed_path, "a").await.expect("write file");
+
+        assert!(restore_all(&entries).await.is_err());
+        assert!(TokioFs::symlink_metadata(&entries[0].trashed_path).await.is_ok());
+        assert!(TokioFs::symlink_metadata(&entries[0].original_path).await.is_err());
+        assert!(TokioFs::symlink_metadata(&entries[0].info_path).await.is_ok());
+    }
+}
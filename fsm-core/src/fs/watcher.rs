@@ -0,0 +1,325 @@
+//! ``src/fs/watcher.rs``
+//! ============================================================================
+//! # `DirWatcher`: Live Refresh of Pane Directories and Cache Invalidation
+//!
+//! Watches the directory of every pane (inotify on Linux, through `notify`)
+//! so listings follow external changes without a manual reload. The parent
+//! directories of cached entries are watched as well (re-synced every
+//! `CACHE_RESYNC_INTERVAL`, at most `MAX_CACHED_DIRS`), so sizes and mtimes
+//! in `ObjectInfoCache` do not wait for TTL expiry to become correct.
+//!
+//! Events are collected per directory and coalesced with a `Debouncer`
+//! (`DebounceConfig::fs_watch`). When a burst settles every touched path is
+//! invalidated in the cache together with its ancestors, whose directory
+//! sizes include it. In pane directories the paths are also stat'ed again:
+//! existing paths become `FsChange::Upsert`, vanished ones
+//! `FsChange::Removed`, sent as one `Action::FsChanged` for panes to patch
+//! their rows in place.
+
+use std::collections::{HashMap, HashSet};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tracing::{debug, trace, warn};
+
+use crate::cache::cache_manager::ObjectInfoCache;
+use crate::controller::actions::Action;
+use crate::error::AppError;
+use crate::fs::object_info::ObjectInfo;
+use crate::util::debounce::{DebounceConfig, Debouncer};
+
+/// How often the cached directories to watch are re-read from the cache.
+const CACHE_RESYNC_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Upper bound on directories watched only for cache invalidation.
+const MAX_CACHED_DIRS: usize = 512;
+
+/// Paths touched by events, per directory, waiting for the burst to settle.
+type PendingChanges = Arc<Mutex<HashMap<PathBuf, HashSet<PathBuf>>>>;
+
+/// Current state of one path reported by the watcher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FsChange {
+    /// The path exists (created, modified or renamed to).
+    Upsert(ObjectInfo),
+
+    /// The path no longer exists (deleted or renamed away).
+    Removed(PathBuf),
+}
+
+impl FsChange {
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Upsert(info) => &info.path,
+
+            Self::Removed(path) => path,
+        }
+    }
+
+    /// Row to show for an upsert of `existing`. Directory sizes and item
+    /// counts already found by the size task are kept unless the entry
+    /// changed type.
+    #[must_use]
+    pub fn refreshed(existing: Option<ObjectInfo>, info: &ObjectInfo) -> ObjectInfo {
+        match existing {
+            Some(mut entry) if entry.is_dir == info.is_dir && entry.is_symlink == info.is_symlink => {
+                entry.apply_metadata(info);
+                entry
+            }
+
+            _ => info.clone(),
+        }
+    }
+}
+
+/// Watched directories by reason, and the watcher itself.
+struct WatchState {
+    watcher: RecommendedWatcher,
+
+    /// Directories shown by panes (rows are patched)
+    panes: HashSet<PathBuf>,
+
+    /// Parent directories of cached entries (invalidation only)
+    cached: HashSet<PathBuf>,
+
+    /// Union of both, as currently registered with the watcher
+    watched: HashSet<PathBuf>,
+}
+
+impl WatchState {
+    /// Register new directories and drop the ones no longer wanted.
+    fn apply(&mut self) {
+        let wanted: HashSet<PathBuf> = self.panes.union(&self.cached).cloned().collect();
+
+        if wanted == self.watched {
+            return;
+        }
+
+        for dir in self.watched.difference(&wanted) {
+            if let Err(e) = self.watcher.unwatch(dir) {
+                trace!("Unwatching {} failed: {}", dir.display(), e);
+            }
+        }
+
+        for dir in wanted.difference(&self.watched) {
+            match self.watcher.watch(dir, RecursiveMode::NonRecursive) {
+                Ok(()) => trace!("Watching {}", dir.display()),
+
+                Err(e) => debug!("Cannot watch {}: {}", dir.display(), e),
+            }
+        }
+
+        self.watched = wanted;
+    }
+}
+
+/// Watches pane directories and cached directories, reporting coalesced
+/// changes.
+pub struct DirWatcher {
+    state: Arc<Mutex<WatchState>>,
+}
+
+impl DirWatcher {
+    /// Start the watcher and its background tasks. Must be called within a
+    /// Tokio runtime.
+    pub fn spawn(
+        action_tx: UnboundedSender<Action>,
+        cache: Arc<ObjectInfoCache>,
+    ) -> notify::Result<Self> {
+        let (event_tx, event_rx) = mpsc::unbounded_channel::<Event>();
+
+        let watcher: RecommendedWatcher = notify::recommended_watcher(
+            move |result: notify::Result<Event>| match result {
+                Ok(event) => {
+                    let _ = event_tx.send(event);
+                }
+
+                Err(e) => warn!("File watcher error: {}", e),
+            },
+        )?;
+
+        let state: Arc<Mutex<WatchState>> = Arc::new(Mutex::new(WatchState {
+            watcher,
+            panes: HashSet::new(),
+            cached: HashSet::new(),
+            watched: HashSet::new(),
+        }));
+
+        let (debouncer, ready_rx) = Debouncer::<PathBuf>::new(DebounceConfig::fs_watch());
+        let pending: PendingChanges = Arc::new(Mutex::new(HashMap::new()));
+
+        tokio::spawn(Self::collect_events(event_rx, debouncer, pending.clone()));
+        tokio::spawn(Self::publish_changes(
+            ready_rx,
+            pending,
+            Arc::downgrade(&state),
+            action_tx,
+            cache.clone(),
+        ));
+        tokio::spawn(Self::follow_cache(Arc::downgrade(&state), cache));
+
+        Ok(Self { state })
+    }
+
+    /// Watch exactly `dirs` as pane directories: new directories are added
+    /// and directories no pane shows any more are dropped.
+    pub fn sync(&self, dirs: impl IntoIterator<Item = PathBuf>) {
+        if let Ok(mut state) = self.state.lock() {
+            state.panes = dirs.into_iter().collect();
+            state.apply();
+        }
+    }
+
+    /// Keep watching the parent directories of cached entries until the
+    /// watcher is dropped.
+    async fn follow_cache(state: Weak<Mutex<WatchState>>, cache: Arc<ObjectInfoCache>) {
+        let mut resync: tokio::time::Interval = tokio::time::interval(CACHE_RESYNC_INTERVAL);
+
+        loop {
+            resync.tick().await;
+
+            let Some(state) = state.upgrade() else {
+                return;
+            };
+
+            let cached: HashSet<PathBuf> = cache.cached_dirs(MAX_CACHED_DIRS).into_iter().collect();
+
+            if let Ok(mut state) = state.lock() {
+                state.cached = cached;
+                state.apply();
+            }
+        }
+    }
+
+    /// Record every path an event touches under its directory and restart
+    /// that directory's debounce timer.
+    async fn collect_events(
+        mut event_rx: UnboundedReceiver<Event>,
+        debouncer: Debouncer<PathBuf>,
+        pending: PendingChanges,
+    ) {
+        while let Some(event) = event_rx.recv().await {
+            // Reads and opens do not change listings
+            if matches!(event.kind, EventKind::Access(_)) {
+                continue;
+            }
+
+            for path in event.paths {
+                let Some(dir) = path.parent().map(Path::to_path_buf) else {
+                    continue;
+                };
+
+                if let Ok(mut pending) = pending.lock() {
+                    pending.entry(dir.clone()).or_default().insert(path);
+                }
+
+                // Debug formatting keeps keys of non-UTF-8 paths distinct
+                debouncer.submit(format!("{dir:?}"), dir).await;
+            }
+        }
+    }
+
+    /// Invalidate the paths of each settled directory; for pane directories
+    /// stat them again and send them as one action.
+    async fn publish_changes(
+        mut ready_rx: UnboundedReceiver<(String, PathBuf)>,
+        pending: PendingChanges,
+        state: Weak<Mutex<WatchState>>,
+        action_tx: UnboundedSender<Action>,
+        cache: Arc<ObjectInfoCache>,
+    ) {
+        while let Some(first) = ready_rx.recv().await {
+            // Take every directory that settled meanwhile along, so the
+            // whole flush is invalidated in one pass over the cache
+            let mut ready: Vec<(String, PathBuf)> = vec![first];
+            while let Ok(next) = ready_rx.try_recv() {
+                ready.push(next);
+            }
+
+            let flushed: Vec<(PathBuf, HashSet<PathBuf>)> = pending
+                .lock()
+                .map(|mut pending| {
+                    ready
+                        .into_iter()
+                        .filter_map(|(_, dir)| pending.remove(&dir).map(|paths| (dir, paths)))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            // Invalidate before stat'ing, so size tasks started for the
+            // changes do not find stale entries
+            let changed: Vec<&Path> = flushed
+                .iter()
+                .flat_map(|(_, paths)| paths.iter().map(PathBuf::as_path))
+                .collect();
+            let dropped: u64 = cache.invalidate_paths(changed).await;
+
+            trace!("{} settled dir(s) dropped {} cache key(s)", flushed.len(), dropped);
+
+            for (dir, paths) in flushed {
+                if !Self::publish_dir(&state, &action_tx, dir, paths).await {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Stat the changed `paths` of `dir` again and send them if `dir` is
+    /// shown in a pane. Returns `false` once the watcher or app is gone.
+    async fn publish_dir(
+        state: &Weak<Mutex<WatchState>>,
+        action_tx: &UnboundedSender<Action>,
+        dir: PathBuf,
+        paths: HashSet<PathBuf>,
+    ) -> bool {
+        let Some(state) = state.upgrade() else {
+            return false;
+        };
+
+        let shown: bool = state
+            .lock()
+            .is_ok_and(|state| state.panes.contains(&dir));
+
+        if !shown {
+            return true;
+        }
+
+        let mut changes: Vec<FsChange> = Vec::with_capacity(paths.len());
+
+        for path in paths {
+            match ObjectInfo::from_path_direct(&path).await {
+                Ok(info) => changes.push(FsChange::Upsert(info)),
+
+                Err(AppError::Io(e)) if e.kind() == ErrorKind::NotFound => {
+                    changes.push(FsChange::Removed(path));
+                }
+
+                Err(e) => debug!("Skipping change of {}: {}", path.display(), e),
+            }
+        }
+
+        debug!("{} change(s) in {}", changes.len(), dir.display());
+
+        changes.is_empty() || action_tx.send(Action::FsChanged { dir, changes }).is_ok()
+    }
+}
+
+impl std::fmt::Debug for DirWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (panes, watched): (usize, usize) = self
+            .state
+            .lock()
+            .map(|state| (state.panes.len(), state.watched.len()))
+            .unwrap_or_default();
+
+        f.debug_struct("DirWatcher")
+            .field("panes", &panes)
+            .field("watched", &watched)
+            .finish()
+    }
+}
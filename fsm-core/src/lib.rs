@@ -15,6 +15,7 @@ pub mod error_core;
 
 pub mod cache {
     pub mod cache_manager;
+    pub mod disk_cache;
 }
 
 pub mod controller {
@@ -30,17 +31,43 @@ pub mod model {
     pub mod app_state;
     pub use app_state::{AppHistoryEvent, AppState, PluginInfo};
 
+    pub mod bookmarks;
+    pub use bookmarks::BookmarkStore;
+
     pub mod command_palette;
     pub use command_palette::{Command, CommandAction, CommandPaletteState};
 
+    pub mod diagnostics;
+    pub use diagnostics::DiagnosticsSnapshot;
+
+    pub mod frecency;
+    pub use frecency::FrecencyStore;
+
+    pub mod hit_map;
+    pub use hit_map::{Click, HitMap, ListHit, PaneHit};
+
     pub mod fs_state;
-    pub use fs_state::{EntryFilter, EntrySort, FSState, PaneState};
+    pub use fs_state::{EntryFilter, EntrySort, FSState, HistoryEntry, PaneState};
+
+    pub mod preview;
+    pub use preview::{Preview, PreviewStore};
+
+    pub mod sort_registry;
+    pub use sort_registry::{Comparator, CustomSort, SortRegistry};
+
+    pub mod tabs;
+    pub use tabs::{Tab, TabId, TabSet};
+
+    pub mod tree;
+    pub use tree::{TreeRow, TreeState};
 
     pub mod ui_state;
     pub use ui_state::{
         FileOperationProgress, LoadingState, Notification, NotificationLevel, RedrawFlag,
         SearchType, UIMode, UIOverlay, UIState,
     };
+
+    pub mod undo;
 }
 
 pub mod view {
@@ -53,6 +80,9 @@ pub mod view {
     pub use ui::View;
 
     pub mod components {
+        pub mod bookmarks_overlay;
+        pub use bookmarks_overlay::BookmarksOverlay;
+
         pub mod command_completion;
         pub use command_completion::CommandCompletion;
 
@@ -62,21 +92,33 @@ pub mod view {
         pub mod content_search_overlay;
         pub use content_search_overlay::ContentSearchOverlay;
 
+        pub mod diagnostics_overlay;
+        pub use diagnostics_overlay::DiagnosticsOverlay;
+
         pub mod file_operations_overlay;
         pub use file_operations_overlay::FileOperationsOverlay;
 
         pub mod filename_search_overlay;
         pub use filename_search_overlay::FileNameSearchOverlay;
 
+        pub mod frecency_overlay;
+        pub use frecency_overlay::FrecencyOverlay;
+
         pub mod help_overlay;
         pub use help_overlay::HelpOverlay;
 
+        pub mod history_overlay;
+        pub use history_overlay::HistoryOverlay;
+
         pub mod input_prompt_overlay;
         pub use input_prompt_overlay::InputPromptOverlay;
 
         pub mod loading_overlay;
         pub use loading_overlay::LoadingOverlay;
 
+        pub mod miller_columns;
+        pub use miller_columns::MillerColumns;
+
         pub mod notification_overlay;
         pub use notification_overlay::NotificationOverlay;
         pub mod object_table;
@@ -93,6 +135,15 @@ pub mod view {
 
         pub mod status_bar;
         pub use status_bar::StatusBar;
+
+        pub mod tab_bar;
+        pub use tab_bar::TabBar;
+
+        pub mod trash_overlay;
+        pub use trash_overlay::TrashOverlay;
+
+        pub mod undo_history_overlay;
+        pub use undo_history_overlay::UndoHistoryOverlay;
     }
 
     pub use components::*;
@@ -103,6 +154,10 @@ pub use view::*;
 pub mod fs {
     pub mod dir_scanner;
     pub mod object_info;
+    pub mod owners;
+    pub mod rename;
+    pub mod trash;
+    pub mod watcher;
 }
 
 pub mod tasks {
@@ -110,6 +165,8 @@ pub mod tasks {
 
     pub mod metadata_task;
 
+    pub mod preview_task;
+
     pub mod search_task;
 
     pub mod size_task;
@@ -23,17 +23,18 @@ use fsm_core::logging::{init_logging_with_level, ProfilingData};
 
 use anyhow::{Context, Error, Result};
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{Frame, Terminal, backend::CrosstermBackend as Backend};
 use tokio::{
     signal,
-    sync::{mpsc, Mutex, MutexGuard, Notify}, task::JoinHandle,
+    sync::{mpsc, watch, Mutex, MutexGuard, Notify}, task::JoinHandle,
 };
 
 use fsm_core::{
-    cache::cache_manager::ObjectInfoCache,
+    cache::{cache_manager::ObjectInfoCache, disk_cache},
     config::Config,
     controller::{
         actions::Action,
@@ -42,6 +43,8 @@ use fsm_core::{
     logging::shutdown_logging,
     model::{
         app_state::AppState,
+        bookmarks::BookmarkStore,
+        frecency::FrecencyStore,
         fs_state::FSState,
         ui_state::{RedrawFlag, UIState},
     },
@@ -75,6 +78,9 @@ struct App {
     terminal: AppTerminal,
     controller: EventLoop,
     state: Arc<Mutex<AppState>>,
+    cache: Arc<ObjectInfoCache>,
+    /// Snapshot path, and whether its startup load has finished.
+    snapshot: Option<(PathBuf, watch::Receiver<bool>)>,
     shutdown: Arc<Notify>,
     last_memory_check: Instant,
     _tracer_guard: WorkerGuard,
@@ -92,6 +98,8 @@ impl App {
         // Concurrently load configuration and determine the current directory to improve startup time.
         let config_handle: JoinHandle<StdResult<Config, Error>> = tokio::spawn(Config::load());
         let dir_handle = tokio::spawn(tokio::fs::canonicalize("."));
+        let bookmarks_handle: JoinHandle<BookmarkStore> = tokio::spawn(BookmarkStore::load());
+        let frecency_handle: JoinHandle<FrecencyStore> = tokio::spawn(FrecencyStore::load());
 
         let config: Arc<Config> = Arc::new(
             config_handle
@@ -105,10 +113,34 @@ impl App {
                 )
             );
 
+        // Without capture the terminal keeps its own text selection
+        if config.mouse {
+            execute!(io::stdout(), EnableMouseCapture).context("Failed to enable mouse capture")?;
+        }
+
         let cache: Arc<ObjectInfoCache> =
             Arc::new(ObjectInfoCache::with_config(config.cache.clone()));
-        let fs_state: FSState = FSState::default();
-        let ui_state: UIState = UIState::default();
+
+        let snapshot: Option<(PathBuf, watch::Receiver<bool>)> = if config.cache.persist {
+            match Config::cache_snapshot_path() {
+                Ok(path) => Some((path.clone(), disk_cache::spawn_persistence(cache.clone(), path))),
+
+                Err(e) => {
+                    Tracer::warn!("Cache snapshot disabled: {e}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let mut fs_state: FSState = FSState::default();
+        fs_state.active_pane_mut().sort_config = config.sort;
+        let ui_state: UIState = UIState {
+            layout: config.layout,
+            columns: config.columns.clone(),
+            ..UIState::default()
+        };
 
         let (
             task_tx,
@@ -122,7 +154,7 @@ impl App {
 
         let app_state: Arc<Mutex<AppState>> = Arc::new(Mutex::new(AppState::new(
             config,
-            cache,
+            cache.clone(),
             fs_state,
             ui_state,
             task_tx.clone(),
@@ -138,6 +170,8 @@ impl App {
 
         {
             let mut state: MutexGuard<'_, AppState> = app_state.lock().await;
+            state.bookmarks = bookmarks_handle.await?;
+            state.frecency = frecency_handle.await?;
             state.enter_directory(current_dir).await;
             state.ui.request_redraw(RedrawFlag::All); // Use UI state for redraw management
         }
@@ -148,6 +182,8 @@ impl App {
             terminal,
             controller,
             state: app_state,
+            cache,
+            snapshot,
             shutdown,
             last_memory_check: Instant::now(),
             _tracer_guard: tracer_guard,
@@ -208,7 +244,7 @@ impl App {
                 "Dispatching action to controller"
             );
 
-            self.controller.dispatch_action(action).await;
+            self.controller.process_action(action).await;
         }
 
         tracing::info!(
@@ -301,7 +337,8 @@ impl App {
                     let used_percent: f64 =
                         ((total_mb - available_mb) as f64 / total_mb as f64) * 100.0;
 
-                    // Log memory warnings based on available memory
+                    // Log memory warnings based on available memory, giving
+                    // back cache memory under pressure
                     if available_mb < 100 {
                         // Less than 100MB available
                         Tracer::warn!(
@@ -309,6 +346,7 @@ impl App {
                             available_mb,
                             used_percent as u32
                         );
+                        self.shrink_cache(4);
                     } else if available_mb < 500 {
                         // Less than 500MB available
                         Tracer::info!(
@@ -316,12 +354,17 @@ impl App {
                             available_mb,
                             used_percent as u32
                         );
-                    } else if used_percent > 80.0 {
-                        Tracer::debug!(
-                            "Memory usage: {}MB available ({}% used)",
-                            available_mb,
-                            used_percent as u32
-                        );
+                        self.shrink_cache(2);
+                    } else {
+                        if used_percent > 80.0 {
+                            Tracer::debug!(
+                                "Memory usage: {}MB available ({}% used)",
+                                available_mb,
+                                used_percent as u32
+                            );
+                        }
+
+                        self.restore_cache_budget();
                     }
                 }
 
@@ -329,9 +372,36 @@ impl App {
                     Tracer::debug!("Failed to get memory info: {}", e);
                 }
             }
+
+            self.cache.health_check();
         }
     }
 
+    /// Shrink the object cache to `1 / divisor` of its current size in the
+    /// background.
+    fn shrink_cache(&self, divisor: u64) {
+        let cache: Arc<ObjectInfoCache> = self.cache.clone();
+        let target: u64 = cache.weighted_size() / divisor;
+
+        tokio::spawn(async move {
+            cache.shrink_to(target).await;
+        });
+    }
+
+    /// Give a cache shrunk under memory pressure its configured budget
+    /// back in the background.
+    fn restore_cache_budget(&self) {
+        let cache: Arc<ObjectInfoCache> = self.cache.clone();
+
+        if cache.memory_budget() >= cache.config().max_memory_mb.saturating_mul(1024 * 1024) {
+            return;
+        }
+
+        tokio::spawn(async move {
+            cache.restore_budget().await;
+        });
+    }
+
     #[allow(clippy::unused_async)]
     /// Setup signal handlers for graceful shutdown
     async fn setup_shutdown_handler(&self) {
@@ -378,6 +448,21 @@ impl App {
     pub async fn shutdown(mut self) -> Result<()> {
         Tracer::info!("Application shutting down gracefully");
 
+        // Write the cache snapshot while logging still works. Until the
+        // startup load is done the cache lacks the snapshot's entries, and
+        // saving would throw them away.
+        if let Some((path, loaded)) = self.snapshot.as_mut() {
+            if !disk_cache::wait_for_load(loaded, disk_cache::SHUTDOWN_LOAD_WAIT).await {
+                Tracer::warn!("Cache snapshot still loading, keeping {} as it is", path.display());
+            } else {
+                match disk_cache::save(&self.cache, path).await {
+                    Ok(count) => Tracer::info!("Saved {} cache entries to {}", count, path.display()),
+
+                    Err(e) => Tracer::warn!("Failed to save cache snapshot: {e}"),
+                }
+            }
+        }
+
         // Shutdown logging system first (this is async-safe)
         shutdown_logging().await?;
 
@@ -416,7 +501,7 @@ fn setup_terminal() -> Result<AppTerminal> {
 fn cleanup_terminal(terminal: &mut AppTerminal) -> Result<()> {
     disable_raw_mode().context("Failed to disable raw mode")?;
 
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)
+    execute!(terminal.backend_mut(), DisableMouseCapture, LeaveAlternateScreen)
         .context("Failed to leave alternate screen")?;
 
     terminal.show_cursor().context("Failed to show cursor")?;
@@ -435,7 +520,7 @@ fn setup_panic_handler() {
     StdPanicker::set_hook(Box::new(move |panic_info: &PanicHookInfo<'_>| {
         // Try to restore terminal on panic
         let _ = disable_raw_mode();
-        let _ = execute!(io::stderr(), LeaveAlternateScreen);
+        let _ = execute!(io::stderr(), DisableMouseCapture, LeaveAlternateScreen);
 
         eprintln!("Application panicked: {panic_info}");
 
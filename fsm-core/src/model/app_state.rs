@@ -19,17 +19,29 @@
 
 use crate::{
     cache::cache_manager::ObjectInfoCache, 
-    fs::dir_scanner::scan_dir_streaming_with_background_metadata
+    fs::dir_scanner::{SCAN_CHUNK_SIZE, scan_dir_streaming_with_background_metadata}
 };
-use crate::config::Config;
+use crate::config::{Config, LayoutMode, SortConfig};
 use crate::controller::actions::Action;
 use crate::controller::event_loop::TaskResult;
 use crate::fs::object_info::ObjectInfo;
-use crate::model::fs_state::{FSState, PaneState};
-use crate::model::ui_state::{RedrawFlag, UIState};
+use crate::fs::rename::rename_no_replace;
+use crate::fs::trash::{self, TrashEntry};
+use crate::model::undo;
+use crate::fs::watcher::{DirWatcher, FsChange};
+use crate::model::bookmarks::BookmarkStore;
+use crate::model::frecency::{FrecencyEntry, FrecencyStore};
+use crate::model::preview::{Preview, PreviewStore};
+use crate::model::sort_registry::{Comparator, SortRegistry};
+use crate::model::tabs::{Tab, TabId, TabSet, TabUiState};
+use crate::model::fs_state::{EntryFilter, EntrySort, FSState, HistoryEntry, HistoryPosition, PaneState};
+use crate::model::ui_state::{RedrawFlag, UIOverlay, UIState};
+use crate::tasks::file_ops_task::is_valid_name;
 use crate::tasks::filename_search_task::FilenameSearchTask;
+use crate::tasks::preview_task;
 
-use std::{cmp::Ordering, collections::{HashMap, HashSet, VecDeque}};
+use std::collections::{HashMap, VecDeque};
+use std::ffi::OsStr;
 use std::io::Error;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -38,6 +50,9 @@ use tokio::{process::Command, task::JoinError};
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn, Instrument};
 
+/// Undoable operations kept in `AppState::history`.
+pub const MAX_HISTORY: usize = 128;
+
 /// Represents a pending or running asynchronous task (search, copy, delete, etc.).
 #[derive(Debug, Clone)]
 pub struct TaskInfo {
@@ -68,16 +83,48 @@ pub struct AppState {
     pub task_tx: mpsc::UnboundedSender<TaskResult>,
     pub action_tx: mpsc::UnboundedSender<Action>,
 
+    /// Open tabs; the active tab's filesystem state is `fs`
+    pub tabs: TabSet,
+
+    /// Origin tab of each running file operation (by operation id)
+    pub file_op_tabs: HashMap<String, TabId>,
+
+    /// Id of the last directory scan started (see `spawn_directory_scan`)
+    pub last_scan_id: u64,
+
     // --- Business Logic State ---
-    /// Marked files/directories by path for batch operations
-    pub marked: HashSet<PathBuf>,
+
+    /// Named directory bookmarks (persisted next to `config.toml`)
+    pub bookmarks: BookmarkStore,
+
+    /// Visit-ranked directories for `z <fragment>` jumps
+    pub frecency: FrecencyStore,
+
+    /// Parent listings and previews shown by the Miller layout
+    pub previews: PreviewStore,
     
-    /// Application history for undo/redo operations
+    /// Application history for undo/redo operations (newest last)
     pub history: VecDeque<AppHistoryEvent>,
+
+    /// Undone events, next to redo last (cleared by any new operation)
+    pub redo_history: Vec<AppHistoryEvent>,
+
+    /// `history_generation` when the undo or redo run in progress started
+    /// (see `undo::run`)
+    pub undo_running: Option<u64>,
+
+    /// Bumped by every operation recorded with `push_history`
+    pub history_generation: u64,
     
     /// Plugin registry and information
     pub plugins: HashMap<String, PluginInfo>,
     
+    /// Named comparators selectable with `:sort <name>`
+    pub sort_registry: SortRegistry,
+    
+    /// Watches pane and cached directories (`None` if unavailable)
+    pub watcher: Option<DirWatcher>,
+    
     /// Active background tasks
     pub tasks: HashMap<u64, TaskInfo>,
     
@@ -89,13 +136,120 @@ pub struct AppState {
 }
 
 
+/// A filesystem change that can be undone and redone (see `model::undo`).
 #[derive(Debug, Clone)]
 pub enum AppHistoryEvent {
-    Delete { paths: Vec<String> },
-    Move { from: String, to: String },
-    Rename { from: String, to: String },
-    Copy { from: String, to: String },
-    // Extend with plugin hooks, batch ops, custom undo, etc.
+    /// Entries moved to the trash (where they went and came from)
+    Delete { entries: Vec<TrashEntry> },
+
+    Move { from: PathBuf, to: PathBuf },
+
+    Rename { from: PathBuf, to: PathBuf },
+
+    /// `to` is the copy; `fingerprint` its `undo::fingerprint` right after
+    /// copying, `trashed` where undo put it
+    Copy {
+        from: PathBuf,
+        to: PathBuf,
+        fingerprint: Option<u64>,
+        trashed: Option<TrashEntry>,
+    },
+
+    /// A new empty file or directory
+    Create {
+        path: PathBuf,
+        is_dir: bool,
+        fingerprint: Option<u64>,
+        trashed: Option<TrashEntry>,
+    },
+
+    /// Steps of a batch operation, in the order they ran
+    Batch(Vec<AppHistoryEvent>),
+    // Extend with plugin hooks, custom undo, etc.
+}
+
+impl AppHistoryEvent {
+    /// One record for the finished steps of an operation: nothing for no
+    /// steps, trashed entries merged into one `Delete`, a `Batch` otherwise.
+    #[must_use]
+    pub fn combine(mut events: Vec<Self>) -> Option<Self> {
+        if events.len() <= 1 {
+            return events.pop();
+        }
+
+        if events.iter().all(|event: &Self| -> bool { matches!(event, Self::Delete { .. }) }) {
+            let entries: Vec<TrashEntry> = events
+                .into_iter()
+                .flat_map(|event: Self| -> Vec<TrashEntry> {
+                    match event {
+                        Self::Delete { entries } => entries,
+
+                        _ => Vec::new(),
+                    }
+                })
+                .collect();
+
+            return Some(Self::Delete { entries });
+        }
+
+        Some(Self::Batch(events))
+    }
+
+    /// One-line description for notifications and the undo overlay.
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            Self::Delete { entries } => match entries.as_slice() {
+                [entry] => format!("Trash {}", entry.original_path.display()),
+
+                _ => format!("Trash {} entries", entries.len()),
+            },
+
+            Self::Move { from, to } => format!("Move {} → {}", from.display(), to.display()),
+
+            Self::Rename { from, to } => format!(
+                "Rename {} → {}",
+                from.display(),
+                to.file_name().unwrap_or(to.as_os_str()).to_string_lossy()
+            ),
+
+            Self::Copy { from, to, .. } => format!("Copy {} → {}", from.display(), to.display()),
+
+            Self::Create { path, is_dir, .. } => format!(
+                "Create {} {}",
+                if *is_dir { "directory" } else { "file" },
+                path.display()
+            ),
+
+            Self::Batch(events) => match events.first() {
+                Some(first)
+                    if events.iter().all(|event: &Self| -> bool {
+                        std::mem::discriminant(event) == std::mem::discriminant(first)
+                    }) =>
+                {
+                    format!("{} {} entries", first.verb(), events.len())
+                }
+
+                _ => format!("{} operations", events.len()),
+            },
+        }
+    }
+
+    const fn verb(&self) -> &'static str {
+        match self {
+            Self::Delete { .. } => "Trash",
+
+            Self::Move { .. } => "Move",
+
+            Self::Rename { .. } => "Rename",
+
+            Self::Copy { .. } => "Copy",
+
+            Self::Create { .. } => "Create",
+
+            Self::Batch(_) => "Run",
+        }
+    }
 }
 
 /// Info about a registered plugin.
@@ -118,6 +272,12 @@ impl AppState {
         task_tx: mpsc::UnboundedSender<TaskResult>,
         action_tx: mpsc::UnboundedSender<Action>,
     ) -> Self {
+        let sort_registry: SortRegistry = SortRegistry::from_config(&config.sort_comparators);
+
+        let watcher: Option<DirWatcher> = DirWatcher::spawn(action_tx.clone(), cache.clone())
+            .inspect_err(|e: &notify::Error| warn!("File watching disabled: {}", e))
+            .ok();
+
         Self {
             // Core Configuration and Services
             config,
@@ -131,10 +291,21 @@ impl AppState {
             task_tx,
             action_tx,
 
+            tabs: TabSet::default(),
+            file_op_tabs: HashMap::new(),
+            last_scan_id: 0,
+
             // Business Logic State
-            marked: HashSet::new(),
+            bookmarks: BookmarkStore::default(),
+            frecency: FrecencyStore::default(),
+            previews: PreviewStore::default(),
             history: VecDeque::new(),
+            redo_history: Vec::new(),
+            undo_running: None,
+            history_generation: 0,
             plugins: HashMap::new(),
+            sort_registry,
+            watcher,
             tasks: HashMap::new(),
             last_error: None,
             started_at: Instant::now(),
@@ -143,36 +314,435 @@ impl AppState {
 
     /// Mark a file or directory by its canonical path for batch operations.
     pub fn mark_entry(&mut self, path: impl Into<PathBuf>) {
-        self.marked.insert(path.into());
+        self.fs.marked.insert(path.into());
         
         self.ui.request_redraw(RedrawFlag::All); // Use UI state for redraw management
     }
 
     /// Unmark a previously marked entry.
     pub fn unmark_entry(&mut self, path: &Path) {
-        self.marked.remove(path);
+        self.fs.marked.remove(path);
         
         self.ui.request_redraw(RedrawFlag::All);
     }
 
     /// Clear all marked entries.
     pub fn clear_marks(&mut self) {
-        self.marked.clear();
+        self.fs.marked.clear();
         
         self.ui.request_redraw(RedrawFlag::All);
     }
 
-    /// Add a reversible event to the history stack (for undo/redo).
+    /// Toggle the mark of the selected entry and move on to the next one.
+    pub fn toggle_mark_selected(&mut self) {
+        let Some(path) = self.fs.active_pane().selected_entry().map(|e: &ObjectInfo| e.path.clone()) else {
+            return;
+        };
+
+        if !self.fs.marked.remove(&path) {
+            self.fs.marked.insert(path);
+        }
+
+        self.fs.active_pane_mut().move_selection_down();
+        self.ui.selected = self.fs.active_pane().selected;
+        self.ui.request_redraw(RedrawFlag::All);
+    }
+
+    /// Mark every entry listed in the active pane.
+    pub fn mark_all(&mut self) {
+        let paths: Vec<PathBuf> = self.active_entry_paths();
+        self.fs.marked.extend(paths);
+
+        self.ui.request_redraw(RedrawFlag::All);
+    }
+
+    /// Flip the marks of the entries listed in the active pane; marks in
+    /// other directories stay.
+    pub fn invert_marks(&mut self) {
+        for path in self.active_entry_paths() {
+            if !self.fs.marked.remove(&path) {
+                self.fs.marked.insert(path);
+            }
+        }
+
+        self.ui.request_redraw(RedrawFlag::All);
+    }
+
+    /// Marked paths, sorted. Entries inside a marked directory are left out:
+    /// whatever happens to the directory happens to them.
+    #[must_use]
+    pub fn marked_paths(&self) -> Vec<PathBuf> {
+        Self::outermost_paths(self.fs.marked.iter().cloned().collect())
+    }
+
+    /// What an operation on the selection acts on: the visual range while
+    /// one is open (marks elsewhere don't join in), otherwise the marks.
+    /// Listed in the order the active pane shows them, so batch renames
+    /// number entries the way the user sees them.
+    pub fn selection_paths(&mut self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = if self.ui.is_in_visual_mode() {
+            Self::outermost_paths(self.fs.active_pane_mut().visual_paths())
+        } else {
+            self.marked_paths()
+        };
+
+        self.fs.active_pane().sort_by_display_order(&mut paths);
+
+        paths
+    }
+
+    /// Whether an operation acts on a visual range or marks rather than on
+    /// the selected entry.
+    #[must_use]
+    pub fn has_selection(&self) -> bool {
+        self.ui.is_in_visual_mode() || !self.fs.marked.is_empty()
+    }
+
+    /// Drop the selection an operation was started on: the visual range
+    /// if one is open, otherwise the marks.
+    pub fn clear_selection(&mut self) {
+        if self.ui.is_in_visual_mode() {
+            self.fs.active_pane_mut().end_visual();
+            self.ui.exit_visual_mode();
+        } else {
+            self.clear_marks();
+        }
+    }
+
+    /// Sorted, without the paths that lie inside another one of them.
+    fn outermost_paths(mut candidates: Vec<PathBuf>) -> Vec<PathBuf> {
+        candidates.sort();
+
+        // Sorting by components puts every path right after its ancestors
+        let mut paths: Vec<PathBuf> = Vec::with_capacity(candidates.len());
+        for path in candidates {
+            if !paths.last().is_some_and(|kept: &PathBuf| -> bool { path.starts_with(kept) }) {
+                paths.push(path);
+            }
+        }
+
+        paths
+    }
+
+    /// How many of `paths` lie outside the active pane's directory, where
+    /// the user can't see them.
+    #[must_use]
+    pub fn count_outside_cwd<'a>(&self, paths: impl IntoIterator<Item = &'a PathBuf>) -> usize {
+        let cwd: &Path = &self.fs.active_pane().cwd;
+
+        paths
+            .into_iter()
+            .filter(|path: &&PathBuf| -> bool { !path.starts_with(cwd) })
+            .count()
+    }
+
+    /// Enter visual mode anchored at the selected entry, or leave it.
+    pub fn toggle_visual_mode(&mut self) {
+        if self.ui.is_in_visual_mode() {
+            self.fs.active_pane_mut().end_visual();
+            self.ui.exit_visual_mode();
+            return;
+        }
+
+        let pane: &mut PaneState = self.fs.active_pane_mut();
+        pane.start_visual();
+
+        if pane.visual_anchor.is_some() {
+            self.ui.visual_range = pane.visual_range();
+            self.ui.enter_visual_mode();
+        }
+    }
+
+    /// Mark every entry of the visual range and leave visual mode. Returns
+    /// how many entries were in the range.
+    pub fn mark_visual_range(&mut self) -> usize {
+        let paths: Vec<PathBuf> = self.fs.active_pane_mut().visual_paths();
+        let count: usize = paths.len();
+
+        self.fs.marked.extend(paths);
+        self.fs.active_pane_mut().end_visual();
+        self.ui.exit_visual_mode();
+
+        count
+    }
+
+    fn active_entry_paths(&self) -> Vec<PathBuf> {
+        self.fs
+            .active_pane()
+            .entries
+            .iter()
+            .map(|e: &ObjectInfo| -> PathBuf { e.path.clone() })
+            .collect()
+    }
+
+    /// Bookmark the active pane's directory. Without a name, the directory's
+    /// own name is used.
+    pub async fn add_bookmark(&mut self, name: Option<String>) {
+        let cwd: PathBuf = self.fs.active_pane().cwd.clone();
+
+        let name: String = name
+            .map(|n: String| -> String { n.trim().to_string() })
+            .filter(|n: &String| -> bool { !n.is_empty() })
+            .unwrap_or_else(|| {
+                cwd.file_name()
+                    .map_or_else(|| cwd.display().to_string(), |n| n.to_string_lossy().into_owned())
+            });
+
+        self.bookmarks.set(name.clone(), cwd.clone());
+        self.save_bookmarks().await;
+
+        self.show_success(format!("Bookmarked {} as '{name}'", cwd.display()));
+    }
+
+    /// Remove a bookmark by name.
+    pub async fn remove_bookmark(&mut self, name: &str) {
+        if self.bookmarks.remove(name).is_some() {
+            self.save_bookmarks().await;
+            self.show_success(format!("Removed bookmark '{name}'"));
+        } else {
+            self.show_warning(format!("No bookmark named '{name}'"));
+        }
+    }
+
+    /// Jump the active pane to a bookmark. Bookmarks whose target no longer
+    /// exists are flagged as missing instead of producing an error.
+    pub async fn jump_to_bookmark(&mut self, name: &str) {
+        let Some(path) = self.bookmarks.get(name).cloned() else {
+            self.show_warning(format!("No bookmark named '{name}'"));
+            return;
+        };
+
+        let is_dir: bool = tokio::fs::metadata(&path)
+            .await
+            .is_ok_and(|m: std::fs::Metadata| -> bool { m.is_dir() });
+
+        self.bookmarks.set_missing(name, !is_dir);
+
+        if is_dir {
+            info!("Jumping to bookmark '{}': {}", name, path.display());
+            self.enter_directory(path).await;
+        } else {
+            self.show_warning(format!(
+                "Bookmark '{name}' points to a missing directory: {}",
+                path.display()
+            ));
+        }
+    }
+
+    /// Open (or close) the bookmark overlay, re-checking bookmark targets.
+    pub async fn toggle_bookmarks_overlay(&mut self) {
+        if self.ui.overlay != UIOverlay::Bookmarks {
+            self.bookmarks.refresh_status().await;
+        }
+
+        self.ui.toggle_bookmarks_overlay();
+    }
+
+    async fn save_bookmarks(&mut self) {
+        if let Err(e) = self.bookmarks.save().await {
+            self.set_error(format!("Failed to save bookmarks: {e}"));
+        }
+    }
+
+    /// Jump to the best frecency match for `query` (space-separated
+    /// fragments). Candidates that no longer exist are forgotten and the next
+    /// best one is tried.
+    pub async fn jump_to_frecent(&mut self, query: &str) {
+        let candidates: Vec<PathBuf> = self
+            .frecency
+            .ranked(query)
+            .into_iter()
+            .map(|(_, e): (f64, &FrecencyEntry)| -> PathBuf { e.path.clone() })
+            .filter(|p: &PathBuf| -> bool { *p != self.fs.active_pane().cwd })
+            .collect();
+
+        let mut pruned: bool = false;
+
+        for path in candidates {
+            let is_dir: bool = tokio::fs::metadata(&path)
+                .await
+                .is_ok_and(|m: std::fs::Metadata| -> bool { m.is_dir() });
+
+            if is_dir {
+                if pruned {
+                    self.frecency.save_in_background();
+                }
+
+                info!("Frecency jump '{}' -> {}", query, path.display());
+                self.enter_directory(path).await;
+                return;
+            }
+
+            debug!("Forgetting missing frecent directory: {}", path.display());
+            self.frecency.remove(&path);
+            pruned = true;
+        }
+
+        if pruned {
+            self.frecency.save_in_background();
+        }
+
+        self.show_warning(format!("No frecent directory matches '{query}'"));
+    }
+
+    /// Jump to a specific frecent directory picked from the overlay.
+    pub async fn jump_to_frecent_path(&mut self, path: PathBuf) {
+        let is_dir: bool = tokio::fs::metadata(&path)
+            .await
+            .is_ok_and(|m: std::fs::Metadata| -> bool { m.is_dir() });
+
+        if is_dir {
+            self.enter_directory(path).await;
+        } else {
+            self.frecency.remove(&path);
+            self.frecency.save_in_background();
+            self.show_warning(format!("Directory no longer exists: {}", path.display()));
+        }
+    }
+
+    /// Add a reversible event to the history stack (for undo/redo). A new
+    /// operation makes the undone ones unreachable, so they are dropped.
     pub fn push_history(&mut self, event: AppHistoryEvent) {
+        self.redo_history.clear();
+        self.history_generation += 1;
+        self.remember(event);
+    }
+
+    /// Put `event` into the history below its newest `newer` events, which
+    /// were recorded while an undo or redo run was in flight.
+    fn remember_below(&mut self, event: AppHistoryEvent, newer: usize) {
+        let at: usize = self.history.len().saturating_sub(newer);
+        self.history.insert(at, event);
+
+        if self.history.len() > MAX_HISTORY {
+            self.history.pop_front();
+        }
+    }
+
+    fn remember(&mut self, event: AppHistoryEvent) {
         self.history.push_back(event);
         
-        if self.history.len() > 128 {
+        if self.history.len() > MAX_HISTORY {
             self.history.pop_front();
         }
         
         self.ui.request_redraw(RedrawFlag::All);
     }
 
+    /// Undo the last `steps` operations, newest first, on a background
+    /// task; `finish_undo` takes the result. An operation that can no
+    /// longer be undone safely stops the run and stays in the history.
+    pub fn undo(&mut self, steps: usize) {
+        if self.undo_running.is_some() {
+            self.set_status("Still undoing or redoing, try again when it is done");
+            return;
+        }
+
+        if self.history.is_empty() {
+            self.set_status("Nothing to undo");
+            return;
+        }
+
+        let count: usize = steps.min(self.history.len());
+        let events: Vec<AppHistoryEvent> = (0..count).filter_map(|_| self.history.pop_back()).collect();
+
+        self.spawn_undo(undo::Direction::Undo, events);
+    }
+
+    /// Redo the last `steps` undone operations, like `undo`.
+    pub fn redo(&mut self, steps: usize) {
+        if self.undo_running.is_some() {
+            self.set_status("Still undoing or redoing, try again when it is done");
+            return;
+        }
+
+        if self.redo_history.is_empty() {
+            self.set_status("Nothing to redo");
+            return;
+        }
+
+        let count: usize = steps.min(self.redo_history.len());
+        let events: Vec<AppHistoryEvent> = (0..count).filter_map(|_| self.redo_history.pop()).collect();
+
+        self.spawn_undo(undo::Direction::Redo, events);
+    }
+
+    fn spawn_undo(&mut self, direction: undo::Direction, events: Vec<AppHistoryEvent>) {
+        self.undo_running = Some(self.history_generation);
+
+        if let [event] = events.as_slice() {
+            self.ui.show_info(format!("Running {} of {}...", direction.verb(), event.label()));
+        } else {
+            self.ui.show_info(format!("Running {} of {} operations...", direction.verb(), events.len()));
+        }
+
+        let action_tx: mpsc::UnboundedSender<Action> = self.action_tx.clone();
+
+        tokio::spawn(async move {
+            let outcome: undo::Outcome = undo::run(direction, events).await;
+            let _ = action_tx.send(Action::UndoFinished(outcome));
+        });
+
+        self.ui.request_redraw(RedrawFlag::All);
+    }
+
+    /// Take the result of an undo or redo run: applied steps go to the
+    /// other stack, refused ones back where they came from so they can be
+    /// retried once whatever blocked them is out of the way. Operations
+    /// recorded while the run was in flight stay newest, and since they
+    /// cleared the redo stack, nothing is put back on it.
+    pub async fn finish_undo(&mut self, outcome: undo::Outcome) {
+        let started: u64 = self.undo_running.take().unwrap_or(self.history_generation);
+        let newer: usize = usize::try_from(self.history_generation - started).unwrap_or(usize::MAX);
+
+        if newer > 0 {
+            info!("{} operation(s) recorded during the {} run", newer, outcome.direction.verb());
+        }
+
+        let applied: usize = outcome.applied.len();
+
+        for (label, event) in outcome.applied {
+            match outcome.direction {
+                undo::Direction::Undo => {
+                    self.show_success(format!("Undone: {label}"));
+
+                    if newer == 0 {
+                        self.redo_history.push(event);
+                    }
+                }
+
+                undo::Direction::Redo => {
+                    self.show_success(format!("Redone: {label}"));
+                    self.remember_below(event, newer);
+                }
+            }
+        }
+
+        // Popped newest first, so the first one goes back on top
+        for event in outcome.remaining.into_iter().rev() {
+            match outcome.direction {
+                undo::Direction::Undo => self.remember_below(event, newer),
+
+                undo::Direction::Redo if newer == 0 => self.redo_history.push(event),
+
+                undo::Direction::Redo => {}
+            }
+        }
+
+        if let Some(e) = outcome.error {
+            warn!("{} refused: {}", outcome.direction.verb(), e);
+            self.set_error(format!("Cannot {}: {e} (kept in history)", outcome.direction.verb()));
+        }
+
+        if applied > 0 {
+            self.reload_directory().await;
+        }
+
+        let rows: usize = self.redo_history.len() + self.history.len();
+        self.ui.overlay_selected = self.redo_history.len().min(rows.saturating_sub(1));
+        self.ui.request_redraw(RedrawFlag::All);
+    }
+
     /// Register a plugin for later use.
     pub fn register_plugin(&mut self, info: PluginInfo) {
         self.plugins.insert(info.name.clone(), info);
@@ -180,6 +750,15 @@ impl AppState {
         self.ui.request_redraw(RedrawFlag::All);
     }
 
+    /// Register a named comparator (e.g. from a plugin) for `:sort <name>`.
+    /// A comparator with the same name is replaced.
+    pub fn register_sort_comparator(&mut self, name: impl Into<String>, comparator: Comparator) {
+        let name: String = name.into();
+        info!("Registering sort comparator '{}'", name);
+
+        self.sort_registry.register(name, comparator);
+    }
+
     /// Add or update a running/pending async task.
     pub fn add_task(&mut self, task: TaskInfo) {
         info!("Adding task: {}", task.description);
@@ -249,12 +828,63 @@ impl AppState {
     }
 
     /// Navigate to a new directory, updating the active pane.
+    ///
+    /// The directory being left is pushed onto the pane's back stack, and the
+    /// selection previously left in the target directory is restored.
     pub async fn enter_directory(&mut self, path: PathBuf) {
         info!("Entering directory: {}", path.display());
         
+        let canonical_path: PathBuf = match tokio::fs::canonicalize(&path).await {
+            Ok(p) => p,
+        
+            Err(e) => {
+                self.set_error(format!("Invalid path: {}: {}", path.display(), e));
+                self.ui.request_redraw(RedrawFlag::All);
+        
+                return;
+            }
+        };
+
+        let current_pane: &mut PaneState = self.fs.active_pane_mut();
+
+        let restore: Option<HistoryEntry> = if current_pane.cwd == canonical_path {
+            // Reloading keeps the current position
+            Some(current_pane.current_position())
+        } else {
+            let previous_cwd: PathBuf = current_pane.cwd.clone();
+            current_pane.push_history();
+
+            let restore: Option<HistoryEntry> = current_pane
+                .remembered_position(&canonical_path)
+                .or_else(|| Self::child_position(&canonical_path, &previous_cwd));
+
+            self.frecency.record_visit(&canonical_path);
+            self.frecency.save_in_background();
+
+            restore
+        };
+
+        self.open_directory(canonical_path, restore).await;
+    }
+
+    /// When moving up the tree, select the child we came from.
+    fn child_position(parent: &Path, previous_cwd: &Path) -> Option<HistoryEntry> {
+        let child = previous_cwd.strip_prefix(parent).ok()?.components().next()?;
+
+        Some(HistoryEntry {
+            path: parent.to_path_buf(),
+            selected_path: Some(parent.join(child)),
+            selected: None,
+            scroll_offset: 0,
+        })
+    }
+
+    /// Load an already canonical directory into the active pane without
+    /// touching the history stacks.
+    async fn open_directory(&mut self, canonical_path: PathBuf, restore: Option<HistoryEntry>) {
         // Pre-warm cache before directory change for better performance
         let warm_start = std::time::Instant::now();
-        let warmed_count = self.cache.warm_for_navigation(&path).await
+        let warmed_count = self.cache.warm_for_navigation(&canonical_path).await
             .unwrap_or_else(|e| {
                 tracing::warn!("Navigation cache warming failed: {}", e);
                 0
@@ -267,30 +897,246 @@ impl AppState {
                 operation_type = "navigation_pre_warming",
                 warmed_count = warmed_count,
                 warm_duration_us = warm_duration.as_micros(),
-                target_path = %path.display(),
+                target_path = %canonical_path.display(),
                 "Pre-navigation cache warming completed"
             );
         }
+
+        // Side columns are reloaded from the freshly warmed cache
+        self.previews.clear();
+
+        let current_pane: &mut PaneState = self.fs.active_pane_mut();
+
+        // A filter belongs to the listing it was typed in
+        if current_pane.cwd != canonical_path {
+            current_pane.reset_filter();
+        }
+
+        current_pane.cwd.clone_from(&canonical_path);
+        current_pane.is_loading = true;
+        current_pane.pending_restore = restore;
         
-        let canonical_path: PathBuf = match tokio::fs::canonicalize(&path).await {
-            Ok(p) => p,
-        
-            Err(e) => {
-                self.set_error(format!("Invalid path: {}: {}", path.display(), e));
-                self.ui.request_redraw(RedrawFlag::All);
-        
-                return;
-            }
+        self.ui.request_redraw(RedrawFlag::All);
+
+        // Use streaming directory scan for better responsiveness
+        self.enter_directory_streaming(canonical_path).await;
+    }
+
+    /// Go back to the previous directory in the active pane's history.
+    pub async fn navigate_back(&mut self) {
+        let back_len: usize = self.fs.active_pane().back_stack.len();
+
+        if back_len == 0 {
+            self.set_status("No previous directory in history.");
+            return;
+        }
+
+        self.jump_to_history(back_len - 1).await;
+    }
+
+    /// Go forward to the next directory in the active pane's history.
+    pub async fn navigate_forward(&mut self) {
+        let pane: &PaneState = self.fs.active_pane();
+
+        if pane.forward_stack.is_empty() {
+            self.set_status("No next directory in history.");
+            return;
+        }
+
+        let index: usize = pane.back_stack.len() + 1;
+        self.jump_to_history(index).await;
+    }
+
+    /// Jump to an entry of the active pane's history (as listed by
+    /// `PaneState::history_entries`), restoring its selection and scroll.
+    pub async fn jump_to_history(&mut self, index: usize) {
+        let target_path: Option<PathBuf> = self
+            .fs
+            .active_pane()
+            .history_entries()
+            .get(index)
+            .map(|(_, path): &(HistoryPosition, &Path)| -> PathBuf { path.to_path_buf() });
+
+        let Some(target_path) = target_path else {
+            return;
         };
 
-        let current_pane: &mut PaneState = self.fs.active_pane_mut();
-        current_pane.cwd.clone_from(&canonical_path);
-        current_pane.is_loading = true;
-        
+        let is_dir: bool = tokio::fs::metadata(&target_path)
+            .await
+            .is_ok_and(|m: std::fs::Metadata| -> bool { m.is_dir() });
+
+        if !is_dir {
+            self.fs.active_pane_mut().forget_history(index);
+            self.show_warning(format!(
+                "Directory no longer exists, removed from history: {}",
+                target_path.display()
+            ));
+            return;
+        }
+
+        if let Some(target) = self.fs.active_pane_mut().history_jump(index) {
+            info!("History jump to {}", target.path.display());
+
+            self.open_directory(target.path.clone(), Some(target)).await;
+        }
+    }
+
+    /// Open a second pane next to the active one, or close it if already open.
+    pub fn toggle_dual_pane(&mut self) {
+        if self.fs.is_dual_pane() {
+            self.fs.close_inactive_pane();
+            info!("Closed second pane");
+        } else {
+            self.fs.open_second_pane();
+            info!("Opened second pane at {}", self.fs.active_pane().cwd.display());
+        }
+
+        self.sync_active_pane();
+    }
+
+    /// Move keyboard focus to the other pane.
+    pub fn switch_pane(&mut self) {
+        if !self.fs.is_dual_pane() {
+            return;
+        }
+
+        self.fs.focus_next_pane();
+        debug!("Focus moved to pane {}", self.fs.active_pane);
+
+        self.sync_active_pane();
+    }
+
+    /// Focus pane `idx` (e.g. a clicked pane).
+    pub fn focus_pane(&mut self, idx: usize) {
+        if idx == self.fs.active_pane || idx >= self.fs.panes.len() {
+            return;
+        }
+
+        self.fs.set_active_pane(idx);
+        debug!("Focus moved to pane {}", self.fs.active_pane);
+
+        self.sync_active_pane();
+    }
+
+    /// Mirror the focused pane's index and selection into the UI state.
+    fn sync_active_pane(&mut self) {
+        let active: usize = self.fs.active_pane;
+        let selected: Option<usize> = self.fs.active_pane().selected;
+
+        self.ui.set_active_pane(active);
+        self.ui.set_selected(selected);
+    }
+
+    /// Open a new tab in the focused pane's directory and switch to it.
+    pub async fn open_tab(&mut self) {
+        let cwd: PathBuf = self.fs.active_pane().cwd.clone();
+        let mut fs: FSState = FSState::new(cwd.clone());
+        fs.active_pane_mut().sort_config = self.fs.active_pane().sort_config;
+
+        let index: usize = self.tabs.insert_after_active(fs);
+
+        info!("Opening tab {} at {}", index + 1, cwd.display());
+
+        self.switch_tab(index).await;
+        self.open_directory(cwd, None).await;
+    }
+
+    /// Close the active tab. The last tab cannot be closed.
+    pub async fn close_tab(&mut self) {
+        if self.tabs.len() <= 1 {
+            self.show_warning("Cannot close the last tab".to_string());
+            return;
+        }
+
+        let closing: usize = self.tabs.active;
+        let target: usize = if closing + 1 < self.tabs.len() {
+            closing + 1
+        } else {
+            closing - 1
+        };
+
+        self.switch_tab(target).await;
+
+        let closed: Tab = self.tabs.tabs.remove(closing);
+        if self.tabs.active > closing {
+            self.tabs.active -= 1;
+        }
+
+        // Operations started there finish without a tab to report to
+        self.file_op_tabs
+            .retain(|_, tab: &mut TabId| -> bool { *tab != closed.id });
+
+        info!("Closed tab {}", closed.id);
+    }
+
+    /// Activate the tab at `index`, swapping its state into `fs`/`ui`.
+    pub async fn switch_tab(&mut self, index: usize) {
+        if index >= self.tabs.len() || index == self.tabs.active {
+            return;
+        }
+
+        let leaving: &mut Tab = self.tabs.active_tab_mut();
+        std::mem::swap(&mut leaving.fs, &mut self.fs);
+        leaving.ui = TabUiState::take_from(&mut self.ui);
+
+        self.tabs.active = index;
+
+        let entering: &mut Tab = self.tabs.active_tab_mut();
+        std::mem::swap(&mut entering.fs, &mut self.fs);
+        std::mem::take(&mut entering.ui).restore_into(&mut self.ui);
+        let needs_reload: bool = std::mem::take(&mut entering.needs_reload);
+
+        debug!("Switched to tab {} ({})", index + 1, self.tabs.active_id());
+
+        self.sync_active_pane();
+        self.ui.request_redraw(RedrawFlag::All);
+
+        if needs_reload {
+            self.reload_directory().await;
+        }
+    }
+
+    /// Cycle `delta` tabs forward (negative: backward), wrapping around.
+    pub async fn cycle_tab(&mut self, delta: isize) {
+        let index: usize = self.tabs.cycled_index(delta);
+        self.switch_tab(index).await;
+    }
+
+    /// Move the active tab `delta` positions within the tab bar.
+    pub fn move_tab(&mut self, delta: isize) {
+        if self.tabs.move_active(delta) {
+            self.ui.request_redraw(RedrawFlag::All);
+        }
+    }
+
+    /// Name the active tab; an empty name reverts to the directory name.
+    pub fn rename_tab(&mut self, name: &str) {
+        let name: &str = name.trim();
+
+        self.tabs.active_tab_mut().name = (!name.is_empty()).then(|| name.to_string());
         self.ui.request_redraw(RedrawFlag::All);
+    }
 
-        // Use streaming directory scan for better responsiveness
-        self.enter_directory_streaming(canonical_path).await;
+    /// Title of the tab at `index`.
+    #[must_use]
+    pub fn tab_title(&self, index: usize) -> String {
+        let tab: &Tab = &self.tabs.tabs[index];
+
+        if index == self.tabs.active {
+            tab.title(&self.fs)
+        } else {
+            tab.title(&tab.fs)
+        }
+    }
+
+    /// Filesystem state of a tab: `fs` for the active tab, the stored state
+    /// for a background tab, `None` if the tab was closed.
+    pub fn fs_for_tab_mut(&mut self, tab: TabId) -> Option<&mut FSState> {
+        if tab == self.tabs.active_id() {
+            Some(&mut self.fs)
+        } else {
+            self.tabs.background_mut(tab).map(|t: &mut Tab| -> &mut FSState { &mut t.fs })
+        }
     }
 
     /// Go to the parent directory of the current active pane.
@@ -318,8 +1164,23 @@ impl AppState {
 
     /// Enter directory using streaming scan for better responsiveness
     async fn enter_directory_streaming(&mut self, path: PathBuf) {
+        let tab: TabId = self.tabs.active_id();
+        let scan: u64 = self.spawn_directory_scan(tab, path).await;
+
         let current_pane: &mut PaneState = self.fs.active_pane_mut();
-        current_pane.start_incremental_loading();
+        current_pane.start_incremental_loading(scan);
+
+        self.ui.request_redraw(RedrawFlag::All);
+    }
+
+    /// Start a streaming scan of `path` whose updates are delivered as
+    /// `Action::DirectoryScanUpdate` tagged with `tab` and the returned scan
+    /// id, which tells them apart from those of an earlier scan of `path`.
+    pub async fn spawn_directory_scan(&mut self, tab: TabId, path: PathBuf) -> u64 {
+        self.last_scan_id += 1;
+        let scan: u64 = self.last_scan_id;
+
+        self.sync_watches();
 
         let (
             mut rx,
@@ -327,7 +1188,7 @@ impl AppState {
         ) = scan_dir_streaming_with_background_metadata(
             path.clone(),
             self.ui.show_hidden,
-            10, // Batch size for yielding
+            SCAN_CHUNK_SIZE,
             self.action_tx.clone(),
             self.config.profiling.clone(),
             self.cache.clone()
@@ -343,6 +1204,8 @@ impl AppState {
             async move {
                 while let Some(update) = rx.recv().await {
                     let _ = action_tx.send(Action::DirectoryScanUpdate {
+                        tab,
+                        scan,
                         path: scan_path.clone(),
                         update,
                     });
@@ -355,6 +1218,110 @@ impl AppState {
             )),
         );
 
+        scan
+    }
+
+    /// Switch the active pane between flat list and tree view.
+    pub fn toggle_tree_view(&mut self) {
+        let pane: &mut PaneState = self.fs.active_pane_mut();
+        pane.toggle_tree();
+
+        let enabled: bool = pane.is_tree_view();
+        self.ui.selected = pane.selected;
+
+        self.set_status(if enabled { "Tree view" } else { "List view" });
+        self.ui.request_redraw(RedrawFlag::All);
+    }
+
+    /// Expand the selected directory in tree view, scanning its children the
+    /// first time it is opened.
+    pub async fn expand_tree_selection(&mut self) {
+        let pane: &mut PaneState = self.fs.active_pane_mut();
+
+        if !pane.is_tree_view() {
+            return;
+        }
+
+        let Some(dir) = pane
+            .selected_entry()
+            .filter(|e: &&ObjectInfo| -> bool { e.is_dir })
+            .map(|e: &ObjectInfo| -> PathBuf { e.path.clone() })
+        else {
+            return;
+        };
+
+        if pane.expand_tree_dir(&dir) {
+            debug!("Loading tree children of {}", dir.display());
+
+            let tab: TabId = self.tabs.active_id();
+            self.spawn_directory_scan(tab, dir).await;
+        }
+
+        self.ui.selected = self.fs.active_pane().selected;
+        self.ui.request_redraw(RedrawFlag::All);
+    }
+
+    /// Collapse the selected directory in tree view, or move to its parent.
+    pub fn collapse_tree_selection(&mut self) {
+        let pane: &mut PaneState = self.fs.active_pane_mut();
+
+        if pane.is_tree_view() && pane.collapse_tree_selection() {
+            self.ui.selected = self.fs.active_pane().selected;
+            self.ui.request_redraw(RedrawFlag::All);
+        }
+    }
+
+    /// Switch the main view layout.
+    pub fn set_layout(&mut self, layout: LayoutMode) {
+        self.ui.layout = layout;
+        self.refresh_previews();
+
+        self.set_status(format!("Layout: {layout}"));
+        self.ui.request_redraw(RedrawFlag::All);
+    }
+
+    /// Toggle between the table and Miller layouts.
+    pub fn toggle_layout(&mut self) {
+        let layout: LayoutMode = match self.ui.layout {
+            LayoutMode::Table => LayoutMode::Miller,
+
+            LayoutMode::Miller => LayoutMode::Table,
+        };
+
+        self.set_layout(layout);
+    }
+
+    /// Request the side columns of the Miller layout for every pane: the
+    /// parent directory listing and the selected entry's preview. Already
+    /// loaded or in-flight paths are skipped, so this is cheap to call after
+    /// every action.
+    pub fn refresh_previews(&mut self) {
+        if self.ui.layout != LayoutMode::Miller {
+            return;
+        }
+
+        let mut wanted: Vec<(PathBuf, bool)> = Vec::new();
+
+        for pane in &self.fs.panes {
+            if let Some(parent) = pane.cwd.parent() {
+                wanted.push((parent.to_path_buf(), true));
+            }
+
+            if let Some(entry) = pane.selected_entry() {
+                wanted.push((entry.path.clone(), entry.is_dir));
+            }
+        }
+
+        for (path, is_dir) in wanted {
+            if self.previews.request(&path) {
+                preview_task::preview_task(path, is_dir, self.action_tx.clone(), self.cache.clone());
+            }
+        }
+    }
+
+    /// Store a preview loaded in the background.
+    pub fn set_preview(&mut self, path: PathBuf, preview: Preview) {
+        self.previews.insert(path, preview);
         self.ui.request_redraw(RedrawFlag::All);
     }
 
@@ -362,7 +1329,7 @@ impl AppState {
     pub async fn enter_selected_directory(&mut self) {
         let active_pane: PaneState = self.fs.active_pane().clone();
 
-        if let Some(selected_idx) = self.ui.selected
+        if let Some(selected_idx) = active_pane.selected
             && let Some(selected_entry) = active_pane.entries.get(selected_idx)
         {
             let path: &PathBuf = &selected_entry.path;
@@ -380,16 +1347,15 @@ impl AppState {
 
     /// Open a file with external editor (VS Code)
     pub async fn open_file_with_editor(&mut self, file_path: std::path::PathBuf) {
-        let path_str: String = file_path.to_string_lossy().to_string();
-        let open_result: Result<Result<String, String>, JoinError> = tokio::spawn(
+        let open_result: Result<Result<PathBuf, String>, JoinError> = tokio::spawn(
             async move 
             {
                 let mut cmd: Command = Command::new("code");
-                cmd.arg(&path_str);
+                cmd.arg(&file_path);
                 
                 match cmd.spawn() 
                 {
-                    Ok(_) => Ok(path_str),
+                    Ok(_) => Ok(file_path),
                 
                     Err(e) => Err(format!("Failed to open file with code: {e}")),
                 }
@@ -401,10 +1367,8 @@ impl AppState {
             Ok(Ok(path)) => {
                 self.show_success(format!(
                     "Opened {} in VS Code",
-                    std::path::Path::new(&path)
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("file")
+                    path.file_name()
+                        .map_or_else(|| "file".into(), |n| n.to_string_lossy())
                 ));
             }
 
@@ -418,37 +1382,70 @@ impl AppState {
         }
     }
 
-    pub async fn delete_entry(&mut self) {
-        let active_pane: PaneState = self.fs.active_pane().clone();
+    /// Open or close the trash overlay, listing every trash directory.
+    pub async fn toggle_trash_overlay(&mut self) {
+        let entries: Vec<TrashEntry> = if self.ui.overlay == UIOverlay::Trash {
+            Vec::new()
+        } else {
+            trash::list().await
+        };
 
-        if let Some(selected_idx) = self.ui.selected
-            && let Some(selected_entry) = active_pane.entries.get(selected_idx)
-        {
-            let path: &PathBuf = &selected_entry.path;
+        self.ui.toggle_trash_overlay(entries);
+    }
 
-            let result: Result<(), Error> = if selected_entry.is_dir {
-                tokio::fs::remove_dir_all(path).await
-            } else {
-                tokio::fs::remove_file(path).await
-            };
+    /// Restore the trashed entry described by `info_path` to where it came from.
+    pub async fn restore_trashed(&mut self, info_path: &Path) {
+        let Some(entry) = self.trash_entry(info_path) else {
+            return;
+        };
 
-            if let Err(e) = result {
-                self.set_error(format!("Failed to delete {}: {}", path.display(), e));
-            } else {
-                self.show_success(format!("Deleted {}", path.display()));
-                self.reload_directory().await;
+        match trash::restore(&entry).await {
+            Ok(path) => {
+                self.show_success(format!("Restored {}", path.display()));
+
+                if path.parent() == Some(self.fs.active_pane().cwd.as_path()) {
+                    self.reload_directory().await;
+                }
             }
+
+            Err(e) => self.set_error(format!("Failed to restore {}: {e}", entry.original_path.display())),
+        }
+
+        self.ui.set_trash_entries(trash::list().await);
+    }
+
+    /// Remove the trashed entry described by `info_path` for good.
+    pub async fn purge_trashed(&mut self, info_path: &Path) {
+        let Some(entry) = self.trash_entry(info_path) else {
+            return;
+        };
+
+        match trash::purge(&entry).await {
+            Ok(()) => self.show_success(format!("Purged {}", entry.original_path.display())),
+
+            Err(e) => self.set_error(format!("Failed to purge {}: {e}", entry.original_path.display())),
         }
+
+        self.ui.set_trash_entries(trash::list().await);
+    }
+
+    fn trash_entry(&self, info_path: &Path) -> Option<TrashEntry> {
+        self.ui
+            .trash_entries
+            .iter()
+            .find(|entry: &&TrashEntry| entry.info_path == info_path)
+            .cloned()
     }
 
     pub async fn create_file(&mut self) {
         let active_pane: PaneState = self.fs.active_pane().clone();
         let new_file_path: PathBuf = active_pane.cwd.join("new_file.txt");
 
-        if let Err(e) = tokio::fs::File::create(&new_file_path).await {
+        if let Err(e) = Self::create_new_file(&new_file_path).await {
             self.set_error(format!("Failed to create file: {e}"));
         } else {
             self.show_success(format!("Created file: {}", new_file_path.display()));
+            self.record_create(new_file_path, false).await;
             self.reload_directory().await;
         }
     }
@@ -461,6 +1458,7 @@ impl AppState {
             self.set_error(format!("Failed to create directory: {e}"));
         } else {
             self.show_success(format!("Created directory: {}", new_dir_path.display()));
+            self.record_create(new_dir_path, true).await;
             self.reload_directory().await;
         }
     }
@@ -469,10 +1467,11 @@ impl AppState {
         let active_pane: PaneState = self.fs.active_pane().clone();
         let new_file_path: PathBuf = active_pane.cwd.join(&name);
 
-        if let Err(e) = tokio::fs::File::create(&new_file_path).await {
+        if let Err(e) = Self::create_new_file(&new_file_path).await {
             self.set_error(format!("Failed to create file '{name}': {e}"));
         } else {
             self.show_success(format!("Created file: {name}"));
+            self.record_create(new_file_path, false).await;
             self.reload_directory().await;
         }
     }
@@ -485,37 +1484,72 @@ impl AppState {
             self.set_error(format!("Failed to create directory '{name}': {e}"));
         } else {
             self.show_success(format!("Created directory: {name}"));
+            self.record_create(new_dir_path, true).await;
             self.reload_directory().await;
         }
     }
 
+    /// Create an empty file, failing if `path` exists: truncating an
+    /// existing file could not be undone.
+    async fn create_new_file(path: &Path) -> Result<tokio::fs::File, Error> {
+        tokio::fs::OpenOptions::new().write(true).create_new(true).open(path).await
+    }
+
+    async fn record_create(&mut self, path: PathBuf, is_dir: bool) {
+        let fingerprint: Option<u64> = undo::fingerprint(&path).await;
+
+        self.push_history(AppHistoryEvent::Create { path, is_dir, fingerprint, trashed: None });
+    }
+
     /// Rename the currently selected entry
     pub async fn rename_selected_entry(&mut self, new_name: String) {
         let active_pane: &PaneState = self.fs.active_pane();
-        if let Some(selected_idx) = self.ui.selected
+        if let Some(selected_idx) = active_pane.selected
             && let Some(selected_entry) = active_pane.entries.get(selected_idx)
         {
-            let old_path: &PathBuf = &selected_entry.path;
+            let old_path: PathBuf = selected_entry.path.clone();
             let parent_dir: &Path = old_path.parent().unwrap_or(&active_pane.cwd);
             let new_path: PathBuf = parent_dir.join(&new_name);
 
-            if let Err(e) = tokio::fs::rename(old_path, &new_path).await {
-                self.set_error(format!("Failed to rename to '{new_name}': {e}"));
-            } else {
-                self.show_success(format!("Renamed to '{new_name}'"));
-                self.reload_directory().await;
+            // Anything else would land outside the directory, or nowhere
+            if !is_valid_name(OsStr::new(&new_name)) {
+                self.set_error(format!(
+                    "Cannot rename to '{new_name}': names cannot be empty, '.' or '..', or contain a path separator"
+                ));
+                return;
+            }
+
+            if new_path == old_path {
+                return;
+            }
+
+            // A rename replaces an existing target, which undo could not bring back
+            match rename_no_replace(&old_path, &new_path).await {
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    self.set_error(format!("Failed to rename to '{new_name}': it already exists"));
+                }
+
+                Err(e) => self.set_error(format!("Failed to rename to '{new_name}': {e}")),
+
+                Ok(()) => {
+                    self.show_success(format!("Renamed to '{new_name}'"));
+                    self.push_history(AppHistoryEvent::Rename { from: old_path, to: new_path });
+                    self.reload_directory().await;
+                }
             }
         } else {
             self.set_error("No entry selected for renaming".to_string());
         }
     }
 
-    /// Navigate to the specified path
-    pub async fn navigate_to_path(&mut self, path_str: String) {
-        let path: PathBuf = PathBuf::from(path_str.trim());
+    /// A path typed by the user: `~` is the home directory and relative
+    /// paths start from the active pane's directory.
+    #[must_use]
+    pub fn resolve_input_path(&self, input: &str) -> PathBuf {
+        let path: PathBuf = PathBuf::from(input.trim());
 
         // Expand tilde for home directory
-        let expanded_path: PathBuf = if path.starts_with("~") {
+        if path.starts_with("~") {
             if let Some(home) = directories::UserDirs::new().map(|u| u.home_dir().to_path_buf()) {
                 home.join(path.strip_prefix("~").unwrap_or(&path))
             } else {
@@ -526,7 +1560,12 @@ impl AppState {
             self.fs.active_pane().cwd.join(path)
         } else {
             path
-        };
+        }
+    }
+
+    /// Navigate to the specified path
+    pub async fn navigate_to_path(&mut self, path_str: String) {
+        let expanded_path: PathBuf = self.resolve_input_path(&path_str);
 
         if expanded_path.exists() {
             if expanded_path.is_dir() {
@@ -630,6 +1669,7 @@ impl AppState {
 
         FilenameSearchTask::filename_search_task(
             task_id,
+            self.tabs.active_id(),
             trimmed_pattern.to_string(),
             current_dir,
             self.task_tx.clone(),
@@ -672,32 +1712,29 @@ impl AppState {
         let task_tx = self.task_tx.clone();
         let action_tx = self.action_tx.clone();
 
-        crate::tasks::search_task::search_task(task_id, pattern, path, task_tx, action_tx);
+        let tab: TabId = self.tabs.active_id();
+
+        crate::tasks::search_task::search_task(task_id, tab, pattern, path, task_tx, action_tx);
     }
 
-    /// Updates an `ObjectInfo` in the active pane with new data from a background task.
-    pub fn update_object_info(&mut self, parent_dir: &PathBuf, info: &ObjectInfo) {
-        if let Some(pane) = self
-            .fs
-            .panes
-            .iter_mut()
-            .find(|p: &&mut PaneState| -> bool 
+    /// Updates an `ObjectInfo` in every pane (of every tab) showing
+    /// `parent_dir` with new data from a background task.
+    pub fn update_object_info(&mut self, parent_dir: &Path, info: &ObjectInfo) {
+        let mut updated: bool = false;
+
+        for pane in std::iter::once(&mut self.fs)
+            .chain(self.tabs.background_fs_mut())
+            .flat_map(|fs: &mut FSState| -> &mut Vec<PaneState> { &mut fs.panes })
+            .filter(|p: &&mut PaneState| -> bool 
                 {
-                    &p.cwd == parent_dir
+                    p.shows_dir(parent_dir)
                 }
             )
-        && let Some(entry) = pane
-            .entries
-            .iter_mut()
-            .find(
-                |e: &&mut ObjectInfo| -> bool 
-                {e.path == info.path})
         {
-            entry.size = info.size;
-            entry.items_count = info.items_count;
-            entry.modified = info.modified;
-            entry.metadata_loaded = info.metadata_loaded;
-            
+            updated |= pane.update_entry(parent_dir, info);
+        }
+
+        if updated {
             debug!(
                 "Updating object info for {}: modified = {}",
                 info.path.display(),
@@ -708,8 +1745,55 @@ impl AppState {
         }
     }
 
+    /// Watch the directory of every pane in every tab, and nothing else.
+    pub fn sync_watches(&self) {
+        let Some(watcher) = self.watcher.as_ref() else {
+            return;
+        };
+
+        let dirs = std::iter::once(&self.fs)
+            .chain(self.tabs.background_fs())
+            .flat_map(|fs: &FSState| -> &Vec<PaneState> { &fs.panes })
+            .map(|p: &PaneState| -> PathBuf { p.cwd.clone() });
+
+        watcher.sync(dirs);
+    }
+
+    /// Patch every pane showing `dir` with watcher changes. Returns the
+    /// changed directories, whose sizes need recalculating.
+    pub fn apply_fs_changes(&mut self, dir: &Path, changes: &[FsChange]) -> Vec<ObjectInfo> {
+        let show_hidden: bool = self.ui.show_hidden;
+        let mut updated: bool = false;
+
+        for pane in std::iter::once(&mut self.fs)
+            .chain(self.tabs.background_fs_mut())
+            .flat_map(|fs: &mut FSState| -> &mut Vec<PaneState> { &mut fs.panes })
+            .filter(|p: &&mut PaneState| -> bool { p.shows_dir(dir) })
+        {
+            updated |= pane.apply_fs_changes(dir, changes, show_hidden);
+        }
+
+        if !updated {
+            return Vec::new();
+        }
+
+        self.ui.selected = self.fs.active_pane().selected;
+        self.ui.request_redraw(RedrawFlag::All);
+
+        changes
+            .iter()
+            .filter_map(|change: &FsChange| -> Option<ObjectInfo> {
+                match change {
+                    FsChange::Upsert(info) if info.is_dir => Some(info.clone()),
+
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
     /// Process batch of `ObjectInfo` updates efficiently (single mutex lock per batch) 
-    pub async fn update_object_info_batch(&mut self, parent_dir: &PathBuf, objects: Vec<ObjectInfo>)
+    pub async fn update_object_info_batch(&mut self, parent_dir: &Path, objects: Vec<ObjectInfo>)
     {
         for (i, info) in objects.iter().enumerate()
         {
@@ -723,83 +1807,96 @@ impl AppState {
         }
     }
 
+    /// Sort the active pane by a mode name (`size_desc`), a key (`ext`), a
+    /// key prefixed with `-` for descending order (`-modified`) or the name
+    /// of a registered comparator. Built-in names take precedence.
     pub fn sort_entries(&mut self, sort_criteria: &str) {
-        let active_pane: &mut PaneState = self.fs.active_pane_mut();
-        match sort_criteria {
-            "name_asc" => active_pane
-                .entries
-                .sort_by(
-                    |a: &ObjectInfo, b: &ObjectInfo| -> Ordering 
-                    {
-                        a.name.cmp(&b.name)
-                    }
-                ),
-            
-            "name_desc" => active_pane
-                .entries
-                .sort_by(
-                    |a: &ObjectInfo, b: &ObjectInfo| -> Ordering 
-                    {
-                        b.name.cmp(&a.name)
-                    }
-                ),
-            
-            "size_asc" => active_pane
-                .entries
-                .sort_by(
-                    |a: &ObjectInfo, b: &ObjectInfo| -> Ordering 
-                    {
-                        a.size.cmp(&b.size)
-                    }
-                ),
-            
-            "size_desc" => active_pane
-                .entries
-                .sort_by(
-                    |a: &ObjectInfo, b: &ObjectInfo| -> Ordering 
-                    {
-                        b.size.cmp(&a.size)
-                    }
-                ),
-            
-            "modified_asc" => active_pane
-                .entries
-                .sort_by(|a: &ObjectInfo, b: &ObjectInfo| -> Ordering 
-                    {
-                        a.modified.cmp(&b.modified)
-                    }
-                ),
-            
-            "modified_desc" => active_pane
-                .entries
-                .sort_by(
-                    |a: &ObjectInfo, b: &ObjectInfo| -> Ordering 
-                    {
-                        b.modified.cmp(&a.modified)
-                    }
-                ),
-     
-            _ => {}
+        let criteria: &str = sort_criteria.trim();
+
+        let sort: Result<EntrySort, String> = criteria
+            .parse::<EntrySort>()
+            .or_else(|e: String| -> Result<EntrySort, String> { self.sort_registry.get(criteria).ok_or(e) });
+
+        match sort {
+            Ok(sort) => self.set_pane_sort(sort),
+
+            Err(e) => {
+                let custom: Vec<&str> = self.sort_registry.names().collect();
+
+                if custom.is_empty() {
+                    self.set_status(e);
+                } else {
+                    self.set_status(format!("{e}; custom: {}", custom.join(", ")));
+                }
+            }
+        }
+    }
+
+    /// Apply a sort mode to the active pane.
+    pub fn set_pane_sort(&mut self, sort: EntrySort) {
+        info!("Sorting by {sort}");
+
+        self.fs.active_pane_mut().set_sort(sort.clone());
+        self.set_status(format!("Sorted by {sort}"));
+        self.ui.request_redraw(RedrawFlag::All);
+    }
+
+    /// Advance the active pane to the next built-in sort mode.
+    pub fn cycle_sort(&mut self) {
+        let sort: EntrySort = self.fs.active_pane().sort.next();
+        self.set_pane_sort(sort);
+    }
+
+    /// Toggle listing directories first. The setting is global: every pane
+    /// of every tab is re-sorted.
+    pub fn toggle_dirs_first(&mut self) {
+        let mut sort_config: SortConfig = self.fs.active_pane().sort_config;
+        sort_config.dirs_first = !sort_config.dirs_first;
+
+        for fs in std::iter::once(&mut self.fs).chain(self.tabs.background_fs_mut()) {
+            for pane in &mut fs.panes {
+                pane.set_sort_config(sort_config);
+            }
         }
-      
+
+        let state: &str = if sort_config.dirs_first { "on" } else { "off" };
+        self.set_status(format!("Directories first: {state}"));
         self.ui.request_redraw(RedrawFlag::All);
     }
 
+    /// Filter the active pane with quick-filter syntax: a substring, a glob
+    /// (`*.rs`) or a regex (`/^test_`). An empty string clears the filter.
     pub fn filter_entries(&mut self, filter_criteria: &str) {
+        match EntryFilter::parse_quick(filter_criteria) {
+            Ok(filter) => self.set_pane_filter(filter),
+
+            // Incomplete regexes are common while typing; keep the last filter
+            Err(e) => self.set_status(format!("Invalid filter: {e}")),
+        }
+    }
+
+    /// Open or close the quick-filter bar for the active pane.
+    pub fn toggle_quick_filter(&mut self) {
+        let query: String = self.fs.active_pane().filter.query();
+        self.ui.toggle_quick_filter_overlay(&query);
+    }
+
+    /// Clear the active pane's filter and close the quick-filter bar.
+    pub fn clear_quick_filter(&mut self) {
+        if self.ui.overlay == UIOverlay::QuickFilter {
+            self.ui.close_all_overlays();
+            self.ui.input.clear();
+        }
+
+        self.set_pane_filter(EntryFilter::All);
+    }
+
+    /// Apply `filter` to the active pane, keeping hidden entries loaded.
+    pub fn set_pane_filter(&mut self, filter: EntryFilter) {
         let active_pane: &mut PaneState = self.fs.active_pane_mut();
-        // This is a placeholder for a more complex filtering implementation.
-        // For now, we'll just filter by a simple string contains.
-        let entries: Vec<ObjectInfo> = active_pane.entries.clone();
-        
-        active_pane.entries = entries
-            .into_iter()
-            .filter(|entry: &ObjectInfo| -> bool 
-                {
-                    entry.name.contains(filter_criteria)
-                }
-            )
-            .collect();
-        
+        active_pane.set_filter(filter);
+
+        self.ui.selected = active_pane.selected;
         self.ui.request_redraw(RedrawFlag::All);
     }
 }
@@ -813,13 +1910,186 @@ impl std::fmt::Debug for AppState {
             .field("cache", &"ObjectInfoCache")
             .field("fs", &self.fs)
             .field("ui", &self.ui)
-            .field("marked", &self.marked)
+            .field("tabs", &self.tabs)
+            .field("bookmarks", &self.bookmarks)
+            .field("frecency", &self.frecency)
+            .field("previews", &self.previews)
             .field("history", &self.history)
+            .field("redo_history", &self.redo_history)
+            .field("undo_running", &self.undo_running)
             .field("tasks", &self.tasks)
             .field("plugins", &self.plugins)
+            .field("sort_registry", &self.sort_registry)
+            .field("watcher", &self.watcher)
             .field("last_error", &self.last_error)
             .field("last_status", &self.ui.last_status)
             .field("started_at", &self.started_at)
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app() -> AppState {
+        let (task_tx, _task_rx) = mpsc::unbounded_channel::<TaskResult>();
+        let (action_tx, _action_rx) = mpsc::unbounded_channel::<Action>();
+
+        AppState::new(
+            Arc::new(Config::default()),
+            Arc::new(ObjectInfoCache::new()),
+            FSState::default(),
+            UIState::default(),
+            task_tx,
+            action_tx,
+        )
+    }
+
+    fn rename(name: &str) -> AppHistoryEvent {
+        AppHistoryEvent::Rename {
+            from: PathBuf::from(format!("/d/{name}")),
+            to: PathBuf::from(format!("/d/{name}.new")),
+        }
+    }
+
+    fn delete(name: &str) -> AppHistoryEvent {
+        AppHistoryEvent::Delete {
+            entries: vec![TrashEntry {
+                info_path: PathBuf::from(format!("/t/info/{name}.trashinfo")),
+                trashed_path: PathBuf::from(format!("/t/files/{name}")),
+                original_path: PathBuf::from(format!("/d/{name}")),
+                deleted_at: None,
+                is_dir: false,
+            }],
+        }
+    }
+
+    fn labels<'a>(events: impl IntoIterator<Item = &'a AppHistoryEvent>) -> Vec<String> {
+        events.into_iter().map(AppHistoryEvent::label).collect()
+    }
+
+    fn outcome(
+        direction: undo::Direction,
+        applied: Vec<AppHistoryEvent>,
+        remaining: Vec<AppHistoryEvent>,
+    ) -> undo::Outcome {
+        undo::Outcome {
+            direction,
+            applied: applied
+                .into_iter()
+                .map(|event: AppHistoryEvent| -> (String, AppHistoryEvent) { (event.label(), event) })
+                .collect(),
+            error: (!remaining.is_empty()).then(|| "refused".to_string()),
+            remaining,
+        }
+    }
+
+    #[test]
+    fn combine_merges_deletes_and_batches_the_rest() {
+        assert!(AppHistoryEvent::combine(Vec::new()).is_none());
+        assert_eq!(labels(&AppHistoryEvent::combine(vec![rename("a")])), labels(&[rename("a")]));
+
+        let Some(AppHistoryEvent::Delete { entries }) = AppHistoryEvent::combine(vec![delete("a"), delete("b")]) else {
+            panic!("deletes merge into one");
+        };
+        assert_eq!(entries.len(), 2);
+
+        let Some(AppHistoryEvent::Batch(steps)) = AppHistoryEvent::combine(vec![rename("a"), delete("b")]) else {
+            panic!("mixed steps form a batch");
+        };
+        assert_eq!(labels(&steps), labels(&[rename("a"), delete("b")]));
+    }
+
+    #[tokio::test]
+    async fn a_new_operation_drops_the_undone_ones() {
+        let mut app: AppState = app();
+        app.redo_history.push(rename("a"));
+
+        app.push_history(rename("b"));
+
+        assert!(app.redo_history.is_empty());
+        assert_eq!(labels(&app.history), labels(&[rename("b")]));
+        assert_eq!(app.history_generation, 1);
+    }
+
+    #[tokio::test]
+    async fn finished_undo_moves_applied_steps_to_redo_and_keeps_refused_ones() {
+        let mut app: AppState = app();
+        for name in ["a", "b", "c"] {
+            app.push_history(rename(name));
+        }
+
+        // `undo(3)` pops newest first; "a" was refused
+        app.history.clear();
+        app.undo_running = Some(app.history_generation);
+        app.finish_undo(outcome(undo::Direction::Undo, vec![rename("c"), rename("b")], vec![rename("a")]))
+            .await;
+
+        assert_eq!(labels(&app.history), labels(&[rename("a")]));
+        assert_eq!(labels(&app.redo_history), labels(&[rename("c"), rename("b")]));
+        assert!(app.undo_running.is_none());
+    }
+
+    #[tokio::test]
+    async fn operations_recorded_during_a_run_stay_newest() {
+        let mut app: AppState = app();
+        app.push_history(rename("a"));
+        app.history.clear();
+        app.undo_running = Some(app.history_generation);
+
+        // Recorded while the undo of "a" was in flight
+        app.push_history(rename("x"));
+        app.finish_undo(outcome(undo::Direction::Undo, vec![rename("a")], Vec::new())).await;
+
+        assert_eq!(labels(&app.history), labels(&[rename("x")]));
+        assert!(app.redo_history.is_empty(), "the new operation made the undo unreachable");
+
+        app.undo_running = Some(app.history_generation);
+        app.push_history(rename("y"));
+        app.finish_undo(outcome(undo::Direction::Redo, vec![rename("b")], Vec::new())).await;
+
+        assert_eq!(labels(&app.history), labels(&[rename("x"), rename("b"), rename("y")]));
+    }
+
+    fn object(name: &str, is_dir: bool) -> ObjectInfo {
+        ObjectInfo {
+            path: Path::new("/d").join(name),
+            name: name.into(),
+            is_dir,
+            ..ObjectInfo::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn the_visual_range_wins_over_marks_and_can_be_marked() {
+        let mut app: AppState = app();
+        let pane: &mut PaneState = app.fs.active_pane_mut();
+        pane.cwd = PathBuf::from("/d");
+        pane.entries = vec![object("a", false), object("b", false), object("c", false)];
+        pane.select_index(2);
+        app.mark_entry("/elsewhere/x");
+
+        app.toggle_visual_mode();
+        app.fs.active_pane_mut().select_index(1);
+
+        assert!(app.ui.is_in_visual_mode());
+        assert_eq!(app.selection_paths(), [PathBuf::from("/d/b"), PathBuf::from("/d/c")]);
+
+        assert_eq!(app.mark_visual_range(), 2);
+        assert!(!app.ui.is_in_visual_mode());
+        assert!(app.fs.active_pane().visual_anchor.is_none());
+        assert_eq!(app.marked_paths().len(), 3);
+    }
+
+    #[test]
+    fn outermost_paths_leave_out_entries_inside_a_listed_directory() {
+        let paths: Vec<PathBuf> = AppState::outermost_paths(vec![
+            PathBuf::from("/d/sub/file"),
+            PathBuf::from("/d/sub"),
+            PathBuf::from("/d/subway"),
+        ]);
+
+        assert_eq!(paths, [PathBuf::from("/d/sub"), PathBuf::from("/d/subway")]);
+    }
+}
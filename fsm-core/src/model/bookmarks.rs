@@ -0,0 +1,264 @@
+//! ``src/model/bookmarks.rs``
+//! ============================================================================
+//! # `BookmarkStore`: Persistent Named Directory Bookmarks
+//!
+//! Bookmarks map a name to a directory. Single-character names double as
+//! vim-style marks (`Ma` sets mark `a`, `'a` jumps to it); longer names are
+//! added from the command palette (`:bm <name>`).
+//!
+//! The store is saved as `bookmarks.toml` next to `config.toml`. Targets that
+//! have been deleted are kept and flagged as missing rather than dropped, so a
+//! temporarily unmounted drive does not lose its bookmarks.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::util::fuzzy::fuzzy_score;
+use crate::util::persist::{os_path_map, write_atomic};
+
+/// A bookmark as shown in the bookmark overlay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookmarkView<'a> {
+    pub name: &'a str,
+    pub path: &'a Path,
+    /// True if the target directory no longer exists.
+    pub missing: bool,
+}
+
+/// Named bookmarks, persisted to the config directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BookmarkStore {
+    #[serde(default, with = "os_path_map")]
+    bookmarks: BTreeMap<String, PathBuf>,
+
+    /// Names whose target was missing at the last status refresh.
+    #[serde(skip)]
+    missing: HashSet<String>,
+}
+
+impl BookmarkStore {
+    /// Load bookmarks from disk. A missing or unreadable file yields an
+    /// empty store; the error is logged rather than surfaced.
+    pub async fn load() -> Self {
+        let path: PathBuf = match Config::bookmarks_path() {
+            Ok(p) => p,
+
+            Err(e) => {
+                warn!("Cannot determine bookmarks path: {e}");
+                return Self::default();
+            }
+        };
+
+        match tokio::fs::read_to_string(&path).await {
+            Ok(text) => match toml::from_str::<Self>(&text) {
+                Ok(store) => {
+                    info!("Loaded {} bookmarks from {}", store.len(), path.display());
+                    store
+                }
+
+                Err(e) => {
+                    warn!("Ignoring malformed bookmarks file {}: {e}", path.display());
+                    Self::default()
+                }
+            },
+
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+
+            Err(e) => {
+                warn!("Failed to read bookmarks file {}: {e}", path.display());
+                Self::default()
+            }
+        }
+    }
+
+    /// Save bookmarks next to `config.toml`.
+    pub async fn save(&self) -> anyhow::Result<()> {
+        let path: PathBuf = Config::bookmarks_path()?;
+        let text: String = toml::to_string_pretty(self)?;
+
+        write_atomic(&path, text.as_bytes()).await?;
+        info!("Saved {} bookmarks to {}", self.len(), path.display());
+
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bookmarks.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bookmarks.is_empty()
+    }
+
+    /// Add or replace a bookmark.
+    pub fn set(&mut self, name: impl Into<String>, path: PathBuf) {
+        let name: String = name.into();
+
+        self.missing.remove(&name);
+        self.bookmarks.insert(name, path);
+    }
+
+    /// Remove a bookmark, returning its target.
+    pub fn remove(&mut self, name: &str) -> Option<PathBuf> {
+        self.missing.remove(name);
+        self.bookmarks.remove(name)
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&PathBuf> {
+        self.bookmarks.get(name)
+    }
+
+    #[must_use]
+    pub fn is_missing(&self, name: &str) -> bool {
+        self.missing.contains(name)
+    }
+
+    /// Flag (or un-flag) a bookmark whose target was found to be missing.
+    pub fn set_missing(&mut self, name: &str, missing: bool) {
+        if missing {
+            self.missing.insert(name.to_string());
+        } else {
+            self.missing.remove(name);
+        }
+    }
+
+    /// Re-check which bookmark targets still exist.
+    pub async fn refresh_status(&mut self) {
+        let mut missing: HashSet<String> = HashSet::new();
+
+        for (name, path) in &self.bookmarks {
+            let is_dir: bool = tokio::fs::metadata(path)
+                .await
+                .is_ok_and(|m: std::fs::Metadata| -> bool { m.is_dir() });
+
+            if !is_dir {
+                missing.insert(name.clone());
+            }
+        }
+
+        self.missing = missing;
+    }
+
+    #[must_use]
+    /// Bookmarks matching `query` (fuzzy, on name and path), best match first.
+    /// With an empty query all bookmarks are returned in name order.
+    pub fn filtered(&self, query: &str) -> Vec<BookmarkView<'_>> {
+        let mut scored: Vec<(i64, BookmarkView<'_>)> = self
+            .bookmarks
+            .iter()
+            .filter_map(|(name, path): (&String, &PathBuf)| {
+                let haystack: String = format!("{name} {}", path.display());
+                let score: i64 = fuzzy_score(query, &haystack)?;
+
+                Some((
+                    score,
+                    BookmarkView {
+                        name,
+                        path,
+                        missing: self.missing.contains(name),
+                    },
+                ))
+            })
+            .collect();
+
+        // Stable sort keeps name order among equal scores
+        scored.sort_by_key(|(score, _): &(i64, BookmarkView<'_>)| -> std::cmp::Reverse<i64> {
+            std::cmp::Reverse(*score)
+        });
+
+        scored
+            .into_iter()
+            .map(|(_, view): (i64, BookmarkView<'_>)| -> BookmarkView<'_> { view })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(views: &[BookmarkView<'_>]) -> Vec<String> {
+        views
+            .iter()
+            .map(|view: &BookmarkView<'_>| -> String { view.name.to_string() })
+            .collect()
+    }
+
+    #[test]
+    fn set_replaces_and_remove_returns_the_target() {
+        let mut store: BookmarkStore = BookmarkStore::default();
+        store.set("a", PathBuf::from("/one"));
+        store.set("a", PathBuf::from("/two"));
+
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.get("a"), Some(&PathBuf::from("/two")));
+        assert_eq!(store.remove("a"), Some(PathBuf::from("/two")));
+        assert!(store.is_empty());
+        assert_eq!(store.remove("a"), None);
+    }
+
+    #[test]
+    fn setting_a_bookmark_again_clears_its_missing_flag() {
+        let mut store: BookmarkStore = BookmarkStore::default();
+        store.set("a", PathBuf::from("/gone"));
+        store.set_missing("a", true);
+
+        assert!(store.is_missing("a"));
+        assert!(store.filtered("")[0].missing);
+
+        store.set("a", PathBuf::from("/back"));
+
+        assert!(!store.is_missing("a"));
+    }
+
+    #[tokio::test]
+    async fn refresh_status_flags_targets_that_are_not_directories() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let file: PathBuf = root.path().join("file");
+        std::fs::write(&file, "").expect("write file");
+
+        let mut store: BookmarkStore = BookmarkStore::default();
+        store.set("dir", root.path().to_path_buf());
+        store.set("file", file);
+        store.set("gone", root.path().join("gone"));
+
+        store.refresh_status().await;
+
+        assert!(!store.is_missing("dir"));
+        assert!(store.is_missing("file"));
+        assert!(store.is_missing("gone"));
+    }
+
+    #[test]
+    fn filtered_ranks_matches_and_keeps_name_order_without_a_query() {
+        let mut store: BookmarkStore = BookmarkStore::default();
+        store.set("work", PathBuf::from("/home/me/work"));
+        store.set("music", PathBuf::from("/media/music"));
+        store.set("docs", PathBuf::from("/home/me/documents"));
+
+        assert_eq!(names(&store.filtered("")), ["docs", "music", "work"]);
+        // "docs /home/me/documents" matches too, but scattered
+        assert_eq!(names(&store.filtered("mus")), ["music", "docs"]);
+        assert!(store.filtered("xyz").is_empty());
+    }
+
+    #[test]
+    fn store_round_trips_through_toml_without_the_missing_flags() {
+        let mut store: BookmarkStore = BookmarkStore::default();
+        store.set("a", PathBuf::from("/one"));
+        store.set_missing("a", true);
+
+        let text: String = toml::to_string_pretty(&store).expect("serialize");
+        let loaded: BookmarkStore = toml::from_str(&text).expect("deserialize");
+
+        assert_eq!(loaded.get("a"), Some(&PathBuf::from("/one")));
+        assert!(!loaded.is_missing("a"));
+    }
+}
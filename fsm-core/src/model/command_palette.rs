@@ -2,6 +2,8 @@
 //!  ``src/model/command_palette.rs``
 //! 
 
+use crate::config::{ColumnFormat, LayoutMode, TableColumn};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CommandPaletteState {
     pub input: String,
@@ -61,7 +63,12 @@ impl CommandPaletteState {
         let mut candidates = Vec::new();
 
         // Add built-in command aliases (canonical commands only)
-        let built_in_commands = ["nf", "nd", "reload", "grep", "config"];
+        let built_in_commands = [
+            "nf", "nd", "reload", "grep", "config", "bm", "bdel", "bookmarks", "z", "zi",
+            "tabnew", "tabclose", "tabnext", "tabprev", "tabmove", "tabname", "tree",
+            "layout", "filter", "sort", "dirsfirst", "diag", "col", "trash",
+            "undo", "redo", "undolist",
+        ];
 
         for cmd in &built_in_commands {
             if cmd.starts_with(&command_part) {
@@ -199,6 +206,84 @@ impl CommandPaletteState {
 
             "config" => Some(CommandAction::OpenConfig),
 
+            "bm" => {
+                if parts.len() > 1 {
+                    Some(CommandAction::AddBookmark(Some(parts[1..].join(" "))))
+                } else {
+                    Some(CommandAction::AddBookmark(None))
+                }
+            }
+
+            "bdel" => {
+                if parts.len() > 1 {
+                    Some(CommandAction::RemoveBookmark(parts[1..].join(" ")))
+                } else {
+                    Some(CommandAction::ListBookmarks)
+                }
+            }
+
+            "bookmarks" | "bls" => Some(CommandAction::ListBookmarks),
+
+            "z" => {
+                if parts.len() > 1 {
+                    Some(CommandAction::FrecencyJump(parts[1..].join(" ")))
+                } else {
+                    Some(CommandAction::ShowFrecency(String::new()))
+                }
+            }
+
+            "zi" => Some(CommandAction::ShowFrecency(parts[1..].join(" "))),
+
+            "tabnew" => Some(CommandAction::NewTab),
+
+            "tabclose" => Some(CommandAction::CloseTab),
+
+            "tabnext" | "tabn" => Some(CommandAction::NextTab),
+
+            "tabprev" | "tabp" => Some(CommandAction::PrevTab),
+
+            "tabmove" => parts
+                .get(1)
+                .and_then(|delta: &&str| -> Option<isize> { delta.parse().ok() })
+                .map(CommandAction::MoveTab),
+
+            "tabname" => Some(CommandAction::RenameTab(parts[1..].join(" "))),
+
+            "tree" => Some(CommandAction::ToggleTree),
+
+            "filter" => Some(CommandAction::Filter(parts[1..].join(" "))),
+
+            "sort" => Some(CommandAction::Sort(parts[1..].join(" "))),
+
+            "dirsfirst" => Some(CommandAction::ToggleDirsFirst),
+
+            "diag" => Some(CommandAction::ShowDiagnostics),
+
+            "trash" => Some(CommandAction::ShowTrash),
+
+            "undo" | "u" => Some(CommandAction::Undo),
+
+            "redo" => Some(CommandAction::Redo),
+
+            "undolist" => Some(CommandAction::ShowUndoHistory),
+
+            "col" | "column" => {
+                let column: TableColumn = parts.get(1)?.parse().ok()?;
+
+                if parts.len() > 2 {
+                    let format: ColumnFormat = parts[2..].join(" ").parse().ok()?;
+                    Some(CommandAction::SetColumnFormat(column, format))
+                } else {
+                    Some(CommandAction::ToggleColumn(column))
+                }
+            }
+
+            "layout" => match parts.get(1) {
+                Some(name) => name.parse().ok().map(|l: LayoutMode| CommandAction::SetLayout(Some(l))),
+
+                None => Some(CommandAction::SetLayout(None)),
+            },
+
             _ => {
                 // Try to match existing commands by title
                 self.all_commands
@@ -227,6 +312,29 @@ pub enum CommandAction {
     NewFolderWithName(String),
     SearchContent,
     SearchContentWithPattern(String),
+    AddBookmark(Option<String>),
+    RemoveBookmark(String),
+    ListBookmarks,
+    FrecencyJump(String),
+    ShowFrecency(String),
+    NewTab,
+    CloseTab,
+    NextTab,
+    PrevTab,
+    MoveTab(isize),
+    RenameTab(String),
+    ToggleTree,
+    SetLayout(Option<LayoutMode>),
+    Filter(String),
+    Sort(String),
+    ToggleDirsFirst,
+    ShowDiagnostics,
+    ToggleColumn(TableColumn),
+    SetColumnFormat(TableColumn, ColumnFormat),
+    ShowTrash,
+    Undo,
+    Redo,
+    ShowUndoHistory,
     // ...extend with more actions or plugins
     Custom(String),
 }
@@ -246,6 +354,28 @@ pub fn get_command_description(command: &str) -> Option<&'static str> {
         "reload" => Some("Reload current directory"),
         "grep" => Some("Enter content search overlay"),
         "config" => Some("Open configuration"),
+        "bm" => Some("Bookmark current directory"),
+        "bdel" => Some("Remove a bookmark"),
+        "bookmarks" => Some("Show bookmarks"),
+        "z" => Some("Jump to frecent directory"),
+        "zi" => Some("Pick frecent directory interactively"),
+        "tabnew" => Some("Open a new tab"),
+        "tabclose" => Some("Close the current tab"),
+        "tabnext" => Some("Go to the next tab"),
+        "tabprev" => Some("Go to the previous tab"),
+        "tabmove" => Some("Move the current tab"),
+        "tabname" => Some("Name the current tab"),
+        "tree" => Some("Toggle tree view"),
+        "layout" => Some("Switch between table and Miller columns"),
+        "filter" => Some("Filter the current pane"),
+        "sort" => Some("Sort the current pane"),
+        "dirsfirst" => Some("Toggle listing directories first"),
+        "diag" => Some("Show cache and runtime diagnostics"),
+        "col" => Some("Toggle a table column or set its format"),
+        "trash" => Some("Browse the trash to restore or purge"),
+        "undo" => Some("Undo the last file operation"),
+        "redo" => Some("Redo the last undone file operation"),
+        "undolist" => Some("Show the undo history"),
         _ => None,
     }
 }
@@ -259,5 +389,27 @@ pub fn get_all_commands_with_descriptions() -> Vec<(&'static str, &'static str,
         ("reload", "", "Reload current directory"),
         ("grep", "", "Enter content search overlay"),
         ("config", "", "Open configuration"),
+        ("bm", "", "Bookmark current directory (bm [name])"),
+        ("bdel", "", "Remove a bookmark (bdel <name>)"),
+        ("bookmarks", "bls", "Show bookmarks"),
+        ("z", "", "Jump to best frecent match (z <fragment>...)"),
+        ("zi", "", "Pick from ranked frecent directories"),
+        ("tabnew", "", "Open a new tab"),
+        ("tabclose", "", "Close the current tab"),
+        ("tabnext", "tabn", "Go to the next tab"),
+        ("tabprev", "tabp", "Go to the previous tab"),
+        ("tabmove", "", "Move the current tab (tabmove <+n|-n>)"),
+        ("tabname", "", "Name the current tab (tabname [name])"),
+        ("tree", "", "Toggle tree view in the current pane"),
+        ("layout", "", "Set the layout (layout [table|miller]), toggles without argument"),
+        ("filter", "", "Filter the pane (filter [text|*.glob|/regex]), clears without argument"),
+        ("sort", "", "Sort by name, size, modified, created, ext, type or items (sort [-]<key>) or a custom comparator (sort <name>), cycles without argument"),
+        ("dirsfirst", "", "Toggle listing directories before files in every pane"),
+        ("diag", "", "Toggle the cache and runtime diagnostics overlay"),
+        ("col", "column", "Show or hide a table column (col <name>) or set its format (col <name> <relative|iso|%pattern|si|iec|bytes>)"),
+        ("trash", "", "Toggle the trash overlay (restore or purge trashed entries)"),
+        ("undo", "u", "Undo the last file operation"),
+        ("redo", "", "Redo the last undone file operation"),
+        ("undolist", "", "Toggle the undo history overlay"),
     ]
 }
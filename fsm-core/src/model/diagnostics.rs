@@ -0,0 +1,66 @@
+//! ``src/model/diagnostics.rs``
+//! ============================================================================
+//! # `DiagnosticsSnapshot`: Live Runtime Figures for the Diagnostics Overlay
+//!
+//! Figures that otherwise only reach the logs: cache statistics, dispatch
+//! latency, background work and process resources. `EventLoop` owns the
+//! latency figures and the channel receivers, so it collects the snapshot
+//! (throttled, and only while the overlay is open) into
+//! `UIState::diagnostics`, where `DiagnosticsOverlay` renders it.
+
+use std::time::Duration;
+
+use crate::cache::cache_manager::CacheStatsSnapshot;
+use crate::model::ui_state::FileOperationProgress;
+
+/// Minimum time between two snapshots.
+pub const DIAGNOSTICS_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// One refresh of the diagnostics overlay.
+#[derive(Debug, Clone)]
+pub struct DiagnosticsSnapshot {
+    /// Hit/miss/load counters of the object cache.
+    pub cache: CacheStatsSnapshot,
+
+    /// Entries in the object cache.
+    pub cache_entries: u64,
+
+    /// Estimated bytes held by the object cache.
+    pub cache_bytes: u64,
+
+    /// Configured memory budget of the object cache in bytes.
+    pub cache_budget: u64,
+
+    /// Entries from the disk snapshot not validated yet.
+    pub cache_restored: usize,
+
+    /// Actions dispatched since startup.
+    pub events: u64,
+
+    /// Moving average of the time to dispatch one action.
+    pub avg_latency: Duration,
+
+    /// Slowest dispatch since the previous snapshot.
+    pub peak_latency: Duration,
+
+    /// Background tasks not completed yet.
+    pub pending_tasks: usize,
+
+    /// Running copy/move/rename operations.
+    pub file_operations: Vec<FileOperationProgress>,
+
+    /// Resident memory of the process in KB.
+    pub memory_kb: Option<i64>,
+
+    /// CPU usage of the process in percent.
+    pub cpu_percent: Option<f32>,
+
+    /// Task results waiting in the event loop's channel.
+    pub task_queue: usize,
+
+    /// Actions waiting in the event loop's channel.
+    pub action_queue: usize,
+
+    /// Time since startup.
+    pub uptime: Duration,
+}
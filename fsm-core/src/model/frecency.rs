@@ -0,0 +1,374 @@
+//! ``src/model/frecency.rs``
+//! ============================================================================
+//! # `FrecencyStore`: Ranked Directory Visits (zoxide-style)
+//!
+//! Every directory entered through `AppState::enter_directory` bumps its rank
+//! and visit timestamp. Candidates are scored by rank weighted by how recently
+//! they were visited, and matched against space-separated fragments:
+//!
+//! - every fragment must appear in the path, in order (case-insensitive)
+//! - the last fragment must match within the final path component
+//!
+//! When the summed rank exceeds [`MAX_TOTAL_RANK`] all ranks are scaled down
+//! and entries that fall below 1 are forgotten, so directories that are no
+//! longer visited age out. The store is saved as `frecency.toml` in the data
+//! directory.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::util::persist::{next_version, os_path, write_versioned};
+
+/// Summed rank at which all entries are aged.
+pub const MAX_TOTAL_RANK: f64 = 10_000.0;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// A single remembered directory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrecencyEntry {
+    #[serde(with = "os_path")]
+    pub path: PathBuf,
+
+    /// Visit count, decayed by aging.
+    pub rank: f64,
+
+    /// Last visit, seconds since the Unix epoch.
+    pub last_access: u64,
+}
+
+impl FrecencyEntry {
+    /// Rank weighted by recency of the last visit.
+    #[must_use]
+    pub fn score(&self, now: u64) -> f64 {
+        let age: u64 = now.saturating_sub(self.last_access);
+
+        if age < HOUR {
+            self.rank * 4.0
+        } else if age < DAY {
+            self.rank * 2.0
+        } else if age < WEEK {
+            self.rank * 0.5
+        } else {
+            self.rank * 0.25
+        }
+    }
+
+    /// Whether this entry's path matches all `fragments`.
+    #[must_use]
+    pub fn matches(&self, fragments: &[String]) -> bool {
+        let Some((last, init)) = fragments.split_last() else {
+            return true;
+        };
+
+        let path: String = self.path.to_string_lossy().to_lowercase();
+        let mut rest: &str = &path;
+
+        for fragment in init {
+            let Some(idx) = rest.find(fragment.as_str()) else {
+                return false;
+            };
+
+            rest = &rest[idx + fragment.len()..];
+        }
+
+        // The last fragment must land in the final component
+        rest.rfind(last.as_str())
+            .is_some_and(|idx: usize| -> bool { !rest[idx..].contains('/') })
+    }
+}
+
+/// Persistent frecency database.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FrecencyStore {
+    #[serde(default)]
+    entries: Vec<FrecencyEntry>,
+}
+
+impl FrecencyStore {
+    /// Load the database from disk. A missing or unreadable file yields an
+    /// empty store; the error is logged rather than surfaced.
+    pub async fn load() -> Self {
+        let path: PathBuf = match Config::frecency_path() {
+            Ok(p) => p,
+
+            Err(e) => {
+                warn!("Cannot determine frecency database path: {e}");
+                return Self::default();
+            }
+        };
+
+        match tokio::fs::read_to_string(&path).await {
+            Ok(text) => match toml::from_str::<Self>(&text) {
+                Ok(store) => {
+                    info!("Loaded {} frecency entries from {}", store.len(), path.display());
+                    store
+                }
+
+                Err(e) => {
+                    warn!("Ignoring malformed frecency database {}: {e}", path.display());
+                    Self::default()
+                }
+            },
+
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+
+            Err(e) => {
+                warn!("Failed to read frecency database {}: {e}", path.display());
+                Self::default()
+            }
+        }
+    }
+
+    /// Save the database in the background. Failures are logged. The
+    /// version is taken now, so a save spawned earlier that finishes later
+    /// is dropped instead of overwriting this one.
+    pub fn save_in_background(&self) {
+        let text: String = match toml::to_string(self) {
+            Ok(t) => t,
+
+            Err(e) => {
+                warn!("Failed to serialize frecency database: {e}");
+                return;
+            }
+        };
+
+        let path: PathBuf = match Config::frecency_path() {
+            Ok(path) => path,
+
+            Err(e) => {
+                warn!("Cannot determine frecency database path: {e}");
+                return;
+            }
+        };
+
+        let version: u64 = next_version(&path);
+
+        tokio::spawn(async move {
+            if let Err(e) = write_versioned(&path, version, text.as_bytes()).await {
+                warn!("Failed to save frecency database: {e}");
+            }
+        });
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Record a visit to `path`, aging all entries if needed.
+    pub fn record_visit(&mut self, path: &Path) {
+        let now: u64 = Self::now();
+
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|e: &&mut FrecencyEntry| -> bool { e.path == path })
+        {
+            entry.rank += 1.0;
+            entry.last_access = now;
+        } else {
+            self.entries.push(FrecencyEntry {
+                path: path.to_path_buf(),
+                rank: 1.0,
+                last_access: now,
+            });
+        }
+
+        self.age();
+    }
+
+    /// Forget a directory (e.g. because it no longer exists).
+    pub fn remove(&mut self, path: &Path) {
+        self.entries.retain(|e: &FrecencyEntry| -> bool { e.path != path });
+    }
+
+    /// Entries matching `query` (space-separated fragments), best first.
+    /// An empty query returns every entry.
+    #[must_use]
+    pub fn ranked(&self, query: &str) -> Vec<(f64, &FrecencyEntry)> {
+        let fragments: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        let now: u64 = Self::now();
+
+        let mut ranked: Vec<(f64, &FrecencyEntry)> = self
+            .entries
+            .iter()
+            .filter(|e: &&FrecencyEntry| -> bool { e.matches(&fragments) })
+            .map(|e: &FrecencyEntry| -> (f64, &FrecencyEntry) { (e.score(now), e) })
+            .collect();
+
+        ranked.sort_by(|a: &(f64, &FrecencyEntry), b: &(f64, &FrecencyEntry)| {
+            b.0.total_cmp(&a.0)
+        });
+
+        ranked
+    }
+
+    /// Scale ranks down once their sum exceeds [`MAX_TOTAL_RANK`] and drop
+    /// entries that decay below 1.
+    fn age(&mut self) {
+        let total: f64 = self.entries.iter().map(|e: &FrecencyEntry| -> f64 { e.rank }).sum();
+
+        if total <= MAX_TOTAL_RANK {
+            return;
+        }
+
+        let factor: f64 = 0.9 * MAX_TOTAL_RANK / total;
+
+        for entry in &mut self.entries {
+            entry.rank *= factor;
+        }
+
+        self.entries.retain(|e: &FrecencyEntry| -> bool { e.rank >= 1.0 });
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d: std::time::Duration| -> u64 { d.as_secs() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 100 * WEEK;
+
+    fn entry(path: &str, rank: f64, age: u64) -> FrecencyEntry {
+        FrecencyEntry {
+            path: PathBuf::from(path),
+            rank,
+            last_access: NOW - age,
+        }
+    }
+
+    fn fragments(query: &str) -> Vec<String> {
+        query.split_whitespace().map(str::to_lowercase).collect()
+    }
+
+    #[test]
+    fn score_weights_rank_by_recency_bucket() {
+        let cases: [(u64, f64); 8] = [
+            (0, 40.0),
+            (HOUR - 1, 40.0),
+            (HOUR, 20.0),
+            (DAY - 1, 20.0),
+            (DAY, 5.0),
+            (WEEK - 1, 5.0),
+            (WEEK, 2.5),
+            (50 * WEEK, 2.5),
+        ];
+
+        for (age, expected) in cases {
+            assert_eq!(entry("/d", 10.0, age).score(NOW), expected, "age {age}s");
+        }
+    }
+
+    #[test]
+    fn score_treats_a_visit_in_the_future_as_recent() {
+        let future: FrecencyEntry = FrecencyEntry { last_access: NOW + DAY, ..entry("/d", 1.0, 0) };
+
+        assert_eq!(future.score(NOW), 4.0);
+    }
+
+    #[test]
+    fn matches_fragments_in_order_with_the_last_in_the_final_component() {
+        let project: FrecencyEntry = entry("/home/User/src/Project", 1.0, 0);
+
+        assert!(project.matches(&[]));
+        assert!(project.matches(&fragments("proj")));
+        assert!(project.matches(&fragments("HOME proj")));
+        assert!(project.matches(&fragments("user src pro")));
+
+        // Out of order
+        assert!(!project.matches(&fragments("src home proj")));
+
+        // The last fragment only matches a parent
+        assert!(!project.matches(&fragments("src")));
+        assert!(!project.matches(&fragments("missing")));
+    }
+
+    #[test]
+    fn matches_does_not_reuse_the_text_of_an_earlier_fragment() {
+        let foo: FrecencyEntry = entry("/srv/foo", 1.0, 0);
+
+        assert!(foo.matches(&fragments("fo o")));
+        assert!(!foo.matches(&fragments("foo foo")));
+    }
+
+    #[test]
+    fn record_visit_bumps_an_existing_entry() {
+        let mut store: FrecencyStore = FrecencyStore::default();
+
+        store.record_visit(Path::new("/a"));
+        store.record_visit(Path::new("/b"));
+        store.record_visit(Path::new("/a"));
+
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.ranked("a")[0].1.rank, 2.0);
+    }
+
+    #[test]
+    fn aging_scales_ranks_below_the_maximum_and_forgets_rare_entries() {
+        let mut store: FrecencyStore = FrecencyStore {
+            entries: vec![entry("/busy", MAX_TOTAL_RANK, 0), entry("/rare", 1.0, 0)],
+        };
+
+        // The visit takes the total over the maximum
+        store.record_visit(Path::new("/busy"));
+
+        let total: f64 = store.entries.iter().map(|e: &FrecencyEntry| -> f64 { e.rank }).sum();
+
+        assert!(total <= 0.9 * MAX_TOTAL_RANK, "total {total}");
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.entries[0].path, PathBuf::from("/busy"));
+    }
+
+    #[test]
+    fn aging_leaves_ranks_alone_up_to_the_maximum() {
+        let mut store: FrecencyStore = FrecencyStore {
+            entries: vec![entry("/busy", MAX_TOTAL_RANK - 2.0, 0), entry("/rare", 1.0, 0)],
+        };
+
+        store.record_visit(Path::new("/rare"));
+
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.ranked("rare")[0].1.rank, 2.0);
+        assert_eq!(store.ranked("busy")[0].1.rank, MAX_TOTAL_RANK - 2.0);
+    }
+
+    #[test]
+    fn ranked_orders_matches_by_score() {
+        let now: u64 = FrecencyStore::now();
+        let visited = |path: &str, rank: f64, age: u64| -> FrecencyEntry { FrecencyEntry { last_access: now - age, ..entry(path, rank, 0) } };
+
+        // 10 * 0.25 against 2 * 4
+        let store: FrecencyStore = FrecencyStore {
+            entries: vec![
+                visited("/old/docs", 10.0, 2 * WEEK),
+                visited("/new/docs", 2.0, 0),
+                visited("/other", 50.0, 0),
+            ],
+        };
+
+        let paths: Vec<&Path> = store
+            .ranked("docs")
+            .into_iter()
+            .map(|(_, e): (f64, &FrecencyEntry)| -> &Path { &e.path })
+            .collect();
+
+        assert_eq!(paths, vec![Path::new("/new/docs"), Path::new("/old/docs")]);
+    }
+}
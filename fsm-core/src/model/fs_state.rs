@@ -7,9 +7,25 @@
 
 use ratatui::widgets::TableState;
 
-use crate::fs::object_info::ObjectInfo;
-use std::collections::{HashSet, VecDeque};
-use std::path::PathBuf;
+use crate::config::SortConfig;
+use crate::fs::object_info::{ObjectInfo, ObjectType};
+use crate::fs::watcher::FsChange;
+use crate::model::sort_registry::CustomSort;
+use crate::model::tree::{TreeRow, TreeState};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Maximum number of entries kept in each back/forward stack.
+pub const HISTORY_LIMIT: usize = 64;
+
+/// Listings shorter than this take streamed entries by sorted insertion.
+pub const DIRECT_INSERT_LIMIT: usize = 1024;
+
+/// Longer listings buffer streamed entries until the buffer reaches
+/// `1 / MERGE_RATIO` of the listing, so the number of merges grows only
+/// logarithmically with the directory size.
+pub const MERGE_RATIO: usize = 4;
 
 /// Filter and sort mode for directory views.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -20,31 +36,259 @@ pub enum EntrySort {
     SizeDesc,
     ModifiedAsc,
     ModifiedDesc,
-    Custom(String), // for plugin/user
+    CreatedAsc,
+    CreatedDesc,
+    ExtensionAsc,
+    ExtensionDesc,
+    TypeAsc,
+    TypeDesc,
+    ItemsAsc,
+    ItemsDesc,
+    Custom(CustomSort), // named comparator from the config or a plugin
+}
+
+impl EntrySort {
+    /// Every built-in mode in the order the sort key cycles through them.
+    pub const CYCLE: [Self; 14] = [
+        Self::NameAsc,
+        Self::NameDesc,
+        Self::SizeAsc,
+        Self::SizeDesc,
+        Self::ModifiedAsc,
+        Self::ModifiedDesc,
+        Self::CreatedAsc,
+        Self::CreatedDesc,
+        Self::ExtensionAsc,
+        Self::ExtensionDesc,
+        Self::TypeAsc,
+        Self::TypeDesc,
+        Self::ItemsAsc,
+        Self::ItemsDesc,
+    ];
+
+    #[must_use]
+    /// Mode sorting by `key` in the given direction.
+    pub const fn from_key(key: SortKey, descending: bool) -> Self {
+        match (key, descending) {
+            (SortKey::Name, false) => Self::NameAsc,
+            (SortKey::Name, true) => Self::NameDesc,
+            (SortKey::Size, false) => Self::SizeAsc,
+            (SortKey::Size, true) => Self::SizeDesc,
+            (SortKey::Modified, false) => Self::ModifiedAsc,
+            (SortKey::Modified, true) => Self::ModifiedDesc,
+            (SortKey::Created, false) => Self::CreatedAsc,
+            (SortKey::Created, true) => Self::CreatedDesc,
+            (SortKey::Extension, false) => Self::ExtensionAsc,
+            (SortKey::Extension, true) => Self::ExtensionDesc,
+            (SortKey::Type, false) => Self::TypeAsc,
+            (SortKey::Type, true) => Self::TypeDesc,
+            (SortKey::Items, false) => Self::ItemsAsc,
+            (SortKey::Items, true) => Self::ItemsDesc,
+        }
+    }
+
+    #[must_use]
+    /// Sort key and direction (true = descending); `None` for `Custom`.
+    pub const fn key(&self) -> Option<(SortKey, bool)> {
+        let key: (SortKey, bool) = match self {
+            Self::NameAsc => (SortKey::Name, false),
+            Self::NameDesc => (SortKey::Name, true),
+            Self::SizeAsc => (SortKey::Size, false),
+            Self::SizeDesc => (SortKey::Size, true),
+            Self::ModifiedAsc => (SortKey::Modified, false),
+            Self::ModifiedDesc => (SortKey::Modified, true),
+            Self::CreatedAsc => (SortKey::Created, false),
+            Self::CreatedDesc => (SortKey::Created, true),
+            Self::ExtensionAsc => (SortKey::Extension, false),
+            Self::ExtensionDesc => (SortKey::Extension, true),
+            Self::TypeAsc => (SortKey::Type, false),
+            Self::TypeDesc => (SortKey::Type, true),
+            Self::ItemsAsc => (SortKey::Items, false),
+            Self::ItemsDesc => (SortKey::Items, true),
+            Self::Custom(_) => return None,
+        };
+
+        Some(key)
+    }
+
+    #[must_use]
+    /// The mode following this one in `CYCLE` (wraps around).
+    pub fn next(&self) -> Self {
+        let idx: Option<usize> = Self::CYCLE
+            .iter()
+            .position(|s: &Self| -> bool { s == self });
+
+        idx.map_or(Self::NameAsc, |i: usize| -> Self {
+            Self::CYCLE[(i + 1) % Self::CYCLE.len()].clone()
+        })
+    }
 }
 
 impl std::fmt::Display for EntrySort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Self::Custom(custom) = self {
+            return write!(f, "{custom}");
+        }
+
+        let Some((key, descending)) = self.key() else {
+            return Ok(());
+        };
+
+        let order: &str = if descending { "desc" } else { "asc" };
+        write!(f, "{key}_{order}")
+    }
+}
+
+impl std::str::FromStr for EntrySort {
+    type Err = String;
+
+    /// Accepts `name_asc` / `size_desc` style names as well as a bare key
+    /// (`size`) or a key prefixed with `-` for descending order (`-size`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s: &str = s.trim();
+
+        if let Some(key) = s.strip_prefix('-') {
+            return Ok(Self::from_key(key.parse()?, true));
+        }
+
+        if let Some(key) = s.strip_suffix("_desc") {
+            return Ok(Self::from_key(key.parse()?, true));
+        }
+
+        let key: &str = s.strip_suffix("_asc").unwrap_or(s);
+        Ok(Self::from_key(key.parse()?, false))
+    }
+}
+
+/// A single property entries can be ordered by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortKey {
+    Name,
+    Size,
+    Modified,
+    Created,
+    Extension,
+    Type,
+    Items,
+}
+
+impl std::fmt::Display for SortKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
-            Self::NameAsc => "name_asc",
-            
-            Self::NameDesc => "name_desc",
-            
-            Self::SizeAsc => "size_asc",
-            
-            Self::SizeDesc => "size_desc",
-            
-            Self::ModifiedAsc => "modified_asc",
-            
-            Self::ModifiedDesc => "modified_desc",
-            
-            Self::Custom(s) => s,
+            Self::Name => "name",
+
+            Self::Size => "size",
+
+            Self::Modified => "modified",
+
+            Self::Created => "created",
+
+            Self::Extension => "ext",
+
+            Self::Type => "type",
+
+            Self::Items => "items",
         };
 
         write!(f, "{s}")
     }
 }
 
+impl std::str::FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "name" => Ok(Self::Name),
+
+            "size" => Ok(Self::Size),
+
+            "modified" | "mtime" | "date" => Ok(Self::Modified),
+
+            "created" | "btime" => Ok(Self::Created),
+
+            "ext" | "extension" => Ok(Self::Extension),
+
+            "type" | "kind" => Ok(Self::Type),
+
+            "items" | "count" => Ok(Self::Items),
+
+            other => Err(format!(
+                "Unknown sort key '{other}' (expected name, size, modified, created, ext, type or items)"
+            )),
+        }
+    }
+}
+
+/// A compiled regular expression together with the text it was built from.
+/// Compared by source text so filters stay comparable.
+#[derive(Debug, Clone)]
+pub struct FilterRegex {
+    pub source: String,
+    regex: regex::Regex,
+}
+
+impl FilterRegex {
+    /// Compile `pattern` (case-insensitive unless it contains uppercase).
+    pub fn new(source: &str, pattern: &str) -> Result<Self, regex::Error> {
+        let case_insensitive: bool = !pattern.chars().any(char::is_uppercase);
+        let regex: regex::Regex = regex::RegexBuilder::new(pattern)
+            .case_insensitive(case_insensitive)
+            .build()?;
+
+        Ok(Self {
+            source: source.to_string(),
+            regex,
+        })
+    }
+
+    /// Translate a shell glob (`*`, `?`, `[...]`) into an anchored regex.
+    pub fn from_glob(glob: &str) -> Result<Self, regex::Error> {
+        let mut pattern: String = String::from("^");
+        let mut in_class: bool = false;
+
+        for c in glob.chars() {
+            match c {
+                '*' if !in_class => pattern.push_str(".*"),
+
+                '?' if !in_class => pattern.push('.'),
+
+                '[' if !in_class => {
+                    in_class = true;
+                    pattern.push('[');
+                }
+
+                ']' if in_class => {
+                    in_class = false;
+                    pattern.push(']');
+                }
+
+                '!' if in_class && pattern.ends_with('[') => pattern.push('^'),
+
+                c if in_class => pattern.push(c),
+
+                c => pattern.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+
+        pattern.push('$');
+        Self::new(glob, &pattern)
+    }
+
+    #[must_use]
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+}
+
+impl PartialEq for FilterRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for FilterRegex {}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EntryFilter {
     All,
@@ -52,6 +296,8 @@ pub enum EntryFilter {
     DirsOnly,
     Extension(String),
     Pattern(String),
+    Glob(FilterRegex),
+    Regex(FilterRegex),
     Custom(String), // plugin/user script
 }
 
@@ -67,12 +313,244 @@ impl std::fmt::Display for EntryFilter {
             Self::Extension(s) 
             | Self::Pattern(s) 
             | Self::Custom(s) => s,
+            
+            Self::Glob(re) => &re.source,
+            
+            Self::Regex(re) => return write!(f, "/{}", re.source),
         };
 
         write!(f, "{s}")
     }
 }
 
+impl EntryFilter {
+    /// Parse quick-filter input: `/re` is a regex, text containing `*`, `?`
+    /// or `[` is a glob, anything else a substring. Empty input clears the
+    /// filter.
+    pub fn parse_quick(query: &str) -> Result<Self, regex::Error> {
+        if query.is_empty() {
+            return Ok(Self::All);
+        }
+
+        if let Some(pattern) = query.strip_prefix('/') {
+            return FilterRegex::new(pattern, pattern).map(Self::Regex);
+        }
+
+        if query.contains(['*', '?', '[']) {
+            return FilterRegex::from_glob(query).map(Self::Glob);
+        }
+
+        Ok(Self::Pattern(query.to_string()))
+    }
+
+    #[must_use]
+    /// The filter as quick-filter input (empty for non-text filters).
+    pub fn query(&self) -> String {
+        match self {
+            Self::Pattern(_) | Self::Glob(_) | Self::Regex(_) => self.to_string(),
+
+            _ => String::new(),
+        }
+    }
+
+    #[must_use]
+    /// Whether `entry` passes this filter.
+    pub fn matches(&self, entry: &ObjectInfo) -> bool {
+        match self {
+            Self::All | Self::Custom(_) => true,
+
+            Self::FilesOnly => !entry.is_dir,
+
+            Self::DirsOnly => entry.is_dir,
+
+            Self::Extension(ext) => entry
+                .extension
+                .as_ref()
+                .is_some_and(|e| -> bool { e.eq_ignore_ascii_case(ext) }),
+
+            Self::Pattern(pattern) => entry
+                .name
+                .to_string_lossy()
+                .to_lowercase()
+                .contains(&pattern.to_lowercase()),
+
+            Self::Glob(re) | Self::Regex(re) => re.is_match(&entry.name.to_string_lossy()),
+        }
+    }
+}
+
+/// Sort `entries` in place by `sort`.
+pub fn sort_objects(entries: &mut [ObjectInfo], sort: &EntrySort, config: &SortConfig) {
+    entries.sort_by(|a: &ObjectInfo, b: &ObjectInfo| -> Ordering {
+        compare_objects(a, b, sort, config)
+    });
+}
+
+/// Total order used by every listing: directories first (if enabled), then
+/// the sort mode's key(s), then the name and finally the path so that ties
+/// never depend on scan order.
+#[must_use]
+pub fn compare_objects(a: &ObjectInfo, b: &ObjectInfo, sort: &EntrySort, config: &SortConfig) -> Ordering {
+    if config.dirs_first && a.is_dir != b.is_dir {
+        return b.is_dir.cmp(&a.is_dir);
+    }
+
+    let primary: Ordering = match (sort, sort.key()) {
+        (EntrySort::Custom(custom), _) => custom.compare(a, b, config),
+
+        (_, Some((key, false))) => compare_key(a, b, key, config),
+
+        (_, Some((key, true))) => compare_key(a, b, key, config).reverse(),
+
+        (_, None) => Ordering::Equal,
+    };
+
+    primary
+        .then_with(|| -> Ordering { compare_names(&a.name.to_string_lossy(), &b.name.to_string_lossy(), config) })
+        .then_with(|| -> Ordering { a.name.cmp(&b.name) })
+        .then_with(|| -> Ordering { a.path.cmp(&b.path) })
+}
+
+/// Ascending comparison of a single key.
+#[must_use]
+pub fn compare_key(a: &ObjectInfo, b: &ObjectInfo, key: SortKey, config: &SortConfig) -> Ordering {
+    match key {
+        SortKey::Name => compare_names(&a.name.to_string_lossy(), &b.name.to_string_lossy(), config),
+
+        SortKey::Size => a.size.cmp(&b.size),
+
+        SortKey::Modified => a.modified.cmp(&b.modified),
+
+        SortKey::Created => a.created.cmp(&b.created),
+
+        SortKey::Extension => {
+            let ext_a: &str = a.extension.as_deref().unwrap_or("");
+            let ext_b: &str = b.extension.as_deref().unwrap_or("");
+
+            compare_names(ext_a, ext_b, config)
+        }
+
+        SortKey::Type => type_rank(a).cmp(&type_rank(b)),
+
+        SortKey::Items => a.items_count.cmp(&b.items_count),
+    }
+}
+
+/// Directories, then symlinks, then regular files.
+const fn type_rank(entry: &ObjectInfo) -> u8 {
+    match entry.object_type() {
+        ObjectType::Dir => 0,
+        ObjectType::Symlink => 1,
+        ObjectType::File => 2,
+    }
+}
+
+/// Compare two names honouring the natural and case-sensitivity settings.
+#[must_use]
+pub fn compare_names(a: &str, b: &str, config: &SortConfig) -> Ordering {
+    if config.natural {
+        return natural_cmp(a, b, config.case_sensitive);
+    }
+
+    if config.case_sensitive {
+        a.cmp(b)
+    } else {
+        a.chars()
+            .flat_map(char::to_lowercase)
+            .cmp(b.chars().flat_map(char::to_lowercase))
+    }
+}
+
+/// Version-aware comparison: runs of ASCII digits compare by numeric value,
+/// so `file2` sorts before `file10`. Equal values with different zero
+/// padding (`01` vs `1`) fall back to the shorter run first.
+#[must_use]
+pub fn natural_cmp(a: &str, b: &str, case_sensitive: bool) -> Ordering {
+    let (mut a, mut b): (&str, &str) = (a, b);
+
+    loop {
+        let (Some(ca), Some(cb)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+
+        let ordering: Ordering = if ca.is_ascii_digit() && cb.is_ascii_digit() {
+            let (run_a, rest_a): (&str, &str) = split_digits(a);
+            let (run_b, rest_b): (&str, &str) = split_digits(b);
+            (a, b) = (rest_a, rest_b);
+
+            let value_a: &str = run_a.trim_start_matches('0');
+            let value_b: &str = run_b.trim_start_matches('0');
+
+            value_a
+                .len()
+                .cmp(&value_b.len())
+                .then_with(|| -> Ordering { value_a.cmp(value_b) })
+                .then_with(|| -> Ordering { run_a.len().cmp(&run_b.len()) })
+        } else {
+            (a, b) = (&a[ca.len_utf8()..], &b[cb.len_utf8()..]);
+
+            if case_sensitive || ca == cb {
+                ca.cmp(&cb)
+            } else if ca.is_ascii() && cb.is_ascii() {
+                ca.to_ascii_lowercase().cmp(&cb.to_ascii_lowercase())
+            } else {
+                ca.to_lowercase().cmp(cb.to_lowercase())
+            }
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Split `s` into its leading run of ASCII digits and the rest.
+fn split_digits(s: &str) -> (&str, &str) {
+    let end: usize = s
+        .bytes()
+        .position(|b: u8| -> bool { !b.is_ascii_digit() })
+        .unwrap_or(s.len());
+
+    s.split_at(end)
+}
+
+/// A visited directory together with where the user was inside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    /// Directory that was visited.
+    pub path: PathBuf,
+
+    /// Path of the entry that was selected (survives re-sorting).
+    pub selected_path: Option<PathBuf>,
+
+    /// Selected index, used when `selected_path` no longer exists.
+    pub selected: Option<usize>,
+
+    /// First visible row at the time the directory was left.
+    pub scroll_offset: usize,
+}
+
+impl HistoryEntry {
+    #[must_use]
+    /// Entry for `path` with no remembered position.
+    pub const fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            selected_path: None,
+            selected: None,
+            scroll_offset: 0,
+        }
+    }
+}
+
+/// Where a history entry sits relative to the pane's current directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryPosition {
+    Back,
+    Current,
+    Forward,
+}
+
 /// Single pane state (e.g., for dual/multi-pane UI)
 #[derive(Debug, Clone)]
 pub struct PaneState {
@@ -97,6 +575,9 @@ pub struct PaneState {
     /// Applied sort mode for entries.
     pub sort: EntrySort,
 
+    /// Ordering rules applied on top of `sort` (shared by all panes).
+    pub sort_config: SortConfig,
+
     /// Active filter mode.
     pub filter: EntryFilter,
 
@@ -109,14 +590,43 @@ pub struct PaneState {
     /// Number of visible rows in the viewport
     pub viewport_height: usize,
 
-    /// Incremental loading state
+    /// Streamed entries not yet merged into `entries` (see
+    /// `merge_incremental_entries`).
     pub incremental_entries: Vec<ObjectInfo>,
 
     /// Whether we're currently in incremental loading mode
     pub is_incremental_loading: bool,
 
+    /// Scan streaming into this pane; updates of other scans are dropped
+    pub scan_id: Option<u64>,
+
     /// Total expected entries (if known)
     pub expected_entries: Option<usize>,
+
+    /// Directories to return to with "back" (most recent last).
+    pub back_stack: Vec<HistoryEntry>,
+
+    /// Directories to return to with "forward" (most recent last).
+    pub forward_stack: Vec<HistoryEntry>,
+
+    /// Position to restore once the directory being loaded completes.
+    pub pending_restore: Option<HistoryEntry>,
+
+    /// Expandable tree state; `entries` is the flattened tree when set.
+    pub tree: Option<TreeState>,
+
+    /// Entries hidden by `filter` (flat view), kept so they still receive
+    /// metadata updates and reappear when the filter changes.
+    pub filtered_out: Vec<ObjectInfo>,
+
+    /// Entry that was selected before the filter was set; reselected when
+    /// the filter is cleared.
+    pub filter_anchor: Option<PathBuf>,
+
+    /// Entry visual mode was entered on, with the index it was last found
+    /// at. The range runs from it to the selection and follows its entries
+    /// when rows are inserted or re-sorted.
+    pub visual_anchor: Option<(PathBuf, usize)>,
 }
 
 impl PaneState {
@@ -130,13 +640,22 @@ impl PaneState {
             is_loading: false,
             last_error: None,
             sort: EntrySort::NameAsc,
+            sort_config: SortConfig::default(),
             filter: EntryFilter::All,
             table_state: TableState::default(),
             scroll_offset: 0,
             viewport_height: 20, // Default viewport height
             incremental_entries: Vec::new(),
             is_incremental_loading: false,
+            scan_id: None,
             expected_entries: None,
+            back_stack: Vec::new(),
+            forward_stack: Vec::new(),
+            pending_restore: None,
+            tree: None,
+            filtered_out: Vec::new(),
+            filter_anchor: None,
+            visual_anchor: None,
         }
     }
 
@@ -251,144 +770,859 @@ impl PaneState {
         }
     }
 
-    /// Start incremental loading mode
-    pub fn start_incremental_loading(&mut self) {
+    /// Scroll the viewport by `delta` rows (mouse wheel), stopping at either
+    /// end. The selection moves only as far as needed to stay visible.
+    pub fn scroll_viewport(&mut self, delta: isize) {
+        let max_offset: usize = self.entries.len().saturating_sub(self.viewport_height);
+        self.scroll_offset = self.scroll_offset.saturating_add_signed(delta).min(max_offset);
+
+        if let Some(selected) = self.selected
+            && !self.entries.is_empty()
+        {
+            let last_visible: usize = (self.scroll_offset + self.viewport_height.max(1) - 1)
+                .min(self.entries.len() - 1);
+            let clamped: usize = selected.clamp(self.scroll_offset, last_visible);
+
+            self.selected = Some(clamped);
+            self.table_state.select(Some(clamped - self.scroll_offset));
+        }
+    }
+
+    /// Start incremental loading mode, fed by the scan with id `scan`
+    pub fn start_incremental_loading(&mut self, scan: u64) {
         self.is_incremental_loading = true;
+        self.scan_id = Some(scan);
         self.incremental_entries.clear();
         self.expected_entries = None;
         self.is_loading = true;
+        self.entries.clear();
+        self.filtered_out.clear();
+        self.selected = Some(0);
+        self.scroll_offset = 0;
+        self.table_state.select(Some(0));
+
+        // Expanded subtrees survive a reload of the same directory
+        if let Some(tree) = self.tree.as_mut() {
+            tree.reset(&self.cwd);
+        }
     }
 
     /// Add an entry during incremental loading
     pub fn add_incremental_entry(&mut self, entry: ObjectInfo) {
         if self.is_incremental_loading {
-            self.incremental_entries.push(entry);
-            // Update displayed entries with current incremental state
-            self.entries = self.incremental_entries.clone();
-            // Sort incrementally (basic sorting for now)
-            self.sort_entries();
-        }
-    }
-
-    /// Complete incremental loading with final sorted entries
-    pub fn complete_incremental_loading(&mut self, final_entries: Vec<ObjectInfo>) {
-        self.is_incremental_loading = false;
-        self.is_loading = false;
-        self.entries = final_entries;
-        self.incremental_entries.clear();
+            if let Some(tree) = self.tree.as_mut() {
+                let cwd: PathBuf = self.cwd.clone();
 
-        // Reset selection to first item if we have entries
-        if !self.entries.is_empty() {
-            self.selected = Some(0);
-            self.scroll_offset = 0;
-            self.table_state.select(Some(0));
-        }
-    }
+                if tree.add_child(&cwd, entry) {
+                    self.rebuild_tree();
+                }
 
-    /// Sort entries in place based on current sort mode
-    pub fn sort_entries(&mut self) {
-        match self.sort {
-            EntrySort::NameAsc => {
-                self.entries.sort_by(|a, b| {
-                    if a.is_dir && !b.is_dir {
-                        std::cmp::Ordering::Less
-                    } else if !a.is_dir && b.is_dir {
-                        std::cmp::Ordering::Greater
-                    } else {
-                        a.name.cmp(&b.name)
-                    }
-                });
-            }
-            EntrySort::NameDesc => {
-                self.entries.sort_by(|a, b| {
-                    if a.is_dir && !b.is_dir {
-                        std::cmp::Ordering::Less
-                    } else if !a.is_dir && b.is_dir {
-                        std::cmp::Ordering::Greater
-                    } else {
-                        b.name.cmp(&a.name)
-                    }
-                });
-            }
-            EntrySort::SizeAsc => {
-                self.entries.sort_by(|a, b| a.size.cmp(&b.size));
+                return;
             }
-            EntrySort::SizeDesc => {
-                self.entries.sort_by(|a, b| b.size.cmp(&a.size));
-            }
-            EntrySort::ModifiedAsc => {
-                self.entries.sort_by(|a, b| a.modified.cmp(&b.modified));
+
+            // Hidden rows are set aside, the listing shows matches only
+            if !self.filter.matches(&entry) {
+                self.filtered_out.push(entry);
+                return;
             }
-            EntrySort::ModifiedDesc => {
-                self.entries.sort_by(|a, b| b.modified.cmp(&a.modified));
+
+            // Small listings take a sorted insert; larger ones buffer and merge
+            if self.entries.len() < DIRECT_INSERT_LIMIT && self.incremental_entries.is_empty() {
+                self.insert_sorted(entry);
+                return;
             }
-            EntrySort::Custom(_) => {
-                // For custom sorting, keep current order for now
+
+            self.incremental_entries.push(entry);
+
+            if self.incremental_entries.len() * MERGE_RATIO >= self.entries.len() {
+                self.merge_incremental_entries();
             }
         }
     }
-}
 
-/// Persistent, advanced FS state for the app/session.
-#[derive(Debug, Clone)]
-pub struct FSState {
-    /// One or more open panes (for dual-pane, etc.).
-    pub panes: Vec<PaneState>,
-    /// Which pane is currently focused.
-    pub active_pane: usize,
-    /// Batch operation progress (for power-user bulk actions).
-    pub batch_op_status: Option<String>,
-    /// Set of favorite/recent directories.
-    pub recent_dirs: VecDeque<PathBuf>,
-    pub favorite_dirs: HashSet<PathBuf>,
-}
+    /// Insert `entry` at its sorted position (binary search).
+    fn insert_sorted(&mut self, entry: ObjectInfo) {
+        let anchor: Option<usize> = self.selection_anchor();
 
+        let idx: usize = self.entries.partition_point(|e: &ObjectInfo| -> bool {
+            compare_objects(e, &entry, &self.sort, &self.sort_config).is_lt()
+        });
+        self.entries.insert(idx, entry);
 
-impl FSState {
-    #[must_use]
-    /// Construct FS state with one pane in the given directory.
-    pub fn new(cwd: PathBuf) -> Self {
-        Self {
-            panes: vec![PaneState::new(cwd)],
-            active_pane: 0,
-            batch_op_status: None,
-            recent_dirs: VecDeque::with_capacity(32),
-            favorite_dirs: HashSet::new(),
+        if let Some(old) = anchor
+            && idx <= old
+        {
+            self.shift_selection(old, old + 1);
         }
     }
 
-    /// Get the currently active pane as mutable.
-    pub fn active_pane_mut(&mut self) -> &mut PaneState {
-        &mut self.panes[self.active_pane]
-    }
+    /// Merge buffered streamed entries into `entries`: the batch is sorted
+    /// on its own and merged in one linear pass, moving (not cloning) rows.
+    pub fn merge_incremental_entries(&mut self) {
+        if self.incremental_entries.is_empty() {
+            return;
+        }
 
-    #[must_use]
-    /// Get the currently active pane as immutable.
-    pub fn active_pane(&self) -> &PaneState {
-        &self.panes[self.active_pane]
-    }
+        let mut batch: Vec<ObjectInfo> = std::mem::take(&mut self.incremental_entries);
+        sort_objects(&mut batch, &self.sort, &self.sort_config);
 
-    /// Switch focus to a different pane.
-    pub const fn set_active_pane(&mut self, idx: usize) {
-        if idx < self.panes.len() {
-            self.active_pane = idx;
-        }
-    }
+        let anchor: Option<usize> = self.selection_anchor();
+        let current: Vec<ObjectInfo> = std::mem::take(&mut self.entries);
+        let mut merged: Vec<ObjectInfo> = Vec::with_capacity(current.len() + batch.len());
+        let mut anchor_moved_to: Option<usize> = None;
+        let mut taken: usize = 0;
 
-    /// Add a path to recents (evicts oldest if over 32).
-    pub fn add_recent_dir(&mut self, path: PathBuf) {
-        if self.recent_dirs.len() == 32 {
-            self.recent_dirs.pop_front();
+        let mut current = current.into_iter().peekable();
+        let mut batch = batch.into_iter().peekable();
+
+        loop {
+            let from_batch: bool = match (current.peek(), batch.peek()) {
+                (Some(a), Some(b)) => compare_objects(b, a, &self.sort, &self.sort_config).is_lt(),
+
+                (Some(_), None) => false,
+
+                (None, Some(_)) => true,
+
+                (None, None) => break,
+            };
+
+            if from_batch {
+                merged.extend(batch.next());
+            } else {
+                if anchor == Some(taken) {
+                    anchor_moved_to = Some(merged.len());
+                }
+
+                taken += 1;
+                merged.extend(current.next());
+            }
+        }
+
+        self.entries = merged;
+
+        if let (Some(old), Some(new)) = (anchor, anchor_moved_to) {
+            self.shift_selection(old, new);
+        }
+    }
+
+    /// Index of the selected row when it should follow its entry while rows
+    /// stream in. The top row stays on top, so a fresh listing starts at the
+    /// first entry in sort order rather than the first one scanned.
+    fn selection_anchor(&self) -> Option<usize> {
+        // An open visual range keeps even the top row on its entry
+        self.selected.filter(|&idx: &usize| -> bool {
+            (idx > 0 || self.visual_anchor.is_some()) && idx < self.entries.len()
+        })
+    }
+
+    /// Anchor a visual range at the selected entry.
+    pub fn start_visual(&mut self) {
+        self.visual_anchor = self
+            .selected
+            .and_then(|idx: usize| -> Option<(PathBuf, usize)> {
+                self.entries.get(idx).map(|e: &ObjectInfo| -> (PathBuf, usize) { (e.path.clone(), idx) })
+            });
+    }
+
+    /// Drop the visual range.
+    pub fn end_visual(&mut self) {
+        self.visual_anchor = None;
+    }
+
+    /// Rows of the visual range, inclusive and in order. The anchor is
+    /// looked up again when rows moved; if its entry is gone, the entry now
+    /// at its last index becomes the anchor.
+    pub fn visual_range(&mut self) -> Option<(usize, usize)> {
+        let selected: usize = self.selected?;
+        let (path, idx) = self.visual_anchor.as_mut()?;
+        let last_idx: usize = self.entries.len().checked_sub(1)?;
+
+        if self.entries.get(*idx).is_none_or(|e: &ObjectInfo| -> bool { e.path != *path }) {
+            match self.entries.iter().position(|e: &ObjectInfo| -> bool { e.path == *path }) {
+                Some(found) => *idx = found,
+
+                None => {
+                    *idx = (*idx).min(last_idx);
+                    path.clone_from(&self.entries[*idx].path);
+                }
+            }
+        }
+
+        let selected: usize = selected.min(last_idx);
+
+        Some(((*idx).min(selected), (*idx).max(selected)))
+    }
+
+    /// Paths of the entries in the visual range.
+    pub fn visual_paths(&mut self) -> Vec<PathBuf> {
+        self.visual_range()
+            .map(|(start, end)| -> Vec<PathBuf> {
+                self.entries[start..=end]
+                    .iter()
+                    .map(|e: &ObjectInfo| -> PathBuf { e.path.clone() })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Put `paths` in the order their rows are shown. Paths without a row
+    /// (marks in other directories) go last, keeping their relative order.
+    pub fn sort_by_display_order(&self, paths: &mut [PathBuf]) {
+        let rows: HashMap<&Path, usize> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, e): (usize, &ObjectInfo)| -> (&Path, usize) { (e.path.as_path(), idx) })
+            .collect();
+
+        paths.sort_by_key(|path: &PathBuf| -> usize { rows.get(path.as_path()).copied().unwrap_or(usize::MAX) });
+    }
+
+    /// Move the selection from `old` to `new` and scroll by the same amount,
+    /// so the selected row keeps its place on screen.
+    fn shift_selection(&mut self, old: usize, new: usize) {
+        self.scroll_offset = (self.scroll_offset + new).saturating_sub(old);
+        self.selected = Some(new);
+        self.adjust_scroll();
+        self.table_state.select(Some(new.saturating_sub(self.scroll_offset)));
+    }
+
+    /// Finish incremental loading: merge what is still buffered, or rebuild
+    /// the view in tree mode, where the top level was collected while
+    /// streaming.
+    pub fn complete_incremental_loading(&mut self) {
+        self.merge_incremental_entries();
+        self.is_incremental_loading = false;
+        self.scan_id = None;
+        self.is_loading = false;
+
+        if let Some(tree) = self.tree.as_mut() {
+            self.entries = tree.flatten(&self.sort, &self.sort_config, &self.filter);
+        }
+
+        // Restore the remembered position; otherwise the selection stayed
+        // on its entry while streaming
+        if let Some(position) = self.pending_restore.take()
+            && position.path == self.cwd
+        {
+            self.restore_position(&position);
+        } else if self.selected.is_none_or(|idx: usize| -> bool { idx >= self.entries.len() }) {
+            self.select_first();
+        }
+    }
+
+    /// Stop incremental loading after a scan error, keeping what arrived.
+    pub fn abort_incremental_loading(&mut self, error: String) {
+        self.merge_incremental_entries();
+        self.is_incremental_loading = false;
+        self.scan_id = None;
+        self.is_loading = false;
+        self.last_error = Some(error);
+    }
+
+    #[must_use]
+    /// Snapshot of the current directory and selection for the history stacks.
+    pub fn current_position(&self) -> HistoryEntry {
+        HistoryEntry {
+            path: self.cwd.clone(),
+            selected_path: self.selected_entry().map(|e: &ObjectInfo| -> PathBuf { e.path.clone() }),
+            selected: self.selected,
+            scroll_offset: self.scroll_offset,
+        }
+    }
+
+    /// Select the remembered entry (by path, falling back to index) and
+    /// restore the scroll offset, clamped to the current entry count.
+    pub fn restore_position(&mut self, position: &HistoryEntry) {
+        if self.entries.is_empty() {
+            self.selected = Some(0);
+            self.scroll_offset = 0;
+            self.table_state.select(Some(0));
+            return;
+        }
+
+        let last_idx: usize = self.entries.len() - 1;
+        let by_path: Option<usize> = position.selected_path.as_ref().and_then(|p: &PathBuf| {
+            self.entries.iter().position(|e: &ObjectInfo| -> bool { &e.path == p })
+        });
+        let selected: usize = by_path
+            .or(position.selected)
+            .unwrap_or(0)
+            .min(last_idx);
+
+        self.selected = Some(selected);
+        self.scroll_offset = position.scroll_offset.min(selected);
+        self.adjust_scroll();
+        self.table_state.select(Some(selected - self.scroll_offset));
+    }
+
+    /// Record the current position before navigating to a new directory.
+    /// Starting a new branch of history drops the forward stack.
+    pub fn push_history(&mut self) {
+        // Nothing meaningful to remember before the first real directory load
+        if !self.cwd.is_absolute() {
+            return;
+        }
+
+        let position: HistoryEntry = self.current_position();
+        Self::push_bounded(&mut self.back_stack, position);
+        self.forward_stack.clear();
+    }
+
+    /// Step `steps` entries back, moving the skipped positions onto the
+    /// forward stack. Returns the target entry.
+    pub fn history_back(&mut self, steps: usize) -> Option<HistoryEntry> {
+        if steps == 0 || steps > self.back_stack.len() {
+            return None;
+        }
+
+        let mut current: HistoryEntry = self.current_position();
+        for _ in 0..steps {
+            let target: HistoryEntry = self.back_stack.pop()?;
+            Self::push_bounded(&mut self.forward_stack, current);
+            current = target;
+        }
+
+        Some(current)
+    }
+
+    /// Step `steps` entries forward, moving the skipped positions onto the
+    /// back stack. Returns the target entry.
+    pub fn history_forward(&mut self, steps: usize) -> Option<HistoryEntry> {
+        if steps == 0 || steps > self.forward_stack.len() {
+            return None;
+        }
+
+        let mut current: HistoryEntry = self.current_position();
+        for _ in 0..steps {
+            let target: HistoryEntry = self.forward_stack.pop()?;
+            Self::push_bounded(&mut self.back_stack, current);
+            current = target;
+        }
+
+        Some(current)
+    }
+
+    /// Jump to an index of `history_entries`, returning the target entry.
+    pub fn history_jump(&mut self, index: usize) -> Option<HistoryEntry> {
+        let back_len: usize = self.back_stack.len();
+
+        match index.cmp(&back_len) {
+            std::cmp::Ordering::Less => self.history_back(back_len - index),
+
+            std::cmp::Ordering::Equal => None,
+
+            std::cmp::Ordering::Greater => self.history_forward(index - back_len),
+        }
+    }
+
+    #[must_use]
+    /// Full history, oldest first: back stack, current directory, forward stack.
+    pub fn history_entries(&self) -> Vec<(HistoryPosition, &Path)> {
+        let back = self
+            .back_stack
+            .iter()
+            .map(|e: &HistoryEntry| -> (HistoryPosition, &Path) { (HistoryPosition::Back, &e.path) });
+
+        let forward = self
+            .forward_stack
+            .iter()
+            .rev()
+            .map(|e: &HistoryEntry| -> (HistoryPosition, &Path) { (HistoryPosition::Forward, &e.path) });
+
+        back.chain(std::iter::once((HistoryPosition::Current, self.cwd.as_path())))
+            .chain(forward)
+            .collect()
+    }
+
+    /// Drop an entry of `history_entries` (e.g. a directory that was deleted).
+    pub fn forget_history(&mut self, index: usize) {
+        let back_len: usize = self.back_stack.len();
+
+        if index < back_len {
+            self.back_stack.remove(index);
+        } else if index > back_len
+            && let Some(forward_idx) = self.forward_stack.len().checked_sub(index - back_len)
+        {
+            self.forward_stack.remove(forward_idx);
+        }
+    }
+
+    #[must_use]
+    /// Most recently remembered position inside `path`, if any.
+    pub fn remembered_position(&self, path: &Path) -> Option<HistoryEntry> {
+        self.back_stack
+            .iter()
+            .rev()
+            .chain(self.forward_stack.iter().rev())
+            .find(|e: &&HistoryEntry| -> bool { e.path == path })
+            .cloned()
+    }
+
+    fn push_bounded(stack: &mut Vec<HistoryEntry>, entry: HistoryEntry) {
+        if stack.len() == HISTORY_LIMIT {
+            stack.remove(0);
+        }
+        stack.push(entry);
+    }
+
+    /// Sort entries in place based on current sort mode
+    pub fn sort_entries(&mut self) {
+        if self.tree.is_some() {
+            self.rebuild_tree();
+        } else {
+            sort_objects(&mut self.entries, &self.sort, &self.sort_config);
+        }
+    }
+
+    /// Switch to a new sort mode, keeping the selected entry selected.
+    pub fn set_sort(&mut self, sort: EntrySort) {
+        self.sort = sort;
+        self.resort();
+    }
+
+    /// Replace the shared ordering rules, keeping the selected entry selected.
+    pub fn set_sort_config(&mut self, sort_config: SortConfig) {
+        if self.sort_config == sort_config {
+            return;
+        }
+
+        self.sort_config = sort_config;
+        self.resort();
+    }
+
+    fn resort(&mut self) {
+        self.merge_incremental_entries();
+
+        let selected: Option<PathBuf> = self.selected_entry().map(|e: &ObjectInfo| -> PathBuf { e.path.clone() });
+
+        self.sort_entries();
+        self.select_path(selected.as_deref());
+    }
+
+    /// Apply a new filter. The selection is kept where possible; clearing
+    /// the filter reselects the entry that was selected before filtering.
+    pub fn set_filter(&mut self, filter: EntryFilter) {
+        if self.filter == EntryFilter::All && filter != EntryFilter::All {
+            self.filter_anchor = self.selected_entry().map(|e: &ObjectInfo| -> PathBuf { e.path.clone() });
+        }
+
+        let clearing: bool = filter == EntryFilter::All;
+        self.filter = filter;
+
+        if self.tree.is_some() {
+            self.rebuild_tree();
+        } else {
+            self.apply_filter();
+        }
+
+        if clearing && let Some(anchor) = self.filter_anchor.take() {
+            self.select_path(Some(&anchor));
+        }
+    }
+
+    /// Re-partition all entries (shown and hidden) by the current filter.
+    fn apply_filter(&mut self) {
+        self.merge_incremental_entries();
+
+        let selected: Option<PathBuf> = self.selected_entry().map(|e: &ObjectInfo| -> PathBuf { e.path.clone() });
+
+        let mut all: Vec<ObjectInfo> = std::mem::take(&mut self.entries);
+        all.append(&mut self.filtered_out);
+
+        let filter: &EntryFilter = &self.filter;
+        let (mut shown, hidden): (Vec<ObjectInfo>, Vec<ObjectInfo>) = all
+            .into_iter()
+            .partition(|e: &ObjectInfo| -> bool { filter.matches(e) });
+
+        sort_objects(&mut shown, &self.sort, &self.sort_config);
+        self.entries = shown;
+        self.filtered_out = hidden;
+
+        self.select_path(selected.as_deref());
+    }
+
+    #[must_use]
+    /// Number of loaded entries hidden by the filter.
+    pub fn hidden_count(&self) -> usize {
+        self.tree
+            .as_ref()
+            .map_or(self.filtered_out.len(), |t: &TreeState| -> usize { t.hidden })
+    }
+
+    /// Forget the filter (e.g. when the pane moves to another directory).
+    pub fn reset_filter(&mut self) {
+        self.filter = EntryFilter::All;
+        self.filter_anchor = None;
+        self.filtered_out.clear();
+    }
+
+    #[must_use]
+    /// True when the pane shows an expandable tree instead of a flat list.
+    pub const fn is_tree_view(&self) -> bool {
+        self.tree.is_some()
+    }
+
+    /// Switch between flat and tree view. The top level of the tree is the
+    /// current entry list; leaving tree mode keeps only that level.
+    pub fn toggle_tree(&mut self) {
+        match self.tree.take() {
+            Some(mut tree) => {
+                let selected: Option<PathBuf> = self.selected_path_at_depth_zero(&tree);
+                self.entries = tree.levels.remove(&self.cwd).unwrap_or_default();
+                self.apply_filter();
+                self.select_path(selected.as_deref());
+            }
+
+            None => {
+                self.merge_incremental_entries();
+
+                let selected: Option<PathBuf> = self.selected_entry().map(|e: &ObjectInfo| -> PathBuf { e.path.clone() });
+                let mut entries: Vec<ObjectInfo> = std::mem::take(&mut self.entries);
+                entries.append(&mut self.filtered_out);
+                self.tree = Some(TreeState::new(self.cwd.clone(), entries));
+                self.rebuild_tree();
+                self.select_path(selected.as_deref());
+            }
+        }
+    }
+
+    /// Expand the directory at `path`. Returns true if its children must be
+    /// scanned first.
+    pub fn expand_tree_dir(&mut self, path: &Path) -> bool {
+        let Some(tree) = self.tree.as_mut() else {
+            return false;
+        };
+
+        let needs_scan: bool = tree.expand(path);
+        self.rebuild_tree();
+
+        needs_scan
+    }
+
+    /// Collapse the selected directory, or select its parent row when the
+    /// selection is not an expanded directory. Returns false at the top level.
+    pub fn collapse_tree_selection(&mut self) -> bool {
+        let Some(idx) = self.selected else {
+            return false;
+        };
+
+        let Some(tree) = self.tree.as_mut() else {
+            return false;
+        };
+
+        let Some(row) = tree.rows.get(idx) else {
+            return false;
+        };
+
+        if row.expanded {
+            let path: PathBuf = self.entries[idx].path.clone();
+            tree.collapse(&path);
+            self.rebuild_tree();
+            return true;
+        }
+
+        let depth: usize = row.depth;
+        if depth == 0 {
+            return false;
+        }
+
+        // The parent is the closest shallower row above the selection
+        let parent: Option<usize> = tree.rows[..idx]
+            .iter()
+            .rposition(|r: &TreeRow| -> bool { r.depth < depth });
+
+        if let Some(parent) = parent {
+            self.select_index(parent);
+        }
+
+        parent.is_some()
+    }
+
+    /// Add a streamed child of an expanded directory.
+    pub fn add_tree_child(&mut self, parent: &Path, entry: ObjectInfo) {
+        if let Some(tree) = self.tree.as_mut()
+            && tree.add_child(parent, entry)
+        {
+            self.rebuild_tree();
+        }
+    }
+
+    /// Finish loading the children of `parent`.
+    pub fn complete_tree_children(&mut self, parent: &Path) {
+        if let Some(tree) = self.tree.as_mut() {
+            tree.complete(parent);
+            self.rebuild_tree();
+        }
+    }
+
+    /// Drop a directory whose children could not be loaded.
+    pub fn fail_tree_children(&mut self, parent: &Path, error: String) {
+        if let Some(tree) = self.tree.as_mut() {
+            tree.fail(parent);
+            self.last_error = Some(error);
+            self.rebuild_tree();
+        }
+    }
+
+    #[must_use]
+    /// True when `dir` is the pane's directory or a loaded tree level.
+    pub fn shows_dir(&self, dir: &Path) -> bool {
+        self.cwd == dir
+            || self
+                .tree
+                .as_ref()
+                .is_some_and(|t: &TreeState| -> bool { t.levels.contains_key(dir) })
+    }
+
+    /// Apply freshly loaded metadata of an entry of `parent_dir`, both to
+    /// the displayed list and to the tree level it belongs to.
+    pub fn update_entry(&mut self, parent_dir: &Path, info: &ObjectInfo) -> bool {
+        let mut updated: bool = false;
+
+        if let Some(tree) = self.tree.as_mut() {
+            updated = tree.update_entry(parent_dir, info);
+        }
+
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .chain(self.incremental_entries.iter_mut())
+            .chain(self.filtered_out.iter_mut())
+            .find(|e: &&mut ObjectInfo| -> bool { e.path == info.path })
+        {
+            entry.apply_metadata(info);
+
+            updated = true;
+        }
+
+        updated
+    }
+
+    /// Patch the rows of `dir` after filesystem changes instead of reloading
+    /// the listing. The selection stays on its entry, or on the same row when
+    /// that entry was removed. Returns true if anything changed.
+    pub fn apply_fs_changes(&mut self, dir: &Path, changes: &[FsChange], show_hidden: bool) -> bool {
+        // A running scan picks the changes up itself
+        if self.is_incremental_loading {
+            return false;
+        }
+
+        if let Some(tree) = self.tree.as_mut() {
+            let changed: bool = tree.apply_changes(dir, changes, show_hidden);
+
+            if changed {
+                self.rebuild_tree();
+            }
+
+            return changed;
+        }
+
+        if dir != self.cwd {
+            return false;
+        }
+
+        let selected: Option<PathBuf> = self.selected_entry().map(|e: &ObjectInfo| -> PathBuf { e.path.clone() });
+        let mut changed: bool = false;
+
+        for change in changes {
+            let existing: Option<ObjectInfo> = self.take_row(change.path());
+            changed |= existing.is_some();
+
+            let FsChange::Upsert(info) = change else {
+                continue;
+            };
+
+            if !show_hidden && info.is_hidden() {
+                continue;
+            }
+
+            let entry: ObjectInfo = FsChange::refreshed(existing, info);
+
+            if self.filter.matches(&entry) {
+                let idx: usize = self.entries.partition_point(|e: &ObjectInfo| -> bool {
+                    compare_objects(e, &entry, &self.sort, &self.sort_config).is_lt()
+                });
+                self.entries.insert(idx, entry);
+            } else {
+                self.filtered_out.push(entry);
+            }
+
+            changed = true;
+        }
+
+        if changed {
+            self.select_path(selected.as_deref());
+        }
+
+        changed
+    }
+
+    /// Remove the row (shown or filtered out) for `path`.
+    fn take_row(&mut self, path: &Path) -> Option<ObjectInfo> {
+        if let Some(idx) = self.entries.iter().position(|e: &ObjectInfo| -> bool { e.path == path }) {
+            return Some(self.entries.remove(idx));
+        }
+
+        self.filtered_out
+            .iter()
+            .position(|e: &ObjectInfo| -> bool { e.path == path })
+            .map(|idx: usize| -> ObjectInfo { self.filtered_out.swap_remove(idx) })
+    }
+
+    /// Expanded directories that need their children (re)scanned, marked as
+    /// loading.
+    pub fn take_tree_pending_loads(&mut self) -> Vec<PathBuf> {
+        let Some(tree) = self.tree.as_mut() else {
+            return Vec::new();
+        };
+
+        let pending: Vec<PathBuf> = tree.pending_loads();
+        for dir in &pending {
+            tree.expand(dir);
+        }
+
+        pending
+    }
+
+    /// Rebuild the flattened tree view, keeping the selection on the same
+    /// path where possible.
+    pub fn rebuild_tree(&mut self) {
+        let Some(tree) = self.tree.as_mut() else {
+            return;
+        };
+
+        let selected: Option<PathBuf> = self
+            .selected
+            .and_then(|idx: usize| self.entries.get(idx))
+            .map(|e: &ObjectInfo| -> PathBuf { e.path.clone() });
+
+        self.entries = tree.flatten(&self.sort, &self.sort_config, &self.filter);
+        self.select_path(selected.as_deref());
+    }
+
+    /// Select the entry at `path`, falling back to the clamped current index.
+    fn select_path(&mut self, path: Option<&Path>) {
+        let idx: usize = path
+            .and_then(|p: &Path| {
+                self.entries.iter().position(|e: &ObjectInfo| -> bool { e.path == p })
+            })
+            .or(self.selected)
+            .unwrap_or(0);
+
+        self.select_index(idx.min(self.entries.len().saturating_sub(1)));
+    }
+
+    /// Select the entry at `idx` and scroll it into view.
+    pub fn select_index(&mut self, idx: usize) {
+        self.selected = Some(idx);
+        self.adjust_scroll();
+        self.table_state.select(Some(idx.saturating_sub(self.scroll_offset)));
+    }
+
+    /// Path of the selection's top-level ancestor (used when leaving tree
+    /// mode, where only the top level remains).
+    fn selected_path_at_depth_zero(&self, tree: &TreeState) -> Option<PathBuf> {
+        let idx: usize = self.selected?;
+        let top: usize = tree.rows[..=idx.min(tree.rows.len().checked_sub(1)?)]
+            .iter()
+            .rposition(|r: &TreeRow| -> bool { r.depth == 0 })?;
+
+        self.entries.get(top).map(|e: &ObjectInfo| -> PathBuf { e.path.clone() })
+    }
+}
+
+/// Persistent, advanced FS state for the app/session.
+#[derive(Debug, Clone)]
+pub struct FSState {
+    /// One or more open panes (for dual-pane, etc.).
+    pub panes: Vec<PaneState>,
+    /// Which pane is currently focused.
+    pub active_pane: usize,
+    /// Batch operation progress (for power-user bulk actions).
+    pub batch_op_status: Option<String>,
+    /// Marked files/directories by path for batch operations.
+    pub marked: HashSet<PathBuf>,
+}
+
+
+impl FSState {
+    #[must_use]
+    /// Construct FS state with one pane in the given directory.
+    pub fn new(cwd: PathBuf) -> Self {
+        Self {
+            panes: vec![PaneState::new(cwd)],
+            active_pane: 0,
+            batch_op_status: None,
+            marked: HashSet::new(),
+        }
+    }
+
+    /// Get the currently active pane as mutable.
+    pub fn active_pane_mut(&mut self) -> &mut PaneState {
+        &mut self.panes[self.active_pane]
+    }
+
+    #[must_use]
+    /// Get the currently active pane as immutable.
+    pub fn active_pane(&self) -> &PaneState {
+        &self.panes[self.active_pane]
+    }
+
+    /// Switch focus to a different pane.
+    pub const fn set_active_pane(&mut self, idx: usize) {
+        if idx < self.panes.len() {
+            self.active_pane = idx;
+        }
+    }
+
+    #[must_use]
+    /// True when the second (dual) pane is open.
+    pub const fn is_dual_pane(&self) -> bool {
+        self.panes.len() > 1
+    }
+
+    #[must_use]
+    /// Get the pane that does not have focus, if a second pane is open.
+    pub fn inactive_pane(&self) -> Option<&PaneState> {
+        if self.is_dual_pane() {
+            self.panes.get((self.active_pane + 1) % self.panes.len())
+        } else {
+            None
         }
-        self.recent_dirs.push_back(path);
     }
 
-    pub fn add_favorite(&mut self, path: PathBuf) {
-        self.favorite_dirs.insert(path);
+    /// Open a second pane as a copy of the active one (cwd, entries, sort,
+    /// filter and scroll). Focus stays on the current pane.
+    pub fn open_second_pane(&mut self) -> bool {
+        if self.is_dual_pane() {
+            return false;
+        }
+
+        let pane: PaneState = self.active_pane().clone();
+        self.panes.push(pane);
+
+        true
     }
 
-    pub fn remove_favorite(&mut self, path: &PathBuf) {
-        self.favorite_dirs.remove(path);
+    /// Close the pane that does not have focus, returning to single-pane mode.
+    pub fn close_inactive_pane(&mut self) -> bool {
+        if !self.is_dual_pane() {
+            return false;
+        }
+
+        let inactive: usize = (self.active_pane + 1) % self.panes.len();
+        self.panes.remove(inactive);
+        self.active_pane = self.active_pane.min(self.panes.len() - 1);
+
+        true
+    }
+
+    /// Move focus to the next pane (wraps around).
+    pub const fn focus_next_pane(&mut self) {
+        if !self.panes.is_empty() {
+            self.active_pane = (self.active_pane + 1) % self.panes.len();
+        }
     }
 }
 
@@ -397,3 +1631,625 @@ impl Default for FSState {
         Self::new(PathBuf::from("."))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(name: &str, is_dir: bool, size: u64) -> ObjectInfo {
+        ObjectInfo {
+            path: Path::new("/d").join(name),
+            name: name.into(),
+            extension: Path::new(name).extension().map(|ext: &std::ffi::OsStr| -> compact_str::CompactString { ext.to_string_lossy().into() }),
+            size,
+            is_dir,
+            ..ObjectInfo::default()
+        }
+    }
+
+    /// `count` files whose sorted order is their number, arriving in a
+    /// fixed scrambled order (7919 is prime, so every number comes once).
+    fn scrambled(count: usize) -> Vec<ObjectInfo> {
+        (0..count)
+            .map(|i: usize| -> ObjectInfo { object(&format!("f{:05}", (i * 7919 + 13) % count), false, 0) })
+            .collect()
+    }
+
+    fn streaming_pane() -> PaneState {
+        let mut pane: PaneState = PaneState::new(PathBuf::from("/d"));
+        pane.viewport_height = 10;
+        pane.start_incremental_loading(1);
+        pane
+    }
+
+    fn pane_names(pane: &PaneState) -> Vec<String> {
+        pane.entries
+            .iter()
+            .map(|e: &ObjectInfo| -> String { e.name.to_string_lossy().into_owned() })
+            .collect()
+    }
+
+    fn expected_names(count: usize) -> Vec<String> {
+        (0..count).map(|n: usize| -> String { format!("f{n:05}") }).collect()
+    }
+
+    /// Select `row` with the viewport scrolled so it shows on screen line 5.
+    fn select_row(pane: &mut PaneState, row: usize) {
+        pane.selected = Some(row);
+        pane.scroll_offset = row - 5;
+        pane.table_state.select(Some(5));
+    }
+
+    fn sorted_names(mut entries: Vec<ObjectInfo>, sort: &EntrySort, config: &SortConfig) -> Vec<String> {
+        sort_objects(&mut entries, sort, config);
+
+        entries
+            .iter()
+            .map(|e: &ObjectInfo| -> String { e.name.to_string_lossy().into_owned() })
+            .collect()
+    }
+
+    #[test]
+    fn natural_cmp_orders_digit_runs_by_value() {
+        assert_eq!(natural_cmp("file2", "file10", false), Ordering::Less);
+        assert_eq!(natural_cmp("file10", "file9", false), Ordering::Greater);
+        assert_eq!(natural_cmp("v1.10.0", "v1.9.3", false), Ordering::Greater);
+        assert_eq!(natural_cmp("a99999999999999999999999", "a100000000000000000000000", false), Ordering::Less);
+    }
+
+    #[test]
+    fn natural_cmp_puts_shorter_padding_first_on_equal_values() {
+        assert_eq!(natural_cmp("1", "01", false), Ordering::Less);
+        assert_eq!(natural_cmp("x01y", "x1z", false), Ordering::Greater);
+        assert_eq!(natural_cmp("007", "007", false), Ordering::Equal);
+    }
+
+    #[test]
+    fn natural_cmp_honours_case_sensitivity() {
+        assert_eq!(natural_cmp("Apple", "apple", false), Ordering::Equal);
+        assert_eq!(natural_cmp("Banana", "apple", false), Ordering::Greater);
+        assert_eq!(natural_cmp("Banana", "apple", true), Ordering::Less);
+        assert_eq!(natural_cmp("Äpfel", "äpfel", false), Ordering::Equal);
+    }
+
+    #[test]
+    fn natural_cmp_orders_prefixes_first() {
+        assert_eq!(natural_cmp("", "a", false), Ordering::Less);
+        assert_eq!(natural_cmp("file", "file1", false), Ordering::Less);
+        assert_eq!(natural_cmp("file1", "file1a", false), Ordering::Less);
+    }
+
+    #[test]
+    fn compare_objects_lists_directories_first_in_every_direction() {
+        let entries: Vec<ObjectInfo> = vec![object("b.txt", false, 1), object("z", true, 0), object("a", true, 0)];
+        let config: SortConfig = SortConfig::default();
+
+        assert_eq!(sorted_names(entries.clone(), &EntrySort::NameAsc, &config), ["a", "z", "b.txt"]);
+        assert_eq!(sorted_names(entries.clone(), &EntrySort::NameDesc, &config), ["z", "a", "b.txt"]);
+
+        let mixed: SortConfig = SortConfig { dirs_first: false, ..SortConfig::default() };
+        assert_eq!(sorted_names(entries, &EntrySort::NameAsc, &mixed), ["a", "b.txt", "z"]);
+    }
+
+    #[test]
+    fn compare_objects_breaks_key_ties_by_name() {
+        let entries: Vec<ObjectInfo> = vec![object("c", false, 5), object("b", false, 1), object("a", false, 5)];
+        let config: SortConfig = SortConfig::default();
+
+        assert_eq!(sorted_names(entries.clone(), &EntrySort::SizeAsc, &config), ["b", "a", "c"]);
+        assert_eq!(sorted_names(entries, &EntrySort::SizeDesc, &config), ["a", "c", "b"]);
+    }
+
+    #[test]
+    fn compare_objects_follows_the_name_settings() {
+        let entries: Vec<ObjectInfo> = vec![object("file10", false, 0), object("File2", false, 0), object("file1", false, 0)];
+
+        let natural: SortConfig = SortConfig::default();
+        assert_eq!(sorted_names(entries.clone(), &EntrySort::NameAsc, &natural), ["file1", "File2", "file10"]);
+
+        let plain: SortConfig = SortConfig { natural: false, case_sensitive: true, ..SortConfig::default() };
+        assert_eq!(sorted_names(entries, &EntrySort::NameAsc, &plain), ["File2", "file1", "file10"]);
+    }
+
+    #[test]
+    fn compare_objects_is_total_for_equal_names() {
+        let config: SortConfig = SortConfig::default();
+        let mut upper: ObjectInfo = object("Same", false, 0);
+        let lower: ObjectInfo = object("same", false, 0);
+
+        assert_eq!(compare_objects(&upper, &lower, &EntrySort::NameAsc, &config), Ordering::Less);
+
+        // Same name in different directories (search results)
+        upper.name = "same".into();
+        upper.path = PathBuf::from("/a/same");
+        assert_eq!(compare_objects(&upper, &lower, &EntrySort::NameAsc, &config), Ordering::Less);
+        assert_eq!(compare_objects(&lower, &lower, &EntrySort::NameAsc, &config), Ordering::Equal);
+    }
+
+    fn quick_matches(query: &str, name: &str) -> bool {
+        EntryFilter::parse_quick(query)
+            .expect("valid query")
+            .matches(&object(name, false, 0))
+    }
+
+    #[test]
+    fn parse_quick_tells_substrings_globs_and_regexes_apart() {
+        assert_eq!(EntryFilter::parse_quick("").expect("valid query"), EntryFilter::All);
+        assert_eq!(EntryFilter::parse_quick("main").expect("valid query"), EntryFilter::Pattern("main".to_string()));
+        assert!(matches!(EntryFilter::parse_quick("*.rs"), Ok(EntryFilter::Glob(_))));
+        assert!(matches!(EntryFilter::parse_quick("file?"), Ok(EntryFilter::Glob(_))));
+        assert!(matches!(EntryFilter::parse_quick("[ab]*"), Ok(EntryFilter::Glob(_))));
+        assert!(matches!(EntryFilter::parse_quick("/^ma"), Ok(EntryFilter::Regex(_))));
+    }
+
+    #[test]
+    fn parse_quick_round_trips_through_query() {
+        for query in ["main", "*.rs", "/^ma.n$"] {
+            assert_eq!(EntryFilter::parse_quick(query).expect("valid query").query(), query);
+        }
+    }
+
+    #[test]
+    fn quick_substrings_ignore_case() {
+        assert!(quick_matches("MAIN", "src_main.rs"));
+        assert!(quick_matches("main", "Main.rs"));
+        assert!(!quick_matches("mian", "main.rs"));
+    }
+
+    #[test]
+    fn quick_globs_match_whole_names() {
+        assert!(quick_matches("*.rs", "main.rs"));
+        assert!(!quick_matches("*.rs", "main.rs.bak"));
+        assert!(quick_matches("file?.txt", "file1.txt"));
+        assert!(!quick_matches("file?.txt", "file10.txt"));
+        assert!(quick_matches("[!a]*", "main.rs"));
+        assert!(!quick_matches("[!a]*", "app.rs"));
+        assert!(quick_matches("a+b.*", "a+b.txt"));
+    }
+
+    #[test]
+    fn quick_globs_and_regexes_are_case_sensitive_only_with_uppercase() {
+        assert!(quick_matches("*.rs", "MAIN.RS"));
+        assert!(!quick_matches("*.RS", "main.rs"));
+        assert!(quick_matches("/^readme", "README.md"));
+        assert!(!quick_matches("/^README", "readme.md"));
+    }
+
+    #[test]
+    fn quick_regexes_match_anywhere_in_the_name() {
+        assert!(quick_matches("/a.n", "main.rs"));
+        assert!(quick_matches(r"/\d{3}", "img_042.jpg"));
+        assert!(!quick_matches("/^rs", "main.rs"));
+    }
+
+    #[test]
+    fn parse_quick_rejects_invalid_regexes() {
+        assert!(EntryFilter::parse_quick("/(unclosed").is_err());
+        assert!(EntryFilter::parse_quick("/a{2,1}").is_err());
+        assert!(EntryFilter::parse_quick("[z-a]").is_err());
+    }
+
+    #[test]
+    fn sort_by_display_order_follows_natural_rows() {
+        let mut pane: PaneState = PaneState::new(PathBuf::from("/d"));
+        let mut entries: Vec<ObjectInfo> = vec![object("img10.jpg", false, 0), object("img2.jpg", false, 0), object("img1.jpg", false, 0)];
+        sort_objects(&mut entries, &EntrySort::NameAsc, &SortConfig::default());
+        pane.set_entries(entries);
+
+        // Sorted as paths, the way the marks come out of the mark set
+        let mut paths: Vec<PathBuf> = vec![
+            PathBuf::from("/a/z"),
+            PathBuf::from("/d/img1.jpg"),
+            PathBuf::from("/d/img10.jpg"),
+            PathBuf::from("/d/img2.jpg"),
+            PathBuf::from("/e/b"),
+        ];
+        pane.sort_by_display_order(&mut paths);
+
+        let expected: Vec<PathBuf> = ["/d/img1.jpg", "/d/img2.jpg", "/d/img10.jpg", "/a/z", "/e/b"].into_iter().map(PathBuf::from).collect();
+        assert_eq!(paths, expected);
+    }
+
+    #[test]
+    fn streamed_entries_keep_the_top_row_on_the_first_entry() {
+        let mut pane: PaneState = streaming_pane();
+
+        for entry in scrambled(200) {
+            pane.add_incremental_entry(entry);
+            assert!(pane.incremental_entries.is_empty(), "small listings insert directly");
+        }
+        pane.complete_incremental_loading();
+
+        assert_eq!(pane_names(&pane), expected_names(200));
+        assert_eq!(pane.selected, Some(0));
+        assert_eq!(pane.scroll_offset, 0);
+    }
+
+    #[test]
+    fn streamed_entries_keep_the_selected_row_on_its_entry() {
+        let mut pane: PaneState = streaming_pane();
+        let mut entries = scrambled(300).into_iter();
+
+        for entry in entries.by_ref().take(100) {
+            pane.add_incremental_entry(entry);
+        }
+
+        select_row(&mut pane, 50);
+        let selected: PathBuf = pane.entries[50].path.clone();
+
+        for entry in entries {
+            pane.add_incremental_entry(entry);
+        }
+        pane.complete_incremental_loading();
+
+        assert_eq!(pane_names(&pane), expected_names(300));
+        assert_eq!(pane.selected_entry().map(|e: &ObjectInfo| -> &Path { &e.path }), Some(selected.as_path()));
+        assert_eq!(pane.selected.map(|idx: usize| -> usize { idx - pane.scroll_offset }), Some(5));
+    }
+
+    #[test]
+    fn large_streams_buffer_and_merge_after_the_direct_insert_limit() {
+        let count: usize = DIRECT_INSERT_LIMIT * 3;
+        let mut pane: PaneState = streaming_pane();
+        let mut entries = scrambled(count).into_iter();
+
+        for entry in entries.by_ref().take(DIRECT_INSERT_LIMIT) {
+            pane.add_incremental_entry(entry);
+        }
+        assert_eq!(pane.entries.len(), DIRECT_INSERT_LIMIT);
+        assert!(pane.incremental_entries.is_empty());
+
+        select_row(&mut pane, 700);
+        let selected: PathBuf = pane.entries[700].path.clone();
+
+        let mut merges: usize = 0;
+        for entry in entries {
+            let shown: usize = pane.entries.len();
+            pane.add_incremental_entry(entry);
+
+            if pane.entries.len() > shown {
+                merges += 1;
+                assert!(pane.incremental_entries.is_empty());
+            } else {
+                assert!(pane.incremental_entries.len() * MERGE_RATIO < pane.entries.len());
+            }
+
+            // The selection stays on its entry through inserts and merges
+            assert!(pane.selected_entry().is_some_and(|e: &ObjectInfo| -> bool { e.path == selected }));
+        }
+        pane.complete_incremental_loading();
+
+        assert!(merges > 1 && merges < 10, "{merges} merges");
+        assert_eq!(pane_names(&pane), expected_names(count));
+        assert_eq!(pane.selected_entry().map(|e: &ObjectInfo| -> &Path { &e.path }), Some(selected.as_path()));
+        assert_eq!(pane.selected.map(|idx: usize| -> usize { idx - pane.scroll_offset }), Some(5));
+    }
+
+    fn dual_state() -> FSState {
+        let mut fs: FSState = FSState::new(PathBuf::from("/d"));
+        fs.active_pane_mut().entries = vec![object("a", false, 1), object("b", true, 0)];
+        fs.active_pane_mut().select_index(1);
+        fs
+    }
+
+    #[test]
+    fn open_second_pane_copies_the_active_pane_once() {
+        let mut fs: FSState = dual_state();
+
+        assert!(fs.inactive_pane().is_none());
+        assert!(fs.open_second_pane());
+        assert!(!fs.open_second_pane());
+
+        let inactive: &PaneState = fs.inactive_pane().expect("second pane");
+
+        assert!(fs.is_dual_pane());
+        assert_eq!(fs.active_pane, 0);
+        assert_eq!(inactive.cwd, fs.active_pane().cwd);
+        assert_eq!(pane_names(inactive), vec!["a", "b"]);
+        assert_eq!(inactive.selected, Some(1));
+    }
+
+    #[test]
+    fn panes_keep_their_own_state() {
+        let mut fs: FSState = dual_state();
+        fs.open_second_pane();
+
+        fs.focus_next_pane();
+        fs.active_pane_mut().cwd = PathBuf::from("/e");
+        fs.active_pane_mut().select_index(0);
+
+        assert_eq!(fs.active_pane, 1);
+        assert_eq!(fs.panes[0].cwd, PathBuf::from("/d"));
+        assert_eq!(fs.panes[0].selected, Some(1));
+        assert_eq!(fs.inactive_pane().map(|p: &PaneState| -> &Path { &p.cwd }), Some(Path::new("/d")));
+    }
+
+    #[test]
+    fn focus_next_pane_wraps_and_set_active_pane_ignores_missing_panes() {
+        let mut fs: FSState = dual_state();
+
+        fs.focus_next_pane();
+        assert_eq!(fs.active_pane, 0);
+
+        fs.open_second_pane();
+        fs.focus_next_pane();
+        assert_eq!(fs.active_pane, 1);
+        fs.focus_next_pane();
+        assert_eq!(fs.active_pane, 0);
+
+        fs.set_active_pane(2);
+        assert_eq!(fs.active_pane, 0);
+        fs.set_active_pane(1);
+        assert_eq!(fs.active_pane, 1);
+    }
+
+    #[test]
+    fn close_inactive_pane_keeps_the_focused_pane() {
+        let mut fs: FSState = dual_state();
+
+        assert!(!fs.close_inactive_pane());
+
+        fs.open_second_pane();
+        fs.set_active_pane(1);
+        fs.active_pane_mut().cwd = PathBuf::from("/e");
+
+        assert!(fs.close_inactive_pane());
+        assert!(!fs.is_dual_pane());
+        assert_eq!(fs.active_pane, 0);
+        assert_eq!(fs.active_pane().cwd, PathBuf::from("/e"));
+    }
+
+    #[test]
+    fn completing_a_scan_clears_the_pane_scan_id() {
+        let mut pane: PaneState = streaming_pane();
+
+        assert_eq!(pane.scan_id, Some(1));
+
+        pane.complete_incremental_loading();
+        assert_eq!(pane.scan_id, None);
+
+        pane.start_incremental_loading(2);
+        pane.abort_incremental_loading("gone".to_string());
+        assert_eq!(pane.scan_id, None);
+        assert_eq!(pane.last_error.as_deref(), Some("gone"));
+    }
+
+    /// Navigate `pane` to `dir` the way `enter_directory` does.
+    fn visit(pane: &mut PaneState, dir: &str) {
+        pane.push_history();
+        pane.cwd = PathBuf::from(dir);
+    }
+
+    fn history_paths(pane: &PaneState) -> Vec<(HistoryPosition, String)> {
+        pane.history_entries()
+            .into_iter()
+            .map(|(position, path): (HistoryPosition, &Path)| -> (HistoryPosition, String) { (position, path.display().to_string()) })
+            .collect()
+    }
+
+    #[test]
+    fn back_and_forward_move_positions_between_the_stacks() {
+        let mut pane: PaneState = PaneState::new(PathBuf::from("/a"));
+        visit(&mut pane, "/b");
+        visit(&mut pane, "/c");
+
+        let target: HistoryEntry = pane.history_back(2).expect("two steps back");
+        pane.cwd = target.path.clone();
+
+        assert_eq!(target.path, PathBuf::from("/a"));
+        assert_eq!(
+            history_paths(&pane),
+            vec![
+                (HistoryPosition::Current, "/a".to_string()),
+                (HistoryPosition::Forward, "/b".to_string()),
+                (HistoryPosition::Forward, "/c".to_string()),
+            ]
+        );
+
+        let target: HistoryEntry = pane.history_forward(1).expect("one step forward");
+        pane.cwd = target.path;
+
+        assert_eq!(pane.cwd, PathBuf::from("/b"));
+        assert!(pane.history_back(3).is_none());
+        assert!(pane.history_forward(0).is_none());
+    }
+
+    #[test]
+    fn a_new_visit_drops_the_forward_stack() {
+        let mut pane: PaneState = PaneState::new(PathBuf::from("/a"));
+        visit(&mut pane, "/b");
+
+        pane.cwd = pane.history_back(1).expect("back").path;
+        visit(&mut pane, "/x");
+
+        assert!(pane.forward_stack.is_empty());
+        assert_eq!(pane.back_stack.last().map(|e: &HistoryEntry| -> &Path { &e.path }), Some(Path::new("/a")));
+    }
+
+    #[test]
+    fn history_is_bounded_and_skips_relative_start_directories() {
+        let mut pane: PaneState = PaneState::new(PathBuf::from("."));
+        visit(&mut pane, "/0");
+
+        assert!(pane.back_stack.is_empty());
+
+        for n in 1..=HISTORY_LIMIT + 5 {
+            visit(&mut pane, &format!("/{n}"));
+        }
+
+        assert_eq!(pane.back_stack.len(), HISTORY_LIMIT);
+        assert_eq!(pane.back_stack[0].path, PathBuf::from("/5"));
+    }
+
+    #[test]
+    fn history_jump_and_forget_use_overlay_indices() {
+        let mut pane: PaneState = PaneState::new(PathBuf::from("/a"));
+        visit(&mut pane, "/b");
+        visit(&mut pane, "/c");
+        pane.cwd = pane.history_back(1).expect("back").path;
+
+        // [/a, /b (current), /c]
+        assert!(pane.history_jump(1).is_none());
+        pane.forget_history(2);
+        assert!(pane.forward_stack.is_empty());
+
+        pane.cwd = pane.history_jump(0).expect("jump back").path;
+        assert_eq!(pane.cwd, PathBuf::from("/a"));
+        assert_eq!(history_paths(&pane)[1], (HistoryPosition::Forward, "/b".to_string()));
+    }
+
+    #[test]
+    fn restore_position_prefers_the_selected_path_over_the_index() {
+        let mut pane: PaneState = PaneState::new(PathBuf::from("/d"));
+        pane.viewport_height = 10;
+        pane.entries = vec![object("a", false, 0), object("b", false, 0), object("c", false, 0)];
+
+        let mut position: HistoryEntry = HistoryEntry::new(PathBuf::from("/d"));
+        position.selected_path = Some(PathBuf::from("/d/c"));
+        position.selected = Some(0);
+
+        pane.restore_position(&position);
+        assert_eq!(pane.selected, Some(2));
+
+        // The entry is gone: fall back to the index, clamped
+        position.selected_path = Some(PathBuf::from("/d/gone"));
+        position.selected = Some(7);
+
+        pane.restore_position(&position);
+        assert_eq!(pane.selected, Some(2));
+    }
+
+    #[test]
+    fn remembered_position_finds_the_latest_visit() {
+        let mut pane: PaneState = PaneState::new(PathBuf::from("/a"));
+        pane.entries = vec![object("x", false, 0)];
+        pane.cwd = PathBuf::from("/d");
+        pane.select_index(0);
+        visit(&mut pane, "/e");
+
+        let position: HistoryEntry = pane.remembered_position(Path::new("/d")).expect("visited");
+
+        assert_eq!(position.selected_path, Some(PathBuf::from("/d/x")));
+        assert!(pane.remembered_position(Path::new("/never")).is_none());
+    }
+
+    fn listed_pane(names: &[&str]) -> PaneState {
+        let mut pane: PaneState = PaneState::new(PathBuf::from("/d"));
+        pane.viewport_height = 10;
+        pane.entries = names.iter().map(|name: &&str| -> ObjectInfo { object(name, false, 0) }).collect();
+        pane
+    }
+
+    #[test]
+    fn fs_changes_patch_rows_in_sorted_position_and_keep_the_selection() {
+        let mut pane: PaneState = listed_pane(&["a", "c", "e"]);
+        pane.select_index(1);
+
+        let changes: Vec<FsChange> = vec![
+            FsChange::Upsert(object("d", false, 0)),
+            FsChange::Upsert(object("b", true, 0)),
+            FsChange::Removed(PathBuf::from("/d/a")),
+        ];
+
+        assert!(pane.apply_fs_changes(Path::new("/d"), &changes, false));
+        assert_eq!(pane_names(&pane), ["b", "c", "d", "e"]);
+        assert_eq!(pane.selected_entry().map(|e: &ObjectInfo| -> &Path { &e.path }), Some(Path::new("/d/c")));
+    }
+
+    #[test]
+    fn removing_the_selected_row_keeps_the_row_index() {
+        let mut pane: PaneState = listed_pane(&["a", "b", "c"]);
+        pane.select_index(1);
+
+        assert!(pane.apply_fs_changes(Path::new("/d"), &[FsChange::Removed(PathBuf::from("/d/b"))], false));
+        assert_eq!(pane.selected, Some(1));
+        assert_eq!(pane_names(&pane), ["a", "c"]);
+    }
+
+    #[test]
+    fn fs_changes_outside_the_listing_are_ignored() {
+        let mut pane: PaneState = listed_pane(&["a"]);
+
+        let hidden: Vec<FsChange> = vec![FsChange::Upsert(object(".h", false, 0))];
+        let unknown: Vec<FsChange> = vec![FsChange::Removed(PathBuf::from("/d/zz"))];
+
+        assert!(!pane.apply_fs_changes(Path::new("/elsewhere"), &[FsChange::Upsert(object("b", false, 0))], false));
+        assert!(!pane.apply_fs_changes(Path::new("/d"), &hidden, false));
+        assert!(!pane.apply_fs_changes(Path::new("/d"), &unknown, false));
+        assert!(pane.apply_fs_changes(Path::new("/d"), &hidden, true));
+        assert_eq!(pane_names(&pane), [".h", "a"]);
+
+        let mut loading: PaneState = streaming_pane();
+        assert!(!loading.apply_fs_changes(Path::new("/d"), &[FsChange::Upsert(object("b", false, 0))], false));
+    }
+
+    #[test]
+    fn an_upsert_keeps_the_directory_size_already_found() {
+        let mut pane: PaneState = PaneState::new(PathBuf::from("/d"));
+        pane.entries = vec![object("sub", true, 4096)];
+
+        assert!(pane.apply_fs_changes(Path::new("/d"), &[FsChange::Upsert(object("sub", true, 0))], false));
+        assert_eq!(pane.entries[0].size, 4096);
+
+        // Replaced by a file: nothing carries over
+        assert!(pane.apply_fs_changes(Path::new("/d"), &[FsChange::Upsert(object("sub", false, 7))], false));
+        assert_eq!(pane.entries[0].size, 7);
+    }
+
+    #[test]
+    fn the_visual_range_spans_anchor_and_selection_either_way() {
+        let mut pane: PaneState = listed_pane(&["a", "b", "c", "d", "e"]);
+        pane.select_index(2);
+        pane.start_visual();
+
+        pane.select_index(4);
+        assert_eq!(pane.visual_range(), Some((2, 4)));
+
+        pane.select_index(0);
+        assert_eq!(pane.visual_range(), Some((0, 2)));
+        assert_eq!(pane.visual_paths(), [PathBuf::from("/d/a"), PathBuf::from("/d/b"), PathBuf::from("/d/c")]);
+
+        pane.end_visual();
+        assert_eq!(pane.visual_range(), None);
+    }
+
+    #[test]
+    fn the_visual_range_follows_its_entries_through_streamed_rows() {
+        let mut pane: PaneState = streaming_pane();
+        let mut entries = scrambled(400).into_iter();
+
+        for entry in entries.by_ref().take(100) {
+            pane.add_incremental_entry(entry);
+        }
+
+        // The range starts on the top row, which must not stay pinned there
+        pane.select_index(0);
+        pane.start_visual();
+        pane.select_index(3);
+        let expected: Vec<PathBuf> = pane.visual_paths();
+        assert_eq!(expected.len(), 4);
+
+        for entry in entries {
+            pane.add_incremental_entry(entry);
+        }
+        pane.complete_incremental_loading();
+
+        // Rows that arrived between the two ends join the range
+        let paths: Vec<PathBuf> = pane.visual_paths();
+        assert_eq!((paths.first(), paths.last()), (expected.first(), expected.last()));
+        assert!(paths.len() > expected.len());
+    }
+
+    #[test]
+    fn a_removed_anchor_hands_over_to_the_row_at_its_index() {
+        let mut pane: PaneState = listed_pane(&["a", "b", "c", "d"]);
+        pane.select_index(3);
+        pane.start_visual();
+        pane.select_index(1);
+
+        assert!(pane.apply_fs_changes(Path::new("/d"), &[FsChange::Removed(PathBuf::from("/d/d"))], false));
+
+        assert_eq!(pane.visual_range(), Some((1, 2)));
+        assert_eq!(pane.visual_anchor, Some((PathBuf::from("/d/c"), 2)));
+    }
+}
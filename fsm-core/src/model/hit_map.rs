@@ -0,0 +1,175 @@
+//! ``src/model/hit_map.rs``
+//! ============================================================================
+//! # `HitMap`: Where the Last Frame Put Things
+//!
+//! Mouse events arrive as terminal cells, while rows and lists only know
+//! their own indices. While drawing, components record the screen areas of
+//! their rows here (together with the index of the first row shown), so the
+//! controller can map a click back to a pane entry or an overlay item.
+//!
+//! Every frame starts from an empty map: a component that was not drawn
+//! cannot be clicked.
+
+use std::time::{Duration, Instant};
+
+use ratatui::layout::{Position, Rect};
+
+/// Two presses on the same cell within this interval are a double click.
+pub const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Rows of a list on screen, one item per row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListHit {
+    /// Cells the items are drawn in (inside any border)
+    pub area: Rect,
+
+    /// Index of the item in the first row
+    pub offset: usize,
+
+    /// Number of items in the list
+    pub len: usize,
+}
+
+impl ListHit {
+    #[must_use]
+    pub const fn new(area: Rect, offset: usize, len: usize) -> Self {
+        Self { area, offset, len }
+    }
+
+    /// Index of the item drawn at (`column`, `row`), if any.
+    #[must_use]
+    pub fn item_at(&self, column: u16, row: u16) -> Option<usize> {
+        if !self.area.contains(Position::new(column, row)) {
+            return None;
+        }
+
+        let idx: usize = self.offset + usize::from(row - self.area.y);
+
+        (idx < self.len).then_some(idx)
+    }
+}
+
+/// A pane on screen: its whole area focuses it, its rows select entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaneHit {
+    /// Index into `FSState::panes`
+    pub pane: usize,
+
+    /// Everything drawn for the pane (all three Miller columns)
+    pub area: Rect,
+
+    /// Entry rows of the table
+    pub rows: ListHit,
+}
+
+/// Clickable areas of the last frame.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HitMap {
+    pub panes: Vec<PaneHit>,
+
+    /// Item list of the open overlay, if it has one
+    pub overlay_list: Option<ListHit>,
+
+    /// Command-mode completion suggestions, while shown
+    pub completions: Option<ListHit>,
+}
+
+impl HitMap {
+    /// Forget the previous frame.
+    pub fn clear(&mut self) {
+        self.panes.clear();
+        self.overlay_list = None;
+        self.completions = None;
+    }
+
+    /// Pane drawn at (`column`, `row`), if any.
+    #[must_use]
+    pub fn pane_at(&self, column: u16, row: u16) -> Option<&PaneHit> {
+        self.panes
+            .iter()
+            .find(|hit: &&PaneHit| -> bool { hit.area.contains(Position::new(column, row)) })
+    }
+}
+
+/// A press of the left button, remembered to recognise double clicks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Click {
+    at: Instant,
+    column: u16,
+    row: u16,
+}
+
+impl Click {
+    #[must_use]
+    pub fn new(column: u16, row: u16) -> Self {
+        Self {
+            at: Instant::now(),
+            column,
+            row,
+        }
+    }
+
+    /// True when this press completes a double click with `previous`.
+    #[must_use]
+    pub fn is_double(&self, previous: Option<Self>) -> bool {
+        previous.is_some_and(|prev: Self| -> bool {
+            prev.column == self.column
+                && prev.row == self.row
+                && self.at.duration_since(prev.at) <= DOUBLE_CLICK_INTERVAL
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_map_to_items_through_the_scroll_offset() {
+        let hit: ListHit = ListHit::new(Rect::new(2, 5, 20, 10), 30, 33);
+
+        assert_eq!(hit.item_at(2, 5), Some(30));
+        assert_eq!(hit.item_at(21, 7), Some(32));
+
+        // Below the last item, outside the rows
+        assert_eq!(hit.item_at(2, 8), None);
+        assert_eq!(hit.item_at(1, 5), None);
+        assert_eq!(hit.item_at(2, 15), None);
+    }
+
+    #[test]
+    fn panes_are_found_by_area_until_cleared() {
+        let rows: ListHit = ListHit::new(Rect::new(1, 2, 38, 8), 0, 0);
+        let mut map: HitMap = HitMap {
+            panes: vec![
+                PaneHit { pane: 0, area: Rect::new(0, 0, 40, 10), rows },
+                PaneHit { pane: 1, area: Rect::new(40, 0, 40, 10), rows },
+            ],
+            ..HitMap::default()
+        };
+
+        assert_eq!(map.pane_at(40, 3).map(|hit: &PaneHit| -> usize { hit.pane }), Some(1));
+        assert!(map.pane_at(80, 3).is_none());
+
+        map.clear();
+
+        assert!(map.pane_at(0, 0).is_none());
+    }
+
+    #[test]
+    fn a_double_click_needs_the_same_cell_within_the_interval() {
+        let first: Click = Click::new(3, 4);
+        let second: Click = Click::new(3, 4);
+
+        assert!(second.is_double(Some(first)));
+        assert!(!second.is_double(None));
+        assert!(!Click::new(3, 5).is_double(Some(first)));
+
+        let late: Click = Click {
+            at: first.at + DOUBLE_CLICK_INTERVAL + Duration::from_millis(1),
+            ..first
+        };
+
+        assert!(!late.is_double(Some(first)));
+    }
+}
@@ -0,0 +1,131 @@
+//! ``src/model/preview.rs``
+//! ============================================================================
+//! # `PreviewStore`: Side-Column Contents for the Miller Layout
+//!
+//! The parent column and the preview column of the Miller layout show data
+//! that is not part of any pane's listing: the parent directory's children
+//! and the selected entry's contents. Both are loaded in the background by
+//! `tasks::preview_task` and kept here by path. The store is bounded and is
+//! emptied whenever a pane enters a directory, so stale previews do not
+//! outlive a reload.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::fs::object_info::ObjectInfo;
+
+/// Maximum number of previews kept before the store is emptied.
+pub const PREVIEW_LIMIT: usize = 256;
+
+/// Loaded contents of one entry.
+#[derive(Debug, Clone)]
+pub enum Preview {
+    /// Children of a directory.
+    Directory(Vec<ObjectInfo>),
+
+    /// Leading lines of a text file.
+    Text(Vec<String>),
+
+    /// A non-text file of the given size in bytes.
+    Binary(u64),
+
+    /// The entry could not be read.
+    Error(String),
+}
+
+/// Previews by path plus the paths currently being loaded.
+#[derive(Debug, Clone, Default)]
+pub struct PreviewStore {
+    previews: HashMap<PathBuf, Preview>,
+    loading: HashSet<PathBuf>,
+}
+
+impl PreviewStore {
+    #[must_use]
+    pub fn get(&self, path: &Path) -> Option<&Preview> {
+        self.previews.get(path)
+    }
+
+    #[must_use]
+    pub fn is_loading(&self, path: &Path) -> bool {
+        self.loading.contains(path)
+    }
+
+    /// Mark `path` as being loaded. Returns false if it is already loaded or
+    /// in flight.
+    pub fn request(&mut self, path: &Path) -> bool {
+        if self.previews.contains_key(path) {
+            return false;
+        }
+
+        self.loading.insert(path.to_path_buf())
+    }
+
+    /// Store a loaded preview.
+    pub fn insert(&mut self, path: PathBuf, preview: Preview) {
+        if !self.loading.remove(&path) {
+            // Requested before the store was cleared; the result may be stale
+            return;
+        }
+
+        if self.previews.len() >= PREVIEW_LIMIT {
+            self.previews.clear();
+        }
+
+        self.previews.insert(path, preview);
+    }
+
+    /// Drop all previews (e.g. after entering or reloading a directory).
+    pub fn clear(&mut self) {
+        self.previews.clear();
+        self.loading.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_is_refused_while_in_flight_or_loaded() {
+        let mut store: PreviewStore = PreviewStore::default();
+        let path: &Path = Path::new("/d/file");
+
+        assert!(store.request(path));
+        assert!(!store.request(path));
+        assert!(store.is_loading(path));
+
+        store.insert(path.to_path_buf(), Preview::Binary(3));
+
+        assert!(!store.is_loading(path));
+        assert!(matches!(store.get(path), Some(Preview::Binary(3))));
+        assert!(!store.request(path));
+    }
+
+    #[test]
+    fn results_requested_before_a_clear_are_dropped() {
+        let mut store: PreviewStore = PreviewStore::default();
+        let path: &Path = Path::new("/d/file");
+
+        assert!(store.request(path));
+        store.clear();
+        store.insert(path.to_path_buf(), Preview::Binary(3));
+
+        assert!(store.get(path).is_none());
+        assert!(store.request(path));
+    }
+
+    #[test]
+    fn the_store_is_emptied_when_full() {
+        let mut store: PreviewStore = PreviewStore::default();
+
+        for n in 0..=PREVIEW_LIMIT {
+            let path: PathBuf = PathBuf::from(format!("/d/{n}"));
+            store.request(&path);
+            store.insert(path, Preview::Text(Vec::new()));
+        }
+
+        assert!(store.get(Path::new("/d/0")).is_none());
+        assert!(store.get(&PathBuf::from(format!("/d/{PREVIEW_LIMIT}"))).is_some());
+    }
+}
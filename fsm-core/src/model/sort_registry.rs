@@ -0,0 +1,232 @@
+//! ``src/model/sort_registry.rs``
+//! ============================================================================
+//! # `SortRegistry`: Named Comparators for `EntrySort::Custom`
+//!
+//! Users define comparators in `config.toml` as an ordered list of sort keys,
+//! each optionally prefixed with `-` for descending order:
+//!
+//! ```toml
+//! [sort_comparators]
+//! rust = ["ext", "-size", "name"]
+//! ```
+//!
+//! Plugins register comparators as functions. Either kind is selected with
+//! `:sort <name>`, which stores the resolved comparator in the pane's
+//! `EntrySort::Custom`, so sorting never has to look the name up again.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::config::SortConfig;
+use crate::fs::object_info::ObjectInfo;
+use crate::model::fs_state::{EntrySort, SortKey, compare_key};
+
+/// Signature of a comparator registered by a plugin.
+pub type SortFn = dyn Fn(&ObjectInfo, &ObjectInfo, &SortConfig) -> Ordering + Send + Sync;
+
+/// How a custom sort orders two entries.
+#[derive(Clone)]
+pub enum Comparator {
+    /// Keys compared in turn until one differs; true = descending.
+    Keys(Vec<(SortKey, bool)>),
+
+    /// Arbitrary comparison registered by a plugin.
+    Function(Arc<SortFn>),
+}
+
+impl Comparator {
+    /// Parse a key list such as `["ext", "-size", "name"]`.
+    pub fn parse<S: AsRef<str>>(keys: &[S]) -> Result<Self, String> {
+        if keys.is_empty() {
+            return Err("A comparator needs at least one key".to_string());
+        }
+
+        let keys: Vec<(SortKey, bool)> = keys
+            .iter()
+            .map(|key: &S| -> Result<(SortKey, bool), String> {
+                let key: &str = key.as_ref().trim();
+
+                match key.strip_prefix('-') {
+                    Some(name) => Ok((name.parse()?, true)),
+
+                    None => Ok((key.trim_start_matches('+').parse()?, false)),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::Keys(keys))
+    }
+
+    #[must_use]
+    pub fn compare(&self, a: &ObjectInfo, b: &ObjectInfo, config: &SortConfig) -> Ordering {
+        match self {
+            Self::Keys(keys) => keys
+                .iter()
+                .map(|&(key, descending): &(SortKey, bool)| -> Ordering {
+                    let ordering: Ordering = compare_key(a, b, key, config);
+                    if descending { ordering.reverse() } else { ordering }
+                })
+                .find(|ordering: &Ordering| -> bool { ordering.is_ne() })
+                .unwrap_or(Ordering::Equal),
+
+            Self::Function(f) => f(a, b, config),
+        }
+    }
+}
+
+impl std::fmt::Debug for Comparator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Keys(keys) => f.debug_tuple("Keys").field(keys).finish(),
+
+            Self::Function(_) => f.write_str("Function(..)"),
+        }
+    }
+}
+
+/// A named comparator. Compared by name so sort modes stay comparable.
+#[derive(Debug, Clone)]
+pub struct CustomSort {
+    pub name: String,
+    comparator: Comparator,
+}
+
+impl CustomSort {
+    #[must_use]
+    pub fn new(name: impl Into<String>, comparator: Comparator) -> Self {
+        Self {
+            name: name.into(),
+            comparator,
+        }
+    }
+
+    #[must_use]
+    pub fn compare(&self, a: &ObjectInfo, b: &ObjectInfo, config: &SortConfig) -> Ordering {
+        self.comparator.compare(a, b, config)
+    }
+}
+
+impl PartialEq for CustomSort {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for CustomSort {}
+
+impl std::fmt::Display for CustomSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Custom comparators by name, from the config and from plugins.
+#[derive(Debug, Clone, Default)]
+pub struct SortRegistry {
+    comparators: BTreeMap<String, CustomSort>,
+}
+
+impl SortRegistry {
+    /// Build the registry from `Config::sort_comparators`. Invalid
+    /// definitions are logged and skipped.
+    #[must_use]
+    pub fn from_config(definitions: &BTreeMap<String, Vec<String>>) -> Self {
+        let mut registry: Self = Self::default();
+
+        for (name, keys) in definitions {
+            match Comparator::parse(keys) {
+                Ok(comparator) => registry.register(name.clone(), comparator),
+
+                Err(e) => warn!("Ignoring sort comparator '{}': {}", name, e),
+            }
+        }
+
+        registry
+    }
+
+    /// Add or replace a comparator.
+    pub fn register(&mut self, name: impl Into<String>, comparator: Comparator) {
+        let name: String = name.into();
+        self.comparators
+            .insert(name.clone(), CustomSort::new(name, comparator));
+    }
+
+    /// Sort mode for a registered comparator.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<EntrySort> {
+        self.comparators.get(name).cloned().map(EntrySort::Custom)
+    }
+
+    /// Registered names in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.comparators.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(comparator: Result<Comparator, String>) -> Vec<(SortKey, bool)> {
+        match comparator {
+            Ok(Comparator::Keys(keys)) => keys,
+
+            other => panic!("expected a key list, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_reads_keys_in_order() {
+        assert_eq!(
+            keys(Comparator::parse(&["ext", "size", "name"])),
+            [(SortKey::Extension, false), (SortKey::Size, false), (SortKey::Name, false)]
+        );
+    }
+
+    #[test]
+    fn parse_accepts_aliases_signs_and_spaces() {
+        assert_eq!(
+            keys(Comparator::parse(&[" mtime ", "+Kind", "extension"])),
+            [(SortKey::Modified, false), (SortKey::Type, false), (SortKey::Extension, false)]
+        );
+    }
+
+    #[test]
+    fn parse_reads_a_leading_dash_as_descending() {
+        assert_eq!(
+            keys(Comparator::parse(&["-size", "name", "-items"])),
+            [(SortKey::Size, true), (SortKey::Name, false), (SortKey::Items, true)]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unknown_keys() {
+        let err: String = Comparator::parse(&["name", "colour"]).expect_err("unknown key");
+        assert!(err.contains("colour"), "{err}");
+
+        assert!(Comparator::parse(&["-"]).is_err());
+        assert!(Comparator::parse(&[""]).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_an_empty_list() {
+        assert!(Comparator::parse::<&str>(&[]).is_err());
+    }
+
+    #[test]
+    fn from_config_skips_invalid_definitions() {
+        let definitions: BTreeMap<String, Vec<String>> = BTreeMap::from([
+            ("big".to_string(), vec!["-size".to_string()]),
+            ("broken".to_string(), vec!["colour".to_string()]),
+            ("empty".to_string(), Vec::new()),
+        ]);
+
+        let registry: SortRegistry = SortRegistry::from_config(&definitions);
+
+        assert_eq!(registry.names().collect::<Vec<&str>>(), ["big"]);
+        assert!(matches!(registry.get("big"), Some(EntrySort::Custom(custom)) if custom.name == "big"));
+    }
+}
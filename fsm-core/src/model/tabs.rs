@@ -0,0 +1,363 @@
+//! ``src/model/tabs.rs``
+//! ============================================================================
+//! # `TabSet`: Tabs, Each With Its Own Filesystem State
+//!
+//! Every tab owns an `FSState` (panes, marks, per-pane history) plus the
+//! search results and search overlay it was showing. The active tab's
+//! `FSState` lives in `AppState::fs` so existing code keeps working on "the
+//! current state"; its slot in the `TabSet` holds a placeholder until the tab
+//! is switched away from and the states are swapped back.
+//!
+//! Background work (directory scans, searches, file operations) is tagged with
+//! the `TabId` of the tab that started it, so results land in that tab even
+//! if another tab is active by then.
+
+use std::fmt;
+
+use crate::fs::object_info::ObjectInfo;
+use crate::model::fs_state::FSState;
+use crate::model::ui_state::{UIOverlay, UIState};
+use crate::tasks::search_task::RawSearchResult;
+
+/// Stable identifier of a tab (indices change when tabs are reordered).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TabId(pub u64);
+
+impl fmt::Display for TabId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// Search state of a background tab, stashed from `UIState` on switch.
+#[derive(Debug, Clone, Default)]
+pub struct TabUiState {
+    pub overlay: UIOverlay,
+    pub input: String,
+    pub last_query: Option<String>,
+    pub search_results: Vec<ObjectInfo>,
+    pub filename_search_results: Vec<ObjectInfo>,
+    pub rich_search_results: Vec<String>,
+    pub raw_search_results: Option<RawSearchResult>,
+    pub raw_search_selected: usize,
+}
+
+impl TabUiState {
+    /// Move the tab-specific parts out of `ui`. Only search overlays travel
+    /// with the tab; any other overlay is closed.
+    pub fn take_from(ui: &mut UIState) -> Self {
+        let overlay: UIOverlay = match ui.overlay {
+            UIOverlay::FileNameSearch | UIOverlay::ContentSearch | UIOverlay::SearchResults => {
+                ui.overlay
+            }
+
+            _ => UIOverlay::None,
+        };
+
+        let input: String = if overlay == UIOverlay::None {
+            ui.input.clear();
+            String::new()
+        } else {
+            std::mem::take(&mut ui.input)
+        };
+
+        ui.overlay = UIOverlay::None;
+
+        Self {
+            overlay,
+            input,
+            last_query: ui.last_query.take(),
+            search_results: std::mem::take(&mut ui.search_results),
+            filename_search_results: std::mem::take(&mut ui.filename_search_results),
+            rich_search_results: std::mem::take(&mut ui.rich_search_results),
+            raw_search_results: ui.raw_search_results.take(),
+            raw_search_selected: std::mem::take(&mut ui.raw_search_selected),
+        }
+    }
+
+    /// Put a stashed state back into `ui`.
+    pub fn restore_into(self, ui: &mut UIState) {
+        ui.overlay = self.overlay;
+        ui.input = self.input;
+        ui.last_query = self.last_query;
+        ui.search_results = self.search_results;
+        ui.filename_search_results = self.filename_search_results;
+        ui.rich_search_results = self.rich_search_results;
+        ui.raw_search_results = self.raw_search_results;
+        ui.raw_search_selected = self.raw_search_selected;
+    }
+}
+
+/// A single tab.
+#[derive(Debug, Clone)]
+pub struct Tab {
+    pub id: TabId,
+
+    /// User-assigned name; the tab is titled after its directory otherwise.
+    pub name: Option<String>,
+
+    /// The tab's filesystem state (a placeholder while the tab is active).
+    pub fs: FSState,
+
+    /// Stashed search state (empty while the tab is active).
+    pub ui: TabUiState,
+
+    /// A file operation started here finished while the tab was in the
+    /// background; reload when it becomes active again.
+    pub needs_reload: bool,
+}
+
+impl Tab {
+    fn new(id: TabId, fs: FSState) -> Self {
+        Self {
+            id,
+            name: None,
+            fs,
+            ui: TabUiState::default(),
+            needs_reload: false,
+        }
+    }
+
+    /// Tab title: the user-assigned name or the focused pane's directory name.
+    #[must_use]
+    pub fn title(&self, fs: &FSState) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+
+        let cwd = &fs.active_pane().cwd;
+
+        cwd.file_name().map_or_else(
+            || cwd.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        )
+    }
+}
+
+/// Ordered set of tabs with one active tab.
+#[derive(Debug, Clone)]
+pub struct TabSet {
+    pub tabs: Vec<Tab>,
+    pub active: usize,
+    next_id: u64,
+}
+
+impl Default for TabSet {
+    fn default() -> Self {
+        Self {
+            tabs: vec![Tab::new(TabId(0), FSState::default())],
+            active: 0,
+            next_id: 1,
+        }
+    }
+}
+
+impl TabSet {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    #[must_use]
+    pub fn active_id(&self) -> TabId {
+        self.tabs[self.active].id
+    }
+
+    #[must_use]
+    pub fn active_tab(&self) -> &Tab {
+        &self.tabs[self.active]
+    }
+
+    pub fn active_tab_mut(&mut self) -> &mut Tab {
+        &mut self.tabs[self.active]
+    }
+
+    #[must_use]
+    pub fn position(&self, id: TabId) -> Option<usize> {
+        self.tabs.iter().position(|t: &Tab| -> bool { t.id == id })
+    }
+
+    /// A background tab by id (`None` for the active tab or a closed one).
+    pub fn background_mut(&mut self, id: TabId) -> Option<&mut Tab> {
+        let active: usize = self.active;
+
+        self.tabs
+            .iter_mut()
+            .enumerate()
+            .find(|(idx, t): &(usize, &mut Tab)| -> bool { *idx != active && t.id == id })
+            .map(|(_, t): (usize, &mut Tab)| -> &mut Tab { t })
+    }
+
+    /// Filesystem states of all background tabs.
+    pub fn background_fs(&self) -> impl Iterator<Item = &FSState> {
+        let active: usize = self.active;
+
+        self.tabs
+            .iter()
+            .enumerate()
+            .filter(move |(idx, _): &(usize, &Tab)| -> bool { *idx != active })
+            .map(|(_, t): (usize, &Tab)| -> &FSState { &t.fs })
+    }
+
+    /// Mutable filesystem states of all background tabs.
+    pub fn background_fs_mut(&mut self) -> impl Iterator<Item = &mut FSState> {
+        let active: usize = self.active;
+
+        self.tabs
+            .iter_mut()
+            .enumerate()
+            .filter(move |(idx, _): &(usize, &mut Tab)| -> bool { *idx != active })
+            .map(|(_, t): (usize, &mut Tab)| -> &mut FSState { &mut t.fs })
+    }
+
+    /// Insert a new tab right after the active one, returning its index.
+    /// The active tab is left unchanged.
+    pub fn insert_after_active(&mut self, fs: FSState) -> usize {
+        let id: TabId = TabId(self.next_id);
+        self.next_id += 1;
+
+        let index: usize = self.active + 1;
+        self.tabs.insert(index, Tab::new(id, fs));
+
+        index
+    }
+
+    /// Move the active tab by `delta` positions. Returns false at the edges.
+    pub fn move_active(&mut self, delta: isize) -> bool {
+        let Some(target) = self.active.checked_add_signed(delta) else {
+            return false;
+        };
+
+        if target >= self.tabs.len() || target == self.active {
+            return false;
+        }
+
+        let tab: Tab = self.tabs.remove(self.active);
+        self.tabs.insert(target, tab);
+        self.active = target;
+
+        true
+    }
+
+    /// Index reached by cycling `delta` tabs from the active one.
+    #[must_use]
+    pub fn cycled_index(&self, delta: isize) -> usize {
+        let len: isize = isize::try_from(self.tabs.len()).unwrap_or(isize::MAX);
+        let active: isize = isize::try_from(self.active).unwrap_or(0);
+
+        usize::try_from((active + delta).rem_euclid(len)).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    /// Tabs with ids 0..count, the first one active.
+    fn tab_set(count: usize) -> TabSet {
+        let mut tabs: TabSet = TabSet::default();
+
+        for _ in 1..count {
+            let index: usize = tabs.insert_after_active(FSState::default());
+            tabs.active = index;
+        }
+
+        tabs.active = 0;
+        tabs
+    }
+
+    fn ids(tabs: &TabSet) -> Vec<u64> {
+        tabs.tabs.iter().map(|t: &Tab| -> u64 { t.id.0 }).collect()
+    }
+
+    #[test]
+    fn new_tabs_go_after_the_active_one_with_fresh_ids() {
+        let mut tabs: TabSet = tab_set(3);
+
+        let index: usize = tabs.insert_after_active(FSState::default());
+
+        assert_eq!(index, 1);
+        assert_eq!(tabs.active, 0);
+        assert_eq!(ids(&tabs), [0, 3, 1, 2]);
+    }
+
+    #[test]
+    fn move_active_follows_the_tab_and_stops_at_the_edges() {
+        let mut tabs: TabSet = tab_set(3);
+
+        assert!(!tabs.move_active(-1));
+        assert!(tabs.move_active(2));
+        assert_eq!(ids(&tabs), [1, 2, 0]);
+        assert_eq!(tabs.active_id(), TabId(0));
+        assert!(!tabs.move_active(1));
+        assert!(!tabs.move_active(0));
+    }
+
+    #[test]
+    fn cycled_index_wraps_both_ways() {
+        let tabs: TabSet = tab_set(3);
+
+        assert_eq!(tabs.cycled_index(1), 1);
+        assert_eq!(tabs.cycled_index(-1), 2);
+        assert_eq!(tabs.cycled_index(4), 1);
+    }
+
+    #[test]
+    fn background_lookups_skip_the_active_tab() {
+        let mut tabs: TabSet = tab_set(2);
+
+        assert!(tabs.background_mut(TabId(0)).is_none());
+        assert!(tabs.background_mut(TabId(1)).is_some());
+        assert!(tabs.background_mut(TabId(7)).is_none());
+        assert_eq!(tabs.background_fs().count(), 1);
+        assert_eq!(tabs.position(TabId(1)), Some(1));
+    }
+
+    #[test]
+    fn title_prefers_the_name_over_the_directory() {
+        let fs: FSState = FSState::new(PathBuf::from("/home/me/src"));
+        let mut tab: Tab = Tab::new(TabId(0), FSState::default());
+
+        assert_eq!(tab.title(&fs), "src");
+        assert_eq!(tab.title(&FSState::new(PathBuf::from("/"))), "/");
+
+        tab.name = Some("work".to_string());
+
+        assert_eq!(tab.title(&fs), "work");
+    }
+
+    #[test]
+    fn only_search_overlays_travel_with_the_tab() {
+        let mut ui: UIState = UIState::new();
+        ui.overlay = UIOverlay::FileNameSearch;
+        ui.input = "needle".to_string();
+        ui.last_query = Some("needle".to_string());
+
+        let stash: TabUiState = TabUiState::take_from(&mut ui);
+
+        assert_eq!(ui.overlay, UIOverlay::None);
+        assert!(ui.input.is_empty());
+        assert!(ui.last_query.is_none());
+
+        stash.restore_into(&mut ui);
+
+        assert_eq!(ui.overlay, UIOverlay::FileNameSearch);
+        assert_eq!(ui.input, "needle");
+
+        ui.overlay = UIOverlay::Help;
+        ui.input = "typed".to_string();
+
+        let stash: TabUiState = TabUiState::take_from(&mut ui);
+
+        assert_eq!(stash.overlay, UIOverlay::None);
+        assert!(stash.input.is_empty());
+        assert!(ui.input.is_empty());
+    }
+}
@@ -0,0 +1,402 @@
+//! ``src/model/tree.rs``
+//! ============================================================================
+//! # `TreeState`: Expandable Directory Tree for a Pane
+//!
+//! In tree mode a pane keeps every loaded directory level here, keyed by the
+//! directory path (the pane's `cwd` included). `PaneState::entries` becomes
+//! the *flattened* view of the tree: each expanded directory is followed by
+//! its (sorted, filtered) children, so selection and virtual scrolling keep
+//! working on a flat list. `rows` runs parallel to `entries` and carries the
+//! depth and indentation guides of every row.
+//!
+//! Children are loaded lazily the first time a directory is expanded.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::fs::object_info::ObjectInfo;
+use crate::fs::watcher::FsChange;
+use crate::config::SortConfig;
+use crate::model::fs_state::{EntryFilter, EntrySort, sort_objects};
+
+/// Display information of one flattened tree row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeRow {
+    /// Nesting level (0 for entries of the pane's `cwd`).
+    pub depth: usize,
+
+    /// Indentation guides drawn before the row's name.
+    pub guides: String,
+
+    /// True for an expanded directory.
+    pub expanded: bool,
+
+    /// True while the directory's children are being scanned.
+    pub loading: bool,
+}
+
+/// Expanded directories and their loaded children.
+#[derive(Debug, Clone)]
+pub struct TreeState {
+    /// Directory the tree is rooted at (the pane's `cwd`).
+    pub root: PathBuf,
+
+    /// Expanded directories.
+    pub expanded: HashSet<PathBuf>,
+
+    /// Loaded entries per directory, unsorted and unfiltered.
+    pub levels: HashMap<PathBuf, Vec<ObjectInfo>>,
+
+    /// Directories whose children are still streaming in.
+    pub loading: HashSet<PathBuf>,
+
+    /// Row information, parallel to the pane's flattened `entries`.
+    pub rows: Vec<TreeRow>,
+
+    /// Loaded entries hidden by the filter in the last flatten.
+    pub hidden: usize,
+}
+
+impl TreeState {
+    #[must_use]
+    /// Tree rooted at `root` whose top level is `entries`.
+    pub fn new(root: PathBuf, entries: Vec<ObjectInfo>) -> Self {
+        let mut levels: HashMap<PathBuf, Vec<ObjectInfo>> = HashMap::new();
+        levels.insert(root.clone(), entries);
+
+        Self {
+            root,
+            expanded: HashSet::new(),
+            levels,
+            loading: HashSet::new(),
+            rows: Vec::new(),
+            hidden: 0,
+        }
+    }
+
+    /// Prepare for (re)loading the tree at `root`. Expanded directories are
+    /// kept when reloading the same root so they can be re-scanned.
+    pub fn reset(&mut self, root: &Path) {
+        if self.root != root {
+            self.root = root.to_path_buf();
+            self.expanded.clear();
+        }
+
+        self.levels.clear();
+        self.levels.insert(self.root.clone(), Vec::new());
+        self.loading.clear();
+        self.rows.clear();
+    }
+
+    /// Mark `dir` expanded. Returns true if its children still need to be
+    /// scanned.
+    pub fn expand(&mut self, dir: &Path) -> bool {
+        self.expanded.insert(dir.to_path_buf());
+
+        if self.levels.contains_key(dir) || self.loading.contains(dir) {
+            return false;
+        }
+
+        self.levels.insert(dir.to_path_buf(), Vec::new());
+        self.loading.insert(dir.to_path_buf());
+
+        true
+    }
+
+    /// Collapse `dir`. Its loaded children are kept for re-expanding.
+    pub fn collapse(&mut self, dir: &Path) -> bool {
+        self.expanded.remove(dir)
+    }
+
+    /// Add a streamed child of `dir`. Returns true when the view should be
+    /// rebuilt (batched geometrically to keep large directories cheap).
+    pub fn add_child(&mut self, dir: &Path, entry: ObjectInfo) -> bool {
+        let level: &mut Vec<ObjectInfo> = self.levels.entry(dir.to_path_buf()).or_default();
+        level.push(entry);
+
+        level.len().is_power_of_two()
+    }
+
+    /// Patch the loaded children of `dir` after filesystem changes. Returns
+    /// true when the level changed.
+    pub fn apply_changes(&mut self, dir: &Path, changes: &[FsChange], show_hidden: bool) -> bool {
+        let Some(level) = self.levels.get_mut(dir) else {
+            return false;
+        };
+
+        let mut changed: bool = false;
+
+        for change in changes {
+            let existing: Option<usize> = level
+                .iter()
+                .position(|e: &ObjectInfo| -> bool { e.path == change.path() });
+
+            match change {
+                FsChange::Removed(path) => {
+                    if let Some(idx) = existing {
+                        level.remove(idx);
+                        self.expanded.remove(path);
+                        changed = true;
+                    }
+                }
+
+                FsChange::Upsert(info) => {
+                    if !show_hidden && info.is_hidden() {
+                        continue;
+                    }
+
+                    match existing {
+                        Some(idx) => {
+                            let entry: ObjectInfo = level[idx].clone();
+                            level[idx] = FsChange::refreshed(Some(entry), info);
+                        }
+
+                        None => level.push(info.clone()),
+                    }
+
+                    changed = true;
+                }
+            }
+        }
+
+        changed
+    }
+
+    /// Finish loading `dir`.
+    pub fn complete(&mut self, dir: &Path) {
+        self.loading.remove(dir);
+    }
+
+    /// Forget a directory that failed to load.
+    pub fn fail(&mut self, dir: &Path) {
+        self.loading.remove(dir);
+        self.levels.remove(dir);
+        self.expanded.remove(dir);
+    }
+
+    /// Replace the data of an already loaded entry (metadata updates).
+    pub fn update_entry(&mut self, parent_dir: &Path, info: &ObjectInfo) -> bool {
+        let Some(entry) = self
+            .levels
+            .get_mut(parent_dir)
+            .and_then(|level: &mut Vec<ObjectInfo>| {
+                level.iter_mut().find(|e: &&mut ObjectInfo| -> bool { e.path == info.path })
+            })
+        else {
+            return false;
+        };
+
+        entry.apply_metadata(info);
+
+        true
+    }
+
+    #[must_use]
+    /// Expanded directories under a visible, expanded parent whose children
+    /// are not loaded (e.g. after reloading the root).
+    pub fn pending_loads(&self) -> Vec<PathBuf> {
+        let mut pending: Vec<PathBuf> = self
+            .expanded
+            .iter()
+            .filter(|dir: &&PathBuf| -> bool {
+                !self.levels.contains_key(*dir)
+                    && !self.loading.contains(*dir)
+                    && dir.parent().is_some_and(|parent: &Path| -> bool {
+                        parent == self.root || self.levels.contains_key(parent)
+                    })
+            })
+            .cloned()
+            .collect();
+
+        pending.sort();
+        pending
+    }
+
+    /// Flatten the tree into display order, applying `sort` and `filter` on
+    /// every level. Directories stay visible when any loaded descendant
+    /// matches the filter.
+    #[must_use]
+    pub fn flatten(
+        &mut self,
+        sort: &EntrySort,
+        sort_config: &SortConfig,
+        filter: &EntryFilter,
+    ) -> Vec<ObjectInfo> {
+        let order = |level: &mut [ObjectInfo]| sort_objects(level, sort, sort_config);
+
+        let mut flat: Vec<(ObjectInfo, usize)> = Vec::new();
+        let mut hidden: usize = 0;
+        self.flatten_level(&self.root, 0, &order, filter, &mut flat, &mut hidden);
+        self.hidden = hidden;
+
+        let (entries, depths): (Vec<ObjectInfo>, Vec<usize>) = flat.into_iter().unzip();
+        self.rows = self.build_rows(&entries, &depths);
+
+        entries
+    }
+
+    fn flatten_level(
+        &self,
+        dir: &Path,
+        depth: usize,
+        order: &impl Fn(&mut [ObjectInfo]),
+        filter: &EntryFilter,
+        out: &mut Vec<(ObjectInfo, usize)>,
+        hidden: &mut usize,
+    ) {
+        let Some(level) = self.levels.get(dir) else {
+            return;
+        };
+
+        let mut level: Vec<ObjectInfo> = level.clone();
+        order(&mut level);
+
+        for entry in level {
+            let mut children: Vec<(ObjectInfo, usize)> = Vec::new();
+
+            if entry.is_dir && self.expanded.contains(&entry.path) {
+                self.flatten_level(&entry.path, depth + 1, order, filter, &mut children, hidden);
+            }
+
+            if filter.matches(&entry) || !children.is_empty() {
+                out.push((entry, depth));
+                out.append(&mut children);
+            } else {
+                *hidden += 1;
+            }
+        }
+    }
+
+    /// Compute indentation guides in one reverse pass: a row is the last of
+    /// its siblings if no later row at the same depth follows before a
+    /// shallower one, and an ancestor column continues (`│`) while that
+    /// ancestor still has siblings below.
+    fn build_rows(&self, entries: &[ObjectInfo], depths: &[usize]) -> Vec<TreeRow> {
+        let mut rows: Vec<TreeRow> = Vec::with_capacity(entries.len());
+        let mut has_sibling_below: Vec<bool> = Vec::new();
+
+        for (entry, &depth) in entries.iter().zip(depths).rev() {
+            has_sibling_below.resize(depth + 1, false);
+
+            let mut guides: String = String::new();
+            for &continues in has_sibling_below.iter().take(depth).skip(1) {
+                guides.push_str(if continues { "│  " } else { "   " });
+            }
+
+            if depth > 0 {
+                guides.push_str(if has_sibling_below[depth] { "├─ " } else { "└─ " });
+            }
+
+            has_sibling_below[depth] = true;
+
+            let expanded: bool = entry.is_dir && self.expanded.contains(&entry.path);
+
+            rows.push(TreeRow {
+                depth,
+                guides,
+                expanded,
+                loading: self.loading.contains(&entry.path),
+            });
+        }
+
+        rows.reverse();
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(path: &str, is_dir: bool) -> ObjectInfo {
+        let path: PathBuf = PathBuf::from(path);
+
+        ObjectInfo {
+            name: path.file_name().unwrap_or_default().to_os_string(),
+            path,
+            is_dir,
+            ..ObjectInfo::default()
+        }
+    }
+
+    /// Rows for `(path, is_dir, depth)` listed in display order.
+    fn rows(tree: &TreeState, listing: &[(&str, bool, usize)]) -> Vec<TreeRow> {
+        let entries: Vec<ObjectInfo> = listing.iter().map(|&(path, is_dir, _): &(&str, bool, usize)| -> ObjectInfo { object(path, is_dir) }).collect();
+        let depths: Vec<usize> = listing.iter().map(|&(_, _, depth): &(&str, bool, usize)| -> usize { depth }).collect();
+
+        tree.build_rows(&entries, &depths)
+    }
+
+    fn guides(rows: &[TreeRow]) -> Vec<&str> {
+        rows.iter().map(|row: &TreeRow| -> &str { &row.guides }).collect()
+    }
+
+    #[test]
+    fn build_rows_draws_branches_and_continuations() {
+        let tree: TreeState = TreeState::new(PathBuf::from("/r"), Vec::new());
+        let rows: Vec<TreeRow> = rows(&tree, &[
+            ("/r/a", true, 0),
+            ("/r/a/a1", true, 1),
+            ("/r/a/a1/x", false, 2),
+            ("/r/a/a2", false, 1),
+            ("/r/b", true, 0),
+            ("/r/b/b1", false, 1),
+            ("/r/c", false, 0),
+        ]);
+
+        assert_eq!(guides(&rows), ["", "├─ ", "│  └─ ", "└─ ", "", "└─ ", ""]);
+        assert_eq!(rows.iter().map(|row: &TreeRow| -> usize { row.depth }).collect::<Vec<usize>>(), [0, 1, 2, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn build_rows_leaves_finished_ancestor_columns_blank() {
+        let tree: TreeState = TreeState::new(PathBuf::from("/r"), Vec::new());
+        let rows: Vec<TreeRow> = rows(&tree, &[
+            ("/r/a", true, 0),
+            ("/r/a/b", true, 1),
+            ("/r/a/b/c", true, 2),
+            ("/r/a/b/c/d", false, 3),
+            ("/r/a/b/c/e", false, 3),
+        ]);
+
+        assert_eq!(guides(&rows), ["", "└─ ", "   └─ ", "      ├─ ", "      └─ "]);
+    }
+
+    #[test]
+    fn build_rows_does_not_link_siblings_of_different_parents() {
+        let tree: TreeState = TreeState::new(PathBuf::from("/r"), Vec::new());
+        let rows: Vec<TreeRow> = rows(&tree, &[
+            ("/r/a", true, 0),
+            ("/r/a/x", false, 1),
+            ("/r/b", true, 0),
+            ("/r/b/y", false, 1),
+        ]);
+
+        assert_eq!(guides(&rows), ["", "└─ ", "", "└─ "]);
+    }
+
+    #[test]
+    fn build_rows_flags_expanded_and_loading_directories() {
+        let mut tree: TreeState = TreeState::new(PathBuf::from("/r"), Vec::new());
+        tree.expanded.insert(PathBuf::from("/r/a"));
+        assert!(tree.expand(Path::new("/r/b")));
+
+        let rows: Vec<TreeRow> = rows(&tree, &[
+            ("/r/a", true, 0),
+            ("/r/b", true, 0),
+            ("/r/c", true, 0),
+        ]);
+
+        assert_eq!(
+            rows.iter().map(|row: &TreeRow| -> (bool, bool) { (row.expanded, row.loading) }).collect::<Vec<(bool, bool)>>(),
+            [(true, false), (true, true), (false, false)]
+        );
+    }
+
+    #[test]
+    fn build_rows_is_empty_for_an_empty_listing() {
+        let tree: TreeState = TreeState::new(PathBuf::from("/r"), Vec::new());
+
+        assert!(rows(&tree, &[]).is_empty());
+    }
+}
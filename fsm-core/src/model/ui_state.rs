@@ -16,8 +16,12 @@ use std::time::{Duration, Instant};
 use tokio_util::sync::CancellationToken;
 
 use crate::{controller::actions::InputPromptType, FileNameSearchOverlay};
+use crate::config::{ColumnFormat, ColumnSpec, LayoutMode, TableColumn};
 use crate::fs::object_info::ObjectInfo;
+use crate::fs::trash::TrashEntry;
 use crate::model::command_palette::{Command, CommandAction, CommandPaletteState};
+use crate::model::diagnostics::DiagnosticsSnapshot;
+use crate::model::hit_map::{Click, HitMap};
 use crate::tasks::search_task::RawSearchResult;
 
 /// Granular redraw flags for selective UI updates
@@ -93,6 +97,33 @@ pub enum UIOverlay {
     Batch,
 
     Scripting,
+
+    History,
+
+    Bookmarks,
+
+    Frecency,
+
+    QuickFilter,
+
+    Diagnostics,
+
+    Trash,
+
+    UndoHistory,
+}
+
+/// First key of a two-key sequence waiting for its second key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PendingKey {
+    /// `M` pressed: the next key names the mark to set.
+    SetMark,
+
+    /// `'` pressed: the next key names the mark to jump to.
+    JumpToMark,
+
+    /// Purge pressed once in the trash overlay: pressing it again confirms.
+    PurgeTrash,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -261,7 +292,8 @@ pub struct UIState {
     /// Multi-selection (indices) for batch ops in current pane.
     pub marked_indices: HashSet<usize>,
 
-    /// Visual/range selection, if active: (start, end)
+    /// Visual/range selection of the active pane as last drawn, if active:
+    /// (start, end), inclusive
     pub visual_range: Option<(usize, usize)>,
 
     /// Index of active pane.
@@ -287,10 +319,22 @@ pub struct UIState {
     /// Type of input prompt currently active.
     pub input_prompt_type: Option<InputPromptType>,
 
+    /// Highlighted row in list overlays (history, bookmarks, ...).
+    pub overlay_selected: usize,
+
+    /// Pending two-key sequence (marks).
+    pub pending_key: Option<PendingKey>,
+
     // --- Visual and Display State ---
     /// Show hidden files flag.
     pub show_hidden: bool,
 
+    /// Main view layout (table or Miller columns).
+    pub layout: LayoutMode,
+
+    /// Table columns in display order (hidden ones included).
+    pub columns: Vec<ColumnSpec>,
+
     /// Current theme (theme name).
     pub theme: String,
 
@@ -335,6 +379,19 @@ pub struct UIState {
     pub clipboard_overlay_active: bool,
     pub selected_clipboard_item: Option<String>,
     pub clipboard_view_mode: ClipBoardViewMode,
+
+    /// Latest diagnostics snapshot (while the diagnostics overlay is open)
+    pub diagnostics: Option<DiagnosticsSnapshot>,
+
+    /// Trash contents listed by the trash overlay (loaded when it opens)
+    pub trash_entries: Vec<TrashEntry>,
+
+    // --- Mouse State ---
+    /// Clickable areas of the last frame
+    pub hits: HitMap,
+
+    /// Last left-button press, for double clicks
+    pub last_click: Option<Click>,
 }
 
 impl PartialEq for UIState {
@@ -370,9 +427,13 @@ impl UIState {
                 },
             ]),
             input_prompt_type: None,
+            overlay_selected: 0,
+            pending_key: None,
 
             // Visual and Display State
             show_hidden: false,
+            layout: LayoutMode::Table,
+            columns: ColumnSpec::defaults(),
             theme: "default".to_string(),
 
             // Search Results State
@@ -400,6 +461,13 @@ impl UIState {
             clipboard_overlay_active: false,
             selected_clipboard_item: None,
             clipboard_view_mode: ClipBoardViewMode::default(),
+
+            diagnostics: None,
+
+            trash_entries: Vec::new(),
+
+            hits: HitMap::default(),
+            last_click: None,
         }
     }
 
@@ -468,6 +536,146 @@ impl UIState {
         self.request_redraw_all();
     }
 
+    /// Toggle the navigation history overlay, highlighting `current` when opened.
+    pub const fn toggle_history_overlay(&mut self, current: usize) {
+        self.overlay = match self.overlay {
+            UIOverlay::History => UIOverlay::None,
+            _ => UIOverlay::History,
+        };
+        self.overlay_selected = current;
+
+        self.request_redraw_all();
+    }
+
+    /// Toggle the undo history overlay, highlighting row `next_undo` (the
+    /// operation the next undo reverses).
+    pub const fn toggle_undo_history_overlay(&mut self, next_undo: usize) {
+        self.overlay = match self.overlay {
+            UIOverlay::UndoHistory => UIOverlay::None,
+            _ => UIOverlay::UndoHistory,
+        };
+        self.overlay_selected = next_undo;
+
+        self.request_redraw_all();
+    }
+
+    /// Toggle the diagnostics overlay; closing it drops the last snapshot.
+    pub fn toggle_diagnostics_overlay(&mut self) {
+        self.overlay = match self.overlay {
+            UIOverlay::Diagnostics => {
+                self.diagnostics = None;
+                UIOverlay::None
+            }
+
+            _ => UIOverlay::Diagnostics,
+        };
+
+        self.request_redraw_all();
+    }
+
+    /// Toggle the trash overlay with freshly listed `entries`; closing it
+    /// drops the list.
+    pub fn toggle_trash_overlay(&mut self, entries: Vec<TrashEntry>) {
+        self.overlay = match self.overlay {
+            UIOverlay::Trash => {
+                self.trash_entries.clear();
+                UIOverlay::None
+            }
+
+            _ => {
+                self.trash_entries = entries;
+                UIOverlay::Trash
+            }
+        };
+        self.overlay_selected = 0;
+        self.pending_key = None;
+
+        self.request_redraw_all();
+    }
+
+    /// Replace the listed trash contents, keeping the highlight in range.
+    pub fn set_trash_entries(&mut self, entries: Vec<TrashEntry>) {
+        self.overlay_selected = self.overlay_selected.min(entries.len().saturating_sub(1));
+        self.trash_entries = entries;
+
+        self.request_redraw(RedrawFlag::Overlay);
+    }
+
+    /// Toggle the bookmark overlay; opening it clears the filter input.
+    pub fn toggle_bookmarks_overlay(&mut self) {
+        self.toggle_filterable_overlay(UIOverlay::Bookmarks, "");
+    }
+
+    /// Toggle the frecency overlay; opening it prefills the filter input.
+    pub fn toggle_frecency_overlay(&mut self, query: &str) {
+        self.toggle_filterable_overlay(UIOverlay::Frecency, query);
+    }
+
+    /// Toggle the quick-filter input bar, prefilled with the pane's filter.
+    pub fn toggle_quick_filter_overlay(&mut self, query: &str) {
+        self.toggle_filterable_overlay(UIOverlay::QuickFilter, query);
+    }
+
+    fn toggle_filterable_overlay(&mut self, overlay: UIOverlay, query: &str) {
+        self.overlay = if self.overlay == overlay {
+            UIOverlay::None
+        } else {
+            overlay
+        };
+
+        if self.overlay == overlay {
+            self.input.clear();
+            self.input.push_str(query);
+            self.overlay_selected = 0;
+        }
+
+        self.request_redraw_all();
+    }
+
+    /// Move the list overlay highlight up one row.
+    pub const fn overlay_select_prev(&mut self) {
+        self.overlay_selected = self.overlay_selected.saturating_sub(1);
+        self.request_redraw(RedrawFlag::Overlay);
+    }
+
+    /// Move the list overlay highlight down one row, bounded by `len`.
+    pub fn overlay_select_next(&mut self, len: usize) {
+        self.overlay_selected = (self.overlay_selected + 1).min(len.saturating_sub(1));
+        self.request_redraw(RedrawFlag::Overlay);
+    }
+
+    /// Remember a left-button press; true when it completes a double click.
+    pub fn register_click(&mut self, column: u16, row: u16) -> bool {
+        let click: Click = Click::new(column, row);
+        let double: bool = click.is_double(self.last_click);
+
+        // A third press starts a new pair
+        self.last_click = if double { None } else { Some(click) };
+
+        double
+    }
+
+    /// Highlight item `idx` of the open list overlay (a clicked row).
+    pub fn select_overlay_item(&mut self, idx: usize) {
+        match self.overlay {
+            UIOverlay::History
+            | UIOverlay::Bookmarks
+            | UIOverlay::Frecency
+            | UIOverlay::Trash
+            | UIOverlay::UndoHistory => {
+                self.overlay_selected = idx;
+            }
+
+            UIOverlay::FileNameSearch | UIOverlay::ContentSearch | UIOverlay::SearchResults => {
+                self.selected = Some(idx);
+            }
+
+            _ => return,
+        }
+
+        self.request_redraw(RedrawFlag::Overlay);
+    }
+
     /// Enter vim-style command mode
     pub fn enter_command_mode(&mut self) {
         self.mode = UIMode::Command;
@@ -494,6 +702,25 @@ impl UIState {
         self.mode == UIMode::Command
     }
 
+    /// Enter visual (range selection) mode
+    pub const fn enter_visual_mode(&mut self) {
+        self.mode = UIMode::Visual;
+        self.request_redraw(RedrawFlag::All);
+    }
+
+    /// Leave visual mode and forget its range
+    pub const fn exit_visual_mode(&mut self) {
+        self.mode = UIMode::Browse;
+        self.visual_range = None;
+        self.request_redraw(RedrawFlag::All);
+    }
+
+    #[must_use]
+    /// Check if currently selecting a range in visual mode
+    pub fn is_in_visual_mode(&self) -> bool {
+        self.mode == UIMode::Visual
+    }
+
     pub fn toggle_filename_search_overlay(&mut self) {
         self.overlay = match self.overlay {
             UIOverlay::FileNameSearch => UIOverlay::None,
@@ -657,6 +884,68 @@ impl UIState {
         self.request_redraw(RedrawFlag::Main);
     }
 
+    /// Columns currently shown, in display order.
+    pub fn visible_columns(&self) -> impl Iterator<Item = &ColumnSpec> {
+        self.columns.iter().filter(|spec: &&ColumnSpec| -> bool { spec.visible })
+    }
+
+    /// Show or hide a table column. Returns true if it is now shown; the
+    /// last visible column stays shown.
+    pub fn toggle_column(&mut self, column: TableColumn) -> bool {
+        let visible: usize = self.visible_columns().count();
+
+        let shown: bool = if let Some(spec) = self.column_mut(column) {
+            spec.visible = !spec.visible || visible == 1;
+            spec.visible
+        } else {
+            self.insert_column(ColumnSpec::new(column));
+            true
+        };
+
+        self.request_redraw(RedrawFlag::Main);
+
+        shown
+    }
+
+    /// Show a column with the given format.
+    pub fn set_column_format(&mut self, column: TableColumn, format: ColumnFormat) {
+        if self.column_mut(column).is_none() {
+            self.insert_column(ColumnSpec::new(column));
+        }
+
+        if let Some(spec) = self.column_mut(column) {
+            spec.visible = true;
+            spec.format = format;
+        }
+
+        self.request_redraw(RedrawFlag::Main);
+    }
+
+    fn column_mut(&mut self, column: TableColumn) -> Option<&mut ColumnSpec> {
+        self.columns
+            .iter_mut()
+            .find(|spec: &&mut ColumnSpec| -> bool { spec.column == column })
+    }
+
+    /// Insert a column not configured so far after the last column that
+    /// precedes it in `TableColumn::ALL`.
+    fn insert_column(&mut self, spec: ColumnSpec) {
+        let rank = |column: TableColumn| -> usize {
+            TableColumn::ALL
+                .iter()
+                .position(|c: &TableColumn| -> bool { *c == column })
+                .unwrap_or(usize::MAX)
+        };
+
+        let idx: usize = self
+            .columns
+            .iter()
+            .rposition(|s: &ColumnSpec| -> bool { rank(s.column) < rank(spec.column) })
+            .map_or(0, |i: usize| -> usize { i + 1 });
+
+        self.columns.insert(idx, spec);
+    }
+
     pub fn set_theme(&mut self, theme: impl Into<String>) {
         self.theme = theme.into();
         self.request_redraw_all();
@@ -722,3 +1011,75 @@ impl UIState {
         self.request_redraw(RedrawFlag::Overlay);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::cache::cache_manager::ObjectInfoCache;
+
+    #[test]
+    fn closing_the_diagnostics_overlay_drops_the_snapshot() {
+        let mut ui: UIState = UIState::new();
+
+        ui.toggle_diagnostics_overlay();
+        assert_eq!(ui.overlay, UIOverlay::Diagnostics);
+
+        ui.diagnostics = Some(DiagnosticsSnapshot {
+            cache: ObjectInfoCache::new().stats(),
+            cache_entries: 0,
+            cache_bytes: 0,
+            cache_budget: 0,
+            cache_restored: 0,
+            events: 0,
+            avg_latency: Duration::ZERO,
+            peak_latency: Duration::ZERO,
+            pending_tasks: 0,
+            file_operations: Vec::new(),
+            memory_kb: None,
+            cpu_percent: None,
+            task_queue: 0,
+            action_queue: 0,
+            uptime: Duration::ZERO,
+        });
+        ui.toggle_diagnostics_overlay();
+
+        assert_eq!(ui.overlay, UIOverlay::None);
+        assert!(ui.diagnostics.is_none());
+    }
+
+    fn visible(ui: &UIState) -> Vec<TableColumn> {
+        ui.visible_columns().map(|spec: &ColumnSpec| -> TableColumn { spec.column }).collect()
+    }
+
+    #[test]
+    fn toggled_columns_keep_their_place_and_one_stays_shown() {
+        let mut ui: UIState = UIState::new();
+        ui.columns = vec![ColumnSpec::new(TableColumn::Name), ColumnSpec::new(TableColumn::Modified)];
+
+        assert!(ui.toggle_column(TableColumn::Size));
+        assert!(ui.toggle_column(TableColumn::Owner));
+        assert_eq!(visible(&ui), [TableColumn::Name, TableColumn::Size, TableColumn::Owner, TableColumn::Modified]);
+
+        assert!(!ui.toggle_column(TableColumn::Size));
+        assert!(!ui.toggle_column(TableColumn::Owner));
+        assert!(!ui.toggle_column(TableColumn::Modified));
+        assert!(ui.toggle_column(TableColumn::Name), "the last column stays shown");
+        assert_eq!(visible(&ui), [TableColumn::Name]);
+    }
+
+    #[test]
+    fn setting_a_format_shows_the_column() {
+        let mut ui: UIState = UIState::new();
+        ui.columns = vec![ColumnSpec::new(TableColumn::Name)];
+        ui.toggle_column(TableColumn::Size);
+        ui.toggle_column(TableColumn::Size);
+
+        ui.set_column_format(TableColumn::Size, ColumnFormat::Bytes);
+        ui.set_column_format(TableColumn::Accessed, ColumnFormat::Relative);
+
+        assert_eq!(visible(&ui), [TableColumn::Name, TableColumn::Size, TableColumn::Accessed]);
+        assert_eq!(ui.columns[1].format, ColumnFormat::Bytes);
+    }
+}
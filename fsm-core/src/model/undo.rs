@@ -0,0 +1,598 @@
+//! ``src/model/undo.rs``
+//! ============================================================================
+//! # Undo/Redo: Reversing Recorded File Operations
+//!
+//! Every operation that changes the filesystem records an `AppHistoryEvent`
+//! through `AppState::push_history`. Undoing applies its inverse and hands
+//! back the event to keep for redo, redoing applies it again:
+//!
+//! | Event    | Undo                           | Redo                          |
+//! |----------|--------------------------------|-------------------------------|
+//! | `Delete` | restore the entries from trash | trash them again              |
+//! | `Move`   | move `to` back to `from`       | move `from` to `to` again     |
+//! | `Rename` | rename `to` back to `from`     | rename `from` to `to` again   |
+//! | `Copy`   | trash the copy                 | restore the copy from trash   |
+//! | `Create` | trash the new entry            | restore it from trash         |
+//! | `Batch`  | undo the steps, last first     | redo the steps, first first   |
+//!
+//! Nothing is removed for good and nothing is overwritten (a move across
+//! filesystems removes the original only once it is copied). Before touching
+//! anything, each step checks that the filesystem still looks the way the
+//! operation left it (the entry is still there, the path it goes back to is
+//! free, a copy or new file was not edited since, down to the files inside
+//! a copied directory) and refuses otherwise. A batch that fails halfway is
+//! rolled back to where it started.
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use tokio::fs as TokioFs;
+use walkdir::WalkDir;
+use tracing::{debug, warn};
+
+use crate::error::AppError;
+use crate::fs::rename::rename_no_replace;
+use crate::fs::trash::{self, TrashEntry};
+use crate::model::app_state::AppHistoryEvent;
+
+/// Which way a run of recorded operations goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Undo,
+    Redo,
+}
+
+impl Direction {
+    #[must_use]
+    pub const fn verb(self) -> &'static str {
+        match self {
+            Self::Undo => "undo",
+            Self::Redo => "redo",
+        }
+    }
+}
+
+/// What a run of undo or redo steps did, handed back to `AppState` to
+/// update its stacks.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub direction: Direction,
+
+    /// Label of each applied step and the event to keep for the other way
+    pub applied: Vec<(String, AppHistoryEvent)>,
+
+    /// The refused step and the ones after it, untouched, in run order
+    pub remaining: Vec<AppHistoryEvent>,
+
+    /// Why the run stopped early
+    pub error: Option<String>,
+}
+
+/// Undo or redo `events` in order, stopping at the first one that is
+/// refused. Runs on its own task: moving a tree across filesystems takes
+/// as long as the original move did.
+pub async fn run(direction: Direction, events: Vec<AppHistoryEvent>) -> Outcome {
+    let mut outcome: Outcome = Outcome {
+        direction,
+        applied: Vec::with_capacity(events.len()),
+        remaining: Vec::new(),
+        error: None,
+    };
+
+    let mut events: std::vec::IntoIter<AppHistoryEvent> = events.into_iter();
+
+    while let Some(event) = events.next() {
+        let result: Result<AppHistoryEvent, AppError> = match direction {
+            Direction::Undo => undo(&event).await,
+
+            Direction::Redo => redo(&event).await,
+        };
+
+        match result {
+            Ok(other) => {
+                let label: String = match direction {
+                    Direction::Undo => event.label(),
+
+                    Direction::Redo => other.label(),
+                };
+
+                outcome.applied.push((label, other));
+            }
+
+            Err(e) => {
+                outcome.error = Some(e.to_string());
+                outcome.remaining.push(event);
+                outcome.remaining.extend(events);
+                break;
+            }
+        }
+    }
+
+    outcome
+}
+
+/// Reverse `event`. Returns the event to keep for redo.
+pub async fn undo(event: &AppHistoryEvent) -> Result<AppHistoryEvent, AppError> {
+    debug!("Undoing {}", event.label());
+
+    match event {
+        AppHistoryEvent::Delete { entries } => {
+            for entry in entries {
+                if !exists(&entry.trashed_path).await {
+                    return Err(refused("undo", &entry.original_path, "it is no longer in the trash"));
+                }
+
+                if exists(&entry.original_path).await {
+                    return Err(refused("undo", &entry.original_path, "the path is taken again"));
+                }
+            }
+
+            trash::restore_all(entries).await?;
+
+            Ok(event.clone())
+        }
+
+        AppHistoryEvent::Move { from, to } | AppHistoryEvent::Rename { from, to } => {
+            relocate("undo", to, from).await?;
+
+            Ok(event.clone())
+        }
+
+        AppHistoryEvent::Copy { from, to, fingerprint, .. } => {
+            let entry: TrashEntry = trash_unchanged(to, *fingerprint).await?;
+
+            Ok(AppHistoryEvent::Copy {
+                from: from.clone(),
+                to: to.clone(),
+                fingerprint: *fingerprint,
+                trashed: Some(entry),
+            })
+        }
+
+        AppHistoryEvent::Create { path, is_dir, fingerprint, .. } => {
+            let entry: TrashEntry = trash_unchanged(path, *fingerprint).await?;
+
+            Ok(AppHistoryEvent::Create {
+                path: path.clone(),
+                is_dir: *is_dir,
+                fingerprint: *fingerprint,
+                trashed: Some(entry),
+            })
+        }
+
+        AppHistoryEvent::Batch(events) => {
+            let mut undone: Vec<AppHistoryEvent> = Vec::with_capacity(events.len());
+
+            for step in events.iter().rev() {
+                match Box::pin(undo(step)).await {
+                    Ok(redo_step) => undone.push(redo_step),
+
+                    Err(e) => {
+                        for redo_step in undone.iter().rev() {
+                            let _ = Box::pin(redo(redo_step)).await;
+                        }
+
+                        return Err(e);
+                    }
+                }
+            }
+
+            undone.reverse();
+
+            Ok(AppHistoryEvent::Batch(undone))
+        }
+    }
+}
+
+/// Apply an event returned by [`undo`] again. Returns the event to keep
+/// for the next undo.
+pub async fn redo(event: &AppHistoryEvent) -> Result<AppHistoryEvent, AppError> {
+    debug!("Redoing {}", event.label());
+
+    match event {
+        AppHistoryEvent::Delete { entries } => {
+            for entry in entries {
+                if !exists(&entry.original_path).await {
+                    return Err(refused("redo", &entry.original_path, "it no longer exists"));
+                }
+            }
+
+            let mut trashed: Vec<TrashEntry> = Vec::with_capacity(entries.len());
+            for entry in entries {
+                match trash::trash(&entry.original_path).await {
+                    Ok(entry) => trashed.push(entry),
+
+                    Err(e) => {
+                        // Put back what this redo trashed, so it can run again
+                        if let Err(e) = trash::restore_all(&trashed).await {
+                            warn!("Could not roll back a partial redo: {}", e);
+                        }
+
+                        return Err(e);
+                    }
+                }
+            }
+
+            Ok(AppHistoryEvent::Delete { entries: trashed })
+        }
+
+        AppHistoryEvent::Move { from, to } | AppHistoryEvent::Rename { from, to } => {
+            relocate("redo", from, to).await?;
+
+            Ok(event.clone())
+        }
+
+        AppHistoryEvent::Copy { from, to, fingerprint, trashed } => {
+            restore_from(trashed.as_ref(), to).await?;
+
+            Ok(AppHistoryEvent::Copy {
+                from: from.clone(),
+                to: to.clone(),
+                fingerprint: *fingerprint,
+                trashed: None,
+            })
+        }
+
+        AppHistoryEvent::Create { path, is_dir, fingerprint, trashed } => {
+            restore_from(trashed.as_ref(), path).await?;
+
+            Ok(AppHistoryEvent::Create {
+                path: path.clone(),
+                is_dir: *is_dir,
+                fingerprint: *fingerprint,
+                trashed: None,
+            })
+        }
+
+        AppHistoryEvent::Batch(events) => {
+            let mut redone: Vec<AppHistoryEvent> = Vec::with_capacity(events.len());
+
+            for step in events {
+                match Box::pin(redo(step)).await {
+                    Ok(undo_step) => redone.push(undo_step),
+
+                    Err(e) => {
+                        for undo_step in redone.iter().rev() {
+                            let _ = Box::pin(undo(undo_step)).await;
+                        }
+
+                        return Err(e);
+                    }
+                }
+            }
+
+            Ok(AppHistoryEvent::Batch(redone))
+        }
+    }
+}
+
+/// Names, sizes and modification times of `path` and everything below it,
+/// hashed (symlinks are not followed). Recorded right after an operation,
+/// so an edit anywhere inside a directory is noticed, not only changes to
+/// its own listing. `None` if part of it cannot be read.
+pub async fn fingerprint(path: &Path) -> Option<u64> {
+    let root: PathBuf = path.to_path_buf();
+
+    tokio::task::spawn_blocking(move || -> Option<u64> {
+        let mut hasher: DefaultHasher = DefaultHasher::new();
+
+        for entry in WalkDir::new(&root).sort_by_file_name() {
+            let entry: walkdir::DirEntry = entry.ok()?;
+            let meta: std::fs::Metadata = entry.metadata().ok()?;
+
+            entry.path().strip_prefix(&root).ok()?.hash(&mut hasher);
+            meta.is_dir().hash(&mut hasher);
+            meta.len().hash(&mut hasher);
+            meta.modified().ok()?.hash(&mut hasher);
+        }
+
+        Some(hasher.finish())
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+async fn exists(path: &Path) -> bool {
+    TokioFs::symlink_metadata(path).await.is_ok()
+}
+
+/// Rename `from` to `to`, which must still be free. Across filesystems the
+/// entry is copied and the original removed, as the move itself did.
+async fn relocate(operation: &str, from: &Path, to: &Path) -> Result<(), AppError> {
+    if !exists(from).await {
+        return Err(refused(operation, from, "it no longer exists"));
+    }
+
+    if exists(to).await {
+        return Err(refused(operation, to, "the path is taken"));
+    }
+
+    match rename_no_replace(from, to).await {
+        Err(e) if e.kind() == ErrorKind::CrossesDevices => {
+            debug!("{} crosses filesystems, copying it to {}", from.display(), to.display());
+
+            if let Err(e) = copy_tree(from, to).await {
+                // Only the partial copy goes; `to` was free before
+                let _ = remove_entry(to).await;
+
+                return Err(e.into());
+            }
+
+            Ok(remove_entry(from).await?)
+        }
+
+        result => Ok(result?),
+    }
+}
+
+/// Copy `from` to the free path `to`: directories with everything in them,
+/// symlinks as symlinks. Never merges into or overwrites anything.
+async fn copy_tree(from: &Path, to: &Path) -> Result<(), std::io::Error> {
+    let mut stack: Vec<(PathBuf, PathBuf)> = vec![(from.to_path_buf(), to.to_path_buf())];
+
+    while let Some((from, to)) = stack.pop() {
+        let file_type: std::fs::FileType = TokioFs::symlink_metadata(&from).await?.file_type();
+
+        if file_type.is_symlink() {
+            copy_symlink(&from, &to).await?;
+        } else if file_type.is_dir() {
+            TokioFs::create_dir(&to).await?;
+
+            let mut entries: TokioFs::ReadDir = TokioFs::read_dir(&from).await?;
+
+            while let Some(entry) = entries.next_entry().await? {
+                stack.push((entry.path(), to.join(entry.file_name())));
+            }
+        } else {
+            if exists(&to).await {
+                return Err(std::io::Error::from(ErrorKind::AlreadyExists));
+            }
+
+            TokioFs::copy(&from, &to).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Create a symlink at `to` pointing where the symlink `from` points.
+#[cfg(unix)]
+pub async fn copy_symlink(from: &Path, to: &Path) -> Result<(), std::io::Error> {
+    let link: PathBuf = TokioFs::read_link(from).await?;
+
+    TokioFs::symlink(link, to).await
+}
+
+#[cfg(not(unix))]
+pub async fn copy_symlink(_from: &Path, _to: &Path) -> Result<(), std::io::Error> {
+    Err(std::io::Error::new(ErrorKind::Unsupported, "symlinks cannot be copied on this platform"))
+}
+
+/// Remove `path` for good, without following a symlink.
+pub async fn remove_entry(path: &Path) -> Result<(), std::io::Error> {
+    if TokioFs::symlink_metadata(path).await?.is_dir() {
+        TokioFs::remove_dir_all(path).await
+    } else {
+        TokioFs::remove_file(path).await
+    }
+}
+
+/// Trash `path` unless it or anything inside it changed since `recorded`
+/// was taken (see [`fingerprint`]).
+async fn trash_unchanged(path: &Path, recorded: Option<u64>) -> Result<TrashEntry, AppError> {
+    if !exists(path).await {
+        return Err(refused("undo", path, "it no longer exists"));
+    }
+
+    if recorded.is_none() || fingerprint(path).await != recorded {
+        return Err(refused("undo", path, "it was changed since"));
+    }
+
+    trash::trash(path).await
+}
+
+/// Move `trashed` back to `path`, where undo put it in the trash from.
+async fn restore_from(trashed: Option<&TrashEntry>, path: &Path) -> Result<PathBuf, AppError> {
+    let Some(entry) = trashed else {
+        return Err(refused("redo", path, "it was not trashed by undo"));
+    };
+
+    if !exists(&entry.trashed_path).await {
+        return Err(refused("redo", path, "it is no longer in the trash"));
+    }
+
+    trash::restore(entry).await
+}
+
+fn refused(operation: &str, path: &Path, reason: &str) -> AppError {
+    AppError::FileOperationFailed {
+        operation: operation.to_string(),
+        path: path.to_path_buf(),
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A trashed file under `root` as `trash::trash` would leave it.
+    async fn trashed(root: &Path, name: &str) -> TrashEntry {
+        let trashed_path: PathBuf = root.join("files").join(name);
+        let info_path: PathBuf = root.join("info").join(format!("{name}.trashinfo"));
+
+        for path in [&trashed_path, &info_path] {
+            TokioFs::create_dir_all(path.parent().expect("has a parent")).await.expect("create dir");
+            TokioFs::write(path, name).await.expect("write file");
+        }
+
+        TrashEntry {
+            info_path,
+            trashed_path,
+            original_path: root.join("home").join(name),
+            deleted_at: None,
+            is_dir: false,
+        }
+    }
+
+    fn delete(entries: Vec<TrashEntry>) -> AppHistoryEvent {
+        AppHistoryEvent::Delete { entries }
+    }
+
+    fn reason(result: Result<AppHistoryEvent, AppError>) -> String {
+        match result {
+            Err(AppError::FileOperationFailed { reason, .. }) => reason,
+
+            other => panic!("expected a refusal, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn undo_delete_refuses_entries_gone_from_the_trash() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let kept: TrashEntry = trashed(root.path(), "a").await;
+        let gone: TrashEntry = trashed(root.path(), "b").await;
+        TokioFs::remove_file(&gone.trashed_path).await.expect("remove");
+
+        let event: AppHistoryEvent = delete(vec![kept.clone(), gone]);
+
+        assert_eq!(reason(undo(&event).await), "it is no longer in the trash");
+        assert!(exists(&kept.trashed_path).await);
+        assert!(!exists(&kept.original_path).await);
+    }
+
+    #[tokio::test]
+    async fn undo_delete_refuses_a_taken_path() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let kept: TrashEntry = trashed(root.path(), "a").await;
+        let taken: TrashEntry = trashed(root.path(), "b").await;
+        TokioFs::create_dir_all(root.path().join("home")).await.expect("create dir");
+        TokioFs::write(&taken.original_path, "new").await.expect("write file");
+
+        let event: AppHistoryEvent = delete(vec![kept.clone(), taken.clone()]);
+
+        assert_eq!(reason(undo(&event).await), "the path is taken again");
+        assert!(exists(&kept.trashed_path).await);
+        assert_eq!(TokioFs::read_to_string(&taken.original_path).await.expect("read"), "new");
+    }
+
+    #[tokio::test]
+    async fn undo_delete_restores_every_entry_and_drops_the_info_files() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let entries: Vec<TrashEntry> = vec![trashed(root.path(), "a").await, trashed(root.path(), "b").await];
+
+        undo(&delete(entries.clone())).await.expect("undo");
+
+        for entry in &entries {
+            assert!(exists(&entry.original_path).await);
+            assert!(!exists(&entry.trashed_path).await);
+            assert!(!exists(&entry.info_path).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn undo_move_refuses_a_taken_source_path() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let from: PathBuf = root.path().join("from");
+        let to: PathBuf = root.path().join("to");
+        TokioFs::write(&from, "new").await.expect("write file");
+        TokioFs::write(&to, "moved").await.expect("write file");
+
+        let event: AppHistoryEvent = AppHistoryEvent::Move { from: from.clone(), to: to.clone() };
+
+        assert_eq!(reason(undo(&event).await), "the path is taken");
+        assert_eq!(TokioFs::read_to_string(&from).await.expect("read"), "new");
+        assert_eq!(TokioFs::read_to_string(&to).await.expect("read"), "moved");
+    }
+
+    #[tokio::test]
+    async fn undo_copy_refuses_a_directory_edited_inside() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let copy: PathBuf = root.path().join("copy");
+        let nested: PathBuf = copy.join("sub").join("file");
+        TokioFs::create_dir_all(nested.parent().expect("has a parent")).await.expect("create dir");
+        TokioFs::write(&nested, "a").await.expect("write file");
+
+        let recorded: Option<u64> = fingerprint(&copy).await;
+        assert!(recorded.is_some());
+        assert_eq!(fingerprint(&copy).await, recorded);
+
+        TokioFs::write(&nested, "edited").await.expect("write file");
+        assert_ne!(fingerprint(&copy).await, recorded);
+
+        let event: AppHistoryEvent = AppHistoryEvent::Copy {
+            from: root.path().join("original"),
+            to: copy.clone(),
+            fingerprint: recorded,
+            trashed: None,
+        };
+
+        assert_eq!(reason(undo(&event).await), "it was changed since");
+        assert!(exists(&nested).await);
+    }
+
+    fn rename(root: &Path, from: &str, to: &str) -> AppHistoryEvent {
+        AppHistoryEvent::Rename { from: root.join(from), to: root.join(to) }
+    }
+
+    #[tokio::test]
+    async fn a_renamed_entry_goes_back_and_forth() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        TokioFs::write(root.path().join("new"), "data").await.expect("write file");
+
+        let event: AppHistoryEvent = rename(root.path(), "old", "new");
+        let redo_event: AppHistoryEvent = undo(&event).await.expect("undo");
+
+        assert!(exists(&root.path().join("old")).await);
+        assert!(!exists(&root.path().join("new")).await);
+
+        redo(&redo_event).await.expect("redo");
+
+        assert_eq!(TokioFs::read_to_string(root.path().join("new")).await.expect("read"), "data");
+    }
+
+    #[tokio::test]
+    async fn a_refused_batch_step_rolls_back_the_steps_before_it() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        for name in ["a", "b", "d"] {
+            TokioFs::write(root.path().join(name), name).await.expect("write file");
+        }
+
+        // Ran a -> b, then c -> d; "a" was taken again since
+        let event: AppHistoryEvent = AppHistoryEvent::Batch(vec![
+            rename(root.path(), "a", "b"),
+            rename(root.path(), "c", "d"),
+        ]);
+
+        assert_eq!(reason(undo(&event).await), "the path is taken");
+        assert!(exists(&root.path().join("d")).await, "the undone second step is redone");
+        assert!(!exists(&root.path().join("c")).await);
+        assert_eq!(TokioFs::read_to_string(root.path().join("a")).await.expect("read"), "a");
+    }
+
+    #[tokio::test]
+    async fn run_stops_at_the_first_refused_event() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        for name in ["y", "x2"] {
+            TokioFs::write(root.path().join(name), name).await.expect("write file");
+        }
+        TokioFs::write(root.path().join("x"), "taken").await.expect("write file");
+
+        let events: Vec<AppHistoryEvent> = vec![
+            rename(root.path(), "y0", "y"),
+            rename(root.path(), "x", "x2"),
+            rename(root.path(), "z", "z2"),
+        ];
+
+        let outcome: Outcome = run(Direction::Undo, events).await;
+
+        assert_eq!(outcome.applied.len(), 1);
+        assert_eq!(outcome.applied[0].0, rename(root.path(), "y0", "y").label());
+        assert_eq!(outcome.remaining.len(), 2);
+        assert!(outcome.error.is_some());
+        assert!(exists(&root.path().join("y0")).await);
+    }
+}
@@ -2,14 +2,21 @@
 //! ============================================================================
 //! # File Operations Task: Background file operations with progress tracking
 //!
-//! Handles copy, move, and rename operations asynchronously to prevent UI
-//! blocking during large file operations.
+//! Handles copy, move, rename and trash operations asynchronously to prevent UI
+//! blocking during large file operations. A batch (the marked entries) runs
+//! as one task with combined progress and is undone as one step.
 
 use crate::{config::Config, error::AppError, logging::ProfilingData};
 use crate::{AppState, controller::event_loop::TaskResult};
+use crate::model::app_state::AppHistoryEvent;
+use crate::model::undo;
+use crate::fs::rename::rename_no_replace;
+use crate::fs::trash::{self, TrashEntry};
 use std::{sync::Arc, time::Duration};
 use std::{
-    fs::Metadata,
+    collections::HashSet,
+    ffi::{OsStr, OsString},
+    fs::{FileType, Metadata},
     io::{Error, ErrorKind},
     path::{Path, PathBuf},
     time::Instant,
@@ -23,6 +30,7 @@ use tokio::{
     },
 };
 use tokio_util::sync::CancellationToken;
+use tracing::warn;
 use uuid::Uuid;
 
 use tokio::fs as TokioFs;
@@ -48,14 +56,20 @@ pub enum FileOperation {
     /// Move file/directory from source to destination
     Move { source: PathBuf, dest: PathBuf },
     /// Rename file/directory
-    Rename { source: PathBuf, new_name: String },
+    Rename { source: PathBuf, new_name: OsString },
+    /// Move file/directory to the trash
+    Trash { source: PathBuf },
+    /// Remove file/directory for good, bypassing the trash
+    Delete { source: PathBuf },
+    /// Several operations run one after another with combined progress
+    Batch(Vec<FileOperation>),
 }
 
 impl std::fmt::Display for FileOperation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use super::file_ops_task::FileOperation::{Copy, Move, Rename};
+        use super::file_ops_task::FileOperation::{Batch, Copy, Delete, Move, Rename, Trash};
 
-        let ret_str: &'static str = match *self {
+        let ret_str: &'static str = match self {
             Copy { source: _, dest: _ } => "Copy",
 
             Move { source: _, dest: _ } => "Move",
@@ -64,12 +78,169 @@ impl std::fmt::Display for FileOperation {
                 source: _,
                 new_name: _,
             } => "Rename",
+
+            Trash { source: _ } => "Trash",
+
+            Delete { source: _ } => "Delete",
+
+            // "Copy 3 items" when all items are alike
+            Batch(ops) => {
+                return match ops.first() {
+                    Some(first)
+                        if ops.iter().all(|op: &Self| -> bool {
+                            std::mem::discriminant(op) == std::mem::discriminant(first)
+                        }) =>
+                    {
+                        write!(f, "{first} {} items", ops.len())
+                    }
+
+                    _ => write!(f, "Batch of {} items", ops.len()),
+                };
+            }
         };
 
         write!(f, "{ret_str}")
     }
 }
 
+impl FileOperation {
+    /// The single operations this one runs, in order (a batch's items).
+    #[must_use]
+    pub fn steps(&self) -> Vec<&Self> {
+        match self {
+            Self::Batch(ops) => ops.iter().flat_map(Self::steps).collect(),
+
+            op => vec![op],
+        }
+    }
+
+    /// Entry a single operation acts on (the first one of a batch).
+    #[must_use]
+    pub fn source(&self) -> &Path {
+        match self {
+            Self::Copy { source, .. }
+            | Self::Move { source, .. }
+            | Self::Rename { source, .. }
+            | Self::Trash { source }
+            | Self::Delete { source } => source,
+
+            Self::Batch(ops) => ops.first().map_or(Path::new(""), Self::source),
+        }
+    }
+
+    /// Path the source of a single operation ends up at: a directory
+    /// destination receives the source under its own name. Only meaningful
+    /// before the operation runs.
+    #[must_use]
+    pub fn target(&self) -> PathBuf {
+        match self {
+            Self::Copy { source, dest } | Self::Move { source, dest } => match source.file_name() {
+                Some(name) if dest.is_dir() => dest.join(name),
+
+                _ => dest.clone(),
+            },
+
+            Self::Rename { source, new_name } => source.with_file_name(new_name),
+
+            Self::Trash { source } | Self::Delete { source } => source.clone(),
+
+            Self::Batch(_) => self.source().to_path_buf(),
+        }
+    }
+
+    /// Rename every source after `pattern`, where `{name}` is the name
+    /// without its extension, `{ext}` the extension and `{n}` the position in
+    /// `sources` (from 1, zero-padded). Names are built from the original
+    /// bytes, so names that are not valid UTF-8 keep them. Sources whose name
+    /// stays the same are left out. Refuses patterns that yield an empty
+    /// name, a `/` or the same name twice in a directory.
+    pub fn batch_rename(sources: &[PathBuf], pattern: &str) -> Result<Self, String> {
+        let width: usize = sources.len().to_string().len();
+        let mut ops: Vec<Self> = Vec::with_capacity(sources.len());
+        let mut targets: HashSet<PathBuf> = HashSet::with_capacity(sources.len());
+
+        for (idx, source) in sources.iter().enumerate() {
+            let stem: &OsStr = source.file_stem().unwrap_or_default();
+            let ext: &OsStr = source.extension().unwrap_or_default();
+
+            // "{name}.{ext}" should not leave a trailing dot on names without one
+            let item_pattern: String = if ext.is_empty() { pattern.replace(".{ext}", "") } else { pattern.to_string() };
+
+            let number: String = format!("{:0width$}", idx + 1);
+            let new_name: OsString = expand_pattern(&item_pattern, stem, ext, OsStr::new(&number));
+            if !is_valid_name(&new_name) {
+                return Err(format!("'{pattern}' gives an invalid name for {}", source.display()));
+            }
+
+            let target: PathBuf = source.with_file_name(&new_name);
+
+            if !targets.insert(target.clone()) {
+                return Err(format!("'{pattern}' names two entries {}", new_name.to_string_lossy()));
+            }
+
+            if target == *source {
+                continue;
+            }
+
+            ops.push(Self::Rename {
+                source: source.clone(),
+                new_name,
+            });
+        }
+
+        if ops.is_empty() {
+            return Err(format!("'{pattern}' keeps every name as it is"));
+        }
+
+        Ok(Self::Batch(ops))
+    }
+}
+
+/// Whether `name` can name an entry within its directory: not empty, not
+/// `.` or `..`, and without a path separator (which would move it elsewhere).
+#[must_use]
+pub fn is_valid_name(name: &OsStr) -> bool {
+    let bytes: &[u8] = name.as_encoded_bytes();
+
+    !bytes.is_empty()
+        && bytes != b"."
+        && bytes != b".."
+        && !bytes.iter().any(|&byte: &u8| -> bool { std::path::is_separator(char::from(byte)) })
+}
+
+/// Replace the `{name}`, `{ext}` and `{n}` placeholders of `pattern`; any
+/// other text is copied as is.
+fn expand_pattern(pattern: &str, name: &OsStr, ext: &OsStr, number: &OsStr) -> OsString {
+    let mut expanded: OsString = OsString::with_capacity(pattern.len() + name.len());
+    let mut rest: &str = pattern;
+
+    while let Some(start) = rest.find('{') {
+        expanded.push(&rest[..start]);
+        rest = &rest[start..];
+
+        let placeholder: Option<(&OsStr, usize)> = [("{name}", name), ("{ext}", ext), ("{n}", number)]
+            .into_iter()
+            .find(|(key, _): &(&str, &OsStr)| -> bool { rest.starts_with(key) })
+            .map(|(key, value): (&str, &OsStr)| -> (&OsStr, usize) { (value, key.len()) });
+
+        match placeholder {
+            Some((value, len)) => {
+                expanded.push(value);
+                rest = &rest[len..];
+            }
+
+            None => {
+                expanded.push("{");
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    expanded.push(rest);
+
+    expanded
+}
+
 impl FileOperationTask {
     /// Create new file operation task with unique ID
     pub fn new(
@@ -89,8 +260,6 @@ impl FileOperationTask {
 
     /// Execute file operation with full progress reporting
     pub async fn execute(&self) -> Result<(), AppError> {
-        use FileOperation::{Copy, Move, Rename};
-
         let start_time = Instant::now();
         let start_memory_kb = ProfilingData::get_current_memory_kb();
 
@@ -102,20 +271,21 @@ impl FileOperationTask {
             return Err(Self::error(err_kind, err_msg));
         }
 
+        // Resolve where each source lands while the destinations are untouched
+        let steps: Vec<(&FileOperation, PathBuf)> = self
+            .operation
+            .steps()
+            .into_iter()
+            .map(|op: &FileOperation| -> (&FileOperation, PathBuf) { (op, op.target()) })
+            .collect();
+
         // Calculate total operation size first
         let (total_bytes, total_files) = self.calculate_operation_size().await?;
         let mut current_bytes: u64 = 0;
         let mut files_completed: u32 = 0;
 
         // Report initial progress
-        let initial_file: &Path = match &self.operation {
-            Copy { source, dest: _ }
-            | Move { source, dest: _ }
-            | Rename {
-                source,
-                new_name: _,
-            } => source,
-        };
+        let initial_file: &Path = self.operation.source();
 
         self.report_progress(
             0,
@@ -126,44 +296,37 @@ impl FileOperationTask {
         )
         .await?;
 
-        // Execute operation with progress tracking
-        let result: Result<(), AppError> = match &self.operation {
-            Copy { source, dest } => {
-                self.copy_file_with_progress(
-                    source,
-                    dest,
-                    &mut current_bytes,
-                    total_bytes,
-                    &mut files_completed,
-                    total_files,
-                )
-                .await
-            }
+        // Execute the steps in order with progress tracking. Steps finished
+        // before a failure stay recorded for undo. Nothing starts when a
+        // target is taken.
+        let mut events: Vec<AppHistoryEvent> = Vec::with_capacity(steps.len());
+        let mut result: Result<(), AppError> = Self::check_targets(&steps).await;
 
-            Move { source, dest } => {
-                self.move_file_with_progress(
-                    source,
-                    dest,
-                    &mut current_bytes,
-                    total_bytes,
-                    &mut files_completed,
-                    total_files,
-                )
-                .await
+        for (op, target) in steps {
+            if result.is_err() {
+                break;
             }
 
-            Rename { source, new_name } => {
-                self.rename_with_progress(
-                    source,
-                    new_name,
+            let step: Result<Option<AppHistoryEvent>, AppError> = self
+                .run_step(
+                    op,
+                    target,
                     &mut current_bytes,
                     total_bytes,
                     &mut files_completed,
                     total_files,
                 )
-                .await
+                .await;
+
+            match step {
+                Ok(event) => events.extend(event),
+
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
             }
-        };
+        }
 
         // Calculate final profiling data using new API
         let duration: Duration = start_time.elapsed();
@@ -182,15 +345,154 @@ impl FileOperationTask {
 
         let _send_result: Result<(), SendError<TaskResult>> = self.task_tx.send(completion_result);
 
-        // Cleanup operation from UI state
+        let event: Option<AppHistoryEvent> = AppHistoryEvent::combine(events);
+
+        // Cleanup operation from UI state and record it for undo
         {
             let mut app: MutexGuard<'_, AppState> = self.app.lock().await;
             app.ui.remove_operation(&self.operation_id);
+
+            if let Some(event) = event {
+                app.push_history(event);
+            }
         }
 
         result
     }
 
+    /// Refuse the operation when a source is gone, a target already exists
+    /// or two steps share one, so a batch does not stop halfway and nothing
+    /// is overwritten or merged into.
+    async fn check_targets(steps: &[(&FileOperation, PathBuf)]) -> Result<(), AppError> {
+        let mut seen: HashSet<&Path> = HashSet::with_capacity(steps.len());
+
+        for (op, target) in steps {
+            if TokioFs::symlink_metadata(op.source()).await.is_err() {
+                return Err(AppError::FileOperationFailed {
+                    operation: op.to_string(),
+                    path: op.source().to_path_buf(),
+                    reason: "it no longer exists".to_string(),
+                });
+            }
+
+            if matches!(op, FileOperation::Trash { .. } | FileOperation::Delete { .. }) {
+                continue;
+            }
+
+            if !seen.insert(target) || TokioFs::symlink_metadata(target).await.is_ok() {
+                return Err(AppError::FileOperationFailed {
+                    operation: op.to_string(),
+                    path: target.clone(),
+                    reason: "the target already exists".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run a single operation whose source lands at `target`. Returns its
+    /// undo record, if it can be undone.
+    async fn run_step(
+        &self,
+        op: &FileOperation,
+        target: PathBuf,
+        current_bytes: &mut u64,
+        total_bytes: u64,
+        files_completed: &mut u32,
+        total_files: u32,
+    ) -> Result<Option<AppHistoryEvent>, AppError> {
+        // Check for cancellation between the items of a batch
+        if self.cancel_token.is_cancelled() {
+            let err_kind: ErrorKind = ErrorKind::Interrupted;
+            let err_msg: &'static str = "Operation was cancelled.";
+
+            return Err(Self::error(err_kind, err_msg));
+        }
+
+        match op {
+            FileOperation::Copy { source, dest: _ } => {
+                self.copy_with_progress(
+                    source,
+                    &target,
+                    current_bytes,
+                    total_bytes,
+                    files_completed,
+                    total_files,
+                )
+                .await?;
+
+                Ok(Some(AppHistoryEvent::Copy {
+                    from: source.clone(),
+                    fingerprint: undo::fingerprint(&target).await,
+                    to: target,
+                    trashed: None,
+                }))
+            }
+
+            FileOperation::Move { source, dest: _ } => {
+                self.move_file_with_progress(
+                    source,
+                    &target,
+                    current_bytes,
+                    total_bytes,
+                    files_completed,
+                    total_files,
+                )
+                .await?;
+
+                Ok(Some(AppHistoryEvent::Move {
+                    from: source.clone(),
+                    to: target,
+                }))
+            }
+
+            FileOperation::Rename { source, new_name } => {
+                self.rename_with_progress(
+                    source,
+                    new_name,
+                    current_bytes,
+                    total_bytes,
+                    files_completed,
+                    total_files,
+                )
+                .await?;
+
+                Ok(Some(AppHistoryEvent::Rename {
+                    from: source.clone(),
+                    to: target,
+                }))
+            }
+
+            FileOperation::Trash { source } => {
+                let entry: TrashEntry = trash::trash(source).await?;
+
+                *files_completed += 1;
+
+                self.report_progress(
+                    *current_bytes,
+                    total_bytes,
+                    source,
+                    files_completed,
+                    total_files,
+                )
+                .await?;
+
+                Ok(Some(AppHistoryEvent::Delete { entries: vec![entry] }))
+            }
+
+            // Gone for good, nothing to undo
+            FileOperation::Delete { source } => {
+                self.delete_with_progress(source, current_bytes, total_bytes, files_completed, total_files)
+                    .await?;
+
+                Ok(None)
+            }
+
+            FileOperation::Batch(_) => unreachable!("batches are flattened into their steps"),
+        }
+    }
+
     /// Recursively calculate directory size and file count
     async fn calculate_directory_size(&self, dir_path: &Path) -> Result<(u64, u32), AppError> {
         let mut total_size: u64 = 0;
@@ -204,12 +506,17 @@ impl FileOperationTask {
             while let Some(entry) = entries.next_entry().await? {
                 let path: PathBuf = entry.path();
 
-                if path.is_file() {
-                    let metadata: Metadata = TokioFs::metadata(&path).await?;
+                // Symlinks are copied as links, never followed
+                let file_type: FileType = entry.file_type().await?;
+
+                if file_type.is_dir() {
+                    stack.push(path);
+                } else if file_type.is_symlink() {
+                    file_count += 1;
+                } else {
+                    let metadata: Metadata = entry.metadata().await?;
                     total_size += metadata.len();
                     file_count += 1;
-                } else if path.is_dir() {
-                    stack.push(path);
                 }
             }
         }
@@ -217,18 +524,32 @@ impl FileOperationTask {
         Ok((total_size, file_count))
     }
 
-    /// Calculate total size and file count for progress tracking
+    /// Calculate total size and file count for progress tracking, summed
+    /// over the items of a batch
     async fn calculate_operation_size(&self) -> Result<(u64, u32), AppError> {
-        match &self.operation {
+        let mut total_size: u64 = 0;
+        let mut file_count: u32 = 0;
+
+        for op in self.operation.steps() {
+            let (size, count): (u64, u32) = self.calculate_step_size(op).await?;
+            total_size += size;
+            file_count += count;
+        }
+
+        Ok((total_size, file_count))
+    }
+
+    async fn calculate_step_size(&self, op: &FileOperation) -> Result<(u64, u32), AppError> {
+        match op {
             FileOperation::Copy { source, dest: _ } | FileOperation::Move { source, dest: _ } => {
-                if source.is_file() {
-                    let metadata: Metadata = TokioFs::metadata(source).await?;
+                let metadata: Metadata = TokioFs::symlink_metadata(source).await?;
 
-                    Ok((metadata.len(), 1))
-                } else if source.is_dir() {
+                if metadata.is_dir() {
                     self.calculate_directory_size(source).await
+                } else if metadata.is_symlink() {
+                    Ok((0, 1))
                 } else {
-                    Ok((0, 0))
+                    Ok((metadata.len(), 1))
                 }
             }
 
@@ -237,10 +558,26 @@ impl FileOperationTask {
                 new_name: _,
             } => {
                 // Rename is O(1), no progress tracker is needed.
-                let metadata: Metadata = TokioFs::metadata(source).await?;
+                let metadata: Metadata = TokioFs::symlink_metadata(source).await?;
 
                 Ok((metadata.len(), 1))
             }
+
+            // Trashing renames too; it counts as one item without bytes
+            FileOperation::Trash { source: _ } => Ok((0, 1)),
+
+            // Deleting counts the files it removes, without bytes
+            FileOperation::Delete { source } => {
+                if TokioFs::symlink_metadata(source).await?.is_dir() {
+                    let (_, count): (u64, u32) = self.calculate_directory_size(source).await?;
+
+                    Ok((0, count))
+                } else {
+                    Ok((0, 1))
+                }
+            }
+
+            FileOperation::Batch(_) => Ok((0, 0)),
         }
     }
 
@@ -281,8 +618,11 @@ impl FileOperationTask {
         Ok(())
     }
 
-    /// Copy file with progress reporting using streaming
-    async fn copy_file_with_progress(
+    /// Copy a file, or a directory with everything in it. Symlinks are
+    /// recreated as symlinks, not followed. A copy that fails or is
+    /// cancelled halfway is removed again; `dest` was free before, unless
+    /// creating it is what failed.
+    async fn copy_with_progress(
         &self,
         source: &PathBuf,
         dest: &Path,
@@ -291,22 +631,145 @@ impl FileOperationTask {
         files_completed: &mut u32,
         total_files: u32,
     ) -> Result<(), AppError> {
-        // Handle case where dest is a directory
-        let final_dst: PathBuf = if dest.is_dir() {
-            if let Some(filename) = source.file_name() {
-                let new_dest: PathBuf = dest.join(filename);
-                new_dest
-            } else {
-                let err_kind: ErrorKind = ErrorKind::InvalidInput;
-                let err_msg: &'static str = "Cannot determine filename from source.";
+        let result: Result<(), AppError> = self
+            .copy_tree_with_progress(source, dest, current_bytes, total_bytes, files_completed, total_files)
+            .await;
+
+        if let Err(e) = &result
+            && !matches!(e, AppError::Io(io) if io.kind() == ErrorKind::AlreadyExists)
+            && let Err(cleanup) = undo::remove_entry(dest).await
+            && cleanup.kind() != ErrorKind::NotFound
+        {
+            warn!("Could not remove the partial copy {}: {}", dest.display(), cleanup);
+        }
 
-                return Err(Self::error(err_kind, err_msg));
-            }
-        } else {
-            let new_dest: PathBuf = dest.to_path_buf();
-            new_dest
+        result
+    }
+
+    async fn copy_tree_with_progress(
+        &self,
+        source: &PathBuf,
+        dest: &Path,
+        current_bytes: &mut u64,
+        total_bytes: u64,
+        files_completed: &mut u32,
+        total_files: u32,
+    ) -> Result<(), AppError> {
+        let file_type: FileType = TokioFs::symlink_metadata(source).await?.file_type();
+
+        if file_type.is_symlink() {
+            return self
+                .copy_symlink_with_progress(source, dest, current_bytes, total_bytes, files_completed, total_files)
+                .await;
+        }
+
+        if !file_type.is_dir() {
+            return self
+                .copy_file_with_progress(
+                    source,
+                    dest,
+                    current_bytes,
+                    total_bytes,
+                    files_completed,
+                    total_files,
+                )
+                .await;
+        }
+
+        // Compare real paths: `dest` does not exist yet, its parent does
+        let real_source: PathBuf = TokioFs::canonicalize(source).await?;
+        let real_dest_parent: Option<PathBuf> = match dest.parent() {
+            Some(parent) => TokioFs::canonicalize(parent).await.ok(),
+
+            None => None,
         };
 
+        if real_dest_parent.is_some_and(|parent: PathBuf| -> bool { parent.starts_with(&real_source) }) {
+            let err_kind: ErrorKind = ErrorKind::InvalidInput;
+            let err_msg: &'static str = "Cannot copy a directory into itself.";
+
+            return Err(Self::error(err_kind, err_msg));
+        }
+
+        let mut stack: Vec<(PathBuf, PathBuf)> = vec![(source.clone(), dest.to_path_buf())];
+
+        while let Some((from_dir, to_dir)) = stack.pop() {
+            // Fails on an existing directory instead of merging into it
+            TokioFs::create_dir(&to_dir).await?;
+
+            let mut entries: ReadDir = TokioFs::read_dir(&from_dir).await?;
+
+            while let Some(entry) = entries.next_entry().await? {
+                let from: PathBuf = entry.path();
+                let to: PathBuf = to_dir.join(entry.file_name());
+                let file_type: FileType = entry.file_type().await?;
+
+                if file_type.is_dir() {
+                    stack.push((from, to));
+                } else if file_type.is_symlink() {
+                    self.copy_symlink_with_progress(
+                        &from,
+                        &to,
+                        current_bytes,
+                        total_bytes,
+                        files_completed,
+                        total_files,
+                    )
+                    .await?;
+                } else {
+                    self.copy_file_with_progress(
+                        &from,
+                        &to,
+                        current_bytes,
+                        total_bytes,
+                        files_completed,
+                        total_files,
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Recreate the symlink `source` at `dest`
+    async fn copy_symlink_with_progress(
+        &self,
+        source: &Path,
+        dest: &Path,
+        current_bytes: &u64,
+        total_bytes: u64,
+        files_completed: &mut u32,
+        total_files: u32,
+    ) -> Result<(), AppError> {
+        undo::copy_symlink(source, dest).await?;
+
+        *files_completed += 1;
+
+        self.report_progress(
+            *current_bytes,
+            total_bytes,
+            source,
+            files_completed,
+            total_files,
+        )
+        .await
+    }
+
+    /// Copy file with progress reporting using streaming
+    async fn copy_file_with_progress(
+        &self,
+        source: &PathBuf,
+        dest: &Path,
+        current_bytes: &mut u64,
+        total_bytes: u64,
+        files_completed: &mut u32,
+        total_files: u32,
+    ) -> Result<(), AppError> {
+        // `dest` is the resolved target, never the directory it goes into
+        let final_dst: PathBuf = dest.to_path_buf();
+
         // Create parent directory if it doesn't exist
         if let Some(parent) = final_dst.parent()
             && !parent.exists()
@@ -315,7 +778,7 @@ impl FileOperationTask {
         }
 
         // Get file size for progress tracking
-        let metadata: Metadata = TokioFs::metadata(source).await?;
+        let metadata: Metadata = TokioFs::symlink_metadata(source).await?;
         let file_size: u64 = metadata.len();
 
         // Report progress before starting file copy
@@ -329,7 +792,12 @@ impl FileOperationTask {
         .await?;
 
         let mut src_file: File = TokioFs::File::open(source).await?;
-        let mut dst_file: File = TokioFs::File::create(&final_dst).await?;
+        // Never truncate an existing file: undo could not bring it back
+        let mut dst_file: File = TokioFs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&final_dst)
+            .await?;
 
         // 64KB buffer
         let mut buffer: Vec<u8> = vec![0; BUFFER_SIZE];
@@ -397,24 +865,8 @@ impl FileOperationTask {
         files_completed: &mut u32,
         total_files: u32,
     ) -> Result<(), AppError> {
-        // Handle case where dest is a directory
-        let final_dst: PathBuf = if dest.is_dir() {
-            if let Some(filename) = source.file_name() {
-                let new_path: PathBuf = dest.join(filename);
-                new_path
-            } else {
-                let ekind: ErrorKind = ErrorKind::InvalidInput;
-                let emsg: &'static str =
-                    "Cannot determine filename from source for move operation.";
-                let err: Error = Error::new(ekind, emsg);
-                let app_err: AppError = AppError::Io(err);
-
-                return Err(app_err);
-            }
-        } else {
-            let new_path: PathBuf = dest.to_path_buf();
-            new_path
-        };
+        // `dest` is the resolved target, never the directory it goes into
+        let final_dst: PathBuf = dest.to_path_buf();
 
         // Create parent directory if it doesn't exist
         if let Some(parent) = final_dst.parent()
@@ -424,7 +876,7 @@ impl FileOperationTask {
         }
 
         // Get file size for progress tracking
-        let metadata: Metadata = TokioFs::metadata(source).await?;
+        let metadata: Metadata = TokioFs::symlink_metadata(source).await?;
         let file_size: u64 = metadata.len();
 
         *files_completed += 1;
@@ -447,8 +899,17 @@ impl FileOperationTask {
             return Err(Self::error(err_kind, err_msg));
         }
 
-        // Try efficient rename first (same filesystem)
-        if matches!(TokioFs::rename(source, &final_dst).await, Ok(())) {
+        // Try efficient rename first (same filesystem); only a move to
+        // another filesystem falls back to copying
+        let renamed: bool = match rename_no_replace(source, &final_dst).await {
+            Ok(()) => true,
+
+            Err(e) if e.kind() == ErrorKind::CrossesDevices => false,
+
+            Err(e) => return Err(e.into()),
+        };
+
+        if renamed {
             // Rename sucessful - update progress instantly
             *current_bytes += file_size;
 
@@ -464,8 +925,8 @@ impl FileOperationTask {
             )
             .await?;
         } else {
-            // Rename failed, fall back to copy with progress + delete
-            self.copy_file_with_progress(
+            // Another filesystem: copy with progress, then delete
+            self.copy_with_progress(
                 source,
                 &final_dst,
                 current_bytes,
@@ -475,29 +936,91 @@ impl FileOperationTask {
             )
             .await?;
 
-            // Delete source after sucessfuly copy
-            if source.is_file() {
-                TokioFs::remove_file(source).await?;
-            } else if source.is_dir() {
+            // Delete source after sucessfuly copy (a symlink itself, not its target)
+            if TokioFs::symlink_metadata(source).await?.is_dir() {
                 TokioFs::remove_dir_all(source).await?;
+            } else {
+                TokioFs::remove_file(source).await?;
             }
         }
 
         Ok(())
     }
 
+    /// Remove `source` for good. A directory is emptied file by file, so
+    /// progress moves and cancellation is noticed; symlinks are removed,
+    /// not followed.
+    async fn delete_with_progress(
+        &self,
+        source: &Path,
+        current_bytes: &u64,
+        total_bytes: u64,
+        files_completed: &mut u32,
+        total_files: u32,
+    ) -> Result<(), AppError> {
+        if !TokioFs::symlink_metadata(source).await?.is_dir() {
+            TokioFs::remove_file(source).await?;
+
+            *files_completed += 1;
+
+            return self
+                .report_progress(*current_bytes, total_bytes, source, files_completed, total_files)
+                .await;
+        }
+
+        // Every directory is listed after its parent, so removing them in
+        // reverse order only ever removes empty ones
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        let mut stack: Vec<PathBuf> = vec![source.to_path_buf()];
+
+        while let Some(dir) = stack.pop() {
+            let mut entries: ReadDir = TokioFs::read_dir(&dir).await?;
+
+            while let Some(entry) = entries.next_entry().await? {
+                let path: PathBuf = entry.path();
+
+                if entry.file_type().await?.is_dir() {
+                    stack.push(path);
+                    continue;
+                }
+
+                if self.cancel_token.is_cancelled() {
+                    let err_kind: ErrorKind = ErrorKind::Interrupted;
+                    let err_msg: &'static str = "Operation was cancelled.";
+
+                    return Err(Self::error(err_kind, err_msg));
+                }
+
+                TokioFs::remove_file(&path).await?;
+
+                *files_completed += 1;
+
+                self.report_progress(*current_bytes, total_bytes, &path, files_completed, total_files)
+                    .await?;
+            }
+
+            dirs.push(dir);
+        }
+
+        for dir in dirs.iter().rev() {
+            TokioFs::remove_dir(dir).await?;
+        }
+
+        Ok(())
+    }
+
     /// Rename file or directory with progress reporting
     async fn rename_with_progress(
         &self,
         source: &PathBuf,
-        new_name: &str,
+        new_name: &OsStr,
         current_bytes: &mut u64,
         total_bytes: u64,
         files_completed: &mut u32,
         total_files: u32,
     ) -> Result<(), AppError> {
-        // Validate source exists
-        if !source.exists() {
+        // Validate source exists (a dangling symlink does)
+        if TokioFs::symlink_metadata(source).await.is_err() {
             let ekind: ErrorKind = ErrorKind::NotFound;
             let emsg: String = format!("Source path does not exist: {}", source.display());
             let err: Error = Error::new(ekind, emsg);
@@ -506,6 +1029,10 @@ impl FileOperationTask {
             return Err(app_err);
         }
 
+        if !is_valid_name(new_name) {
+            return Err(Self::error(ErrorKind::InvalidInput, "Invalid name: empty, '.', '..' or containing a path separator"));
+        }
+
         // Get parent directory
         let parent: &Path = source.parent().ok_or_else(|| {
             let ekind: ErrorKind = ErrorKind::InvalidInput;
@@ -519,7 +1046,7 @@ impl FileOperationTask {
         let new_path: PathBuf = parent.join(new_name);
 
         // Get file size for progress tracking
-        let metadata: Metadata = TokioFs::metadata(source).await?;
+        let metadata: Metadata = TokioFs::symlink_metadata(source).await?;
         let file_size: u64 = metadata.len();
 
         // Report progress before starting rename
@@ -541,7 +1068,7 @@ impl FileOperationTask {
         }
 
         // Perform rename operation
-        TokioFs::rename(source, &new_path).await?;
+        rename_no_replace(source, &new_path).await?;
 
         // Update progress after successful rename
         *current_bytes += file_size;
@@ -569,3 +1096,199 @@ impl FileOperationTask {
         app_err
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(source, new name)` of every rename in a batch.
+    fn renames(operation: FileOperation) -> Vec<(PathBuf, OsString)> {
+        let FileOperation::Batch(ops) = operation else {
+            panic!("batch_rename should build a batch");
+        };
+
+        ops.into_iter()
+            .map(|op: FileOperation| -> (PathBuf, OsString) {
+                match op {
+                    FileOperation::Rename { source, new_name } => (source, new_name),
+
+                    other => panic!("unexpected operation {other}"),
+                }
+            })
+            .collect()
+    }
+
+    /// Run `operation` as a task against a fresh `AppState`.
+    async fn run(operation: FileOperation) -> Result<(), AppError> {
+        use crate::cache::cache_manager::ObjectInfoCache;
+        use crate::model::fs_state::FSState;
+        use crate::model::ui_state::UIState;
+
+        let (task_tx, _task_rx) = mpsc::unbounded_channel::<TaskResult>();
+        let (action_tx, _action_rx) = mpsc::unbounded_channel();
+        let app: AppState = AppState::new(
+            Arc::new(Config::default()),
+            Arc::new(ObjectInfoCache::new()),
+            FSState::default(),
+            UIState::default(),
+            task_tx.clone(),
+            action_tx,
+        );
+
+        FileOperationTask::new(operation, task_tx, CancellationToken::new(), Arc::new(Mutex::new(app)))
+            .execute()
+            .await
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn symlinks_are_moved_and_renamed_as_links() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let dangling: PathBuf = root.path().join("dangling");
+        let to_dir: PathBuf = root.path().join("to_dir");
+        let dest: PathBuf = root.path().join("dest");
+        TokioFs::symlink(root.path().join("missing"), &dangling).await.expect("symlink");
+        TokioFs::create_dir(&dest).await.expect("create dir");
+        TokioFs::symlink(&dest, &to_dir).await.expect("symlink");
+
+        run(FileOperation::Batch(vec![
+            FileOperation::Rename { source: dangling, new_name: OsString::from("renamed") },
+            FileOperation::Move { source: to_dir, dest: root.path().join("moved") },
+        ]))
+        .await
+        .expect("batch runs");
+
+        for name in ["renamed", "moved"] {
+            let meta: Metadata = TokioFs::symlink_metadata(root.path().join(name)).await.expect("exists");
+            assert!(meta.is_symlink(), "{name}");
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn a_failed_copy_leaves_no_partial_target() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let source: PathBuf = root.path().join("source");
+        let target: PathBuf = root.path().join("target");
+        TokioFs::create_dir(&source).await.expect("create dir");
+        TokioFs::write(source.join("file"), "data").await.expect("write file");
+
+        // Sockets cannot be opened for reading, so copying one fails
+        let _socket: std::os::unix::net::UnixListener =
+            std::os::unix::net::UnixListener::bind(source.join("socket")).expect("bind socket");
+
+        let result: Result<(), AppError> = run(FileOperation::Copy { source: source.clone(), dest: target.clone() }).await;
+
+        assert!(result.is_err());
+        assert!(TokioFs::symlink_metadata(&target).await.is_err());
+        assert!(TokioFs::symlink_metadata(source.join("file")).await.is_ok());
+    }
+
+    fn paths(names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(|name: &&str| -> PathBuf { Path::new("/d").join(name) }).collect()
+    }
+
+    #[test]
+    fn batch_rename_expands_placeholders() {
+        let sources: Vec<PathBuf> = paths(&["a.txt", "b.md"]);
+        let operation: FileOperation = FileOperation::batch_rename(&sources, "{n}-{name}.{ext}").expect("valid pattern");
+
+        assert_eq!(
+            renames(operation),
+            vec![
+                (sources[0].clone(), OsString::from("1-a.txt")),
+                (sources[1].clone(), OsString::from("2-b.md")),
+            ]
+        );
+    }
+
+    #[test]
+    fn batch_rename_pads_numbers_to_the_batch_size() {
+        let names: Vec<String> = (1..=10).map(|i: usize| -> String { format!("f{i}") }).collect();
+        let sources: Vec<PathBuf> = names.iter().map(|name: &String| -> PathBuf { Path::new("/d").join(name) }).collect();
+        let renamed: Vec<(PathBuf, OsString)> = renames(FileOperation::batch_rename(&sources, "img_{n}").expect("valid pattern"));
+
+        assert_eq!(renamed[0].1, "img_01");
+        assert_eq!(renamed[9].1, "img_10");
+    }
+
+    #[test]
+    fn batch_rename_drops_the_dot_of_missing_extensions() {
+        let sources: Vec<PathBuf> = paths(&["Makefile", "a.rs"]);
+        let renamed: Vec<(PathBuf, OsString)> = renames(FileOperation::batch_rename(&sources, "{name}_old.{ext}").expect("valid pattern"));
+
+        assert_eq!(renamed[0].1, "Makefile_old");
+        assert_eq!(renamed[1].1, "a_old.rs");
+    }
+
+    #[test]
+    fn batch_rename_keeps_unknown_braces() {
+        let sources: Vec<PathBuf> = paths(&["a.txt"]);
+        let renamed: Vec<(PathBuf, OsString)> = renames(FileOperation::batch_rename(&sources, "{x}{name}{").expect("valid pattern"));
+
+        assert_eq!(renamed[0].1, "{x}a{");
+    }
+
+    #[test]
+    fn batch_rename_leaves_out_unchanged_names() {
+        let sources: Vec<PathBuf> = paths(&["a.txt", "b.md"]);
+        let renamed: Vec<(PathBuf, OsString)> = renames(FileOperation::batch_rename(&sources, "{name}.txt").expect("valid pattern"));
+
+        assert_eq!(renamed, vec![(sources[1].clone(), OsString::from("b.txt"))]);
+        assert!(FileOperation::batch_rename(&sources, "{name}.{ext}").is_err());
+    }
+
+    #[test]
+    fn batch_rename_refuses_invalid_names() {
+        let sources: Vec<PathBuf> = paths(&["a.txt"]);
+
+        for pattern in ["", ".", "..", "sub/{name}"] {
+            assert!(FileOperation::batch_rename(&sources, pattern).is_err(), "pattern {pattern:?}");
+        }
+    }
+
+    #[test]
+    fn batch_rename_refuses_duplicate_targets() {
+        let sources: Vec<PathBuf> = paths(&["a.txt", "b.txt"]);
+
+        assert!(FileOperation::batch_rename(&sources, "same.{ext}").is_err());
+        assert!(FileOperation::batch_rename(&[PathBuf::from("/x/a"), PathBuf::from("/y/b")], "same").is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn batch_rename_keeps_non_utf8_bytes() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let sources: Vec<PathBuf> = vec![Path::new("/d").join(OsStr::from_bytes(b"caf\xE9.txt"))];
+        let renamed: Vec<(PathBuf, OsString)> = renames(FileOperation::batch_rename(&sources, "{n}_{name}.{ext}").expect("valid pattern"));
+
+        assert_eq!(renamed[0].1.as_bytes(), b"1_caf\xE9.txt");
+    }
+
+    #[test]
+    fn is_valid_name_rejects_names_that_leave_the_directory() {
+        for name in ["", ".", "..", "a/b", "/abs", "../up"] {
+            assert!(!is_valid_name(OsStr::new(name)), "{name:?}");
+        }
+
+        for name in ["a", ".hidden", "...", "a.b", "caf\u{e9}"] {
+            assert!(is_valid_name(OsStr::new(name)), "{name:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn rename_refuses_a_name_with_a_separator() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let source: PathBuf = root.path().join("file");
+        TokioFs::create_dir(root.path().join("sub")).await.expect("create dir");
+        TokioFs::write(&source, "data").await.expect("write file");
+
+        let result: Result<(), AppError> =
+            run(FileOperation::Rename { source: source.clone(), new_name: OsString::from("sub/file") }).await;
+
+        assert!(result.is_err());
+        assert!(source.exists());
+        assert!(!root.path().join("sub/file").exists());
+    }
+}
@@ -23,6 +23,7 @@ use crate::{cache::cache_manager::ObjectInfoCache, error_core::{CoreError, CoreR
 use crate::{
     controller::{actions::Action, event_loop::TaskResult},
     fs::object_info::ObjectInfo,
+    model::tabs::TabId,
 };
 
 // ============================================================================
@@ -48,6 +49,7 @@ impl FilenameSearchTask {
     )]
     pub fn filename_search_task(
         task_id: u64,
+        tab: TabId,
         pattern: String,
         search_path: PathBuf,
         task_tx: UnboundedSender<TaskResult>,
@@ -118,6 +120,7 @@ impl FilenameSearchTask {
             // Completion handling
             if let Err(err) = Self::handle_search_completion(
                 task_id,
+                tab,
                 results,
                 task_start,
                 processed_count,
@@ -441,6 +444,7 @@ impl FilenameSearchTask {
     // ------------------------------------------------------------------------
 
     #[expect(clippy::cast_possible_truncation, reason = "Current precision is enough in this context.")]
+    #[allow(clippy::too_many_arguments)]
     #[instrument(
         fields(
             operation_type = "completion_handling",
@@ -452,6 +456,7 @@ impl FilenameSearchTask {
     )]
     async fn handle_search_completion(
         task_id: u64,
+        tab: TabId,
         results: Vec<ObjectInfo>,
         task_start: Instant,
         processed_count: u64,
@@ -495,7 +500,7 @@ impl FilenameSearchTask {
         });
 
         // Forward results to UI
-        let _ = action_tx.send(Action::ShowFilenameSearchResults(results));
+        let _ = action_tx.send(Action::ShowFilenameSearchResults(tab, results));
 
         Ok(())
     }
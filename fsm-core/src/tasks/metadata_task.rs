@@ -2,14 +2,16 @@
 //! ============================================================================
 //! # Background Metadata Loading Task
 //!
-//! Loads expensive metadata (size, modification time, item count) in the background
-//! for better UI responsiveness with cache integration.
+//! Loads expensive metadata (size, timestamps, mode, ownership and inode)
+//! in the background for better UI responsiveness with cache integration.
+//! Batches are stat'ed in parallel on the rayon pool.
 
 use crate::{cache::cache_manager::ObjectInfoCache, controller::actions::Action};
-use crate::fs::object_info::{LightObjectInfo};
+use crate::fs::object_info::{LightObjectInfo, ObjectInfo};
+use rayon::prelude::*;
 use std::{path::PathBuf, sync::Arc};
 use tokio::sync::mpsc;
-use tracing::{debug, info, instrument, Instrument};
+use tracing::{debug, info, instrument, warn, Instrument};
 
 /// Spawn a background task to load full metadata for an entry
 #[instrument(skip(action_tx, cache), fields(path = %light_info.path.display()))]
@@ -72,8 +74,39 @@ pub fn load_metadata_task(
     );
 }
 
+/// Stat entries in parallel on the rayon pool. Blocking; entries that
+/// vanished or cannot be read are logged and left out.
+#[must_use]
+pub fn load_metadata_parallel(light_entries: Vec<LightObjectInfo>) -> Vec<ObjectInfo> {
+    light_entries
+        .into_par_iter()
+        .filter_map(|light_info: LightObjectInfo| -> Option<ObjectInfo> {
+            let path: PathBuf = light_info.path.clone();
+
+            match light_info.into_full_info_sync() {
+                Ok(info) => Some(info),
+
+                Err(e) => {
+                    debug!(
+                        marker = "METADATA_TASK",
+                        operation_type = "metadata_item_failure",
+                        path = %path.display(),
+                        error = %e,
+                        "Failed to load metadata item"
+                    );
+
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
 /// Batch load metadata for multiple entries
-#[expect(clippy::cast_precision_loss, reason = "Expected loss")]
+///
+/// Entries are stat'ed `batch_size` at a time in parallel, so the first rows
+/// fill in before the whole directory is done. Results are cached and sent as
+/// one `Action::BatchUpdateObjectInfo` per batch.
 #[instrument(
     skip(light_entries, action_tx, cache),
     fields(
@@ -104,87 +137,59 @@ pub fn batch_load_metadata_task(
                 "Starting batch metadata load"
             );
 
-            let mut count: usize = 0;
             let mut success_count: usize = 0;
-            let mut error_count: usize = 0;
-            let mut cache_hits: usize = 0;
-            let mut cache_misses: usize = 0;
-
-            for light_info in light_entries {
-                let light_info_path = light_info.path.clone();
-                let item_start = std::time::Instant::now();
-                
-                match cache.get_or_load_path(
-                    light_info_path.clone(), 
-                    || {
-                        cache_misses += 1;
-                        light_info.into_full_info()
-                    }
-                ).await {
-                    Ok(full_info) => {
-                        let item_duration = item_start.elapsed();
-                        
-                        // Fast lookup indicates cache hit
-                        if item_duration.as_micros() < 100 {
-                            cache_hits += 1;
-                        }
-                        
-                        debug!(
-                            marker = "METADATA_TASK",
-                            operation_type = "metadata_item_success",
-                            path = %full_info.path.display(),
-                            load_duration_us = item_duration.as_micros(),
-                            "Metadata item loaded successfully"
-                        );
+            let mut remaining: Vec<LightObjectInfo> = light_entries;
 
-                        let _ = action_tx.send(Action::UpdateObjectInfo {
-                            parent_dir: parent_dir.clone(),
-                            info: full_info,
-                        });
-                        
-                        success_count += 1;
-                    }
+            while !remaining.is_empty() {
+                let rest: Vec<LightObjectInfo> = remaining.split_off(batch_size.clamp(1, remaining.len()));
+                let batch: Vec<LightObjectInfo> = std::mem::replace(&mut remaining, rest);
+
+                let loaded: Vec<ObjectInfo> = match tokio::task::spawn_blocking(
+                    move || -> Vec<ObjectInfo> { load_metadata_parallel(batch) }
+                )
+                .await
+                {
+                    Ok(loaded) => loaded,
 
                     Err(e) => {
-                        let item_duration = item_start.elapsed();
-                        debug!(
+                        warn!(
                             marker = "METADATA_TASK",
-                            operation_type = "metadata_item_failure",
-                            path = %light_info_path.display(),
-                            load_duration_us = item_duration.as_micros(),
+                            operation_type = "batch_metadata_load_failure",
+                            parent_dir = %parent_dir.display(),
                             error = %e,
-                            "Failed to load metadata item"
+                            "Metadata batch failed"
                         );
-                        
-                        error_count += 1;
+
+                        continue;
                     }
+                };
+
+                for full_info in &loaded {
+                    cache.insert_path(&full_info.path, full_info.clone()).await;
                 }
 
-                count += 1;
+                success_count += loaded.len();
 
-                // Yield control periodically to avoid blocking
-                if count.is_multiple_of(batch_size) {
-                    tokio::task::yield_now().await;
+                if action_tx
+                    .send(Action::BatchUpdateObjectInfo {
+                        parent_dir: parent_dir.clone(),
+                        objects: loaded,
+                    })
+                    .is_err()
+                {
+                    return;
                 }
             }
 
             let batch_duration = batch_start.elapsed();
-            let cache_hit_rate = if (cache_hits + cache_misses) > 0 {
-                (cache_hits as f64 / (cache_hits + cache_misses) as f64) * 100.0
-            } else {
-                0.0
-            };
 
             info!(
                 marker = "METADATA_TASK",
                 operation_type = "batch_metadata_load_complete",
                 parent_dir = %parent_dir.display(),
-                total_count = count,
+                total_count = entry_count,
                 success_count = success_count,
-                error_count = error_count,
-                cache_hits = cache_hits,
-                cache_misses = cache_misses,
-                cache_hit_rate = format!("{:.1}%", cache_hit_rate),
+                error_count = entry_count - success_count,
                 batch_duration_ms = batch_duration.as_millis(),
                 "Completed batch metadata loading"
             );
@@ -197,3 +202,30 @@ pub fn batch_load_metadata_task(
         ))
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_metadata_parallel_skips_entries_that_vanished() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        std::fs::write(root.path().join("kept"), "12345").expect("write file");
+        std::fs::write(root.path().join("gone"), "").expect("write file");
+
+        let mut light_entries: Vec<LightObjectInfo> = Vec::new();
+        crate::fs::dir_scanner::read_dir_chunks(root.path(), false, 8, |chunk: Vec<LightObjectInfo>| -> bool {
+            light_entries.extend(chunk);
+            true
+        })
+        .expect("read dir");
+        std::fs::remove_file(root.path().join("gone")).expect("remove file");
+
+        let loaded: Vec<ObjectInfo> = load_metadata_parallel(light_entries);
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].name, "kept");
+        assert_eq!(loaded[0].size, 5);
+        assert!(loaded[0].metadata_loaded);
+    }
+}
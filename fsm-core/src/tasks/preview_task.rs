@@ -0,0 +1,139 @@
+//! ``src/tasks/preview_task.rs``
+//! ============================================================================
+//! # Preview Task: Background Loading of Miller Side Columns
+//!
+//! Directories are listed from the `ObjectInfoCache`: the parent of the
+//! current directory is pre-warmed by `warm_for_navigation` on every
+//! directory change, so the parent column normally costs no extra scan. A
+//! directory that was not warmed yet is warmed here (one level), which also
+//! prepares the cache for entering it. Files are previewed by reading their
+//! first few kilobytes.
+
+use crate::cache::cache_manager::ObjectInfoCache;
+use crate::controller::actions::Action;
+use crate::model::preview::Preview;
+use std::{path::{Path, PathBuf}, sync::Arc};
+use tokio::io::AsyncReadExt;
+use tokio::sync::mpsc;
+use tracing::{debug, Instrument};
+
+/// Bytes read from a file for its preview.
+const PREVIEW_BYTES: u64 = 8 * 1024;
+
+/// Lines kept from a text file.
+const PREVIEW_LINES: usize = 200;
+
+/// Spawn a task that loads the preview of `path` and sends it back as
+/// `Action::PreviewLoaded`.
+pub fn preview_task(
+    path: PathBuf,
+    is_dir: bool,
+    action_tx: mpsc::UnboundedSender<Action>,
+    cache: Arc<ObjectInfoCache>,
+) {
+    let span_path: String = path.display().to_string();
+
+    tokio::spawn(
+        async move {
+            let preview: Preview = if is_dir {
+                load_directory(&path, &cache).await
+            } else {
+                load_file(&path).await
+            };
+
+            let _ = action_tx.send(Action::PreviewLoaded(path, preview));
+        }
+        .instrument(tracing::debug_span!(
+            "preview_load",
+            operation_type = "preview_load",
+            path = %span_path
+        )),
+    );
+}
+
+async fn load_directory(path: &Path, cache: &ObjectInfoCache) -> Preview {
+    if let Some(children) = cache.cached_listing(path).await {
+        debug!("Preview of {} served from warmed cache", path.display());
+        return Preview::Directory(children);
+    }
+
+    if let Err(e) = cache.warm_directory_relationships(path, 1).await {
+        return Preview::Error(e.to_string());
+    }
+
+    cache.cached_listing(path).await.map_or_else(
+        || Preview::Error("Directory could not be read".to_string()),
+        Preview::Directory,
+    )
+}
+
+async fn load_file(path: &Path) -> Preview {
+    let file: tokio::fs::File = match tokio::fs::File::open(path).await {
+        Ok(f) => f,
+
+        Err(e) => return Preview::Error(e.to_string()),
+    };
+
+    let size: u64 = file
+        .metadata()
+        .await
+        .map_or(0, |m: std::fs::Metadata| -> u64 { m.len() });
+
+    let mut buf: Vec<u8> = Vec::new();
+    if let Err(e) = file.take(PREVIEW_BYTES).read_to_end(&mut buf).await {
+        return Preview::Error(e.to_string());
+    }
+
+    // NUL bytes are a reliable sign of binary content
+    if buf.contains(&0) {
+        return Preview::Binary(size);
+    }
+
+    let text: String = String::from_utf8_lossy(&buf).into_owned();
+    let lines: Vec<String> = text
+        .lines()
+        .take(PREVIEW_LINES)
+        .map(|line: &str| -> String { line.replace('\t', "    ") })
+        .collect();
+
+    Preview::Text(lines)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn files_preview_as_text_or_binary() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        let text: PathBuf = root.path().join("text");
+        let binary: PathBuf = root.path().join("binary");
+        std::fs::write(&text, "one\n\ttwo\n").expect("write file");
+        std::fs::write(&binary, [1, 0, 2]).expect("write file");
+
+        let Preview::Text(lines) = load_file(&text).await else {
+            panic!("expected a text preview");
+        };
+
+        assert_eq!(lines, ["one", "    two"]);
+        assert!(matches!(load_file(&binary).await, Preview::Binary(3)));
+        assert!(matches!(load_file(&root.path().join("gone")).await, Preview::Error(_)));
+    }
+
+    #[tokio::test]
+    async fn directories_are_listed_through_the_cache() {
+        let root: tempfile::TempDir = tempfile::tempdir().expect("temp dir");
+        std::fs::write(root.path().join("a"), "").expect("write file");
+        std::fs::create_dir(root.path().join("b")).expect("create dir");
+
+        let cache: ObjectInfoCache = ObjectInfoCache::new();
+
+        let Preview::Directory(children) = load_directory(root.path(), &cache).await else {
+            panic!("expected a directory preview");
+        };
+
+        assert_eq!(children.len(), 2);
+        assert!(cache.cached_listing(root.path()).await.is_some());
+    }
+}
@@ -20,7 +20,8 @@ use tokio::{
 use crate::{
     config::Config,
     controller::{actions::Action, event_loop::TaskResult},
-    logging::{ProfilingData}
+    logging::{ProfilingData},
+    model::tabs::TabId,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -247,6 +248,7 @@ pub struct SearchTask;
 #[expect(clippy::cast_sign_loss, reason = "Expected")]
 pub fn search_task(
       task_id: u64,
+      tab: TabId,
       pattern: String,
       path: PathBuf,
       task_tx: UnboundedSender<TaskResult>,
@@ -395,7 +397,7 @@ pub fn search_task(
           };
 
           let _ =
-  action_tx.send(Action::ShowRawSearchResults(raw_result));
+  action_tx.send(Action::ShowRawSearchResults(tab, raw_result));
       });
   }
 
@@ -146,11 +146,14 @@ impl<T: Clone + Send + 'static> Debouncer<T> {
         // 2) Access the slot mutably
         let slot: &mut Slot<T> = &mut self.slab.lock().await[idx];
 
-        // Leading-edge: fire immediately on first event if configured
-        if self.cfg.leading && slot.last_event.is_none() {
-            debug!("Triggering leading edge for key: {}", key);
-            let _ = self.tx.send((key.clone(), ev.clone()));
-            
+        // First event of a burst: `max_delay` counts from here
+        if slot.last_event.is_none() {
+            // Leading-edge: fire immediately if configured
+            if self.cfg.leading {
+                debug!("Triggering leading edge for key: {}", key);
+                let _ = self.tx.send((key.clone(), ev.clone()));
+            }
+
             slot.last_leading = Instant::now();
         }
 
@@ -0,0 +1,53 @@
+//! src/util/fuzzy.rs
+//! ============================================================================
+//! Minimal fuzzy matcher for filterable overlays.
+//!
+//! A candidate matches when every query character appears in it, in order
+//! (case-insensitive). Consecutive runs and matches at word boundaries
+//! (after `/`, `-`, `_`, `.` or a space) score higher; an empty query matches
+//! everything with a score of zero.
+
+/// Score `candidate` against `query`, or `None` if it does not match.
+#[must_use]
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let mut score: i64 = 0;
+    let mut query_chars = query.chars().flat_map(char::to_lowercase).peekable();
+    let mut prev_matched: bool = false;
+    let mut prev_char: Option<char> = None;
+
+    for c in candidate.chars() {
+        let Some(&wanted) = query_chars.peek() else {
+            break;
+        };
+
+        let is_match: bool = c.to_lowercase().eq(std::iter::once(wanted));
+
+        if is_match {
+            score += 1;
+
+            if prev_matched {
+                score += 4;
+            }
+
+            if prev_char.is_none_or(|p: char| -> bool { matches!(p, '/' | '-' | '_' | '.' | ' ') }) {
+                score += 3;
+            }
+
+            query_chars.next();
+        }
+
+        prev_matched = is_match;
+        prev_char = Some(c);
+    }
+
+    if query_chars.peek().is_some() {
+        return None;
+    }
+
+    // Prefer shorter candidates when scores tie
+    Some(score * 64 - i64::try_from(candidate.len()).unwrap_or(i64::MAX).min(63))
+}
@@ -1 +1,80 @@
+//! ``src/util/humanize.rs``
+//! ============================================================================
+//! # Human-Readable Times
+//!
+//! Timestamp formatting shared by the table columns: local dates in any
+//! `strftime` format and relative ages ("5 min ago").
 
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Local, TimeZone};
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const MONTH: u64 = 30 * DAY;
+const YEAR: u64 = 365 * DAY;
+
+/// `time` in local time, formatted with a `strftime` pattern.
+#[expect(clippy::cast_possible_wrap, reason = "Expected accuracy")]
+#[must_use]
+pub fn format_time(time: SystemTime, format: &str) -> String {
+    let duration: Duration = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+    let datetime: DateTime<Local> = Local
+        .timestamp_opt(duration.as_secs() as i64, duration.subsec_nanos())
+        .single()
+        .unwrap_or_default();
+
+    datetime.format(format).to_string()
+}
+
+/// Age of `time` relative to `now` in the largest fitting unit
+/// ("just now", "3 min ago", "2 days ago"). Times after `now` (clock skew,
+/// files from the future) read "in the future".
+#[must_use]
+pub fn relative_time(time: SystemTime, now: SystemTime) -> String {
+    let Ok(age) = now.duration_since(time) else {
+        return "in the future".to_string();
+    };
+
+    let secs: u64 = age.as_secs();
+
+    let (value, unit): (u64, &str) = match secs {
+        s if s < MINUTE => return "just now".to_string(),
+
+        s if s < HOUR => (s / MINUTE, "min"),
+
+        s if s < DAY => (s / HOUR, "h"),
+
+        s if s < MONTH => (s / DAY, "day"),
+
+        s if s < YEAR => (s / MONTH, "month"),
+
+        s => (s / YEAR, "year"),
+    };
+
+    let plural: &str = if value != 1 && matches!(unit, "day" | "month" | "year") { "s" } else { "" };
+
+    format!("{value} {unit}{plural} ago")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_time_uses_the_largest_fitting_unit() {
+        let now: SystemTime = UNIX_EPOCH + Duration::from_secs(10 * YEAR);
+        let ago = |secs: u64| -> String { relative_time(now - Duration::from_secs(secs), now) };
+
+        assert_eq!(ago(59), "just now");
+        assert_eq!(ago(3 * MINUTE), "3 min ago");
+        assert_eq!(ago(HOUR), "1 h ago");
+        assert_eq!(ago(DAY), "1 day ago");
+        assert_eq!(ago(2 * DAY + HOUR), "2 days ago");
+        assert_eq!(ago(3 * MONTH), "3 months ago");
+        assert_eq!(ago(2 * YEAR), "2 years ago");
+        assert_eq!(relative_time(now + Duration::from_secs(5), now), "in the future");
+    }
+}
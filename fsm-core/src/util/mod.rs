@@ -1,2 +1,4 @@
 pub mod debounce;
+pub mod fuzzy;
 pub mod humanize;
+pub mod persist;
@@ -0,0 +1,210 @@
+//! src/util/persist.rs
+//! ============================================================================
+//! Small helpers for persistent state files (bookmarks, frecency
+//! database, ...).
+//!
+//! Writes go to a sibling temp file which is then renamed over the target, so
+//! a crash mid-write never leaves a truncated file behind. Writes of one file
+//! are serialized and versioned: every contents snapshot takes a version when
+//! it is taken, and a write whose version is older than the one already on
+//! disk is dropped, so a slow stale save can never replace a newer one.
+//!
+//! Paths in text stores go through [`os_path`] / [`os_path_map`], which keep
+//! paths that are not valid UTF-8 as their raw bytes.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs as TokioFs;
+use tracing::debug;
+
+/// Ordering state of one persisted file.
+#[derive(Debug, Default)]
+struct FileWriter {
+    /// Last version handed out.
+    issued: AtomicU64,
+
+    /// Version currently on disk; held for the whole write.
+    written: tokio::sync::Mutex<u64>,
+}
+
+static WRITERS: LazyLock<Mutex<HashMap<PathBuf, Arc<FileWriter>>>> = LazyLock::new(Default::default);
+
+fn writer(path: &Path) -> Arc<FileWriter> {
+    let mut writers = WRITERS.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    writers.entry(path.to_path_buf()).or_default().clone()
+}
+
+/// Claim the next version of `path`. Take it when the contents are
+/// snapshotted, before any work that may let a later snapshot overtake it.
+#[must_use]
+pub fn next_version(path: &Path) -> u64 {
+    writer(path).issued.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+/// Atomically replace `path` with `contents` taken at `version`, creating
+/// parent directories. Returns `false` if a newer version was written first
+/// and this one was dropped.
+pub async fn write_versioned(path: &Path, version: u64, contents: &[u8]) -> std::io::Result<bool> {
+    let writer: Arc<FileWriter> = writer(path);
+    let mut written = writer.written.lock().await;
+
+    if version <= *written {
+        debug!("Dropping stale write of {} (version {version}, on disk {})", path.display(), *written);
+        return Ok(false);
+    }
+
+    if let Some(parent) = path.parent() {
+        TokioFs::create_dir_all(parent).await?;
+    }
+
+    // Unique per process and version, so no two writers ever share one
+    let tmp_path: PathBuf = path.with_extension(format!("tmp.{}.{version}", std::process::id()));
+
+    TokioFs::write(&tmp_path, contents).await?;
+
+    if let Err(e) = TokioFs::rename(&tmp_path, path).await {
+        let _ = TokioFs::remove_file(&tmp_path).await;
+        return Err(e);
+    }
+
+    *written = version;
+
+    Ok(true)
+}
+
+/// Atomically replace `path` with `contents`, creating parent directories.
+pub async fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    write_versioned(path, next_version(path), contents).await?;
+
+    Ok(())
+}
+
+/// A path as stored: a plain string when it is valid UTF-8 (so existing
+/// files stay readable and editable), its raw bytes otherwise.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum StoredPath {
+    Utf8(String),
+    Bytes(Vec<u8>),
+}
+
+impl From<&Path> for StoredPath {
+    fn from(path: &Path) -> Self {
+        match path.to_str() {
+            Some(text) => Self::Utf8(text.to_string()),
+
+            #[cfg(unix)]
+            None => {
+                use std::os::unix::ffi::OsStrExt;
+
+                Self::Bytes(path.as_os_str().as_bytes().to_vec())
+            }
+
+            #[cfg(not(unix))]
+            None => Self::Utf8(path.to_string_lossy().into_owned()),
+        }
+    }
+}
+
+impl TryFrom<StoredPath> for PathBuf {
+    type Error = String;
+
+    fn try_from(stored: StoredPath) -> Result<Self, Self::Error> {
+        match stored {
+            StoredPath::Utf8(text) => Ok(Self::from(text)),
+
+            #[cfg(unix)]
+            StoredPath::Bytes(bytes) => {
+                use std::os::unix::ffi::OsStringExt;
+
+                Ok(Self::from(std::ffi::OsString::from_vec(bytes)))
+            }
+
+            #[cfg(not(unix))]
+            StoredPath::Bytes(_) => Err("path stored as bytes on another platform".to_string()),
+        }
+    }
+}
+
+/// Serde adapter for a `PathBuf` field (`#[serde(with = "os_path")]`).
+pub mod os_path {
+    use std::path::{Path, PathBuf};
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::StoredPath;
+
+    pub fn serialize<S: Serializer>(path: &Path, serializer: S) -> Result<S::Ok, S::Error> {
+        StoredPath::from(path).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PathBuf, D::Error> {
+        PathBuf::try_from(StoredPath::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serde adapter for a map of names to paths (`#[serde(with = "os_path_map")]`).
+pub mod os_path_map {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::StoredPath;
+
+    pub fn serialize<S: Serializer>(map: &BTreeMap<String, PathBuf>, serializer: S) -> Result<S::Ok, S::Error> {
+        map.iter()
+            .map(|(name, path)| (name, StoredPath::from(path.as_path())))
+            .collect::<BTreeMap<&String, StoredPath>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, PathBuf>, D::Error> {
+        BTreeMap::<String, StoredPath>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(name, stored)| PathBuf::try_from(stored).map(|path: PathBuf| (name, path)))
+            .collect::<Result<_, String>>()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Stored {
+        #[serde(with = "super::os_path")]
+        path: PathBuf,
+
+        #[serde(with = "super::os_path_map")]
+        named: BTreeMap<String, PathBuf>,
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn paths_round_trip_through_toml_even_when_not_utf8() {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+
+        let latin1: PathBuf = PathBuf::from(OsString::from_vec(b"/tmp/caf\xe9".to_vec()));
+        let stored: Stored = Stored {
+            path: latin1.clone(),
+            named: BTreeMap::from([("plain".to_string(), PathBuf::from("/tmp/plain")), ("latin1".to_string(), latin1)]),
+        };
+
+        let text: String = toml::to_string(&stored).expect("serialize");
+
+        // Valid UTF-8 stays a readable string
+        assert!(text.contains("plain = \"/tmp/plain\""), "{text}");
+        assert_eq!(toml::from_str::<Stored>(&text).expect("deserialize"), stored);
+    }
+}
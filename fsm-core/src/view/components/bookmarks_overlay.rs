@@ -0,0 +1,132 @@
+//! ``src/view/components/bookmarks_overlay.rs``
+//! ============================================================================
+//! # `BookmarksOverlay`: Fuzzy-Filterable List of Named Bookmarks
+//!
+//! The filter is `UIState::input`; the highlighted row is
+//! `UIState::overlay_selected`. Bookmarks whose target no longer exists are
+//! flagged as missing.
+
+use crate::AppState;
+use crate::model::bookmarks::BookmarkView;
+use crate::model::hit_map::ListHit;
+use crate::view::theme;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+pub struct BookmarksOverlay;
+
+impl BookmarksOverlay {
+    pub fn render(frame: &mut Frame<'_>, app: &mut AppState, area: Rect) {
+        let overlay_area: Rect = Self::centered_rect(70, 60, area);
+        frame.render_widget(Clear, overlay_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(overlay_area);
+
+        let input: Paragraph<'_> = Paragraph::new(app.ui.input.as_str())
+            .block(
+                Block::default()
+                    .title(" Bookmarks ")
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme::PURPLE))
+                    .style(Style::default().bg(theme::BACKGROUND)),
+            )
+            .style(Style::default().fg(theme::FOREGROUND));
+
+        frame.render_widget(input, chunks[0]);
+
+        let bookmarks: Vec<BookmarkView<'_>> = app.bookmarks.filtered(&app.ui.input);
+
+        let items: Vec<ListItem<'_>> = bookmarks
+            .iter()
+            .map(|bookmark: &BookmarkView<'_>| -> ListItem<'_> {
+                let path_style: Style = if bookmark.missing {
+                    Style::default().fg(theme::RED)
+                } else {
+                    Style::default().fg(theme::FOREGROUND)
+                };
+
+                let mut spans: Vec<Span<'_>> = vec![
+                    Span::styled(
+                        format!(" {:<12} ", bookmark.name),
+                        Style::default()
+                            .fg(theme::YELLOW)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(bookmark.path.display().to_string(), path_style),
+                ];
+
+                if bookmark.missing {
+                    spans.push(Span::styled(" (missing)", Style::default().fg(theme::RED)));
+                }
+
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let title: String = if bookmarks.is_empty() && app.bookmarks.is_empty() {
+            " No bookmarks — M<key> or :bm [name] to add ".to_string()
+        } else {
+            format!(" {}/{} ", bookmarks.len(), app.bookmarks.len())
+        };
+
+        let mut list_state: ListState = ListState::default();
+        if !bookmarks.is_empty() {
+            list_state.select(Some(app.ui.overlay_selected.min(bookmarks.len() - 1)));
+        }
+
+        let list: List<'_> = List::new(items)
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_bottom(Line::from(" Enter jump • Del remove • Esc close ").centered())
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme::PURPLE))
+                    .style(Style::default().bg(theme::BACKGROUND).fg(theme::FOREGROUND)),
+            )
+            .highlight_symbol("▶ ")
+            .highlight_style(
+                Style::default()
+                    .bg(theme::CURRENT_LINE)
+                    .fg(theme::PINK)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        let item_count: usize = bookmarks.len();
+        frame.render_stateful_widget(list, chunks[1], &mut list_state);
+
+        app.ui.hits.overlay_list = Some(ListHit::new(
+            chunks[1].inner(Margin::new(1, 1)),
+            list_state.offset(),
+            item_count,
+        ));
+    }
+
+    fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+        let vertical = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ])
+            .split(area);
+
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ])
+            .split(vertical[1])[1]
+    }
+}
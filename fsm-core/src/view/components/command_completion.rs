@@ -7,12 +7,12 @@
 //! This is a core feature for command-line driven interaction.
 
 use crate::{
-    model::{app_state::AppState, command_palette::get_command_description},
+    model::{app_state::AppState, command_palette::get_command_description, hit_map::ListHit},
     view::theme,
 };
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Modifier, Style},
     widgets::{Block, Borders, List, ListItem, Paragraph},
 };
@@ -47,13 +47,13 @@ pub struct CommandCompletion;
 impl CommandCompletion {
     #[allow(clippy::cast_possible_truncation)]
     /// Render the complete command line with auto-completion
-    /// Returns the area used for the command line
+    /// Returns the rows of the suggestions, if any are shown
     pub fn render_command_interface(
         frame: &mut Frame<'_>,
         app: &AppState,
         area: Rect,
         config: &CompletionConfig,
-    ) -> Rect {
+    ) -> Option<ListHit> {
         let palette = &app.ui.command_palette;
         let input = &palette.input;
         let command_text = format!(":{input}");
@@ -80,9 +80,7 @@ impl CommandCompletion {
             frame.set_cursor_position((cursor_x, layout[0].y));
 
             // Render completions
-            Self::render_completions(frame, app, layout[1], config);
-
-            area // Return full area used
+            Self::render_completions(frame, app, layout[1], config)
         } else {
             // Just command line, no completions
             Self::render_command_line(frame, &command_text, area);
@@ -91,8 +89,7 @@ impl CommandCompletion {
             let cursor_x = (area.x + command_text.len() as u16).min(area.x + area.width - 1);
             frame.set_cursor_position((cursor_x, area.y));
 
-            // Return just the command line area
-            Rect { height: 1, ..area }
+            None
         }
     }
 
@@ -133,13 +130,13 @@ impl CommandCompletion {
         app: &AppState,
         area: Rect,
         config: &CompletionConfig,
-    ) {
+    ) -> Option<ListHit> {
         let palette = &app.ui.command_palette;
 
         // Early return check with debug
         if palette.completions.is_empty() {
             tracing::debug!("render_completions: empty completions, area: {:?}", area);
-            return;
+            return None;
         }
 
         tracing::debug!(
@@ -211,6 +208,12 @@ impl CommandCompletion {
         frame.render_widget(completions_list, area);
 
         tracing::debug!("Successfully rendered {} completion items", item_count);
+
+        Some(ListHit::new(
+            area.inner(Margin::new(1, 1)),
+            scroll_offset,
+            completion_count,
+        ))
     }
 
     /// Calculate optimal scroll window for completions
@@ -336,7 +339,7 @@ impl CommandCompletion {
 }
 
 /// Convenience function for default completion rendering
-pub fn render_command_with_completions(frame: &mut Frame<'_>, app: &AppState, area: Rect) -> Rect {
+pub fn render_command_with_completions(frame: &mut Frame<'_>, app: &AppState, area: Rect) -> Option<ListHit> {
     let config = CompletionConfig::default();
     CommandCompletion::render_command_interface(frame, app, area, &config)
 }
@@ -6,10 +6,10 @@
 //! and comprehensive search result display with context and navigation.
 
 use crate::view::theme;
-use crate::{model::app_state::AppState, view::icons};
+use crate::{model::{app_state::AppState, hit_map::ListHit}, view::icons};
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
@@ -19,7 +19,7 @@ use std::time::Instant;
 pub struct ContentSearchOverlay;
 
 impl ContentSearchOverlay {
-    pub fn render(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    pub fn render(frame: &mut Frame<'_>, app: &mut AppState, area: Rect) {
         let render_start = Instant::now();
 
         // Use larger overlay for production quality
@@ -44,7 +44,7 @@ impl ContentSearchOverlay {
         Self::render_search_status(frame, app, layout[1]);
 
         // Render search results or status
-        Self::render_content_results(frame, app, layout[2]);
+        app.ui.hits.overlay_list = Self::render_content_results(frame, app, layout[2]);
 
         // Render enhanced help text
         Self::render_help_text(frame, app, layout[3]);
@@ -172,7 +172,8 @@ impl ContentSearchOverlay {
         frame.render_widget(help_paragraph, area);
     }
 
-    fn render_content_results(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    /// Returns the rows of the result list, if one was drawn.
+    fn render_content_results(frame: &mut Frame<'_>, app: &AppState, area: Rect) -> Option<ListHit> {
         // Check if there's an active search task
         let is_searching = app
             .tasks
@@ -182,27 +183,25 @@ impl ContentSearchOverlay {
         // Show enhanced loading state if searching
         if is_searching {
             Self::render_loading_state(frame, app, area);
-            return;
+            return None;
         }
 
         // Check if we have raw search results first (preferred)
         if let Some(ref raw_results) = app.ui.raw_search_results
             && !raw_results.lines.is_empty()
         {
-            Self::render_raw_results(frame, app, area);
-            return;
+            return Self::render_raw_results(frame, app, area);
         }
 
         // Check if we have rich search results, then fallback to simple results
         if !app.ui.rich_search_results.is_empty() {
-            Self::render_rich_results(frame, app, area);
-            return;
+            return Some(Self::render_rich_results(frame, app, area));
         }
 
         // Check if we have simple search results
         if app.ui.search_results.is_empty() {
             Self::render_empty_state(frame, app, area);
-            return;
+            return None;
         }
 
         // Create list items from search results
@@ -247,9 +246,15 @@ impl ContentSearchOverlay {
         list_state.select(app.ui.selected);
 
         frame.render_stateful_widget(list, area, &mut list_state);
+
+        Some(ListHit::new(
+            area.inner(Margin::new(1, 1)),
+            list_state.offset(),
+            app.ui.search_results.len(),
+        ))
     }
 
-    fn render_rich_results(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    fn render_rich_results(frame: &mut Frame<'_>, app: &AppState, area: Rect) -> ListHit {
         // Simple display of rich search results (deprecated - using raw results now)
         let list_items: Vec<ListItem> = app
             .ui
@@ -279,10 +284,16 @@ impl ContentSearchOverlay {
         list_state.select(app.ui.selected);
 
         frame.render_stateful_widget(list, area, &mut list_state);
+
+        ListHit::new(
+            area.inner(Margin::new(1, 1)),
+            list_state.offset(),
+            app.ui.rich_search_results.len(),
+        )
     }
 
-    fn render_raw_results(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
-        if let Some(ref raw_results) = app.ui.raw_search_results {
+    fn render_raw_results(frame: &mut Frame<'_>, app: &AppState, area: Rect) -> Option<ListHit> {
+        app.ui.raw_search_results.as_ref().map(|raw_results| {
             // Create list items from parsed ANSI text (preserving colors)
             let list_items: Vec<ListItem> = raw_results
                 .parsed_lines
@@ -314,7 +325,13 @@ impl ContentSearchOverlay {
             list_state.select(app.ui.selected);
 
             frame.render_stateful_widget(list, area, &mut list_state);
-        }
+
+            ListHit::new(
+                area.inner(Margin::new(1, 1)),
+                list_state.offset(),
+                raw_results.parsed_lines.len(),
+            )
+        })
     }
 
     fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
@@ -0,0 +1,276 @@
+//! ``src/view/components/diagnostics_overlay.rs``
+//! ============================================================================
+//! # `DiagnosticsOverlay`: Cache and Runtime Figures
+//!
+//! Renders the latest `DiagnosticsSnapshot` (collected by the event loop
+//! while the overlay is open). Figures that need attention, such as cache
+//! usage close to its budget or dispatches slower than a frame, are
+//! highlighted.
+
+use std::time::Duration;
+
+use bytesize::ByteSize;
+
+use crate::AppState;
+use crate::model::diagnostics::DiagnosticsSnapshot;
+use crate::view::theme;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Dispatches slower than one frame at 60 fps are highlighted.
+const SLOW_DISPATCH: Duration = Duration::from_millis(16);
+
+pub struct DiagnosticsOverlay;
+
+impl DiagnosticsOverlay {
+    pub fn render(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+        let overlay_area: Rect = Self::centered_rect(60, 70, area);
+        frame.render_widget(Clear, overlay_area);
+
+        let lines: Vec<Line<'_>> = app.ui.diagnostics.as_ref().map_or_else(
+            || vec![Line::from(Span::styled("Collecting...", Style::default().fg(theme::COMMENT)))],
+            Self::lines,
+        );
+
+        let paragraph: Paragraph<'_> = Paragraph::new(Text::from(lines)).block(
+            Block::default()
+                .title(" Diagnostics ")
+                .title_alignment(Alignment::Center)
+                .title_bottom(Line::from(" F12/Esc close ").centered())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme::PURPLE))
+                .style(Style::default().bg(theme::BACKGROUND).fg(theme::FOREGROUND)),
+        );
+
+        frame.render_widget(paragraph, overlay_area);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn lines(diag: &DiagnosticsSnapshot) -> Vec<Line<'static>> {
+        let cache_ratio: f64 = if diag.cache_budget == 0 {
+            0.0
+        } else {
+            diag.cache_bytes as f64 / diag.cache_budget as f64
+        };
+
+        let mut lines: Vec<Line<'static>> = vec![
+            Self::section("Cache"),
+            Self::row(
+                "Entries",
+                format!("{} ({} pending from disk)", diag.cache_entries, diag.cache_restored),
+                theme::FOREGROUND,
+            ),
+            Self::row(
+                "Memory",
+                format!(
+                    "{} of {} ({:.0}%)",
+                    ByteSize::b(diag.cache_bytes),
+                    ByteSize::b(diag.cache_budget),
+                    cache_ratio * 100.0
+                ),
+                if cache_ratio >= 0.9 { theme::YELLOW } else { theme::FOREGROUND },
+            ),
+            Self::row(
+                "Hit rate",
+                format!(
+                    "{:.1}% ({} hits, {} misses)",
+                    diag.cache.hit_rate() * 100.0,
+                    diag.cache.hits,
+                    diag.cache.misses
+                ),
+                theme::FOREGROUND,
+            ),
+            Self::row(
+                "Loads",
+                format!(
+                    "{} ({:.1}% failed, avg {:?})",
+                    diag.cache.load_count(),
+                    diag.cache.exception_rate() * 100.0,
+                    diag.cache.average_load_penalty
+                ),
+                theme::FOREGROUND,
+            ),
+            Self::row(
+                "Evictions",
+                format!(
+                    "{} evicted, {} invalidated ({} keys)",
+                    diag.cache.evictions, diag.cache.invalidations, diag.cache.invalidated_keys
+                ),
+                theme::FOREGROUND,
+            ),
+            Self::row(
+                "Restored",
+                format!("{} valid, {} stale", diag.cache.restored_hits, diag.cache.restored_stale),
+                theme::FOREGROUND,
+            ),
+            Line::from(""),
+            Self::section("Event loop"),
+            Self::row("Actions", diag.events.to_string(), theme::FOREGROUND),
+            Self::row(
+                "Dispatch",
+                format!("avg {:.2?}, peak {:.2?}", diag.avg_latency, diag.peak_latency),
+                if diag.peak_latency > SLOW_DISPATCH { theme::ORANGE } else { theme::FOREGROUND },
+            ),
+            Self::row(
+                "Queues",
+                format!("{} task results, {} actions", diag.task_queue, diag.action_queue),
+                if diag.task_queue + diag.action_queue > 0 { theme::YELLOW } else { theme::FOREGROUND },
+            ),
+            Line::from(""),
+            Self::section("Background work"),
+            Self::row("Pending tasks", diag.pending_tasks.to_string(), theme::FOREGROUND),
+            Self::row(
+                "File operations",
+                diag.file_operations.len().to_string(),
+                theme::FOREGROUND,
+            ),
+        ];
+
+        for op in &diag.file_operations {
+            lines.push(Self::row(
+                "",
+                format!(
+                    "{} {:.0}% ({}/{} files)",
+                    op.operation_type,
+                    op.progress_ratio() * 100.0,
+                    op.files_completed,
+                    op.total_files
+                ),
+                theme::CYAN,
+            ));
+        }
+
+        lines.extend([
+            Line::from(""),
+            Self::section("Process"),
+            Self::row(
+                "Memory",
+                diag.memory_kb.map_or_else(
+                    || "n/a".to_string(),
+                    |kb: i64| -> String { ByteSize::kib(kb.unsigned_abs()).to_string() },
+                ),
+                theme::FOREGROUND,
+            ),
+            Self::row(
+                "CPU",
+                diag.cpu_percent
+                    .map_or_else(|| "n/a".to_string(), |cpu: f32| -> String { format!("{cpu:.1}%") }),
+                theme::FOREGROUND,
+            ),
+            Self::row(
+                "Uptime",
+                format!("{}s", diag.uptime.as_secs()),
+                theme::FOREGROUND,
+            ),
+        ]);
+
+        lines
+    }
+
+    fn section(title: &'static str) -> Line<'static> {
+        Line::from(Span::styled(
+            title,
+            Style::default()
+                .fg(theme::PURPLE)
+                .add_modifier(Modifier::BOLD),
+        ))
+    }
+
+    fn row(label: &'static str, value: String, color: Color) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(format!("  {label:<16}"), Style::default().fg(theme::COMMENT)),
+            Span::styled(value, Style::default().fg(color)),
+        ])
+    }
+
+    fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+        let vertical = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ])
+            .split(area);
+
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ])
+            .split(vertical[1])[1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::cache_manager::ObjectInfoCache;
+    use crate::model::ui_state::FileOperationProgress;
+
+    fn snapshot() -> DiagnosticsSnapshot {
+        DiagnosticsSnapshot {
+            cache: ObjectInfoCache::new().stats(),
+            cache_entries: 10,
+            cache_bytes: 10,
+            cache_budget: 100,
+            cache_restored: 0,
+            events: 3,
+            avg_latency: Duration::from_millis(1),
+            peak_latency: Duration::from_millis(2),
+            pending_tasks: 0,
+            file_operations: Vec::new(),
+            memory_kb: None,
+            cpu_percent: None,
+            task_queue: 0,
+            action_queue: 0,
+            uptime: Duration::from_secs(5),
+        }
+    }
+
+    /// Value and color of the first row labelled `label`.
+    fn row(lines: &[Line<'static>], label: &str) -> (String, Option<Color>) {
+        let line: &Line<'static> = lines
+            .iter()
+            .find(|line: &&Line<'static>| -> bool {
+                line.spans.len() == 2 && line.spans[0].content.trim() == label
+            })
+            .expect("row shown");
+
+        (line.spans[1].content.to_string(), line.spans[1].style.fg)
+    }
+
+    #[test]
+    fn quiet_figures_are_not_highlighted() {
+        let lines: Vec<Line<'static>> = DiagnosticsOverlay::lines(&snapshot());
+
+        assert_eq!(row(&lines, "Memory"), ("10 B of 100 B (10%)".to_string(), Some(theme::FOREGROUND)));
+        assert_eq!(row(&lines, "Dispatch").1, Some(theme::FOREGROUND));
+        assert_eq!(row(&lines, "Queues").1, Some(theme::FOREGROUND));
+        assert_eq!(row(&lines, "CPU").0, "n/a");
+    }
+
+    #[test]
+    fn figures_that_need_attention_are_highlighted() {
+        let mut diag: DiagnosticsSnapshot = snapshot();
+        diag.cache_bytes = 95;
+        diag.peak_latency = Duration::from_millis(40);
+        diag.action_queue = 2;
+        diag.file_operations = vec![FileOperationProgress::new("Copy".to_string(), 10, 4)];
+
+        let lines: Vec<Line<'static>> = DiagnosticsOverlay::lines(&diag);
+
+        assert_eq!(row(&lines, "Memory").1, Some(theme::YELLOW));
+        assert_eq!(row(&lines, "Dispatch").1, Some(theme::ORANGE));
+        assert_eq!(row(&lines, "Queues").1, Some(theme::YELLOW));
+        assert_eq!(row(&lines, "File operations").0, "1");
+        assert_eq!(row(&lines, "").0, "Copy 0% (0/4 files)");
+    }
+}
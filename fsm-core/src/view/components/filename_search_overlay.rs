@@ -1,11 +1,11 @@
 //! High-performance filename search overlay — ratatui 0.29 + moka 0.12
 
 use crate::{fs::object_info::ObjectInfo,
-model::app_state::AppState, view::theme};
+model::{app_state::AppState, hit_map::ListHit}, view::theme};
 use moka::sync::Cache;
 use rustc_hash::{FxBuildHasher, FxHashSet};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    layout::{Constraint, Direction, Layout, Margin, Rect, Alignment},
     style::{Modifier, Style},
     widgets::{Block, Borders, Clear, List, ListItem, ListState,
 Paragraph, Wrap},
@@ -187,7 +187,8 @@ impl FileNameSearchOverlay {
             area_height = %all.height
         )
     )]
-    pub fn render(&mut self, f: &mut Frame<'_>, app: &AppState, all: Rect) {
+    /// Returns the rows of the result list, if one was drawn.
+    pub fn render(&mut self, f: &mut Frame<'_>, app: &AppState, all: Rect) -> Option<ListHit> {
         let render_start = Instant::now();
 
         trace!(
@@ -229,7 +230,7 @@ impl FileNameSearchOverlay {
         );
 
         Self::draw_input(f, app, chunks[0]);
-        self.draw_results(f, app, chunks[1], chunks[2]);
+        let hit: Option<ListHit> = self.draw_results(f, app, chunks[1], chunks[2]);
 
         let render_time: Duration = render_start.elapsed();
         if render_time.as_millis() > SLOW_RENDER_MS {
@@ -245,6 +246,8 @@ impl FileNameSearchOverlay {
                 "Filename search overlay render completed"
             );
         }
+
+        hit
     }
 }
 
@@ -305,7 +308,7 @@ impl FileNameSearchOverlay {
             input = %app.ui.input
         )
     )]
-    fn draw_results(&self, f: &mut Frame<'_>, app: &AppState, stat: Rect, lst: Rect) {
+    fn draw_results(&self, f: &mut Frame<'_>, app: &AppState, stat: Rect, lst: Rect) -> Option<ListHit> {
         let cache_start: Instant = Instant::now();
         let ce: Arc<CacheEntry> = self.cache.get_or(
             &app.ui.input,
@@ -333,13 +336,13 @@ impl FileNameSearchOverlay {
         if is_searching(app) {
             trace!("Rendering spinner (search in progress)");
             self.spinner(f, app, lst);
-            return;
+            return None;
         }
 
         if ce.items.is_empty() {
             trace!("Rendering empty state (no results)");
             Self::empty(f, app, lst);
-            return;
+            return None;
         }
 
         let list_start: Instant = Instant::now();
@@ -364,7 +367,7 @@ impl FileNameSearchOverlay {
             .border_style(Style::default().fg(theme::CYAN));
 
         let mut st: ListState = ListState::default();
-        let selection = app.ui.raw_search_selected.min(ce.items.len().saturating_sub(1));
+        let selection = app.ui.selected.unwrap_or(0).min(ce.items.len().saturating_sub(1));
         st.select(Some(selection));
 
         f.render_stateful_widget(
@@ -389,6 +392,8 @@ impl FileNameSearchOverlay {
             selected_index = %selection,
             "Results list rendered"
         );
+
+        Some(ListHit::new(lst.inner(Margin::new(1, 1)), st.offset(), ce.items.len()))
     }
 
     // ----------------- spinner / empty -----------------
@@ -488,7 +493,7 @@ impl FileNameSearchOverlay {
         |o: &ObjectInfo, is_local: bool|
         {
             if !o.is_dir &&
-                o.name.to_lowercase().contains(&term) &&
+                o.name.to_string_lossy().to_lowercase().contains(&term) &&
                 seen.insert(o.path.clone())
             {
                 let display_text = build_display(o, cwd);
@@ -615,7 +620,7 @@ fn is_searching(app: &AppState) -> bool {
     fields(
         marker = "BUILD_DISPLAY_STRING",
         operation_type = "string_format",
-        file_name = %obj.name,
+        file_name = %obj.display_name(),
         file_size = %obj.size
     )
 )]
@@ -623,7 +628,7 @@ fn build_display(obj: &ObjectInfo, cwd: &Path) -> String {
     let display_path = obj.path.strip_prefix(cwd).map_or_else(
         |_| {
             trace!("Using absolute path (strip_prefix failed)");
-            Cow::from(obj.name.as_str())
+            obj.display_name()
         },
         |p: &Path| -> Cow<'_, str> {
             trace!("Using relative path");
@@ -0,0 +1,117 @@
+//! ``src/view/components/frecency_overlay.rs``
+//! ============================================================================
+//! # `FrecencyOverlay`: Ranked Candidates for `z <fragment>` Jumps
+//!
+//! The query is `UIState::input` (space-separated fragments, as for `:z`);
+//! the highlighted row is `UIState::overlay_selected`.
+
+use crate::AppState;
+use crate::model::frecency::FrecencyEntry;
+use crate::model::hit_map::ListHit;
+use crate::view::theme;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+pub struct FrecencyOverlay;
+
+impl FrecencyOverlay {
+    pub fn render(frame: &mut Frame<'_>, app: &mut AppState, area: Rect) {
+        let overlay_area: Rect = Self::centered_rect(70, 60, area);
+        frame.render_widget(Clear, overlay_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(overlay_area);
+
+        let input: Paragraph<'_> = Paragraph::new(app.ui.input.as_str())
+            .block(
+                Block::default()
+                    .title(" Jump (frecency) ")
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme::PURPLE))
+                    .style(Style::default().bg(theme::BACKGROUND)),
+            )
+            .style(Style::default().fg(theme::FOREGROUND));
+
+        frame.render_widget(input, chunks[0]);
+
+        let candidates: Vec<(f64, &FrecencyEntry)> = app.frecency.ranked(&app.ui.input);
+
+        let items: Vec<ListItem<'_>> = candidates
+            .iter()
+            .map(|(score, entry): &(f64, &FrecencyEntry)| -> ListItem<'_> {
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {score:>8.1} "), Style::default().fg(theme::COMMENT)),
+                    Span::styled(
+                        entry.path.display().to_string(),
+                        Style::default().fg(theme::FOREGROUND),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let title: String = if app.frecency.is_empty() {
+            " No visited directories yet ".to_string()
+        } else {
+            format!(" {}/{} ", candidates.len(), app.frecency.len())
+        };
+
+        let mut list_state: ListState = ListState::default();
+        if !candidates.is_empty() {
+            list_state.select(Some(app.ui.overlay_selected.min(candidates.len() - 1)));
+        }
+
+        let list: List<'_> = List::new(items)
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_bottom(Line::from(" Enter jump • Del forget • Esc close ").centered())
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme::PURPLE))
+                    .style(Style::default().bg(theme::BACKGROUND).fg(theme::FOREGROUND)),
+            )
+            .highlight_symbol("▶ ")
+            .highlight_style(
+                Style::default()
+                    .bg(theme::CURRENT_LINE)
+                    .fg(theme::PINK)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        let item_count: usize = candidates.len();
+        frame.render_stateful_widget(list, chunks[1], &mut list_state);
+
+        app.ui.hits.overlay_list = Some(ListHit::new(
+            chunks[1].inner(Margin::new(1, 1)),
+            list_state.offset(),
+            item_count,
+        ));
+    }
+
+    fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+        let vertical = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ])
+            .split(area);
+
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ])
+            .split(vertical[1])[1]
+    }
+}
@@ -25,9 +25,26 @@ impl HelpOverlay {
                 Style::default().fg(theme::PURPLE),
             )),
             Line::from("  Up/Down       Move selection"),
-            Line::from("  Left/Right    Switch pane"),
+            Line::from("  Tab           Switch pane focus"),
+            Line::from("  Ctrl+W        Open/close second pane"),
+            Line::from("  Ctrl+T        New tab (Alt+W closes)"),
+            Line::from("  { / }         Previous/next tab (Ctrl+PgUp/PgDn)"),
+            Line::from("  Alt+1..9      Go to tab"),
+            Line::from("  < / >         Move tab left/right"),
             Line::from("  Enter         Open/Enter directory"),
             Line::from("  Backspace     Go to parent directory"),
+            Line::from("  [             Back in history (Alt+Left)"),
+            Line::from("  ]             Forward in history (Alt+Right)"),
+            Line::from("  H             History list"),
+            Line::from("  T             Toggle tree view"),
+            Line::from("  Right/Left    Expand/collapse directory (tree view)"),
+            Line::from("  L             Toggle Miller columns (Left/Right move up/in)"),
+            Line::from("  f             Quick filter: text, *.glob or /regex (Esc clears)"),
+            Line::from("  s             Cycle sort mode"),
+            Line::from("  M<key>        Set mark (bookmark) <key>"),
+            Line::from("  '<key>        Jump to mark <key>"),
+            Line::from("  b             Bookmark list"),
+            Line::from("  z             Frecent directories"),
             Line::from(""),
             Line::from(Span::styled(
                 "File Operations:",
@@ -35,8 +52,11 @@ impl HelpOverlay {
             )),
             Line::from("  n             New file"),
             Line::from("  f             New folder"),
-            Line::from("  d             Delete selected"),
-            Line::from("  r             Rename"),
+            Line::from("  d / Del       Move selected (or marked) to trash (asks if marks are elsewhere)"),
+            Line::from("  D / Shift+Del Delete selected (or marked) permanently (asks first)"),
+            Line::from("  u / Ctrl+R    Undo / redo last file operation"),
+            Line::from("  U             Undo history (Enter undoes/redoes to row)"),
+            Line::from("  r             Rename (marked: pattern with {name} {ext} {n})"),
             Line::from("  y             Copy"),
             Line::from("  p             Paste"),
             Line::from("  m             Move"),
@@ -54,6 +74,26 @@ impl HelpOverlay {
             Line::from("  grep          Enter content search overlay"),
             Line::from("  reload        Reload directory"),
             Line::from("  config        Open configuration"),
+            Line::from("  bm [name]     Bookmark current directory"),
+            Line::from("  bdel <name>   Remove bookmark"),
+            Line::from("  bookmarks     Show bookmarks"),
+            Line::from("  z <frag>...   Jump to best frecent match"),
+            Line::from("  zi [frag]...  Pick from frecent directories"),
+            Line::from("  tabnew        Open a new tab (tabclose closes)"),
+            Line::from("  tabname [n]   Name the current tab"),
+            Line::from("  tabmove <±n>  Move the current tab"),
+            Line::from("  tree          Toggle tree view"),
+            Line::from("  layout [name] Set layout: table or miller"),
+            Line::from("  filter [q]    Filter pane (text, *.glob, /regex)"),
+            Line::from("  sort [-]<key> Sort: name size modified created ext type items"),
+            Line::from("  sort <name>   Sort with a comparator from [sort_comparators]"),
+            Line::from("  dirsfirst     Toggle directories before files"),
+            Line::from("  diag          Cache and runtime diagnostics (F12)"),
+            Line::from("  col <name>    Toggle column: name type items size modified"),
+            Line::from("                created accessed perms owner"),
+            Line::from("  col <c> <fmt> Format: relative iso %pattern si iec bytes"),
+            Line::from("  trash         Trash: Enter/r restores, Del/x twice purges"),
+            Line::from("  undo / redo   Undo or redo a file operation (undolist: U)"),
             Line::from(""),
             Line::from(Span::styled(
                 "Command Auto-completion:",
@@ -63,15 +103,27 @@ impl HelpOverlay {
             Line::from("  Up/Down       Navigate completions"),
             Line::from("  Esc           Exit command mode"),
             Line::from(""),
+            Line::from(Span::styled(
+                "Mouse (mouse = false in config turns capture off):",
+                Style::default().fg(theme::CYAN),
+            )),
+            Line::from("  Click         Focus pane and select row / highlight item"),
+            Line::from("  Double-click  Open/Enter directory, or activate item"),
+            Line::from("  Wheel         Scroll pane / move through list"),
+            Line::from(""),
             Line::from(Span::styled(
                 "UI/General:",
                 Style::default().fg(theme::PURPLE),
             )),
-            Line::from("  Space         Select for batch"),
-            Line::from("  a             Select all"),
+            Line::from("  Space         Mark/unmark and move down"),
+            Line::from("  a / *         Mark all / invert marks"),
+            Line::from("  A / Esc       Clear marks (kept across directories)"),
+            Line::from("  V             Visual mode: arrows extend the range, Space marks,"),
+            Line::from("                c / m / d / D copy, move, trash, delete just the range"),
             Line::from("  : or Ctrl+P   Command palette"),
             Line::from("  t             Toggle theme"),
             Line::from("  h or ?        Show/hide help"),
+            Line::from("  F12           Show/hide diagnostics"),
             Line::from("  q             Quit"),
             Line::from(""),
             Line::from(Span::styled(
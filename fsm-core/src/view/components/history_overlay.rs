@@ -0,0 +1,104 @@
+//! ``src/view/components/history_overlay.rs``
+//! ============================================================================
+//! # `HistoryOverlay`: Back/Forward Navigation History of the Active Pane
+//!
+//! Lists the pane's back stack, current directory and forward stack (oldest
+//! first). The highlighted row is `UIState::overlay_selected`.
+
+use std::path::Path;
+
+use crate::AppState;
+use crate::model::fs_state::HistoryPosition;
+use crate::model::hit_map::ListHit;
+use crate::view::theme;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+};
+
+pub struct HistoryOverlay;
+
+impl HistoryOverlay {
+    pub fn render(frame: &mut Frame<'_>, app: &mut AppState, area: Rect) {
+        let overlay_area: Rect = Self::centered_rect(70, 60, area);
+        frame.render_widget(Clear, overlay_area);
+
+        let entries: Vec<(HistoryPosition, &Path)> = app.fs.active_pane().history_entries();
+
+        let items: Vec<ListItem<'_>> = entries
+            .iter()
+            .map(|(position, path)| {
+                let (marker, style) = match position {
+                    HistoryPosition::Back => ("←", Style::default().fg(theme::FOREGROUND)),
+
+                    HistoryPosition::Current => (
+                        "●",
+                        Style::default()
+                            .fg(theme::YELLOW)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+
+                    HistoryPosition::Forward => ("→", Style::default().fg(theme::CYAN)),
+                };
+
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {marker} "), style),
+                    Span::styled(path.display().to_string(), style),
+                ]))
+            })
+            .collect();
+
+        let mut list_state: ListState = ListState::default();
+        list_state.select(Some(app.ui.overlay_selected.min(entries.len().saturating_sub(1))));
+
+        let list: List<'_> = List::new(items)
+            .block(
+                Block::default()
+                    .title(" History ")
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(Line::from(" Enter jump • H/Esc close ").centered())
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme::PURPLE))
+                    .style(Style::default().bg(theme::BACKGROUND).fg(theme::FOREGROUND)),
+            )
+            .highlight_symbol("▶ ")
+            .highlight_style(
+                Style::default()
+                    .bg(theme::CURRENT_LINE)
+                    .fg(theme::PINK)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        let item_count: usize = entries.len();
+        frame.render_stateful_widget(list, overlay_area, &mut list_state);
+
+        app.ui.hits.overlay_list = Some(ListHit::new(
+            overlay_area.inner(Margin::new(1, 1)),
+            list_state.offset(),
+            item_count,
+        ));
+    }
+
+    fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+        let vertical = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ])
+            .split(area);
+
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ])
+            .split(vertical[1])[1]
+    }
+}
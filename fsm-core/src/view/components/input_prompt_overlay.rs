@@ -4,6 +4,7 @@
 
 use crate::model::app_state::AppState;
 use crate::view::theme;
+use std::path::PathBuf;
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -19,11 +20,49 @@ impl InputPromptOverlay {
         let overlay_area = Self::centered_rect(50, 10, area);
         frame.render_widget(Clear, overlay_area);
 
-        let title = match app.ui.input_prompt_type {
+        let dynamic_title: String;
+        let title = match &app.ui.input_prompt_type {
             Some(crate::controller::actions::InputPromptType::CreateFile) => " Create New File ",
             Some(crate::controller::actions::InputPromptType::CreateDirectory) => {
                 " Create New Directory "
             }
+            Some(crate::controller::actions::InputPromptType::CopyDestination) if app.ui.is_in_visual_mode() => {
+                dynamic_title = format!(" Copy {} selected to ", Self::visual_count(app));
+                &dynamic_title
+            }
+            Some(crate::controller::actions::InputPromptType::MoveDestination) if app.ui.is_in_visual_mode() => {
+                dynamic_title = format!(" Move {} selected to ", Self::visual_count(app));
+                &dynamic_title
+            }
+            Some(crate::controller::actions::InputPromptType::CopyDestination) if !app.fs.marked.is_empty() => {
+                dynamic_title = format!(" Copy {} to ", Self::marked_summary(app, app.fs.marked.len(), &app.fs.marked));
+                &dynamic_title
+            }
+            Some(crate::controller::actions::InputPromptType::MoveDestination) if !app.fs.marked.is_empty() => {
+                dynamic_title = format!(" Move {} to ", Self::marked_summary(app, app.fs.marked.len(), &app.fs.marked));
+                &dynamic_title
+            }
+            Some(crate::controller::actions::InputPromptType::CopyDestination) => " Copy To ",
+            Some(crate::controller::actions::InputPromptType::MoveDestination) => " Move To ",
+            Some(crate::controller::actions::InputPromptType::ConfirmDelete(paths)) => {
+                dynamic_title = match paths.as_slice() {
+                    [path] => {
+                        let name = path.file_name().unwrap_or(path.as_os_str());
+                        format!(" Delete {} permanently? (y/N) ", name.to_string_lossy())
+                    }
+                    _ if app.ui.is_in_visual_mode() => format!(" Delete {} selected permanently? (y/N) ", paths.len()),
+                    _ => format!(" Delete {} permanently? (y/N) ", Self::marked_summary(app, paths.len(), paths)),
+                };
+                &dynamic_title
+            }
+            Some(crate::controller::actions::InputPromptType::ConfirmTrashMarked) => {
+                dynamic_title = format!(" Trash {}? (y/N) ", Self::marked_summary(app, app.fs.marked.len(), &app.fs.marked));
+                &dynamic_title
+            }
+            Some(crate::controller::actions::InputPromptType::RenameFile) if !app.fs.marked.is_empty() => {
+                dynamic_title = format!(" Rename {} ({{name}} {{ext}} {{n}}) ", Self::marked_summary(app, app.fs.marked.len(), &app.fs.marked));
+                &dynamic_title
+            }
             _ => " Input ",
         };
 
@@ -65,6 +104,20 @@ impl InputPromptOverlay {
         }
     }
 
+    /// Rows of the open visual range.
+    fn visual_count(app: &AppState) -> usize {
+        app.ui.visual_range.map_or(0, |(start, end)| -> usize { end - start + 1 })
+    }
+
+    /// "3 marked", plus how many of them the active pane doesn't show.
+    fn marked_summary<'a>(app: &AppState, count: usize, paths: impl IntoIterator<Item = &'a PathBuf>) -> String {
+        match app.count_outside_cwd(paths) {
+            0 => format!("{count} marked"),
+
+            elsewhere => format!("{count} marked ({elsewhere} outside this directory)"),
+        }
+    }
+
     fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
         let popup_layout = Layout::default()
             .direction(Direction::Vertical)
@@ -0,0 +1,184 @@
+//! ``src/view/components/miller_columns.rs``
+//! ============================================================================
+//! # `MillerColumns`: Parent and Preview Columns Around a Pane
+//!
+//! In the Miller layout every pane is drawn as three columns: the parent
+//! directory (with the current directory highlighted), the pane's own table
+//! in the middle, and a preview of the selected entry. The side columns come
+//! from `AppState::previews`; until a preview has loaded the column shows a
+//! placeholder.
+
+use crate::fs::object_info::ObjectInfo;
+use crate::model::fs_state::{PaneState, sort_objects};
+use crate::model::preview::{Preview, PreviewStore};
+use crate::view::{icons, theme};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+use std::path::Path;
+
+pub struct MillerColumns;
+
+impl MillerColumns {
+    /// Parent directory listing with the pane's directory highlighted.
+    pub fn render_parent(
+        frame: &mut Frame<'_>,
+        pane: &PaneState,
+        previews: &PreviewStore,
+        show_hidden: bool,
+        area: Rect,
+    ) {
+        let Some(parent) = pane.cwd.parent() else {
+            frame.render_widget(Self::block(" / ".to_string()), area);
+            return;
+        };
+
+        let title: String = format!(" {} ", Self::name_of(parent));
+
+        let Some(Preview::Directory(children)) = previews.get(parent) else {
+            Self::render_placeholder(frame, previews.is_loading(parent), title, area);
+            return;
+        };
+
+        let entries: Vec<ObjectInfo> = Self::listing(children, pane, show_hidden);
+        let selected: Option<usize> = entries
+            .iter()
+            .position(|e: &ObjectInfo| -> bool { e.path == pane.cwd });
+
+        Self::render_list(frame, &entries, selected, title, area);
+    }
+
+    /// Preview of the pane's selected entry: directory contents or the first
+    /// lines of a text file.
+    pub fn render_preview(
+        frame: &mut Frame<'_>,
+        pane: &PaneState,
+        previews: &PreviewStore,
+        show_hidden: bool,
+        area: Rect,
+    ) {
+        let Some(entry) = pane.selected_entry() else {
+            frame.render_widget(Self::block(" Preview ".to_string()), area);
+            return;
+        };
+
+        let title: String = format!(" {} ", entry.display_name());
+
+        match previews.get(&entry.path) {
+            Some(Preview::Directory(children)) => {
+                let entries: Vec<ObjectInfo> = Self::listing(children, pane, show_hidden);
+
+                if entries.is_empty() {
+                    Self::render_message(frame, "Empty directory", title, area);
+                } else {
+                    Self::render_list(frame, &entries, None, title, area);
+                }
+            }
+
+            Some(Preview::Text(lines)) => {
+                let text: Vec<Line<'_>> = lines
+                    .iter()
+                    .take(area.height as usize)
+                    .map(|line: &String| -> Line<'_> { Line::from(line.as_str()) })
+                    .collect();
+
+                let paragraph: Paragraph<'_> = Paragraph::new(text)
+                    .style(Style::default().fg(theme::FOREGROUND))
+                    .block(Self::block(title));
+
+                frame.render_widget(paragraph, area);
+            }
+
+            Some(Preview::Binary(size)) => {
+                let message: String = format!("Binary file, {size} bytes");
+                Self::render_message(frame, &message, title, area);
+            }
+
+            Some(Preview::Error(e)) => {
+                let message: String = format!("Cannot preview: {e}");
+                Self::render_message(frame, &message, title, area);
+            }
+
+            None => Self::render_placeholder(frame, previews.is_loading(&entry.path), title, area),
+        }
+    }
+
+    /// Children sorted like the pane, hiding dotfiles unless enabled.
+    fn listing(children: &[ObjectInfo], pane: &PaneState, show_hidden: bool) -> Vec<ObjectInfo> {
+        let mut entries: Vec<ObjectInfo> = children
+            .iter()
+            .filter(|e: &&ObjectInfo| -> bool { show_hidden || !e.is_hidden() })
+            .cloned()
+            .collect();
+
+        sort_objects(&mut entries, &pane.sort, &pane.sort_config);
+        entries
+    }
+
+    fn render_list(
+        frame: &mut Frame<'_>,
+        entries: &[ObjectInfo],
+        selected: Option<usize>,
+        title: String,
+        area: Rect,
+    ) {
+        let items: Vec<ListItem<'_>> = entries
+            .iter()
+            .map(|obj: &ObjectInfo| -> ListItem<'_> {
+                let (icon, style) = if obj.is_dir {
+                    (icons::FOLDER_ICON, Style::default().fg(theme::CYAN))
+                } else if obj.is_symlink {
+                    (icons::SYMLINK_ICON, Style::default().fg(theme::PINK))
+                } else {
+                    (icons::FILE_ICON, Style::default().fg(theme::FOREGROUND))
+                };
+
+                ListItem::new(format!("{icon} {}", obj.display_name())).style(style)
+            })
+            .collect();
+
+        let mut list_state: ListState = ListState::default();
+        list_state.select(selected);
+
+        let list: List<'_> = List::new(items).block(Self::block(title)).highlight_style(
+            Style::default()
+                .bg(theme::CURRENT_LINE)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        frame.render_stateful_widget(list, area, &mut list_state);
+    }
+
+    fn render_placeholder(frame: &mut Frame<'_>, loading: bool, title: String, area: Rect) {
+        let message: &str = if loading { "Loading…" } else { "" };
+        Self::render_message(frame, message, title, area);
+    }
+
+    fn render_message(frame: &mut Frame<'_>, message: &str, title: String, area: Rect) {
+        let paragraph: Paragraph<'_> = Paragraph::new(message.to_string())
+            .style(Style::default().fg(theme::COMMENT))
+            .block(Self::block(title));
+
+        frame.render_widget(paragraph, area);
+    }
+
+    fn block(title: String) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_style(Style::default().fg(theme::PURPLE).bold())
+            .border_style(Style::default().fg(theme::COMMENT))
+            .style(Style::default().bg(theme::BACKGROUND))
+    }
+
+    fn name_of(dir: &Path) -> String {
+        dir.file_name().map_or_else(
+            || dir.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        )
+    }
+}
@@ -6,16 +6,20 @@
 //! - Fully async-updatable, selection-aware
 //! - Handles directories, symlinks, files, and custom types
 //! - Shows keymap in the footer, all using ratatui v0.25+
-//! - Visual cues for type, selection, and focus
+//! - Visual cues for type, selection, marks, visual range, and focus
 
+use std::collections::HashSet;
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::{
-    fs::object_info::ObjectInfo, model::{app_state::AppState, PaneState}, view::{
-        components::command_completion::{CommandCompletion, CompletionConfig},
+    config::{ColumnFormat, ColumnSpec, LayoutMode, TableColumn}, fs::object_info::ObjectInfo, model::{app_state::AppState, fs_state::SortKey, EntrySort, ListHit, PaneHit, PaneState, TreeRow, TreeState, UIOverlay}, util::humanize, view::{
+        components::{command_completion::{CommandCompletion, CompletionConfig}, miller_columns::MillerColumns},
         icons, theme,
     }
 };
+use bytesize::ByteSize;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -24,10 +28,19 @@ use ratatui::{
     widgets::{Block, Borders, Cell, HighlightSpacing, Paragraph, Row, Table},
 };
 
+/// Time format of `ColumnFormat::Default`.
+const DEFAULT_DATE_FORMAT: &str = "%d/%m/%Y %I:%M:%S %p";
+
+/// Time format of `ColumnFormat::Iso`.
+const ISO_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Fallback when no column is visible.
+static NAME_ONLY: ColumnSpec = ColumnSpec::new(TableColumn::Name);
+
 pub struct ObjectTable;
 
 impl ObjectTable {
-    #[expect(clippy::too_many_lines, reason = "Marked for refactor")]
+    #[allow(clippy::cast_possible_truncation)]
     pub fn render(frame: &mut Frame<'_>, app: &mut AppState, area: Rect) {
         // Split the area into table, command line (if active), and footer
         let constraints: Vec<Constraint> = if app.ui.is_in_command_mode() {
@@ -60,70 +73,164 @@ impl ObjectTable {
             (None, layout[1])
         };
 
-        let pane: &mut PaneState = &mut app.fs.panes[app.fs.active_pane];
+        // Dual-pane mode splits the table area side by side
+        let pane_count: usize = app.fs.panes.len();
+        let pane_areas: Rc<[Rect]> = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, pane_count as u32); pane_count])
+            .split(table_area);
+
+        let active_pane: usize = app.fs.active_pane;
+        let is_dual: bool = app.fs.is_dual_pane();
+        let is_miller: bool = app.ui.layout == LayoutMode::Miller;
+        let show_hidden: bool = app.ui.show_hidden;
+        let columns: &[ColumnSpec] = &app.ui.columns;
+        let marked: &HashSet<PathBuf> = &app.fs.marked;
+        let is_visual: bool = app.ui.is_in_visual_mode();
+
+        for (idx, pane) in app.fs.panes.iter_mut().enumerate() {
+            let is_focused: bool = idx == active_pane;
+
+            // Only the active pane can hold a visual range
+            let visual: Option<(usize, usize)> = if is_focused && is_visual { pane.visual_range() } else { None };
+            if is_focused {
+                app.ui.visual_range = visual;
+            }
+
+            if !is_miller {
+                let rows: ListHit = Self::render_pane(frame, pane, pane_areas[idx], is_focused, is_dual, columns, marked, visual);
+                app.ui.hits.panes.push(PaneHit { pane: idx, area: pane_areas[idx], rows });
+                continue;
+            }
+
+            // Parent / current / preview columns
+            let miller: Rc<[Rect]> = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Ratio(1, 5), Constraint::Ratio(2, 5), Constraint::Ratio(2, 5)])
+                .split(pane_areas[idx]);
+
+            MillerColumns::render_parent(frame, pane, &app.previews, show_hidden, miller[0]);
+            let rows: ListHit = Self::render_pane(frame, pane, miller[1], is_focused, is_dual, columns, marked, visual);
+            app.ui.hits.panes.push(PaneHit { pane: idx, area: pane_areas[idx], rows });
+            MillerColumns::render_preview(frame, pane, &app.previews, show_hidden, miller[2]);
+        }
+
+        // Render command line if in command mode using new completion system
+        if let Some(cmd_area) = command_area {
+            let config = CompletionConfig::default();
+            app.ui.hits.completions =
+                CommandCompletion::render_command_interface(frame, app, cmd_area, &config);
+        }
+
+        // The quick-filter bar takes the footer's place while typing
+        if app.ui.overlay == UIOverlay::QuickFilter {
+            Self::render_filter_bar(frame, app, footer_area);
+        } else {
+            Self::render_footer(frame, footer_area);
+        }
+    }
 
+    /// Renders a single pane's table. The focused pane gets a highlighted
+    /// border when more than one pane is visible; marked entries are tinted
+    /// and flagged, rows of the visual range get a background. Returns the
+    /// rows the entries were drawn in.
+    #[allow(clippy::too_many_arguments)]
+    fn render_pane(
+        frame: &mut Frame<'_>,
+        pane: &mut PaneState,
+        area: Rect,
+        is_focused: bool,
+        is_dual: bool,
+        columns: &[ColumnSpec],
+        marked: &HashSet<PathBuf>,
+        visual: Option<(usize, usize)>,
+    ) -> ListHit {
         // Update viewport height based on available area (account for borders, header, and footer)
-        let content_height: u16 = table_area.height.saturating_sub(3); // Account for borders and header
+        let content_height: u16 = area.height.saturating_sub(3); // Account for borders and header
         pane.set_viewport_height(content_height as usize);
 
-        let header: Row<'_> = Row::new(vec!["Name", "Type", "Items", "Size", "Modified"])
-            .style(Style::default().fg(theme::YELLOW).bold())
-            .bottom_margin(1);
+        let mut columns: Vec<&ColumnSpec> = columns
+            .iter()
+            .filter(|spec: &&ColumnSpec| -> bool { spec.visible })
+            .collect();
+
+        // A configuration without visible columns still shows the names
+        if columns.is_empty() {
+            columns.push(&NAME_ONLY);
+        }
+
+        // The column of the active sort key carries a direction arrow
+        let sorted: Option<(TableColumn, bool)> = Self::sort_column(&pane.sort);
+
+        let header: Row<'_> = Row::new(columns.iter().map(|spec: &&ColumnSpec| -> Cell<'_> {
+            match sorted {
+                Some((column, descending)) if column == spec.column => Cell::from(format!(
+                    "{} {}",
+                    spec.column.title(),
+                    if descending { "▼" } else { "▲" }
+                ))
+                .style(Style::default().fg(theme::PINK)),
+
+                _ => Cell::from(spec.column.title()),
+            }
+        }))
+        .style(Style::default().fg(theme::YELLOW).bold())
+        .bottom_margin(1);
 
         // Use virtual scrolling - only render visible entries
         let visible_entries: &[ObjectInfo] = pane.visible_entries();
+        let visible_count: u16 = visible_entries.len() as u16;
         let total_entries: usize = pane.entries.len();
+        let scroll_offset: usize = pane.scroll_offset;
+        let now: SystemTime = SystemTime::now();
 
-        let rows = visible_entries.iter().map(|obj| {
-            let (icon, style, type_str) = if obj.is_dir {
-                (icons::FOLDER_ICON, Style::default().fg(theme::CYAN), "Dir")
+        // In tree view every row carries its depth guides and expand state
+        let tree_rows: Option<&[TreeRow]> = pane
+            .tree
+            .as_ref()
+            .map(|tree: &TreeState| -> &[TreeRow] { &tree.rows });
+
+        let rows = visible_entries.iter().enumerate().map(|(i, obj)| {
+            let (icon, style) = if obj.is_dir {
+                (icons::FOLDER_ICON, Style::default().fg(theme::CYAN))
             } else if obj.is_symlink {
-                (
-                    icons::SYMLINK_ICON,
-                    Style::default().fg(theme::PINK),
-                    "Symlink",
-                )
+                (icons::SYMLINK_ICON, Style::default().fg(theme::PINK))
             } else {
-                (
-                    icons::FILE_ICON,
-                    Style::default().fg(theme::FOREGROUND),
-                    obj.extension.as_deref().unwrap_or("File"),
-                )
+                (icons::FILE_ICON, Style::default().fg(theme::FOREGROUND))
             };
 
-            let items_str: String = if obj.is_dir {
-                if obj.items_count > 0 {
-                    obj.items_count.to_string()
-                } else {
-                    "-".to_string()
-                }
+            // Marked entries stand out from every entry type
+            let is_marked: bool = marked.contains(&obj.path);
+            let style: Style = if is_marked {
+                Style::default().fg(theme::YELLOW).add_modifier(Modifier::BOLD)
             } else {
-                String::new()
+                style
             };
 
-            let size_str: String = if obj.is_dir {
-                String::new()
-            } else {
-                obj.size_human()
-            };
+            let in_visual: bool = visual
+                .is_some_and(|(start, end): (usize, usize)| -> bool { (start..=end).contains(&(scroll_offset + i)) });
+            let style: Style = if in_visual { style.bg(theme::PURPLE).fg(theme::BACKGROUND) } else { style };
+
+            let tree_row: Option<&TreeRow> = tree_rows.and_then(|r: &[TreeRow]| r.get(scroll_offset + i));
 
-            Row::new(vec![
-                Cell::from(format!("{icon} {}", obj.name)),
-                Cell::from(type_str.to_string()),
-                Cell::from(items_str),
-                Cell::from(size_str),
-                Cell::from(obj.format_date("%d/%m/%Y %I:%M:%S %p")),
-            ])
-            .style(style)
+            let cells: Vec<Cell<'_>> = columns
+                .iter()
+                .map(|spec: &&ColumnSpec| -> Cell<'_> {
+                    match spec.column {
+                        TableColumn::Name => Self::name_cell(obj, icon, tree_row, is_marked),
+
+                        _ => Self::value_cell(spec, obj, now),
+                    }
+                })
+                .collect();
+
+            Row::new(cells).style(style)
         });
 
-        let widths: [Constraint; 5] = [
-            Constraint::Fill(1),
-            Constraint::Length(10),
-            Constraint::Length(8),
-            Constraint::Length(12),
-            Constraint::Length(24),
-        ];
+        let widths: Vec<Constraint> = columns
+            .iter()
+            .map(|spec: &&ColumnSpec| -> Constraint { Self::column_width(spec, now) })
+            .collect();
 
         // Get the table state from the pane (already set up for virtual scrolling)
         let mut table_state = pane.table_state.clone();
@@ -140,6 +247,29 @@ impl ObjectTable {
             format!(" {} ", pane.cwd.display())
         };
 
+        let title: String = if tree_rows.is_some() {
+            format!("{title}[tree] ")
+        } else {
+            title
+        };
+
+        let border_color = if is_dual && is_focused {
+            theme::PURPLE
+        } else {
+            theme::COMMENT
+        };
+
+        // Only the focused pane shows a strong selection highlight
+        let highlight_style: Style = if is_focused {
+            Style::default()
+                .bg(theme::CURRENT_LINE)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .bg(theme::CURRENT_LINE)
+                .add_modifier(Modifier::DIM)
+        };
+
         let table = Table::new(rows, widths)
             .header(header)
             .block(
@@ -147,37 +277,216 @@ impl ObjectTable {
                     .borders(Borders::ALL)
                     .title(title)
                     .title_style(Style::default().fg(theme::PURPLE).bold())
-                    .border_style(Style::default().fg(theme::COMMENT))
+                    .border_style(Style::default().fg(border_color))
                     .style(Style::default().bg(theme::BACKGROUND)),
             )
-            .row_highlight_style(
-                Style::default()
-                    .bg(theme::CURRENT_LINE)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .highlight_symbol("▶ ")
+            .row_highlight_style(highlight_style)
+            .highlight_symbol(if is_focused { "▶ " } else { "  " })
             .highlight_spacing(HighlightSpacing::Always)
             .column_spacing(2);
 
-        frame.render_stateful_widget(table, table_area, &mut table_state);
+        frame.render_stateful_widget(table, area, &mut table_state);
 
-        // Render command line if in command mode using new completion system
-        if let Some(cmd_area) = command_area {
-            let config = CompletionConfig::default();
-            CommandCompletion::render_command_interface(frame, app, cmd_area, &config);
+        // Update the pane's table state
+        pane.table_state = table_state;
+
+        // Entry rows start below the top border, the header and its margin
+        let rows_area: Rect = Rect {
+            x: area.x + 1,
+            y: area.y + 3,
+            width: area.width.saturating_sub(2),
+            height: area.height.saturating_sub(4).min(visible_count),
+        };
+
+        ListHit::new(rows_area, scroll_offset, total_entries)
+    }
+
+    /// Icon and name, with guides and expand marker in tree view. Marked
+    /// entries are flagged in front of the icon.
+    fn name_cell<'a>(obj: &'a ObjectInfo, icon: &str, tree_row: Option<&TreeRow>, is_marked: bool) -> Cell<'a> {
+        let icon: String = if is_marked { format!("● {icon}") } else { icon.to_string() };
+
+        // Names that are not valid UTF-8 are shown lossily, marked and tinted
+        let name_style: Style = if obj.has_lossy_name() {
+            Style::default().fg(theme::ORANGE)
+        } else {
+            Style::default()
+        };
+
+        match tree_row {
+            Some(row) => {
+                let marker: &str = if row.loading {
+                    "… "
+                } else if row.expanded {
+                    "▾ "
+                } else if obj.is_dir {
+                    "▸ "
+                } else {
+                    "  "
+                };
+
+                Cell::from(Line::from(vec![
+                    Span::styled(row.guides.clone(), Style::default().fg(theme::COMMENT)),
+                    Span::styled(format!("{marker}{icon} {}", obj.display_name()), name_style),
+                ]))
+            }
+
+            None => Cell::from(Span::styled(format!("{icon} {}", obj.display_name()), name_style)),
         }
+    }
 
-        // Render footer with hotkeys
-        Self::render_footer(frame, footer_area);
+    /// Every column but the name. Metadata columns stay blank until the
+    /// metadata phase has loaded the entry.
+    fn value_cell<'a>(spec: &ColumnSpec, obj: &'a ObjectInfo, now: SystemTime) -> Cell<'a> {
+        let text: String = match spec.column {
+            TableColumn::Name => obj.display_name().into_owned(),
 
-        // Update the pane's table state
-        let pane = &mut app.fs.panes[app.fs.active_pane];
-        pane.table_state = table_state;
+            TableColumn::Type if obj.is_dir => "Dir".to_string(),
+
+            TableColumn::Type if obj.is_symlink => "Symlink".to_string(),
+
+            TableColumn::Type => obj.extension.as_deref().unwrap_or("File").to_string(),
+
+            TableColumn::Items if obj.is_dir && obj.items_count > 0 => obj.items_count.to_string(),
+
+            TableColumn::Items if obj.is_dir => "-".to_string(),
+
+            TableColumn::Items => String::new(),
+
+            TableColumn::Size if obj.is_dir => String::new(),
+
+            TableColumn::Size => Self::format_size(obj.size, &spec.format),
+
+            TableColumn::Modified => Self::format_time(obj.modified, &spec.format, now),
+
+            TableColumn::Created => Self::format_time(obj.created, &spec.format, now),
+
+            TableColumn::Accessed => Self::format_time(obj.accessed, &spec.format, now),
+
+            _ if !obj.metadata_loaded => String::new(),
+
+            TableColumn::Permissions => obj.unix.permissions(),
+
+            TableColumn::Owner => format!("{}:{}", obj.unix.owner(), obj.unix.group()),
+        };
+
+        match spec.column {
+            TableColumn::Permissions | TableColumn::Owner => {
+                Cell::from(text).style(Style::default().fg(theme::COMMENT))
+            }
+
+            _ => Cell::from(text),
+        }
+    }
+
+    fn format_size(bytes: u64, format: &ColumnFormat) -> String {
+        match format {
+            ColumnFormat::Si => ByteSize::b(bytes).display().si().to_string(),
+
+            ColumnFormat::Bytes => bytes.to_string(),
+
+            _ => ByteSize::b(bytes).display().iec().to_string(),
+        }
+    }
+
+    /// Timestamps are blank where unknown (not loaded yet, or no birth time).
+    fn format_time(time: SystemTime, format: &ColumnFormat, now: SystemTime) -> String {
+        if time == UNIX_EPOCH {
+            return String::new();
+        }
+
+        match format {
+            ColumnFormat::Relative => humanize::relative_time(time, now),
+
+            ColumnFormat::Iso => humanize::format_time(time, ISO_DATE_FORMAT),
+
+            ColumnFormat::Strftime(pattern) => humanize::format_time(time, pattern),
+
+            _ => humanize::format_time(time, DEFAULT_DATE_FORMAT),
+        }
+    }
+
+    /// Configured width, or one that fits the column's values.
+    fn column_width(spec: &ColumnSpec, now: SystemTime) -> Constraint {
+        if let Some(width) = spec.width {
+            return Constraint::Length(width);
+        }
+
+        let width: usize = match (spec.column, &spec.format) {
+            (TableColumn::Name, _) => return Constraint::Fill(1),
+
+            (TableColumn::Type, _) => 10,
+
+            (TableColumn::Items, _) => 8,
+
+            (TableColumn::Size, ColumnFormat::Bytes) => 14,
+
+            (TableColumn::Size, _) => 12,
+
+            (TableColumn::Permissions, _) => 9,
+
+            (TableColumn::Owner, _) => 17,
+
+            (_, ColumnFormat::Relative) => 14,
+
+            (_, ColumnFormat::Strftime(pattern)) => humanize::format_time(now, pattern).chars().count(),
+
+            (_, ColumnFormat::Iso) => ISO_DATE_FORMAT.len() + 2,
+
+            _ => 24,
+        };
+
+        Constraint::Length(u16::try_from(width).unwrap_or(u16::MAX))
+    }
+
+    /// Column showing the sort key, and true for descending order.
+    const fn sort_column(sort: &EntrySort) -> Option<(TableColumn, bool)> {
+        let Some((key, descending)) = sort.key() else {
+            return None;
+        };
+
+        let column: TableColumn = match key {
+            SortKey::Name => TableColumn::Name,
+
+            SortKey::Size => TableColumn::Size,
+
+            SortKey::Modified => TableColumn::Modified,
+
+            SortKey::Created => TableColumn::Created,
+
+            SortKey::Extension | SortKey::Type => TableColumn::Type,
+
+            SortKey::Items => TableColumn::Items,
+        };
+
+        Some((column, descending))
     }
 
     // Command line rendering is now handled by the dedicated CommandCompletion module
 
     /// Renders the footer bar with hotkey information using dark purple theme
+    /// Quick-filter input line with the number of hidden entries.
+    fn render_filter_bar(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+        let hidden: usize = app.fs.active_pane().hidden_count();
+
+        let line: Line<'_> = Line::from(vec![
+            Span::styled(
+                "Filter: ",
+                Style::default()
+                    .fg(theme::YELLOW)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(app.ui.input.as_str(), Style::default().fg(theme::FOREGROUND)),
+            Span::styled("▏", Style::default().fg(theme::PINK)),
+            Span::styled(
+                format!("  {hidden} hidden • text, *.glob or /regex • Enter keep • Esc clear"),
+                Style::default().fg(theme::COMMENT),
+            ),
+        ]);
+
+        frame.render_widget(Paragraph::new(line), area);
+    }
+
     fn render_footer(frame: &mut Frame<'_>, area: Rect) {
         // Create hotkey spans with command-line focused styling
         let hotkeys = [
@@ -224,3 +533,37 @@ impl ObjectTable {
         frame.render_widget(footer, area);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn sizes_follow_the_column_format() {
+        assert_eq!(ObjectTable::format_size(1_500_000, &ColumnFormat::Si), "1.5 MB");
+        assert_eq!(ObjectTable::format_size(1_500_000, &ColumnFormat::Default), "1.4 MiB");
+        assert_eq!(ObjectTable::format_size(1_500_000, &ColumnFormat::Bytes), "1500000");
+    }
+
+    #[test]
+    fn unknown_times_are_blank_and_known_ones_follow_the_format() {
+        let now: SystemTime = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let then: SystemTime = now - Duration::from_secs(120);
+
+        assert_eq!(ObjectTable::format_time(UNIX_EPOCH, &ColumnFormat::Relative, now), "");
+        assert_eq!(ObjectTable::format_time(then, &ColumnFormat::Relative, now), "2 min ago");
+        assert_eq!(
+            ObjectTable::format_time(then, &ColumnFormat::Strftime("%Y".to_string()), now),
+            humanize::format_time(then, "%Y")
+        );
+    }
+
+    #[test]
+    fn the_sort_arrow_goes_on_the_column_of_the_sort_key() {
+        assert_eq!(ObjectTable::sort_column(&EntrySort::SizeDesc), Some((TableColumn::Size, true)));
+        assert_eq!(ObjectTable::sort_column(&EntrySort::ExtensionAsc), Some((TableColumn::Type, false)));
+        assert_eq!(ObjectTable::sort_column(&EntrySort::ItemsAsc), Some((TableColumn::Items, false)));
+    }
+}
@@ -3,6 +3,7 @@
 //! # `SearchResultsOverlay`: Displays Search Results
 
 use crate::AppState;
+use crate::model::hit_map::ListHit;
 use crate::view::theme;
 use ratatui::{
     Frame,
@@ -15,7 +16,7 @@ use ratatui::{
 pub struct SearchResultsOverlay;
 
 impl SearchResultsOverlay {
-    pub fn render(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    pub fn render(frame: &mut Frame<'_>, app: &mut AppState, area: Rect) {
         let results = &app.ui.search_results;
 
         let mut lines = vec![Line::from(Span::styled(
@@ -33,31 +34,6 @@ impl SearchResultsOverlay {
                 results.len()
             ))));
             lines.push(Line::from(""));
-
-            let items: Vec<ListItem> = results
-                .iter()
-                .map(|obj| ListItem::new(obj.path.to_string_lossy().into_owned()))
-                .collect();
-
-            let mut list_state = ListState::default();
-            list_state.select(app.ui.selected);
-
-            let list = List::new(items)
-                .block(
-                    Block::default()
-                        .borders(Borders::NONE)
-                        .style(Style::default().bg(theme::BACKGROUND).fg(theme::FOREGROUND)),
-                )
-                .highlight_symbol("▶ ")
-                .highlight_style(
-                    Style::default()
-                        .bg(theme::CURRENT_LINE)
-                        .fg(theme::PINK)
-                        .add_modifier(Modifier::BOLD),
-                );
-
-            let list_area = Self::inset_rect(area, 2, 4); // Adjust inset as needed
-            frame.render_stateful_widget(list, list_area, &mut list_state);
         }
 
         let overlay_area = Self::centered_rect(80, 80, area);
@@ -75,6 +51,40 @@ impl SearchResultsOverlay {
             .alignment(Alignment::Left);
 
         frame.render_widget(para, overlay_area);
+
+        if results.is_empty() {
+            return;
+        }
+
+        let items: Vec<ListItem> = results
+            .iter()
+            .map(|obj| ListItem::new(obj.path.to_string_lossy().into_owned()))
+            .collect();
+
+        let result_count: usize = items.len();
+
+        let mut list_state = ListState::default();
+        list_state.select(app.ui.selected);
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::NONE)
+                    .style(Style::default().bg(theme::BACKGROUND).fg(theme::FOREGROUND)),
+            )
+            .highlight_symbol("▶ ")
+            .highlight_style(
+                Style::default()
+                    .bg(theme::CURRENT_LINE)
+                    .fg(theme::PINK)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        // Below the border and the three header lines
+        let list_area = Self::inset_rect(overlay_area, 2, 4, 1);
+        frame.render_stateful_widget(list, list_area, &mut list_state);
+
+        app.ui.hits.overlay_list = Some(ListHit::new(list_area, list_state.offset(), result_count));
     }
 
     fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
@@ -99,12 +109,12 @@ impl SearchResultsOverlay {
         horizontal[1]
     }
 
-    const fn inset_rect(area: Rect, margin_x: u16, margin_y: u16) -> Rect {
+    const fn inset_rect(area: Rect, margin_x: u16, margin_top: u16, margin_bottom: u16) -> Rect {
         Rect {
             x: area.x + margin_x,
-            y: area.y + margin_y,
+            y: area.y + margin_top,
             width: area.width.saturating_sub(margin_x * 2),
-            height: area.height.saturating_sub(margin_y * 2),
+            height: area.height.saturating_sub(margin_top + margin_bottom),
         }
     }
 }
@@ -9,6 +9,7 @@
 use std::rc::Rc;
 
 use crate::AppState;
+use crate::model::fs_state::{EntryFilter, PaneState};
 use crate::view::theme;
 use ratatui::{
     Frame,
@@ -63,7 +64,31 @@ impl StatusBar {
             )
             .alignment(Alignment::Left);
 
-        let right_text: String = format!("{} items ", app.fs.active_pane().entries.len());
+        let pane: &PaneState = app.fs.active_pane();
+        let hidden: usize = pane.hidden_count();
+
+        let right_text: String = if pane.filter == EntryFilter::All {
+            format!("{} items ", pane.entries.len())
+        } else {
+            format!("{} items, {hidden} hidden by filter '{}' ", pane.entries.len(), pane.filter)
+        };
+
+        // Marks can span directories, so the count is over all of them
+        let right_text: String = match (app.fs.marked.len(), app.count_outside_cwd(&app.fs.marked)) {
+            (0, _) => right_text,
+
+            (marked, 0) => format!("{marked} marked • {right_text}"),
+
+            (marked, elsewhere) => format!("{marked} marked ({elsewhere} elsewhere) • {right_text}"),
+        };
+
+        let right_text: String = match app.ui.visual_range {
+            Some((start, end)) if app.ui.is_in_visual_mode() => {
+                format!("-- VISUAL -- {} selected • {right_text}", end - start + 1)
+            }
+
+            _ => right_text,
+        };
         let right_para: Paragraph<'_> = Paragraph::new(Line::from(Span::styled(
             right_text,
             Style::default().fg(theme::PURPLE),
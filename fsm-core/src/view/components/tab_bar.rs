@@ -0,0 +1,57 @@
+//! ``src/view/components/tab_bar.rs``
+//! ============================================================================
+//! # `TabBar`: One-Line Tab Strip Above the Object Table
+//!
+//! Shown only when more than one tab is open. Titles are numbered so they
+//! match the `Alt+<n>` shortcuts; a tab that is still loading is marked `…`.
+
+use crate::AppState;
+use crate::model::fs_state::{FSState, PaneState};
+use crate::view::theme;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Line,
+    widgets::Tabs,
+};
+
+pub struct TabBar;
+
+impl TabBar {
+    /// Height the tab bar needs for the current tab count.
+    #[must_use]
+    pub fn height(app: &AppState) -> u16 {
+        u16::from(app.tabs.len() > 1)
+    }
+
+    pub fn render(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+        let titles: Vec<Line<'_>> = (0..app.tabs.len())
+            .map(|idx: usize| -> Line<'_> {
+                let fs: &FSState = if idx == app.tabs.active {
+                    &app.fs
+                } else {
+                    &app.tabs.tabs[idx].fs
+                };
+
+                let loading: bool = fs.panes.iter().any(|p: &PaneState| -> bool { p.is_loading });
+                let marker: &str = if loading { "…" } else { "" };
+
+                Line::from(format!("{}:{}{marker}", idx + 1, app.tab_title(idx)))
+            })
+            .collect();
+
+        let tabs: Tabs<'_> = Tabs::new(titles)
+            .select(app.tabs.active)
+            .style(Style::default().fg(theme::COMMENT).bg(theme::BACKGROUND))
+            .highlight_style(
+                Style::default()
+                    .fg(theme::PINK)
+                    .bg(theme::CURRENT_LINE)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .divider("│");
+
+        frame.render_widget(tabs, area);
+    }
+}
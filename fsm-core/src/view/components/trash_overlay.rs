@@ -0,0 +1,111 @@
+//! ``src/view/components/trash_overlay.rs``
+//! ============================================================================
+//! # `TrashOverlay`: Trashed Entries, Newest First
+//!
+//! Lists `UIState::trash_entries` (every trash directory of the user) with
+//! the deletion date and original path of each entry. The highlighted row is
+//! `UIState::overlay_selected`.
+
+use crate::AppState;
+use crate::fs::trash::TrashEntry;
+use crate::model::hit_map::ListHit;
+use crate::view::theme;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+pub struct TrashOverlay;
+
+impl TrashOverlay {
+    pub fn render(frame: &mut Frame<'_>, app: &mut AppState, area: Rect) {
+        let overlay_area: Rect = Self::centered_rect(70, 60, area);
+        frame.render_widget(Clear, overlay_area);
+
+        let entries: &[TrashEntry] = &app.ui.trash_entries;
+
+        let block: Block<'_> = Block::default()
+            .title(format!(" Trash ({}) ", entries.len()))
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" Enter/r restore • Del/x purge • Esc close ").centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::PURPLE))
+            .style(Style::default().bg(theme::BACKGROUND).fg(theme::FOREGROUND));
+
+        if entries.is_empty() {
+            let empty: Paragraph<'_> = Paragraph::new(Line::from(Span::styled(
+                "Trash is empty",
+                Style::default().fg(theme::COMMENT),
+            )))
+            .alignment(Alignment::Center)
+            .block(block);
+
+            frame.render_widget(empty, overlay_area);
+            app.ui.hits.overlay_list = None;
+
+            return;
+        }
+
+        let items: Vec<ListItem<'_>> = entries
+            .iter()
+            .map(|entry: &TrashEntry| -> ListItem<'_> {
+                let path_style: Style = if entry.is_dir {
+                    Style::default().fg(theme::CYAN)
+                } else {
+                    Style::default().fg(theme::FOREGROUND)
+                };
+
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {} ", entry.deleted_label()), Style::default().fg(theme::COMMENT)),
+                    Span::styled(entry.original_path.display().to_string(), path_style),
+                ]))
+            })
+            .collect();
+
+        let mut list_state: ListState = ListState::default();
+        list_state.select(Some(app.ui.overlay_selected.min(entries.len() - 1)));
+
+        let item_count: usize = entries.len();
+
+        let list: List<'_> = List::new(items)
+            .block(block)
+            .highlight_symbol("▶ ")
+            .highlight_style(
+                Style::default()
+                    .bg(theme::CURRENT_LINE)
+                    .fg(theme::PINK)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        frame.render_stateful_widget(list, overlay_area, &mut list_state);
+
+        app.ui.hits.overlay_list = Some(ListHit::new(
+            overlay_area.inner(Margin::new(1, 1)),
+            list_state.offset(),
+            item_count,
+        ));
+    }
+
+    fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+        let vertical = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ])
+            .split(area);
+
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ])
+            .split(vertical[1])[1]
+    }
+}
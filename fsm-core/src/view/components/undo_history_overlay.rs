@@ -0,0 +1,110 @@
+//! ``src/view/components/undo_history_overlay.rs``
+//! ============================================================================
+//! # `UndoHistoryOverlay`: Recorded File Operations
+//!
+//! Lists the undone operations (dimmed, furthest redo first) above the done
+//! ones (newest first), so the boundary between them is where the history
+//! currently stands. The highlighted row is `UIState::overlay_selected`.
+
+use crate::AppState;
+use crate::model::app_state::AppHistoryEvent;
+use crate::model::hit_map::ListHit;
+use crate::view::theme;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+pub struct UndoHistoryOverlay;
+
+impl UndoHistoryOverlay {
+    pub fn render(frame: &mut Frame<'_>, app: &mut AppState, area: Rect) {
+        let overlay_area: Rect = Self::centered_rect(70, 60, area);
+        frame.render_widget(Clear, overlay_area);
+
+        let block: Block<'_> = Block::default()
+            .title(" Undo History ")
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(" Enter undo/redo to here • u undo • Ctrl+R redo • Esc close ").centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme::PURPLE))
+            .style(Style::default().bg(theme::BACKGROUND).fg(theme::FOREGROUND));
+
+        let item_count: usize = app.redo_history.len() + app.history.len();
+
+        if item_count == 0 {
+            let empty: Paragraph<'_> = Paragraph::new(Line::from(Span::styled(
+                "No file operations yet",
+                Style::default().fg(theme::COMMENT),
+            )))
+            .alignment(Alignment::Center)
+            .block(block);
+
+            frame.render_widget(empty, overlay_area);
+            app.ui.hits.overlay_list = None;
+
+            return;
+        }
+
+        let undone = app.redo_history.iter().map(|event: &AppHistoryEvent| -> ListItem<'_> {
+            Self::item("↷", event, Style::default().fg(theme::COMMENT))
+        });
+
+        let done = app.history.iter().rev().map(|event: &AppHistoryEvent| -> ListItem<'_> {
+            Self::item("↶", event, Style::default().fg(theme::FOREGROUND))
+        });
+
+        let items: Vec<ListItem<'_>> = undone.chain(done).collect();
+
+        let mut list_state: ListState = ListState::default();
+        list_state.select(Some(app.ui.overlay_selected.min(item_count - 1)));
+
+        let list: List<'_> = List::new(items)
+            .block(block)
+            .highlight_symbol("▶ ")
+            .highlight_style(
+                Style::default()
+                    .bg(theme::CURRENT_LINE)
+                    .fg(theme::PINK)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        frame.render_stateful_widget(list, overlay_area, &mut list_state);
+
+        app.ui.hits.overlay_list = Some(ListHit::new(
+            overlay_area.inner(Margin::new(1, 1)),
+            list_state.offset(),
+            item_count,
+        ));
+    }
+
+    fn item(marker: &str, event: &AppHistoryEvent, style: Style) -> ListItem<'static> {
+        ListItem::new(Line::from(vec![
+            Span::styled(format!(" {marker} "), style),
+            Span::styled(event.label(), style),
+        ]))
+    }
+
+    fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+        let vertical = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ])
+            .split(area);
+
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ])
+            .split(vertical[1])[1]
+    }
+}
@@ -7,9 +7,9 @@ use std::rc::Rc;
 
 use crate::model::ui_state::UIOverlay;
 use crate::{
-    AppState, ContentSearchOverlay, FileNameSearchOverlay, FileOperationsOverlay, HelpOverlay,
-    InputPromptOverlay, LoadingOverlay, NotificationOverlay, ObjectTable, SearchOverlay,
-    SearchResultsOverlay, StatusBar,
+    AppState, BookmarksOverlay, ContentSearchOverlay, DiagnosticsOverlay, FileNameSearchOverlay, FileOperationsOverlay, FrecencyOverlay, HelpOverlay,
+    HistoryOverlay, InputPromptOverlay, LoadingOverlay, NotificationOverlay, ObjectTable, SearchOverlay,
+    SearchResultsOverlay, StatusBar, TabBar, TrashOverlay, UndoHistoryOverlay,
 };
 
 use ratatui::layout::Rect;
@@ -25,27 +25,35 @@ impl View {
     /// Draws the full UI for one frame.
     #[instrument(skip_all, fields(operation_type = "ui_redraw"))]
     pub fn redraw(frame: &mut Frame<'_>, app: &mut AppState) {
+        // Components record their clickable areas as they draw
+        app.ui.hits.clear();
+
         // The main object table's block will act as the background
         let main_layout: Rc<[Rect]> = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Fill(1),   // Main content area
-                Constraint::Length(1), // Status bar
+                Constraint::Length(TabBar::height(app)), // Tab bar (hidden with one tab)
+                Constraint::Fill(1),                     // Main content area
+                Constraint::Length(1),                   // Status bar
             ])
             .split(frame.area());
 
+        if main_layout[0].height > 0 {
+            TabBar::render(frame, app, main_layout[0]);
+        }
+
         let object_table_span = tracing::info_span!(
             "object_table_render",
             operation_type = "object_table_render"
         )
         .entered();
-        ObjectTable::render(frame, app, main_layout[0]);
+        ObjectTable::render(frame, app, main_layout[1]);
         drop(object_table_span);
 
         let status_bar_span =
             tracing::info_span!("status_bar_render", operation_type = "status_bar_render")
                 .entered();
-        StatusBar::render(frame, app, main_layout[1]);
+        StatusBar::render(frame, app, main_layout[2]);
         drop(status_bar_span);
 
         // Overlays are rendered on top of the main UI
@@ -63,7 +71,7 @@ impl View {
                 UIOverlay::FileNameSearch => {
                     let x = &mut app.ui.filename_search_overlay.clone();
 
-                    FileNameSearchOverlay::render(x, frame, app, overlay_area);
+                    app.ui.hits.overlay_list = FileNameSearchOverlay::render(x, frame, app, overlay_area);
                 }
 
                 UIOverlay::ContentSearch => ContentSearchOverlay::render(frame, app, overlay_area),
@@ -74,6 +82,13 @@ impl View {
 
                 UIOverlay::Prompt => InputPromptOverlay::render(frame, app, overlay_area),
 
+                UIOverlay::History => HistoryOverlay::render(frame, app, overlay_area),
+                UIOverlay::Bookmarks => BookmarksOverlay::render(frame, app, overlay_area),
+                UIOverlay::Frecency => FrecencyOverlay::render(frame, app, overlay_area),
+                UIOverlay::Diagnostics => DiagnosticsOverlay::render(frame, app, overlay_area),
+                UIOverlay::Trash => TrashOverlay::render(frame, app, overlay_area),
+                UIOverlay::UndoHistory => UndoHistoryOverlay::render(frame, app, overlay_area),
+
                 _ => {}
             }
         }